@@ -0,0 +1,123 @@
+//! Procedural macros for NeXosim.
+//!
+//! The macros are re-exported by the `nexosim` crate and should not be used
+//! directly from this crate.
+#![warn(missing_docs, missing_debug_implementations, unreachable_pub)]
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields, Type};
+
+/// Derives a forwarding model.
+#[proc_macro_derive(Forward, attributes(forward))]
+pub fn derive_forward(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_forward(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates the input method and the `Model` implementation of a forwarding
+/// model.
+fn expand_forward(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let mut input_type: Option<Type> = None;
+    let mut transform: Option<Expr> = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("forward")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("input") {
+                input_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("transform") {
+                transform = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `forward` attribute, expected `input` or `transform`"))
+            }
+        })?;
+    }
+
+    let input_type = input_type.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing input type, expected `#[forward(input = T)]`",
+        )
+    })?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`Forward` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Forward` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut outputs = fields.iter().filter(|f| is_output(&f.ty));
+    let output = match (outputs.next(), outputs.next()) {
+        (Some(output), None) => output.ident.as_ref().unwrap(),
+        (None, _) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Forward` requires exactly one `Output` field, found none",
+            ))
+        }
+        (Some(_), Some(other)) => {
+            return Err(Error::new_spanned(
+                other,
+                "`Forward` requires exactly one `Output` field, found several",
+            ))
+        }
+    };
+
+    let send = match transform {
+        Some(transform) => quote! {
+            // Constrain the argument type so that it need not be annotated.
+            fn constrain<I, R, F: FnOnce(I) -> R>(f: F) -> F {
+                f
+            }
+            let transform = constrain::<#input_type, _, _>(#transform);
+            self.#output.send(transform(value)).await;
+        },
+        None => quote! {
+            self.#output.send(value).await;
+        },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Forwards an event to the output.
+            pub async fn input(&mut self, value: #input_type) {
+                #send
+            }
+        }
+
+        impl #impl_generics ::nexosim::model::Model for #name #ty_generics #where_clause {}
+    })
+}
+
+/// Checks whether the type of a field is an output port.
+fn is_output(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Output"),
+        _ => false,
+    }
+}
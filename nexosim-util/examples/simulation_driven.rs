@@ -23,6 +23,7 @@
 //! ```
 
 use std::future::Future;
+use std::sync::mpsc::RecvTimeoutError;
 use std::thread;
 use std::time::Duration;
 
@@ -39,6 +40,7 @@ const SWITCH_ON_DELAY: Duration = Duration::from_secs(1);
 const MAX_PULSE_PERIOD: u64 = 100;
 const TICK: Duration = Duration::from_millis(100);
 const N: u64 = 10;
+const STALL_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Counter mode.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -157,10 +159,7 @@ impl Detector {
 
     /// Schedules the next detection.
     async fn schedule_next(&mut self, cx: &mut Context<Self>) {
-        let next = {
-            let mut rng = rand::thread_rng();
-            rng.gen_range(1..MAX_PULSE_PERIOD)
-        };
+        let next = cx.rng().gen_range(1..MAX_PULSE_PERIOD);
         self.next = Some(
             cx.schedule_keyed_event(Duration::from_millis(next), Self::pulse, ())
                 .unwrap(),
@@ -234,12 +233,13 @@ fn main() -> Result<(), SimulationError> {
 
     // Wait until counter mode is `On`.
     loop {
-        let event = observer.next();
+        let event = observer.next_timeout(STALL_TIMEOUT);
         match event {
-            Some(Event::Mode(Mode::On)) => {
+            Ok(Event::Mode(Mode::On)) => {
                 break;
             }
-            None => panic!("Simulation exited unexpectedly"),
+            Err(RecvTimeoutError::Timeout) => panic!("Simulation stalled"),
+            Err(RecvTimeoutError::Disconnected) => panic!("Simulation exited unexpectedly"),
             _ => (),
         }
     }
@@ -254,20 +254,21 @@ fn main() -> Result<(), SimulationError> {
 
     // Wait until `N` detections.
     loop {
-        let event = observer.next();
+        let event = observer.next_timeout(STALL_TIMEOUT);
         match event {
-            Some(Event::Count(c)) if c >= N => {
+            Ok(Event::Count(c)) if c >= N => {
                 break;
             }
-            None => panic!("Simulation exited unexpectedly"),
+            Err(RecvTimeoutError::Timeout) => panic!("Simulation stalled"),
+            Err(RecvTimeoutError::Disconnected) => panic!("Simulation exited unexpectedly"),
             _ => (),
         }
     }
 
     // Stop the simulation.
-    scheduler.halt();
+    scheduler.halt_with_reason(format!("reached {} counts", N));
     match simulation_handle.join().unwrap() {
-        Err(ExecutionError::Halted) => Ok(()),
+        Err(ExecutionError::Halted(_)) => Ok(()),
         Err(e) => Err(e.into()),
         _ => Ok(()),
     }
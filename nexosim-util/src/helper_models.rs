@@ -6,6 +6,8 @@
 use std::time::Duration;
 
 use nexosim::model::{Context, InitializedModel, Model};
+use nexosim::ports::Output;
+use nexosim::simulation::ActionKey;
 
 /// A ticker model.
 ///
@@ -33,3 +35,129 @@ impl Model for Ticker {
         self.into()
     }
 }
+
+/// A debouncer model.
+///
+/// This model forwards an input value to its output only once the input has
+/// been stable for the specified duration. Each input value that differs from
+/// the pending value cancels the pending emission and schedules a new one, so
+/// the emitted value is always the last one received. A value identical to the
+/// last emitted value is ignored if no emission is pending.
+pub struct Debouncer<T>
+where
+    T: Clone + PartialEq + Send + 'static,
+{
+    /// Debounced output.
+    pub output: Output<T>,
+
+    /// Stability duration.
+    delay: Duration,
+
+    /// Pending value and key of its scheduled emission.
+    pending: Option<(T, ActionKey)>,
+
+    /// Last emitted value.
+    last: Option<T>,
+}
+
+impl<T> Debouncer<T>
+where
+    T: Clone + PartialEq + Send + 'static,
+{
+    /// Creates a new `Debouncer` with the specified stability duration.
+    ///
+    /// # Panics
+    ///
+    /// The constructor will panic if the duration is zero.
+    pub fn new(delay: Duration) -> Self {
+        assert!(!delay.is_zero(), "the debouncing delay cannot be zero");
+
+        Self {
+            output: Output::default(),
+            delay,
+            pending: None,
+            last: None,
+        }
+    }
+
+    /// Input value -- input port.
+    pub fn input(&mut self, value: T, cx: &mut Context<Self>) {
+        match &self.pending {
+            Some((pending, _)) if *pending == value => return,
+            None if self.last.as_ref() == Some(&value) => return,
+            _ => {}
+        }
+
+        if let Some((_, key)) = self.pending.take() {
+            key.cancel();
+        }
+        let key = cx.schedule_keyed_event(self.delay, Self::emit, ()).unwrap();
+        self.pending = Some((value, key));
+    }
+
+    /// Emits the pending value -- self-scheduled function.
+    async fn emit(&mut self) {
+        if let Some((value, _)) = self.pending.take() {
+            self.last = Some(value.clone());
+            self.output.send(value).await;
+        }
+    }
+}
+
+impl<T> Model for Debouncer<T> where T: Clone + PartialEq + Send + 'static {}
+
+/// A sampler model.
+///
+/// This model forwards at most one value per period: input values are held
+/// and only the latest one is emitted at each period boundary. Nothing is
+/// emitted at a period boundary if no input was received during that period.
+pub struct Sampler<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Sampled output.
+    pub output: Output<T>,
+
+    /// Sampling period.
+    period: Duration,
+
+    /// Latest value received during the current period.
+    latest: Option<T>,
+}
+
+impl<T> Sampler<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Creates a new `Sampler` with the specified sampling period.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            output: Output::default(),
+            period,
+            latest: None,
+        }
+    }
+
+    /// Input value -- input port.
+    pub fn input(&mut self, value: T) {
+        self.latest = Some(value);
+    }
+
+    /// Emits the latest value, if any -- self-scheduled function.
+    async fn sample(&mut self) {
+        if let Some(value) = self.latest.take() {
+            self.output.send(value).await;
+        }
+    }
+}
+
+impl<T> Model for Sampler<T>
+where
+    T: Clone + Send + 'static,
+{
+    async fn init(self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+        cx.schedule_periodic_event(self.period, self.period, Self::sample, ())
+            .unwrap();
+        self.into()
+    }
+}
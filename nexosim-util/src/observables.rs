@@ -4,9 +4,12 @@
 //! to output on change.
 //!
 
+use std::collections::VecDeque;
 use std::ops::Deref;
 
+use nexosim::model::{Context, Model};
 use nexosim::ports::Output;
+use nexosim::time::MonotonicTime;
 
 /// Observability trait.
 pub trait Observable<T> {
@@ -94,3 +97,146 @@ where
 
 /// Observable value.
 pub type ObservableValue<T> = ObservableState<T, T>;
+
+/// Policy applied when a new change is recorded in a full history.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EvictionPolicy {
+    /// The oldest recorded change is discarded.
+    #[default]
+    DropOldest,
+    /// The new change is not recorded.
+    DropNewest,
+}
+
+/// Observable value with change history.
+///
+/// This object behaves like an [`ObservableValue`] and propagates every change
+/// to the output, but it also records each change together with the simulation
+/// time at which it occurred so the trajectory of the value can be analyzed
+/// after the simulation run.
+#[derive(Debug)]
+pub struct ObservableHistory<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Value.
+    value: T,
+
+    /// Output used for observation.
+    out: Output<T>,
+
+    /// Timestamped changes.
+    history: VecDeque<(MonotonicTime, T)>,
+
+    /// Maximum number of recorded changes.
+    capacity: Option<usize>,
+
+    /// Policy applied when the history is full.
+    policy: EvictionPolicy,
+}
+
+impl<T> ObservableHistory<T>
+where
+    T: Clone + Default + Send + 'static,
+{
+    /// New default value with unbounded history.
+    pub fn new(out: Output<T>) -> Self {
+        Self {
+            value: T::default(),
+            out,
+            history: VecDeque::new(),
+            capacity: None,
+            policy: EvictionPolicy::default(),
+        }
+    }
+
+    /// New default value with a history holding at most `capacity` changes.
+    ///
+    /// The specified eviction policy is applied when a change is recorded in a
+    /// full history.
+    pub fn with_capacity(out: Output<T>, capacity: usize, policy: EvictionPolicy) -> Self {
+        Self {
+            value: T::default(),
+            out,
+            history: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+            policy,
+        }
+    }
+}
+
+impl<T> ObservableHistory<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Get value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Set value, recording the change at the current simulation time.
+    pub async fn set<M: Model>(&mut self, value: T, cx: &Context<M>) {
+        self.value = value;
+        self.record(cx.time());
+        self.out.send(self.value.clone()).await;
+    }
+
+    /// Modify value using mutable reference, recording the change at the
+    /// current simulation time.
+    pub async fn modify<M, F, R>(&mut self, f: F, cx: &Context<M>) -> R
+    where
+        M: Model,
+        F: FnOnce(&mut T) -> R,
+    {
+        let r = f(&mut self.value);
+        self.record(cx.time());
+        self.out.send(self.value.clone()).await;
+        r
+    }
+
+    /// Propagate value.
+    ///
+    /// The value is not recorded since it is unchanged.
+    pub async fn propagate(&mut self) {
+        self.out.send(self.value.clone()).await;
+    }
+
+    /// Returns an iterator over the recorded changes, from oldest to newest.
+    pub fn history(&self) -> impl ExactSizeIterator<Item = &(MonotonicTime, T)> + '_ {
+        self.history.iter()
+    }
+
+    /// Removes and returns all recorded changes, from oldest to newest.
+    pub fn take_history(&mut self) -> Vec<(MonotonicTime, T)> {
+        self.history.drain(..).collect()
+    }
+
+    /// Record the current value.
+    fn record(&mut self, time: MonotonicTime) {
+        if let Some(capacity) = self.capacity {
+            if self.history.len() >= capacity {
+                match self.policy {
+                    EvictionPolicy::DropOldest => {
+                        if self.history.pop_front().is_none() {
+                            // Null capacity.
+                            return;
+                        }
+                    }
+                    EvictionPolicy::DropNewest => return,
+                }
+            }
+        }
+        self.history.push_back((time, self.value.clone()));
+    }
+}
+
+impl<T> Deref for ObservableHistory<T>
+where
+    T: Clone + Send + 'static,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
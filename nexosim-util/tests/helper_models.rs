@@ -0,0 +1,130 @@
+//! Helper models.
+
+use std::time::Duration;
+
+use nexosim::ports::EventBuffer;
+use nexosim::simulation::{Address, Mailbox, SimInit, Simulation};
+use nexosim::time::MonotonicTime;
+use nexosim_util::helper_models::{Debouncer, Sampler};
+
+/// Creates a bench with a debouncer which output is connected to a buffer.
+fn debouncer_bench(delay: Duration) -> (Simulation, Address<Debouncer<u32>>, EventBuffer<u32>) {
+    let mut debouncer = Debouncer::new(delay);
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let output = EventBuffer::new();
+    debouncer.output.connect_sink(&output);
+
+    let simu = SimInit::new()
+        .add_model(debouncer, mbox, "debouncer")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    (simu, addr, output)
+}
+
+#[test]
+fn debouncer_stable_input() {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, addr, mut output) = debouncer_bench(Duration::from_secs(2));
+
+    simu.process_event(Debouncer::input, 1, &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), None);
+}
+
+#[test]
+fn debouncer_chattering_input() {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, addr, mut output) = debouncer_bench(Duration::from_secs(2));
+
+    // Each differing value restarts the delay.
+    simu.process_event(Debouncer::input, 1, &addr).unwrap();
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Debouncer::input, 2, &addr).unwrap();
+    simu.step_until(Duration::from_millis(1500)).unwrap();
+    simu.process_event(Debouncer::input, 3, &addr).unwrap();
+    assert_eq!(output.next(), None);
+
+    // Only the last value is emitted.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_millis(4500));
+    assert_eq!(output.next(), Some(3));
+    assert_eq!(output.next(), None);
+
+    // No other emission is pending.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_millis(4500));
+}
+
+#[test]
+fn debouncer_repeated_input() {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, addr, mut output) = debouncer_bench(Duration::from_secs(2));
+
+    // A value identical to the pending value does not restart the delay.
+    simu.process_event(Debouncer::input, 1, &addr).unwrap();
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Debouncer::input, 1, &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(1));
+
+    // A value identical to the last emitted value is ignored.
+    simu.process_event(Debouncer::input, 1, &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn debouncer_zero_delay() {
+    Debouncer::<u32>::new(Duration::ZERO);
+}
+
+#[test]
+fn sampler() {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut sampler = Sampler::new(Duration::from_secs(2));
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = EventBuffer::new();
+    sampler.output.connect_sink(&output);
+
+    let mut simu = SimInit::new()
+        .add_model(sampler, mbox, "sampler")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Only the latest value received during a period is emitted at the end of
+    // the period.
+    simu.step_until(Duration::from_millis(500)).unwrap();
+    simu.process_event(Sampler::input, 1, &addr).unwrap();
+    simu.step_until(Duration::from_millis(500)).unwrap();
+    simu.process_event(Sampler::input, 2, &addr).unwrap();
+    assert_eq!(output.next(), None);
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(output.next(), None);
+
+    // Nothing is emitted if no value was received during a period.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(4));
+    assert_eq!(output.next(), None);
+
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Sampler::input, 3, &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(6));
+    assert_eq!(output.next(), Some(3));
+    assert_eq!(output.next(), None);
+}
@@ -0,0 +1,151 @@
+//! Observable states with change history.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+use nexosim_util::observables::{EvictionPolicy, ObservableHistory};
+
+/// A model with a value which changes are recorded.
+struct Recorder {
+    value: ObservableHistory<u32>,
+}
+
+impl Recorder {
+    async fn set(&mut self, value: u32, cx: &mut Context<Self>) {
+        self.value.set(value, cx).await;
+    }
+
+    async fn add(&mut self, value: u32, cx: &mut Context<Self>) {
+        self.value.modify(|v| *v += value, cx).await;
+    }
+
+    async fn propagate(&mut self) {
+        self.value.propagate().await;
+    }
+
+    async fn history(&mut self) -> Vec<(MonotonicTime, u32)> {
+        self.value.history().cloned().collect()
+    }
+
+    async fn take_history(&mut self) -> Vec<(MonotonicTime, u32)> {
+        self.value.take_history()
+    }
+}
+
+impl Model for Recorder {}
+
+/// Runs a bench in which the recorder value is set at t0+1s, modified at
+/// t0+2s and propagated at t0+3s, and returns the recorded history and the
+/// broadcast values.
+fn run_bench(capacity: Option<(usize, EvictionPolicy)>) -> (Vec<(MonotonicTime, u32)>, Vec<u32>) {
+    let mut out = Output::default();
+    let mut output = EventBuffer::new();
+    out.connect_sink(&output);
+    let recorder = Recorder {
+        value: match capacity {
+            Some((capacity, policy)) => ObservableHistory::with_capacity(out, capacity, policy),
+            None => ObservableHistory::new(out),
+        },
+    };
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::new()
+        .add_model(recorder, mbox, "recorder")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Recorder::set, 3, &addr).unwrap();
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Recorder::add, 4, &addr).unwrap();
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(Recorder::propagate, (), &addr).unwrap();
+
+    let history = simu.process_query(Recorder::history, (), &addr).unwrap();
+
+    (history, output.by_ref().collect())
+}
+
+#[test]
+fn observable_history_unbounded() {
+    let t0 = MonotonicTime::EPOCH;
+    let (history, output) = run_bench(None);
+
+    // Each change is broadcast and recorded, but a propagation is only
+    // broadcast.
+    assert_eq!(output, vec![3, 7, 7]);
+    assert_eq!(
+        history,
+        vec![
+            (t0 + Duration::from_secs(1), 3),
+            (t0 + Duration::from_secs(2), 7)
+        ]
+    );
+}
+
+#[test]
+fn observable_history_drop_oldest() {
+    let t0 = MonotonicTime::EPOCH;
+    let (history, output) = run_bench(Some((1, EvictionPolicy::DropOldest)));
+
+    assert_eq!(output, vec![3, 7, 7]);
+    assert_eq!(history, vec![(t0 + Duration::from_secs(2), 7)]);
+}
+
+#[test]
+fn observable_history_drop_newest() {
+    let t0 = MonotonicTime::EPOCH;
+    let (history, output) = run_bench(Some((1, EvictionPolicy::DropNewest)));
+
+    assert_eq!(output, vec![3, 7, 7]);
+    assert_eq!(history, vec![(t0 + Duration::from_secs(1), 3)]);
+}
+
+#[test]
+fn observable_history_null_capacity() {
+    let (history, output) = run_bench(Some((0, EvictionPolicy::DropOldest)));
+
+    // Changes are still broadcast.
+    assert_eq!(output, vec![3, 7, 7]);
+    assert!(history.is_empty());
+}
+
+#[test]
+fn observable_history_take() {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut out = Output::default();
+    let mut output = EventBuffer::new();
+    out.connect_sink(&output);
+    let recorder = Recorder {
+        value: ObservableHistory::new(out),
+    };
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut simu = SimInit::new()
+        .add_model(recorder, mbox, "recorder")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Recorder::set, 1, &addr).unwrap();
+    assert_eq!(
+        simu.process_query(Recorder::take_history, (), &addr)
+            .unwrap(),
+        vec![(t0, 1)]
+    );
+
+    // The history is emptied once taken.
+    assert!(simu
+        .process_query(Recorder::take_history, (), &addr)
+        .unwrap()
+        .is_empty());
+    assert_eq!(output.next(), Some(1));
+}
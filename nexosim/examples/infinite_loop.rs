@@ -124,7 +124,7 @@ fn main() -> Result<(), SimulationError> {
     // Stop the simulation.
     scheduler.halt();
     match simulation_handle.join().unwrap() {
-        Err(ExecutionError::Halted) => Ok(()),
+        Err(ExecutionError::Halted(_)) => Ok(()),
         Err(e) => Err(e.into()),
         _ => Ok(()),
     }
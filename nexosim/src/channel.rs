@@ -4,22 +4,30 @@
 
 mod queue;
 
+use std::any::Any;
 use std::cell::Cell;
 use std::error;
 use std::fmt;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
 use std::sync::atomic::{self, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, Weak};
+#[cfg(feature = "dev-hooks")]
+use std::task::{self, Poll};
 
 use async_event::Event;
 use diatomic_waker::primitives::DiatomicWaker;
 use recycle_box::RecycleBox;
 
-use queue::{PopError, PushError, Queue};
+use queue::{MessageBorrow, PopError, PushError, Queue};
 use recycle_box::coerce_box;
 
 use crate::model::{Context, Model};
+use crate::simulation::{PortLabel, CURRENT_MODEL_ID};
+use crate::util::catch_unwind::CatchUnwind;
+use crate::util::slot::SlotWriter;
 
 // Counts the difference between the number of sent and received messages for
 // this thread.
@@ -29,26 +37,98 @@ use crate::model::{Context, Model};
 // occurred.
 thread_local! { pub(crate) static THREAD_MSG_COUNT: Cell<isize> = const { Cell::new(0) }; }
 
+// Causal trace identifier of the message being processed on this thread, if
+// any.
+//
+// The identifier is attached to all messages sent while the message is being
+// processed so that causal relations between messages can be recorded.
+#[cfg(feature = "dev-hooks")]
+thread_local! { pub(crate) static CURRENT_TRACE_ID: Cell<Option<u64>> = const { Cell::new(None) }; }
+
 /// Data shared between the receiver and the senders.
 struct Inner<M> {
     /// Non-blocking internal queue.
     queue: Queue<dyn MessageFn<M>>,
+    /// Non-blocking internal queue for high-priority messages, if the channel
+    /// is prioritized.
+    priority_queue: Option<Queue<dyn MessageFn<M>>>,
     /// Signalling primitive used to notify the receiver.
     receiver_signal: DiatomicWaker,
     /// Signalling primitive used to notify one or several senders.
     sender_signal: Event,
     /// Current count of live senders.
     sender_count: AtomicUsize,
+    /// Identifiers of the models currently waiting for capacity to send a
+    /// message.
+    blocked_senders: Mutex<Vec<usize>>,
+    /// Connections from output and requestor ports to this channel.
+    ///
+    /// A connection is live as long as one of the senders dedicated to it is.
+    connections: Mutex<Vec<Weak<PortInfo>>>,
 }
 
 impl<M: 'static> Inner<M> {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, is_prioritized: bool) -> Self {
         Self {
             queue: Queue::new(capacity),
+            priority_queue: is_prioritized.then(|| Queue::new(capacity)),
             receiver_signal: DiatomicWaker::new(),
             sender_signal: Event::new(),
             sender_count: AtomicUsize::new(0),
+            blocked_senders: Mutex::new(Vec::new()),
+            connections: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Pops a message, giving precedence to high-priority messages.
+    ///
+    /// # Safety
+    ///
+    /// This method may not be called concurrently from multiple threads.
+    unsafe fn pop(&self) -> Result<MessageBorrow<'_, dyn MessageFn<M>>, PopError> {
+        let priority_queue = match &self.priority_queue {
+            Some(priority_queue) => priority_queue,
+            None => return self.queue.pop(),
+        };
+
+        if let Ok(msg) = priority_queue.pop() {
+            return Ok(msg);
+        }
+
+        match self.queue.pop() {
+            // Since the high-priority queue is always closed first, a last
+            // high-priority message may have been pushed after the above
+            // check but before the regular queue was closed.
+            Err(PopError::Closed) => priority_queue.pop(),
+            res => res,
+        }
+    }
+
+    /// Returns the current number of messages in the channel.
+    fn len(&self) -> usize {
+        self.queue.len() + self.priority_queue.as_ref().map_or(0, Queue::len)
+    }
+
+    /// Signals to awaiting senders that a slot was freed.
+    fn notify_senders(&self) {
+        // The slot may have been freed in either queue, so all senders must be
+        // notified in a prioritized channel.
+        if self.priority_queue.is_some() {
+            self.sender_signal.notify_all();
+        } else {
+            self.sender_signal.notify_one();
+        }
+    }
+}
+
+impl<M> Inner<M> {
+    /// Closes the channel.
+    fn close(&self) {
+        // The high-priority queue must be closed first, see `pop`.
+        if let Some(priority_queue) = &self.priority_queue {
+            priority_queue.close();
         }
+        self.queue.close();
     }
 }
 
@@ -69,7 +149,24 @@ impl<M: Model> Receiver<M> {
     /// The constructor will panic if the requested capacity is 0 or is greater
     /// than `usize::MAX/2 + 1`.
     pub(crate) fn new(capacity: usize) -> Self {
-        let inner = Arc::new(Inner::new(capacity));
+        Self::with_priority_classes(capacity, false)
+    }
+
+    /// Creates a new receiver with a regular and a high-priority class of
+    /// messages, each with the specified capacity.
+    ///
+    /// High-priority messages are always received before regular messages.
+    ///
+    /// # Panic
+    ///
+    /// The constructor will panic if the requested capacity is 0 or is greater
+    /// than `usize::MAX/2 + 1`.
+    pub(crate) fn new_prioritized(capacity: usize) -> Self {
+        Self::with_priority_classes(capacity, true)
+    }
+
+    fn with_priority_classes(capacity: usize, is_prioritized: bool) -> Self {
+        let inner = Arc::new(Inner::new(capacity, is_prioritized));
 
         Receiver {
             inner,
@@ -90,6 +187,8 @@ impl<M: Model> Receiver<M> {
 
         Sender {
             inner: self.inner.clone(),
+            port: None,
+            is_high_priority: false,
         }
     }
 
@@ -107,10 +206,35 @@ impl<M: Model> Receiver<M> {
         model: &mut M,
         cx: &mut Context<M>,
     ) -> Result<(), RecvError> {
+        self.recv_inner(model, cx, false)
+            .await
+            .map(|res| res.unwrap_or_else(|payload| panic::resume_unwind(payload)))
+    }
+
+    /// Receives and executes a message asynchronously, if necessary waiting
+    /// until one becomes available, and catches a panic raised while executing
+    /// the message.
+    ///
+    /// If the message has panicked, the panic payload is returned.
+    pub(crate) async fn recv_catch_unwind(
+        &mut self,
+        model: &mut M,
+        cx: &mut Context<M>,
+    ) -> Result<Result<(), Box<dyn Any + Send + 'static>>, RecvError> {
+        self.recv_inner(model, cx, true).await
+    }
+
+    /// Receives and executes a message, optionally catching panics.
+    async fn recv_inner(
+        &mut self,
+        model: &mut M,
+        cx: &mut Context<M>,
+        catch_unwind: bool,
+    ) -> Result<Result<(), Box<dyn Any + Send + 'static>>, RecvError> {
         let msg = unsafe {
             self.inner
                 .receiver_signal
-                .wait_until(|| match self.inner.queue.pop() {
+                .wait_until(|| match self.inner.pop() {
                     Ok(msg) => Some(Some(msg)),
                     Err(PopError::Empty) => None,
                     Err(PopError::Closed) => Some(None),
@@ -123,23 +247,59 @@ impl<M: Model> Receiver<M> {
                 // Decrement the count of in-flight messages.
                 THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_sub(1));
 
-                // Take the message to obtain a boxed future.
-                let fut = msg.call_once(model, cx, self.future_box.take().unwrap());
-
-                // Now that the message was taken, drop `msg` to free its slot
-                // in the queue and signal to one awaiting sender that a slot is
-                // available for sending.
-                drop(msg);
-                self.inner.sender_signal.notify_one();
+                // Take the message to obtain a boxed future. Non-async input
+                // methods are executed at this point.
+                let future_box = self.future_box.take().unwrap();
+                let fut = if catch_unwind {
+                    // `msg` is dropped when the closure returns or unwinds.
+                    panic::catch_unwind(AssertUnwindSafe(move || {
+                        msg.call_once(model, cx, future_box)
+                    }))
+                } else {
+                    let fut = msg.call_once(model, cx, future_box);
+                    drop(msg);
+
+                    Ok(fut)
+                };
+
+                // Retrieve the trace identifier set while the message was
+                // taken so it does not leak to other tasks.
+                #[cfg(feature = "dev-hooks")]
+                let trace_id = CURRENT_TRACE_ID.take();
+
+                // Now that the message was taken and `msg` was dropped to free
+                // its slot in the queue, signal to awaiting senders that a
+                // slot is available for sending.
+                self.inner.notify_senders();
+
+                let fut = match fut {
+                    Ok(fut) => fut,
+                    Err(payload) => {
+                        // The box was consumed by the panicking message.
+                        self.future_box = Some(RecycleBox::new(()));
+
+                        return Ok(Err(payload));
+                    }
+                };
 
                 // Await the future provided by the message.
                 let mut fut = RecycleBox::into_pin(fut);
-                fut.as_mut().await;
-
-                // Recycle the box.
+                #[cfg(feature = "dev-hooks")]
+                let traced_fut = WithTraceId::new(fut.as_mut(), trace_id);
+                #[cfg(not(feature = "dev-hooks"))]
+                let traced_fut = fut.as_mut();
+                let res = if catch_unwind {
+                    CatchUnwind::new(traced_fut).await
+                } else {
+                    traced_fut.await;
+
+                    Ok(())
+                };
+
+                // Recycle the box, dropping the future if it has panicked.
                 self.future_box = Some(RecycleBox::vacate_pinned(fut));
 
-                Ok(())
+                Ok(res)
             }
             None => Err(RecvError),
         }
@@ -159,7 +319,7 @@ impl<M: Model> Receiver<M> {
     #[allow(unused)]
     pub(crate) fn close(&self) {
         if !self.inner.queue.is_closed() {
-            self.inner.queue.close();
+            self.inner.close();
 
             // Notify all blocked senders that the channel is closed.
             self.inner.sender_signal.notify_all();
@@ -178,7 +338,7 @@ impl<M: Model> Receiver<M> {
 
 impl<M> Drop for Receiver<M> {
     fn drop(&mut self) {
-        self.inner.queue.close();
+        self.inner.close();
 
         // Notify all blocked senders that the channel is closed.
         self.inner.sender_signal.notify_all();
@@ -198,6 +358,10 @@ impl<M> fmt::Debug for Receiver<M> {
 pub(crate) struct Sender<M: 'static> {
     /// Shared data.
     inner: Arc<Inner<M>>,
+    /// The port served by this sender, if it is dedicated to a connection.
+    port: Option<Arc<PortInfo>>,
+    /// Whether messages are sent in the high-priority class.
+    is_high_priority: bool,
 }
 
 impl<M: Model> Sender<M> {
@@ -219,22 +383,31 @@ impl<M: Model> Sender<M> {
             coerce_box!(RecycleBox::recycle(vacated_box, MessageFnOnce::new(msg_fn)))
         });
 
+        // Registration of the sending model as blocked, if the channel is
+        // found full. The registration is revoked when the guard is dropped.
+        let mut blocked_sender = None;
+
         let success = self
             .inner
             .sender_signal
             .wait_until(|| {
-                match self.inner.queue.push(msg_fn.take().unwrap()) {
+                match self.queue().push(msg_fn.take().unwrap()) {
                     Ok(()) => Some(true),
                     Err(PushError::Full(m)) => {
                         // Recycle the message.
                         msg_fn = Some(m);
 
+                        if blocked_sender.is_none() {
+                            blocked_sender = BlockedSender::register(&self.inner.blocked_senders);
+                        }
+
                         None
                     }
                     Err(PushError::Closed) => Some(false),
                 }
             })
             .await;
+        drop(blocked_sender);
 
         if success {
             self.inner.receiver_signal.notify();
@@ -248,13 +421,43 @@ impl<M: Model> Sender<M> {
         }
     }
 
+    /// Attempts to send a message without waiting, failing if the channel is
+    /// full or closed.
+    pub(crate) fn try_send<F>(&self, msg_fn: F) -> Result<(), TrySendError>
+    where
+        F: for<'a> FnOnce(
+                &'a mut M,
+                &'a mut Context<M>,
+                RecycleBox<()>,
+            ) -> RecycleBox<dyn Future<Output = ()> + Send + 'a>
+            + Send
+            + 'static,
+    {
+        let msg_fn = |vacated_box| -> RecycleBox<dyn MessageFn<M>> {
+            coerce_box!(RecycleBox::recycle(vacated_box, MessageFnOnce::new(msg_fn)))
+        };
+
+        match self.queue().push(msg_fn) {
+            Ok(()) => {
+                self.inner.receiver_signal.notify();
+
+                // Increment the count of in-flight messages.
+                THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_add(1));
+
+                Ok(())
+            }
+            Err(PushError::Full(_)) => Err(TrySendError::Full),
+            Err(PushError::Closed) => Err(TrySendError::Closed),
+        }
+    }
+
     /// Closes the channel.
     ///
     /// This prevents any further messages from being sent. Messages that were
     /// already sent can still be received.
     #[allow(unused)]
     pub(crate) fn close(&self) {
-        self.inner.queue.close();
+        self.inner.close();
 
         // Notify the receiver and all blocked senders that the channel is
         // closed.
@@ -271,6 +474,24 @@ impl<M: Model> Sender<M> {
         self.inner.queue.is_closed()
     }
 
+    /// Returns an observer of the channel.
+    ///
+    /// Unlike a sender, an observer does not count as a connection to the
+    /// channel.
+    pub(crate) fn observer(&self) -> Observer<M> {
+        Observer {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Checks if the channel is full.
+    ///
+    /// The result is only a hint since messages may be concurrently sent or
+    /// received.
+    pub(crate) fn is_full(&self) -> bool {
+        self.queue().is_full()
+    }
+
     /// Returns a unique identifier for the channel.
     ///
     /// All channels are guaranteed to have different identifiers at any given
@@ -279,6 +500,50 @@ impl<M: Model> Sender<M> {
     pub(crate) fn channel_id(&self) -> usize {
         Arc::as_ptr(&self.inner) as usize
     }
+
+    /// Dedicates this sender to a connection from the specified source port to
+    /// the specified target port.
+    ///
+    /// The connection is recorded in the channel until this sender and all its
+    /// clones are dropped.
+    pub(crate) fn with_port(
+        mut self,
+        source: PortLabel,
+        port: &'static str,
+        event_type: &'static str,
+    ) -> Self {
+        let port = Arc::new(PortInfo {
+            source,
+            port,
+            event_type,
+        });
+
+        let mut connections = self.inner.connections.lock().unwrap();
+        connections.retain(|connection| connection.strong_count() != 0);
+        connections.push(Arc::downgrade(&port));
+        drop(connections);
+
+        self.port = Some(port);
+
+        self
+    }
+
+    /// Makes this sender send messages in the high-priority class.
+    ///
+    /// This has no effect if the channel is not prioritized.
+    pub(crate) fn with_high_priority(mut self) -> Self {
+        self.is_high_priority = true;
+
+        self
+    }
+
+    /// Returns the queue in which messages are sent.
+    fn queue(&self) -> &Queue<dyn MessageFn<M>> {
+        match &self.inner.priority_queue {
+            Some(priority_queue) if self.is_high_priority => priority_queue,
+            _ => &self.inner.queue,
+        }
+    }
 }
 
 impl<M> Clone for Sender<M> {
@@ -294,10 +559,22 @@ impl<M> Clone for Sender<M> {
 
         Self {
             inner: self.inner.clone(),
+            port: self.port.clone(),
+            is_high_priority: self.is_high_priority,
         }
     }
 }
 
+/// The description of a port connection served by a [`Sender`].
+struct PortInfo {
+    /// The label of the source port.
+    source: PortLabel,
+    /// The name of the target port.
+    port: &'static str,
+    /// The name of the event or request type.
+    event_type: &'static str,
+}
+
 /// A model-independent handle to a channel that can observe the current number
 /// of messages.
 pub(crate) trait ChannelObserver: Send {
@@ -311,21 +588,160 @@ pub(crate) trait ChannelObserver: Send {
     /// the past state of the channel, and may be greater than the capacity of
     /// the channel.
     fn len(&self) -> usize;
+
+    /// Returns the current number of senders.
+    fn sender_count(&self) -> usize;
+
+    /// Returns the identifiers of the models that are waiting for capacity to
+    /// send a message to the channel.
+    fn blocked_senders(&self) -> Vec<usize>;
+
+    /// Returns the live connections to the channel as a list of source port
+    /// labels, target port names and event or request type names.
+    fn connections(&self) -> Vec<(String, &'static str, &'static str)>;
+
+    /// Closes the channel.
+    ///
+    /// This prevents any further messages from being sent. Messages that were
+    /// already sent can still be received.
+    fn close(&self);
+
+    /// Returns the observer as `Any` so it can be downcast to an
+    /// [`Observer`].
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns a future that sends a message requesting the model to check its
+    /// invariants.
+    ///
+    /// The result of the check is written to the provided slot once the
+    /// message is processed.
+    fn check_invariants(
+        &self,
+        result: SlotWriter<Result<(), String>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>>;
 }
 
 /// A handle to a channel that can observe the current number of messages.
 ///
 /// Multiple [`Observer`]s can be created using the [`Receiver::observer`]
 /// method or via cloning.
-#[derive(Clone)]
 pub(crate) struct Observer<M: 'static> {
     /// Shared data.
     inner: Arc<Inner<M>>,
 }
 
+impl<M> Clone for Observer<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Model> Observer<M> {
+    /// Creates a new sender.
+    pub(crate) fn sender(&self) -> Sender<M> {
+        // Increase the reference count of senders.
+        //
+        // Ordering: Relaxed ordering is sufficient, see `Receiver::sender`.
+        self.inner.sender_count.fetch_add(1, Ordering::Relaxed);
+
+        Sender {
+            inner: self.inner.clone(),
+            port: None,
+            is_high_priority: false,
+        }
+    }
+}
+
 impl<M: Model> ChannelObserver for Observer<M> {
     fn len(&self) -> usize {
-        self.inner.queue.len()
+        self.inner.len()
+    }
+
+    fn sender_count(&self) -> usize {
+        self.inner.sender_count.load(Ordering::Acquire)
+    }
+
+    fn blocked_senders(&self) -> Vec<usize> {
+        self.inner.blocked_senders.lock().unwrap().clone()
+    }
+
+    fn connections(&self) -> Vec<(String, &'static str, &'static str)> {
+        self.inner
+            .connections
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|connection| connection.upgrade())
+            .map(|port| (port.source.get(), port.port, port.event_type))
+            .collect()
+    }
+
+    fn close(&self) {
+        self.inner.close();
+
+        // Notify the receiver and all blocked senders that the channel is
+        // closed.
+        self.inner.receiver_signal.notify();
+        self.inner.sender_signal.notify_all();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn check_invariants(
+        &self,
+        result: SlotWriter<Result<(), String>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        let sender = self.sender();
+
+        Box::pin(async move {
+            // Ignore send errors.
+            let _ = sender
+                .send(
+                    move |model: &mut M,
+                          _,
+                          recycle_box: RecycleBox<()>|
+                          -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                        let _ = result.write(model.check_invariants());
+
+                        coerce_box!(RecycleBox::recycle(recycle_box, async {}))
+                    },
+                )
+                .await;
+        })
+    }
+}
+
+/// A registration of a model as blocked on a full channel.
+///
+/// The registration is revoked on drop.
+struct BlockedSender<'a> {
+    blocked_senders: &'a Mutex<Vec<usize>>,
+    model_id: usize,
+}
+
+impl<'a> BlockedSender<'a> {
+    /// Registers the model currently being executed, if any.
+    fn register(blocked_senders: &'a Mutex<Vec<usize>>) -> Option<Self> {
+        let model_id = CURRENT_MODEL_ID.get().get()?;
+        blocked_senders.lock().unwrap().push(model_id);
+
+        Some(Self {
+            blocked_senders,
+            model_id,
+        })
+    }
+}
+
+impl Drop for BlockedSender<'_> {
+    fn drop(&mut self) {
+        let mut blocked_senders = self.blocked_senders.lock().unwrap();
+        if let Some(pos) = blocked_senders.iter().position(|&id| id == self.model_id) {
+            blocked_senders.swap_remove(pos);
+        }
     }
 }
 
@@ -349,7 +765,7 @@ impl<M: 'static> Drop for Sender<M> {
             // a Release sequence.
             atomic::fence(Ordering::Acquire);
 
-            self.inner.queue.close();
+            self.inner.close();
 
             // Notify the senders that the channel is closed.
             self.inner.receiver_signal.notify();
@@ -388,12 +804,17 @@ trait MessageFn<M: Model>: Send {
 /// A `MessageFn` implementation wrapping an async `FnOnce`.
 struct MessageFnOnce<F, M> {
     msg_fn: Option<F>,
+    /// Causal trace identifier of the message being processed by the sender.
+    #[cfg(feature = "dev-hooks")]
+    trace_id: Option<u64>,
     _phantom: PhantomData<fn(&mut M)>,
 }
 impl<F, M> MessageFnOnce<F, M> {
     fn new(msg_fn: F) -> Self {
         Self {
             msg_fn: Some(msg_fn),
+            #[cfg(feature = "dev-hooks")]
+            trace_id: CURRENT_TRACE_ID.get(),
             _phantom: PhantomData,
         }
     }
@@ -415,10 +836,50 @@ where
     ) -> RecycleBox<dyn Future<Output = ()> + Send + 'a> {
         let closure = self.msg_fn.take().unwrap();
 
+        // Make the trace identifier of the sender visible to the dispatch
+        // hooks, which may substitute it with that of this message.
+        #[cfg(feature = "dev-hooks")]
+        CURRENT_TRACE_ID.set(self.trace_id);
+
         (closure)(model, cx, recycle_box)
     }
 }
 
+/// A future adapter that sets the current causal trace identifier each time
+/// the inner future is polled.
+#[cfg(feature = "dev-hooks")]
+struct WithTraceId<F> {
+    inner: F,
+    trace_id: Option<u64>,
+}
+
+#[cfg(feature = "dev-hooks")]
+impl<F> WithTraceId<F> {
+    fn new(inner: F, trace_id: Option<u64>) -> Self {
+        Self { inner, trace_id }
+    }
+}
+
+#[cfg(feature = "dev-hooks")]
+impl<F: Future + Unpin> Future for WithTraceId<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        /// Restores the previous trace identifier on drop, even if the inner
+        /// future panics.
+        struct Restore(Option<u64>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_TRACE_ID.set(self.0);
+            }
+        }
+
+        let _restore = Restore(CURRENT_TRACE_ID.replace(self.trace_id));
+
+        Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
 /// Unique identifier for a channel.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct ChannelId(usize);
@@ -434,6 +895,16 @@ impl fmt::Display for ChannelId {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) struct SendError;
 
+/// An error returned when an attempt to send a message without waiting is
+/// unsuccessful.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TrySendError {
+    /// The channel is full.
+    Full,
+    /// The channel is closed.
+    Closed,
+}
+
 /// An error returned when an attempt to receive a message asynchronously is
 /// unsuccessful.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
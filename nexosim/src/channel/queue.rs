@@ -342,6 +342,16 @@ impl<T: ?Sized> Queue<T> {
         (enqueue_idx + (carry_flag as usize) * self.buffer.len()) - dequeue_idx
     }
 
+    /// Checks whether the queue is full.
+    ///
+    /// # Warning
+    ///
+    /// The same caveats as for [`Queue::len`] apply: the returned result is
+    /// only a hint if there are concurrent `push` or `pop` operations.
+    pub(super) fn is_full(&self) -> bool {
+        self.len() >= self.buffer.len()
+    }
+
     /// Increment the queue position, incrementing the sequence count as well if
     /// the index wraps to 0.
     ///
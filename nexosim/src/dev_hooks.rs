@@ -2,10 +2,16 @@
 //!
 //! Not for production use!
 
+use std::fmt;
 use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use crate::channel::CURRENT_TRACE_ID;
 use crate::executor;
+use crate::model::Model;
+use crate::simulation::Mailbox;
 
 /// A multi-threaded `async` executor.
 #[derive(Debug)]
@@ -17,11 +23,11 @@ impl Executor {
     /// The maximum number of threads is set with the `pool_size` parameter.
     pub fn new(pool_size: usize) -> Self {
         let dummy_cx = crate::executor::SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: crate::util::sync_cell::SyncCell::new(
                 crate::time::TearableAtomicTime::new(crate::time::MonotonicTime::EPOCH),
             )
             .reader(),
+            scheduler: crate::simulation::GlobalScheduler::new_dummy(),
         };
         Self(executor::Executor::new_multi_threaded(
             pool_size,
@@ -48,3 +54,309 @@ impl Executor {
         self.0.run(Duration::ZERO).unwrap();
     }
 }
+
+/// A recorder of the global order in which events are processed by models.
+///
+/// Models are tapped by wrapping their mailbox with [`OrderRecorder::tap`]
+/// before they are added to the simulation bench. Each event delivered to an
+/// input port of a tapped model is then appended to a shared log, so that the
+/// log reflects the total order in which events were processed.
+///
+/// Queries are not recorded.
+#[derive(Clone, Debug, Default)]
+pub struct OrderRecorder {
+    log: Arc<Mutex<Vec<Dispatch>>>,
+}
+
+impl OrderRecorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Taps a mailbox so that all events delivered to its model are recorded
+    /// under the specified label.
+    pub fn tap<M: Model>(&self, mut mailbox: Mailbox<M>, label: impl Into<String>) -> Mailbox<M> {
+        let log = self.log.clone();
+        let label = label.into();
+        let mut sequence = 0;
+        mailbox.1.add_dispatch_hook(move |event_type| {
+            log.lock().unwrap().push(Dispatch {
+                label: label.clone(),
+                sequence,
+                event_type,
+            });
+            sequence += 1;
+        });
+
+        mailbox
+    }
+
+    /// Returns a snapshot of the events recorded so far.
+    pub fn log(&self) -> OrderLog {
+        OrderLog(self.log.lock().unwrap().clone())
+    }
+
+    /// Clears the log.
+    ///
+    /// The per-model sequence numbers are not reset.
+    pub fn clear(&self) {
+        self.log.lock().unwrap().clear();
+    }
+}
+
+/// A record of an event processed by a model.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dispatch {
+    /// The label of the model, as specified with [`OrderRecorder::tap`].
+    pub label: String,
+    /// The index of the event among all events processed by the model.
+    pub sequence: u64,
+    /// The name of the event type, as returned by [`std::any::type_name`].
+    pub event_type: &'static str,
+}
+
+/// A selector for an event within an [`OrderLog`].
+///
+/// A selector can be created from a model label, which selects the first
+/// recorded event of this model, or from a `(label, sequence)` tuple, which
+/// selects the event of this model with the specified sequence number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    label: String,
+    sequence: Option<u64>,
+}
+
+impl From<&str> for Selector {
+    fn from(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            sequence: None,
+        }
+    }
+}
+
+impl From<(&str, u64)> for Selector {
+    fn from((label, sequence): (&str, u64)) -> Self {
+        Self {
+            label: label.to_string(),
+            sequence: Some(sequence),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.sequence {
+            Some(sequence) => write!(f, "event #{} of '{}'", sequence, self.label),
+            None => write!(f, "first event of '{}'", self.label),
+        }
+    }
+}
+
+/// A snapshot of the events recorded by an [`OrderRecorder`], in processing
+/// order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderLog(Vec<Dispatch>);
+
+impl OrderLog {
+    /// Returns the recorded events in processing order.
+    pub fn dispatches(&self) -> &[Dispatch] {
+        &self.0
+    }
+
+    /// Returns the position in the log of the selected event, if recorded.
+    pub fn position(&self, selector: impl Into<Selector>) -> Option<usize> {
+        let selector = selector.into();
+
+        self.0.iter().position(|dispatch| {
+            dispatch.label == selector.label
+                && match selector.sequence {
+                    Some(sequence) => dispatch.sequence == sequence,
+                    None => true,
+                }
+        })
+    }
+
+    /// Returns `true` if both events were recorded and the first one was
+    /// processed before the second one.
+    pub fn is_before(&self, first: impl Into<Selector>, second: impl Into<Selector>) -> bool {
+        matches!(
+            (self.position(first), self.position(second)),
+            (Some(first), Some(second)) if first < second
+        )
+    }
+
+    /// Asserts that both events were recorded and that the first one was
+    /// processed before the second one.
+    ///
+    /// # Panics
+    ///
+    /// This method panics with a description of the log if the assertion does
+    /// not hold.
+    #[track_caller]
+    pub fn assert_before(&self, first: impl Into<Selector>, second: impl Into<Selector>) {
+        let (first, second) = (first.into(), second.into());
+        if !self.is_before(first.clone(), second.clone()) {
+            panic!(
+                "expected the {} to be processed before the {}; log: {:?}",
+                first, second, self.0
+            );
+        }
+    }
+
+    /// Asserts that both events were recorded and that the first one was
+    /// processed after the second one.
+    ///
+    /// # Panics
+    ///
+    /// This method panics with a description of the log if the assertion does
+    /// not hold.
+    #[track_caller]
+    pub fn assert_after(&self, first: impl Into<Selector>, second: impl Into<Selector>) {
+        let (first, second) = (first.into(), second.into());
+        if !self.is_before(second.clone(), first.clone()) {
+            panic!(
+                "expected the {} to be processed after the {}; log: {:?}",
+                first, second, self.0
+            );
+        }
+    }
+}
+
+/// Source of the causal trace identifiers, shared by all tracers so that
+/// identifiers are globally unique.
+static NEXT_TRACE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A recorder of the causal relations between the events processed by models.
+///
+/// Models are tapped by wrapping their mailbox with [`CausalTracer::tap`]
+/// before they are added to the simulation bench. Each event delivered to an
+/// input port of a tapped model is then recorded as a [`Delivery`] with a
+/// unique identifier, together with the identifier of the delivery that
+/// caused it, if any. A delivery is caused by another one if the event was
+/// sent (from an output, requestor or replier port) while the model was
+/// processing the other delivery.
+///
+/// Causal relations are propagated through models that are not tapped, as
+/// well as through queries, so that an event sent by an untapped model is
+/// attributed to the delivery that caused the untapped model to send it.
+/// Events scheduled with a scheduler or a model context and events injected
+/// with [`Simulation::process_event`](crate::simulation::Simulation::process_event)
+/// or similar methods start new causal chains, as do events delivered through
+/// delayed connections.
+///
+/// Queries are not recorded.
+#[derive(Clone, Debug, Default)]
+pub struct CausalTracer {
+    log: Arc<Mutex<Vec<Delivery>>>,
+}
+
+impl CausalTracer {
+    /// Creates an empty tracer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Taps a mailbox so that all events delivered to its model are recorded
+    /// under the specified label.
+    pub fn tap<M: Model>(&self, mut mailbox: Mailbox<M>, label: impl Into<String>) -> Mailbox<M> {
+        let log = self.log.clone();
+        let label = label.into();
+        mailbox.1.add_dispatch_hook(move |event_type| {
+            let id = NEXT_TRACE_ID.fetch_add(1, Ordering::Relaxed);
+            let parent = CURRENT_TRACE_ID.replace(Some(id));
+            log.lock().unwrap().push(Delivery {
+                id,
+                parent,
+                label: label.clone(),
+                event_type,
+            });
+        });
+
+        mailbox
+    }
+
+    /// Returns a snapshot of the deliveries recorded so far.
+    pub fn trace(&self) -> CausalTrace {
+        CausalTrace(self.log.lock().unwrap().clone())
+    }
+
+    /// Clears the recorded deliveries.
+    pub fn clear(&self) {
+        self.log.lock().unwrap().clear();
+    }
+}
+
+/// A record of an event delivered to a model.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delivery {
+    /// The unique identifier of the delivery.
+    pub id: u64,
+    /// The identifier of the delivery that caused this one, if any.
+    ///
+    /// The parent delivery may not be part of the trace, for instance if it
+    /// was recorded by another tracer or if the tracer was cleared since.
+    pub parent: Option<u64>,
+    /// The label of the model, as specified with [`CausalTracer::tap`].
+    pub label: String,
+    /// The name of the event type, as returned by [`std::any::type_name`].
+    pub event_type: &'static str,
+}
+
+/// A snapshot of the deliveries recorded by a [`CausalTracer`], in processing
+/// order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CausalTrace(Vec<Delivery>);
+
+impl CausalTrace {
+    /// Returns the recorded deliveries in processing order.
+    pub fn deliveries(&self) -> &[Delivery] {
+        &self.0
+    }
+
+    /// Returns the delivery with the specified identifier, if recorded.
+    pub fn get(&self, id: u64) -> Option<&Delivery> {
+        self.0.iter().find(|delivery| delivery.id == id)
+    }
+
+    /// Returns the deliveries directly caused by the specified delivery, in
+    /// processing order.
+    pub fn children(&self, id: u64) -> impl Iterator<Item = &Delivery> + '_ {
+        self.0
+            .iter()
+            .filter(move |delivery| delivery.parent == Some(id))
+    }
+
+    /// Returns all deliveries directly or indirectly caused by the specified
+    /// delivery, in processing order.
+    pub fn descendants(&self, id: u64) -> Vec<&Delivery> {
+        let mut ids = vec![id];
+
+        // Since a delivery is always recorded after its parent, a single pass
+        // in processing order is enough.
+        self.0
+            .iter()
+            .filter(|delivery| match delivery.parent {
+                Some(parent) if ids.contains(&parent) => {
+                    ids.push(delivery.id);
+                    true
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Returns the earliest recorded ancestor of the specified delivery, which
+    /// is the delivery itself if its parent was not recorded.
+    ///
+    /// This identifies the initial event that caused the specified delivery.
+    pub fn root(&self, id: u64) -> Option<&Delivery> {
+        let mut delivery = self.get(id)?;
+        while let Some(parent) = delivery.parent.and_then(|parent| self.get(parent)) {
+            delivery = parent;
+        }
+
+        Some(delivery)
+    }
+}
@@ -13,9 +13,8 @@ use std::time::Duration;
 use crossbeam_utils::CachePadded;
 
 use crate::macros::scoped_thread_local::scoped_thread_local;
-use crate::simulation::ModelId;
-#[cfg(feature = "tracing")]
-use crate::time::AtomicTimeReader;
+use crate::simulation::{GlobalScheduler, ModelId};
+use crate::time::{AtomicTimeReader, MonotonicTime};
 use task::Promise;
 
 /// Unique identifier for executor instances.
@@ -35,12 +34,19 @@ pub(crate) enum ExecutorError {
 #[derive(Clone)]
 pub(crate) struct SimulationContext {
     /// Read-only handle to the simulation time.
-    #[cfg(feature = "tracing")]
     pub(crate) time_reader: AtomicTimeReader,
+    /// Handle to the global scheduler.
+    pub(crate) scheduler: GlobalScheduler,
 }
 
 scoped_thread_local!(pub(crate) static SIMULATION_CONTEXT: SimulationContext);
 
+/// Returns the current simulation time if called from a simulation executor
+/// thread.
+pub(crate) fn simulation_time() -> Option<MonotonicTime> {
+    SIMULATION_CONTEXT.map(|ctx| ctx.time_reader.try_read().ok())?
+}
+
 /// A single-threaded or multi-threaded `async` executor.
 #[derive(Debug)]
 pub(crate) enum Executor {
@@ -116,6 +122,15 @@ impl Executor {
             Self::MtExecutor(executor) => executor.run(timeout),
         }
     }
+
+    /// Execute spawned tasks like [`run`](Executor::run), but distribute them
+    /// upfront across all worker threads, if any.
+    pub(crate) fn run_distributed(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
+        match self {
+            Self::StExecutor(executor) => executor.run(timeout),
+            Self::MtExecutor(executor) => executor.run_distributed(timeout),
+        }
+    }
 }
 
 /// A single-use shared boolean signal.
@@ -151,11 +166,11 @@ mod tests {
 
     fn dummy_simulation_context() -> SimulationContext {
         SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: crate::util::sync_cell::SyncCell::new(
                 crate::time::TearableAtomicTime::new(crate::time::MonotonicTime::EPOCH),
             )
             .reader(),
+            scheduler: GlobalScheduler::new_dummy(),
         }
     }
 
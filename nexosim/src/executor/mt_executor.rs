@@ -237,6 +237,21 @@ impl Executor {
         self.context.injector.insert_task(runnable);
     }
 
+    /// Execute spawned tasks like [`run`](Executor::run), but with all workers
+    /// activated upfront.
+    ///
+    /// Tasks are otherwise only distributed to other workers when a task is
+    /// woken, so this is beneficial when the spawned tasks may block their
+    /// worker thread for a long time.
+    pub(crate) fn run_distributed(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
+        // One more worker is activated by `run`.
+        for _ in 1..self.worker_handles.len() {
+            self.context.pool_manager.activate_worker();
+        }
+
+        self.run(timeout)
+    }
+
     /// Execute spawned tasks, blocking until all futures have completed or an
     /// error is encountered.
     pub(crate) fn run(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
@@ -260,7 +260,7 @@
 //!    [`Simulation::step_unbounded`](simulation::Simulation::step_unbounded).
 //! 2. by sending events or queries without advancing simulation time, using
 //!    [`Simulation::process_event`](simulation::Simulation::process_event) or
-//!    [`Simulation::send_query`](simulation::Simulation::process_query),
+//!    [`Simulation::process_query`](simulation::Simulation::process_query),
 //! 3. by scheduling events with a [`Scheduler`](simulation::Scheduler).
 //!
 //! When initialized with the default clock, the simulation will run as fast as
@@ -388,6 +388,20 @@
 //! the relative order of same-time events self-scheduled by a model using its
 //! [`Context`](model::Context) is preserved.
 //!
+//! These guarantees only hold between events scheduled with the same
+//! priority. Events scheduled for the same time with an explicit priority, for
+//! instance with
+//! [`Scheduler::schedule_with_priority`](simulation::Scheduler::schedule_with_priority),
+//! are instead processed by increasing priority value: all events with a given
+//! priority, as well as all messages they transitively trigger, are processed
+//! before any event with a higher priority value. Events scheduled without an
+//! explicit priority have a priority of 0.
+//!
+//! The one-to-one ordering guarantee is also relaxed for models with a
+//! prioritized mailbox (see
+//! [`Mailbox::new_prioritized`](simulation::Mailbox::new_prioritized)):
+//! messages are then only processed in FIFO order within each priority class.
+//!
 //! [actor_model]: https://en.wikipedia.org/wiki/Actor_model
 //! [pony]: https://www.ponylang.io/
 //!
@@ -422,6 +436,61 @@
 //! Front-end usage documentation will be added upon release of the NeXosim
 //! Python client.
 //!
+//! ## Chrono
+//!
+//! The `chrono` feature provides conversions between
+//! [`MonotonicTime`](time::MonotonicTime) and the UTC date-times of the
+//! [`chrono`](https://docs.rs/chrono/latest/chrono/) crate. It can be activated
+//! with:
+//!
+//! ```toml
+//! [dependencies]
+//! nexosim = { version = "0.3.1", features = ["chrono"] }
+//! ```
+//!
+//! See the [`time`] module for more information.
+//!
+//! ## CSV
+//!
+//! The `csv` feature provides an event sink which writes events directly to a
+//! CSV file. It can be activated with:
+//!
+//! ```toml
+//! [dependencies]
+//! nexosim = { version = "0.3.1", features = ["csv"] }
+//! ```
+//!
+//! See [`CsvSink`](ports::CsvSink) for more information.
+//!
+//! ## Tokio
+//!
+//! The `tokio` feature provides an event sink which forwards events to a
+//! [`tokio`](https://docs.rs/tokio/latest/tokio/) channel, so that they can be
+//! consumed by asynchronous tasks when the simulation is embedded in a Tokio
+//! application. It can be activated with:
+//!
+//! ```toml
+//! [dependencies]
+//! nexosim = { version = "0.3.1", features = ["tokio"] }
+//! ```
+//!
+//! See [`TokioChannelSink`](ports::TokioChannelSink) for more information.
+//!
+//! ## Manifest
+//!
+//! The `manifest` feature makes it possible for models to report a snapshot of
+//! their configuration, which is then aggregated into a JSON manifest of the
+//! simulation. It only pulls the
+//! [`serde_json`](https://docs.rs/serde_json/latest/serde_json/) crate and is
+//! implied by the `server` feature. It can be activated with:
+//!
+//! ```toml
+//! [dependencies]
+//! nexosim = { version = "0.3.1", features = ["manifest"] }
+//! ```
+//!
+//! See [`Model::describe`](model::Model::describe) for more information.
+//!
 //!
 //! # Other resources
 //!
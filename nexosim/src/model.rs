@@ -119,6 +119,48 @@
 //! }
 //! ```
 //!
+//! # Forwarding models
+//!
+//! Trivial models that merely forward each event received on their input to
+//! their output, possibly after applying a transformation, can be generated
+//! with the [`Forward`] derive macro. The macro applies to structs with a
+//! single [`Output`](crate::ports::Output) field; the type of the input is
+//! specified with the `#[forward(input = T)]` attribute and an optional
+//! transformation with `#[forward(transform = expr)]`, where `expr` evaluates
+//! to a closure taking the input event by value.
+//!
+//! The macro generates an `input` method as well as a default implementation
+//! of the [`Model`] trait.
+//!
+//! #### Example
+//!
+//! ```
+//! use nexosim::model::Forward;
+//! use nexosim::ports::Output;
+//! use nexosim::simulation::Mailbox;
+//!
+//! /// Doubles each input value.
+//! #[derive(Default, Forward)]
+//! #[forward(input = f64, transform = |x| 2.0 * x)]
+//! pub struct Multiplier {
+//!     pub output: Output<f64>,
+//! }
+//!
+//! /// Converts a count to a floating-point value.
+//! #[derive(Default, Forward)]
+//! #[forward(input = u32, transform = f64::from)]
+//! pub struct Converter {
+//!     pub output: Output<f64>,
+//! }
+//!
+//! // The generated `input` methods are regular input ports.
+//! let multiplier_mbox = Mailbox::new();
+//! let mut converter = Converter::default();
+//! converter
+//!     .output
+//!     .connect(Multiplier::input, &multiplier_mbox);
+//! ```
+//!
 //! # Hierarchical models
 //!
 //! Hierarchical models are models build from a prototype, which prototype adds
@@ -126,6 +168,11 @@
 //! formal point of view, however, hierarchical models are just regular models
 //! implementing the [`Model`] trait, as are their submodels.
 //!
+//! Submodels are normally only reachable through the ports of their parent
+//! model. When some of their ports should also be reachable from outside, the
+//! prototype can use [`BuildContext::add_submodel_with_address`] and store the
+//! returned address in the parent model so it can be handed out later.
+//!
 //!
 //! #### Example
 //!
@@ -196,7 +243,12 @@
 //! ```
 use std::future::Future;
 
-pub use context::{BuildContext, Context};
+pub use crate::util::rng::SimRng;
+pub use context::{BuildContext, Context, LogLevel, ModelInjector};
+
+/// See the [module-level documentation](self#forwarding-models) for details
+/// and examples.
+pub use nexosim_macros::Forward;
 
 mod context;
 
@@ -247,6 +299,114 @@ pub trait Model: Sized + Send + 'static {
     fn init(self, _: &mut Context<Self>) -> impl Future<Output = InitializedModel<Self>> + Send {
         async { self.into() }
     }
+
+    /// Returns a serializable snapshot of the model configuration, if any.
+    ///
+    /// This method is called exactly once, after the model is built and
+    /// before it is initialized. The snapshots of all models are aggregated
+    /// into the manifest returned by
+    /// [`Simulation::manifest`](crate::simulation::Simulation::manifest),
+    /// which records the effective parameters of a simulation run for
+    /// reproducibility purposes.
+    ///
+    /// The default implementation returns `None`, in which case the model
+    /// does not appear in the manifest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    ///
+    /// pub struct Tank {
+    ///     capacity: f64,
+    ///     level: f64,
+    /// }
+    ///
+    /// impl Model for Tank {
+    ///     fn describe(&self) -> Option<serde_json::Value> {
+    ///         Some(serde_json::json!({
+    ///             "capacity": self.capacity,
+    ///             "initial_level": self.level,
+    ///         }))
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "manifest")]
+    fn describe(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Checks the invariants of the model.
+    ///
+    /// This method is only called if invariant checking was enabled with
+    /// [`SimInit::check_invariants`](crate::simulation::SimInit::check_invariants),
+    /// in which case it is called at the end of each time slice processed by
+    /// the simulation and after each event or query processed immediately,
+    /// once all models have processed their messages. An error
+    /// message is returned if an invariant is violated, which terminates the
+    /// simulation with an
+    /// [`ExecutionError::InvariantViolated`](crate::simulation::ExecutionError::InvariantViolated)
+    /// error.
+    ///
+    /// The default implementation always succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    ///
+    /// pub struct Tank {
+    ///     capacity: f64,
+    ///     level: f64,
+    /// }
+    ///
+    /// impl Model for Tank {
+    ///     fn check_invariants(&self) -> Result<(), String> {
+    ///         if self.level < 0.0 || self.level > self.capacity {
+    ///             return Err(format!("invalid tank level: {}", self.level));
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    fn check_invariants(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Performs asynchronous model termination.
+    ///
+    /// This asynchronous method is executed exactly once when the model, or
+    /// its parent model, is removed from a started simulation with
+    /// [`Simulation::remove_model`](crate::simulation::Simulation::remove_model).
+    /// The mailbox of the model is already closed at this point, but the model
+    /// can still send messages to other models, for instance to notify them
+    /// of its disconnection. It is not executed when the simulation itself is
+    /// dropped.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// pub struct MyModel {
+    ///     // ...
+    /// }
+    ///
+    /// impl Model for MyModel {
+    ///     async fn terminate(
+    ///         &mut self,
+    ///         cx: &mut Context<Self>
+    ///     ) {
+    ///         println!("...termination...");
+    ///     }
+    /// }
+    /// ```
+    fn terminate(&mut self, _: &mut Context<Self>) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
 
 /// Opaque type containing an initialized model.
@@ -284,6 +444,32 @@ pub trait ProtoModel: Sized {
     /// [`SimInit::add_model`](crate::simulation::SimInit::add_model) or
     /// [`BuildContext::add_submodel`] method are called.
     fn build(self, cx: &mut BuildContext<Self>) -> Self::Model;
+
+    /// Checks the prototype before it is built.
+    ///
+    /// This method makes it possible to verify assembly invariants, such as
+    /// the connection of a mandatory port. An error message should be returned
+    /// if an invariant is violated.
+    ///
+    /// Since building consumes the prototype, this method is invoked right
+    /// before [`ProtoModel::build`]. The prototype of a submodel is thus
+    /// validated after its parent was built, but the validation of a model
+    /// cannot observe connections made by models that are built after it.
+    ///
+    /// The failures of all models added to a
+    /// [`SimInit`](crate::simulation::SimInit), including their submodels, are
+    /// aggregated and reported by
+    /// [`SimInit::init`](crate::simulation::SimInit::init) with an
+    /// [`ExecutionError::ValidationFailure`](crate::simulation::ExecutionError::ValidationFailure)
+    /// error before any model is initialized. The failures of a model added to
+    /// a started simulation with
+    /// [`Simulation::add_model`](crate::simulation::Simulation::add_model) are
+    /// reported in the same way, and the model is then not added.
+    ///
+    /// The default implementation always succeeds.
+    fn validate(&self, _: &BuildContext<Self>) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 // Every model can be used as a prototype for itself.
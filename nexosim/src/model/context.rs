@@ -1,10 +1,20 @@
+use std::any::{Any, TypeId};
 use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use crate::executor::{Executor, Signal};
+use crate::channel::Observer;
+use crate::executor::Signal;
 use crate::ports::InputFn;
-use crate::simulation::{self, ActionKey, Address, GlobalScheduler, Mailbox, SchedulingError};
-use crate::time::{Deadline, MonotonicTime};
+use crate::simulation::{
+    self, ActionInjector, ActionKey, Address, DescriptionRegistry, ExecutionError, GlobalScheduler,
+    Interceptors, Mailbox, PanicRegistry, PendingModels, SchedulingError, ValidationInfo,
+    DEFAULT_PRIORITY,
+};
+use crate::time::{Deadline, Jitter, MonotonicTime};
+use crate::util::background_task::BackgroundTask;
+use crate::util::rng::{ModelRng, SharedSeed, SimRng};
 
 use super::{Model, ProtoModel};
 
@@ -80,22 +90,33 @@ pub struct Context<M: Model> {
     scheduler: GlobalScheduler,
     address: Address<M>,
     origin_id: usize,
+    rng: ModelRng,
+    latest_deadline: Mutex<Option<MonotonicTime>>,
+    coalesced_events: Mutex<Vec<CoalescedEvent>>,
+    interceptors: Interceptors,
+    background_tasks: Mutex<Vec<BackgroundTask>>,
 }
 
 impl<M: Model> Context<M> {
     /// Creates a new local context.
-    pub(crate) fn new(name: String, scheduler: GlobalScheduler, address: Address<M>) -> Self {
-        // The only requirement for the origin ID is that it must be (i)
-        // specific to each model and (ii) different from 0 (which is reserved
-        // for the global scheduler). The channel ID of the model mailbox
-        // fulfills this requirement.
-        let origin_id = address.0.channel_id();
-
+    pub(crate) fn new(
+        name: String,
+        scheduler: GlobalScheduler,
+        address: Address<M>,
+        origin_id: usize,
+        rng: ModelRng,
+        interceptors: Interceptors,
+    ) -> Self {
         Self {
             name,
             scheduler,
             address,
             origin_id,
+            rng,
+            latest_deadline: Mutex::new(None),
+            coalesced_events: Mutex::new(Vec::new()),
+            interceptors,
+            background_tasks: Mutex::new(Vec::new()),
         }
     }
 
@@ -112,6 +133,226 @@ impl<M: Model> Context<M> {
         self.scheduler.time()
     }
 
+    /// Emits a log event stamped with the current simulation time and the
+    /// model name.
+    ///
+    /// When the `tracing` feature is enabled, this emits a
+    /// [`tracing`](https://docs.rs/tracing/latest/tracing/) event with target
+    /// `nexosim` and the specified verbosity level, with a `sim_time` field set
+    /// to the current simulation time and a `model` field set to the fully
+    /// qualified model name. The event is therefore correctly stamped
+    /// regardless of how the subscriber is configured. When the `tracing`
+    /// feature is disabled, this method does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, LogLevel, Model};
+    ///
+    /// pub struct Kettle {
+    ///     temperature: f64,
+    /// }
+    ///
+    /// impl Kettle {
+    ///     pub fn heat(&mut self, delta: f64, cx: &mut Context<Self>) {
+    ///         self.temperature += delta;
+    ///         if self.temperature >= 100.0 {
+    ///             cx.log_event(LogLevel::Warn, format_args!("water is boiling"));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Model for Kettle {}
+    /// ```
+    pub fn log_event(&self, level: LogLevel, args: fmt::Arguments<'_>) {
+        #[cfg(feature = "tracing")]
+        {
+            let time = self.time();
+            let name = self.name.as_str();
+
+            macro_rules! log_event {
+                ($level:expr) => {
+                    tracing::event!(
+                        target: env!("CARGO_PKG_NAME"),
+                        $level,
+                        sim_time = %time,
+                        model = name,
+                        "{}",
+                        args
+                    )
+                };
+            }
+
+            match level {
+                LogLevel::Error => log_event!(tracing::Level::ERROR),
+                LogLevel::Warn => log_event!(tracing::Level::WARN),
+                LogLevel::Info => log_event!(tracing::Level::INFO),
+                LogLevel::Debug => log_event!(tracing::Level::DEBUG),
+                LogLevel::Trace => log_event!(tracing::Level::TRACE),
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = (level, args);
+    }
+
+    /// Returns a handle to the model's mailbox.
+    ///
+    /// The address can be handed to other models, for instance so they can
+    /// send events back to this model. It remains valid as long as the model
+    /// is part of the simulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, InitializedModel, Model};
+    /// use nexosim::ports::Output;
+    /// use nexosim::simulation::Address;
+    ///
+    /// // A client that registers with a server upon initialization.
+    /// #[derive(Default)]
+    /// pub struct Client {
+    ///     pub register: Output<Address<Client>>,
+    /// }
+    ///
+    /// impl Model for Client {
+    ///     async fn init(mut self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+    ///         self.register.send(cx.address()).await;
+    ///
+    ///         self.into()
+    ///     }
+    /// }
+    /// ```
+    pub fn address(&self) -> Address<M> {
+        self.address.clone()
+    }
+
+    /// Returns a thread-safe handle that can be used to send events to the
+    /// input ports of this model from outside the simulation.
+    ///
+    /// See [`ModelInjector`] for more details.
+    pub fn injector(&self) -> ModelInjector<M> {
+        ModelInjector {
+            injector: self.scheduler.injector(),
+            observer: self.address.0.observer(),
+        }
+    }
+
+    /// Spawns a detached background task tied to this model.
+    ///
+    /// This is meant for genuinely asynchronous activities that live alongside
+    /// the processing of events, such as reading data from a socket, which
+    /// would otherwise require the model to repeatedly schedule itself to poll
+    /// for new data.
+    ///
+    /// Since the simulation executor only runs while the simulation is being
+    /// stepped and cannot be woken by external I/O, the future is polled on a
+    /// background thread rather than on the simulation executor. This thread
+    /// is shared by all background tasks of the process, so the future should
+    /// not block: blocking operations would delay all other background tasks.
+    /// The future is cancelled, *i.e.* dropped, when the model is removed from
+    /// the simulation or when the simulation is dropped.
+    ///
+    /// The future cannot access the state of the model, which may only be
+    /// mutated while processing an event. Instead, it should communicate with
+    /// the model through its own input ports using the [`ModelInjector`]
+    /// returned by [`Context::injector`]. Events sent this way are processed by
+    /// the model at the current simulation time at the next simulation step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, InitializedModel, Model};
+    /// use nexosim::ports::Output;
+    ///
+    /// // Some source of data, e.g. a network socket.
+    /// async fn receive_datagram() -> Option<Vec<u8>> {
+    ///     // ...
+    ///     # None
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// pub struct Listener {
+    ///     pub output: Output<Vec<u8>>,
+    /// }
+    ///
+    /// impl Listener {
+    ///     // Forwards a received datagram [private input port].
+    ///     async fn on_datagram(&mut self, datagram: Vec<u8>) {
+    ///         self.output.send(datagram).await;
+    ///     }
+    /// }
+    ///
+    /// impl Model for Listener {
+    ///     async fn init(self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+    ///         let injector = cx.injector();
+    ///         cx.spawn(async move {
+    ///             while let Some(datagram) = receive_datagram().await {
+    ///                 if injector.send(Listener::on_datagram, datagram).is_err() {
+    ///                     break;
+    ///                 }
+    ///             }
+    ///         });
+    ///
+    ///         self.into()
+    ///     }
+    /// }
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut background_tasks = self.background_tasks.lock().unwrap();
+
+        // Discard the handles of the tasks that have already completed.
+        background_tasks.retain(|task| !task.is_finished());
+        background_tasks.push(BackgroundTask::spawn(future));
+    }
+
+    /// Returns the pseudo-random number generator of this model.
+    ///
+    /// If a seed was set with [`SimInit::set_seed`](crate::simulation::SimInit::set_seed),
+    /// the generator of each model is seeded deterministically from the
+    /// simulation seed and from the index of the model in the simulation
+    /// bench, so that the generated sequences are reproducible irrespective of
+    /// thread scheduling. Otherwise, the generator is seeded from a source of
+    /// entropy.
+    ///
+    /// The generator implements the [`rand::RngCore`] trait, and therefore
+    /// the [`rand::Rng`] extension trait. See [`SimRng`] for the stability
+    /// guarantees of the generated sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use rand::Rng;
+    ///
+    /// // A model that emits a signal after a random delay.
+    /// pub struct RandomDelay {}
+    ///
+    /// impl RandomDelay {
+    ///     // Triggers the delay [input port].
+    ///     pub fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         let delay = Duration::from_millis(cx.rng().gen_range(1..1000));
+    ///         cx.schedule_event(delay, Self::done, ()).unwrap();
+    ///     }
+    ///
+    ///     // Signals the end of the delay [private input port].
+    ///     fn done(&mut self) {
+    ///         println!("Done!");
+    ///     }
+    /// }
+    ///
+    /// impl Model for RandomDelay {}
+    /// ```
+    pub fn rng(&mut self) -> &mut SimRng {
+        self.rng.get()
+    }
+
     /// Schedules an event at a future time on this model.
     ///
     /// An error is returned if the specified deadline is not in the future of
@@ -154,8 +395,295 @@ impl<M: Model> Context<M> {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.scheduler
-            .schedule_event_from(deadline, func, arg, &self.address, self.origin_id)
+        self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_event_from(
+                deadline,
+                func,
+                arg,
+                &self.address,
+                DEFAULT_PRIORITY,
+                self.origin_id,
+            )
+        })
+    }
+
+    /// Schedules an event on this model after the specified delay, counted
+    /// from the latest deadline scheduled by this model.
+    ///
+    /// The delay is counted from the latest of the current simulation time and
+    /// of the latest deadline of all events and periodic events that were
+    /// successfully scheduled on this model with any of the `schedule_*`
+    /// methods of its context, including this one. If no event was scheduled
+    /// yet, the delay is counted from the current simulation time. This makes
+    /// it possible to schedule a sequence of staggered events without keeping
+    /// track of their absolute time.
+    ///
+    /// An error is returned if the resulting deadline is not in the future of
+    /// the current simulation time, which can only happen for a null delay, or
+    /// if it cannot be represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// // A traffic light.
+    /// pub struct TrafficLight {}
+    ///
+    /// impl TrafficLight {
+    ///     // Starts a light cycle [input port].
+    ///     pub fn start(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         // Green after 1s, yellow 30s later and red 3s later.
+    ///         cx.schedule_after_last(Duration::from_secs(1), Self::green, ())
+    ///             .unwrap();
+    ///         cx.schedule_after_last(Duration::from_secs(30), Self::yellow, ())
+    ///             .unwrap();
+    ///         cx.schedule_after_last(Duration::from_secs(3), Self::red, ())
+    ///             .unwrap();
+    ///     }
+    ///
+    ///     fn green(&mut self) {}
+    ///     fn yellow(&mut self) {}
+    ///     fn red(&mut self) {}
+    /// }
+    ///
+    /// impl Model for TrafficLight {}
+    /// ```
+    pub fn schedule_after_last<F, T, S>(
+        &self,
+        delay: Duration,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        let now = self.time();
+        let anchor = match *self.latest_deadline.lock().unwrap() {
+            Some(latest_deadline) if latest_deadline > now => latest_deadline,
+            _ => now,
+        };
+        let deadline = anchor
+            .checked_add(delay)
+            .ok_or(SchedulingError::InvalidScheduledTime)?;
+
+        self.schedule_event(deadline, func, arg)
+    }
+
+    /// Schedules an event on this model after a randomly jittered delay.
+    ///
+    /// The delay is obtained by applying to the base delay a random deviation
+    /// sampled from the specified [`Jitter`] distribution with the
+    /// pseudo-random number generator of the model (see [`Context::rng`]), so
+    /// that the jitter is reproducible if a simulation seed was set. The
+    /// resulting delay is clamped so that the event is always scheduled in the
+    /// future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use nexosim::time::Jitter;
+    ///
+    /// // A sensor sampled every 100ms with a 2ms standard deviation.
+    /// pub struct Sensor {}
+    ///
+    /// impl Sensor {
+    ///     // Samples the sensor and schedules the next sample [input port].
+    ///     pub fn sample(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         // ...
+    ///         let jitter = Jitter::Normal(Duration::from_millis(2));
+    ///         cx.schedule_event_jittered(Duration::from_millis(100), jitter, Self::sample, ())
+    ///             .unwrap();
+    ///     }
+    /// }
+    ///
+    /// impl Model for Sensor {}
+    /// ```
+    pub fn schedule_event_jittered<F, T, S>(
+        &mut self,
+        base: Duration,
+        jitter: Jitter,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        let delay = jitter.apply(base, self.rng.get());
+
+        self.schedule_event(delay, func, arg)
+    }
+
+    /// Schedules an event at a future time on this model unless an identical
+    /// event is already pending.
+    ///
+    /// An event is considered identical if it was scheduled with this method
+    /// for the same deadline, with the same input port and with an equal key.
+    /// In such case, no event is scheduled and `Ok(())` is returned. Input
+    /// ports are compared by type identity while the key, rather than the
+    /// event argument, is used to discriminate between events targeting the
+    /// same port.
+    ///
+    /// This implements the "dirty flag and single deferred update" pattern:
+    /// several handlers processed within the same time slice may request an
+    /// update but the update is performed only once.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// // A model that recomputes its state once after any setting change.
+    /// #[derive(Default)]
+    /// pub struct Controller {
+    ///     gain: f64,
+    ///     offset: f64,
+    /// }
+    ///
+    /// impl Controller {
+    ///     // Sets the gain [input port].
+    ///     pub fn set_gain(&mut self, gain: f64, cx: &mut Context<Self>) {
+    ///         self.gain = gain;
+    ///         cx.schedule_event_coalesced(Duration::from_millis(1), (), Self::recompute, ())
+    ///             .unwrap();
+    ///     }
+    ///
+    ///     // Sets the offset [input port].
+    ///     pub fn set_offset(&mut self, offset: f64, cx: &mut Context<Self>) {
+    ///         self.offset = offset;
+    ///         cx.schedule_event_coalesced(Duration::from_millis(1), (), Self::recompute, ())
+    ///             .unwrap();
+    ///     }
+    ///
+    ///     // Recomputes the state [private input port].
+    ///     fn recompute(&mut self) {
+    ///         // ...
+    ///     }
+    /// }
+    ///
+    /// impl Model for Controller {}
+    /// ```
+    pub fn schedule_event_coalesced<F, T, S, K>(
+        &self,
+        deadline: impl Deadline,
+        key: K,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+        K: PartialEq + Send + 'static,
+    {
+        let now = self.time();
+        let deadline = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::InvalidScheduledTime)?;
+        let func_id = TypeId::of::<F>();
+
+        {
+            let mut coalesced_events = self.coalesced_events.lock().unwrap();
+
+            // Events with a deadline that is not in the future have already
+            // been processed or are being processed.
+            coalesced_events.retain(|event| event.deadline > now);
+            if coalesced_events.iter().any(|event| {
+                event.deadline == deadline
+                    && event.func_id == func_id
+                    && event.key.downcast_ref::<K>() == Some(&key)
+            }) {
+                return Ok(());
+            }
+        }
+
+        self.schedule_event(deadline, func, arg)?;
+        self.coalesced_events.lock().unwrap().push(CoalescedEvent {
+            deadline,
+            func_id,
+            key: Box::new(key),
+        });
+
+        Ok(())
+    }
+
+    /// Schedules an event at a future time on this model with the specified
+    /// priority.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time.
+    ///
+    /// Among all actions scheduled for the same simulation time, those with a
+    /// lower priority value are processed first, as described in
+    /// [`Scheduler::schedule_with_priority`](crate::simulation::Scheduler::schedule_with_priority).
+    /// Events scheduled without an explicit priority have a priority of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// // A controller that samples its inputs before computing its command.
+    /// pub struct Controller {}
+    ///
+    /// impl Controller {
+    ///     // Starts a control cycle [input port].
+    ///     pub fn start(&mut self, period: Duration, cx: &mut Context<Self>) {
+    ///         // The sampling is processed first since it has a lower priority
+    ///         // value.
+    ///         cx.schedule_event_with_priority(period, 1, Self::compute, ())
+    ///             .unwrap();
+    ///         cx.schedule_event_with_priority(period, -1, Self::sample, ())
+    ///             .unwrap();
+    ///     }
+    ///
+    ///     // Samples inputs [private input port].
+    ///     fn sample(&mut self) {}
+    ///
+    ///     // Computes the command [private input port].
+    ///     fn compute(&mut self) {}
+    /// }
+    ///
+    /// impl Model for Controller {}
+    /// ```
+    pub fn schedule_event_with_priority<F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        priority: i32,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_event_from(
+                deadline,
+                func,
+                arg,
+                &self.address,
+                priority,
+                self.origin_id,
+            )
+        })
     }
 
     /// Schedules a cancellable event at a future time on this model and returns
@@ -211,17 +739,141 @@ impl<M: Model> Context<M> {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        let event_key = self.scheduler.schedule_keyed_event_from(
-            deadline,
-            func,
-            arg,
-            &self.address,
-            self.origin_id,
-        )?;
+        let event_key = self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_keyed_event_from(
+                deadline,
+                func,
+                arg,
+                &self.address,
+                self.origin_id,
+            )
+        })?;
 
         Ok(event_key)
     }
 
+    /// Schedules an event at a future time on this model, provided that a
+    /// guard still holds at delivery time.
+    ///
+    /// Just before the event is dispatched, the guard is called with an
+    /// immutable reference to the model. If it returns `false`, the event is
+    /// discarded and `func` is not called.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// // A valve that closes automatically unless it was closed manually.
+    /// #[derive(Default)]
+    /// pub struct Valve {
+    ///     is_open: bool,
+    /// }
+    ///
+    /// impl Valve {
+    ///     // Opens the valve for the specified duration [input port].
+    ///     pub fn open(&mut self, duration: Duration, cx: &mut Context<Self>) {
+    ///         self.is_open = true;
+    ///         cx.schedule_conditional_event(
+    ///             duration,
+    ///             |valve: &Self| valve.is_open,
+    ///             Self::close,
+    ///             ()
+    ///         ).unwrap();
+    ///     }
+    ///
+    ///     // Closes the valve [input port].
+    ///     pub fn close(&mut self) {
+    ///         self.is_open = false;
+    ///         println!("The valve is now closed");
+    ///     }
+    /// }
+    ///
+    /// impl Model for Valve {}
+    /// ```
+    pub fn schedule_conditional_event<G, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        guard: G,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        G: Fn(&M) -> bool + Send + 'static,
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_conditional_event_from(
+                deadline,
+                guard,
+                func,
+                arg,
+                &self.address,
+                self.origin_id,
+            )
+        })
+    }
+
+    /// Moves a pending keyed event or action to a new time.
+    ///
+    /// The action key remains valid after rescheduling, so the action can be
+    /// subsequently cancelled or rescheduled again.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time, or if the action is no longer pending
+    /// because it was already processed or cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use nexosim::simulation::ActionKey;
+    ///
+    /// // A watchdog that barks unless it is kicked at least once every second.
+    /// #[derive(Default)]
+    /// pub struct Watchdog {
+    ///     key: Option<ActionKey>,
+    /// }
+    ///
+    /// impl Watchdog {
+    ///     // Kicks the watchdog [input port].
+    ///     pub fn kick(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         let timeout = Duration::from_secs(1);
+    ///         let is_rescheduled = self
+    ///             .key
+    ///             .as_ref()
+    ///             .is_some_and(|key| cx.reschedule(key, timeout).is_ok());
+    ///
+    ///         if !is_rescheduled {
+    ///             self.key = cx.schedule_keyed_event(timeout, Self::bark, ()).ok();
+    ///         }
+    ///     }
+    ///
+    ///     // Barks [private input port].
+    ///     fn bark(&mut self) {
+    ///         println!("Woof!");
+    ///     }
+    /// }
+    ///
+    /// impl Model for Watchdog {}
+    /// ```
+    pub fn reschedule(
+        &self,
+        key: &ActionKey,
+        deadline: impl Deadline,
+    ) -> Result<(), SchedulingError> {
+        self.scheduler.reschedule(key, deadline)
+    }
+
     /// Schedules a periodically recurring event on this model at a future time.
     ///
     /// An error is returned if the specified deadline is not in the future of
@@ -271,14 +923,52 @@ impl<M: Model> Context<M> {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.scheduler.schedule_periodic_event_from(
-            deadline,
-            period,
-            func,
-            arg,
-            &self.address,
-            self.origin_id,
-        )
+        self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_periodic_event_from(
+                deadline,
+                period,
+                func,
+                arg,
+                &self.address,
+                self.origin_id,
+            )
+        })
+    }
+
+    /// Schedules a periodically recurring event on this model at a future time,
+    /// which delivery is conditioned by a guard.
+    ///
+    /// The guard is re-evaluated with an immutable reference to the model just
+    /// before each occurrence of the event is dispatched. Occurrences for which
+    /// the guard returns `false` are skipped, but the event keeps recurring.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time or if the specified period is null.
+    pub fn schedule_conditional_periodic_event<G, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        period: Duration,
+        guard: G,
+        func: F,
+        arg: T,
+    ) -> Result<(), SchedulingError>
+    where
+        G: Fn(&M) -> bool + Clone + Send + 'static,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_conditional_periodic_event_from(
+                deadline,
+                period,
+                guard,
+                func,
+                arg,
+                &self.address,
+                self.origin_id,
+            )
+        })
     }
 
     /// Schedules a cancellable, periodically recurring event on this model at a
@@ -343,17 +1033,153 @@ impl<M: Model> Context<M> {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        let event_key = self.scheduler.schedule_keyed_periodic_event_from(
-            deadline,
-            period,
-            func,
-            arg,
-            &self.address,
-            self.origin_id,
-        )?;
+        let event_key = self.schedule_at(deadline, |deadline| {
+            self.scheduler.schedule_keyed_periodic_event_from(
+                deadline,
+                period,
+                func,
+                arg,
+                &self.address,
+                self.origin_id,
+            )
+        })?;
+
+        Ok(event_key)
+    }
+
+    /// Schedules a cancellable, periodically recurring event at a future time
+    /// and returns an event key, sending an event to `on_cancel` once the key
+    /// is cancelled.
+    ///
+    /// This method behaves like
+    /// [`schedule_keyed_periodic_event`](Self::schedule_keyed_periodic_event),
+    /// except that once the returned key is cancelled, or the
+    /// [`AutoActionKey`](crate::simulation::AutoActionKey) it was converted
+    /// into is dropped, the next periodic event is replaced by a final event
+    /// to the `on_cancel` input. The cancellation event is thus delivered at
+    /// the time the next periodic event was due, or during the current time
+    /// slice if the key is cancelled before the periodic event due at the
+    /// current time was delivered. No periodic event is delivered after the
+    /// cancellation event. Until then, the cancellation event is counted as a
+    /// pending event by the simulation.
+    ///
+    /// An error is returned if the specified time is not in the future of the
+    /// current simulation time or if the specified period is null.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use nexosim::ports::Output;
+    /// use nexosim::simulation::ActionKey;
+    ///
+    /// // A sensor polled at 10Hz which reports when polling is stopped.
+    /// #[derive(Default)]
+    /// pub struct Poller {
+    ///     pub stopped: Output<()>,
+    ///     poll_key: Option<ActionKey>,
+    /// }
+    ///
+    /// impl Poller {
+    ///     // Starts polling [input port].
+    ///     pub fn start(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         self.stop();
+    ///         self.poll_key = cx
+    ///             .schedule_keyed_periodic_event_with_cancel(
+    ///                 Duration::from_millis(100),
+    ///                 Duration::from_millis(100),
+    ///                 Self::poll,
+    ///                 (),
+    ///                 Self::on_stop,
+    ///             )
+    ///             .ok();
+    ///     }
+    ///
+    ///     // Stops polling [input port].
+    ///     pub fn stop(&mut self) {
+    ///         self.poll_key.take().map(|k| k.cancel());
+    ///     }
+    ///
+    ///     // Polls the sensor [private input port].
+    ///     fn poll(&mut self) {
+    ///         // ...
+    ///     }
+    ///
+    ///     // Reports that polling has stopped [private input port].
+    ///     async fn on_stop(&mut self) {
+    ///         self.stopped.send(()).await;
+    ///     }
+    /// }
+    ///
+    /// impl Model for Poller {}
+    /// ```
+    pub fn schedule_keyed_periodic_event_with_cancel<F, T, S, G, U>(
+        &self,
+        deadline: impl Deadline,
+        period: Duration,
+        func: F,
+        arg: T,
+        on_cancel: G,
+    ) -> Result<ActionKey, SchedulingError>
+    where
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+        G: for<'a> InputFn<'a, M, (), U> + Clone,
+        U: Send + 'static,
+    {
+        let event_key = self.schedule_at(deadline, |deadline| {
+            self.scheduler
+                .schedule_keyed_periodic_event_with_cancel_from(
+                    deadline,
+                    period,
+                    func,
+                    arg,
+                    on_cancel,
+                    &self.address,
+                    self.origin_id,
+                )
+        })?;
 
         Ok(event_key)
     }
+
+    /// Resolves the deadline and calls the scheduling closure, recording the
+    /// deadline upon success if it is the latest scheduled deadline.
+    fn schedule_at<R>(
+        &self,
+        deadline: impl Deadline,
+        schedule: impl FnOnce(MonotonicTime) -> Result<R, SchedulingError>,
+    ) -> Result<R, SchedulingError> {
+        let deadline = deadline
+            .checked_into_time(self.time())
+            .ok_or(SchedulingError::InvalidScheduledTime)?;
+        let result = schedule(deadline);
+        if result.is_ok() {
+            let mut latest_deadline = self.latest_deadline.lock().unwrap();
+            if latest_deadline.map_or(true, |latest| deadline > latest) {
+                *latest_deadline = Some(deadline);
+            }
+        }
+
+        result
+    }
+
+    /// Applies the interceptors installed on the model mailbox to an event
+    /// about to be delivered to an input port.
+    pub(crate) fn intercept<T: 'static>(&mut self, arg: &mut T) {
+        self.interceptors.apply(arg);
+    }
+}
+
+/// The identifiers of a pending event scheduled with
+/// [`Context::schedule_event_coalesced`].
+struct CoalescedEvent {
+    deadline: MonotonicTime,
+    func_id: TypeId,
+    key: Box<dyn Any + Send>,
 }
 
 impl<M: Model> fmt::Debug for Context<M> {
@@ -367,6 +1193,60 @@ impl<M: Model> fmt::Debug for Context<M> {
     }
 }
 
+/// A thread-safe handle to send events to the input ports of a model.
+///
+/// A `ModelInjector` is obtained with [`Context::injector`]. Unlike an
+/// [`Address`], it can be used from any thread, and in particular from a
+/// background task spawned with [`Context::spawn`].
+///
+/// Events are not sent directly to the model but are inserted into the
+/// scheduler queue at the current simulation time, so causality is preserved:
+/// an event is processed by the model at the earliest in the time slice that
+/// follows the one being processed, if any, and events sent from the same
+/// handle are processed in the order they were sent.
+///
+/// A `ModelInjector` does not count as a connection to the model, so a model
+/// can be removed from the simulation even if injectors to this model still
+/// exist; events sent to a removed model are discarded.
+pub struct ModelInjector<M: Model> {
+    injector: ActionInjector,
+    observer: Observer<M>,
+}
+
+impl<M: Model> ModelInjector<M> {
+    /// Sends an event to the specified input port of the model at the current
+    /// simulation time.
+    ///
+    /// The event is discarded and an [`ExecutionError::Terminated`] error is
+    /// returned if the simulation and all its
+    /// [`Scheduler`](crate::simulation::Scheduler)s have been dropped.
+    pub fn send<F, T, S>(&self, func: F, arg: T) -> Result<(), ExecutionError>
+    where
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        self.injector
+            .inject_event(func, arg, self.observer.sender())
+            .map_err(|_| ExecutionError::Terminated)
+    }
+}
+
+impl<M: Model> Clone for ModelInjector<M> {
+    fn clone(&self) -> Self {
+        Self {
+            injector: self.injector.clone(),
+            observer: self.observer.clone(),
+        }
+    }
+}
+
+impl<M: Model> fmt::Debug for ModelInjector<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModelInjector").finish_non_exhaustive()
+    }
+}
+
 /// Context available when building a model from a model prototype.
 ///
 /// A `BuildContext` can be used to add the sub-models of a hierarchical model
@@ -455,28 +1335,44 @@ pub struct BuildContext<'a, P: ProtoModel> {
     mailbox: &'a Mailbox<P::Model>,
     name: &'a String,
     scheduler: &'a GlobalScheduler,
-    executor: &'a Executor,
+    pending_models: &'a mut PendingModels,
     abort_signal: &'a Signal,
     model_names: &'a mut Vec<String>,
+    rng_seed: &'a SharedSeed,
+    panic_registry: &'a Arc<PanicRegistry>,
+    description_registry: &'a Arc<DescriptionRegistry>,
+    deterministic_ordering: bool,
+    validation_failures: &'a mut Vec<ValidationInfo>,
 }
 
 impl<'a, P: ProtoModel> BuildContext<'a, P> {
     /// Creates a new local context.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         mailbox: &'a Mailbox<P::Model>,
         name: &'a String,
         scheduler: &'a GlobalScheduler,
-        executor: &'a Executor,
+        pending_models: &'a mut PendingModels,
         abort_signal: &'a Signal,
         model_names: &'a mut Vec<String>,
+        rng_seed: &'a SharedSeed,
+        panic_registry: &'a Arc<PanicRegistry>,
+        description_registry: &'a Arc<DescriptionRegistry>,
+        deterministic_ordering: bool,
+        validation_failures: &'a mut Vec<ValidationInfo>,
     ) -> Self {
         Self {
             mailbox,
             name,
             scheduler,
-            executor,
+            pending_models,
             abort_signal,
             model_names,
+            rng_seed,
+            panic_registry,
+            description_registry,
+            deterministic_ordering,
+            validation_failures,
         }
     }
 
@@ -518,11 +1414,60 @@ impl<'a, P: ProtoModel> BuildContext<'a, P> {
             mailbox,
             submodel_name,
             self.scheduler.clone(),
-            self.executor,
+            self.pending_models,
             self.abort_signal,
             self.model_names,
+            self.rng_seed,
+            self.panic_registry,
+            self.description_registry,
+            self.deterministic_ordering,
+            true,
+            None,
+            self.validation_failures,
         );
     }
+
+    /// Adds a sub-model to the simulation bench and returns its address.
+    ///
+    /// This method behaves like [`add_submodel`](Self::add_submodel) but
+    /// also returns a handle to the sub-model's mailbox, which the parent
+    /// model may store and hand out so that some of the sub-model's input or
+    /// replier ports can be connected from outside the parent model.
+    ///
+    /// Note that the address gives access to *all* input and replier ports of
+    /// the sub-model, which partially lifts the encapsulation otherwise
+    /// provided by hierarchical models: events sent to the sub-model through
+    /// this address are not seen by the parent model, and their ordering
+    /// relative to the events sent by the parent model is only subject to the
+    /// usual [ordering guarantees](crate#message-ordering-guarantees). Parent
+    /// models should therefore only expose the address of sub-models whose
+    /// ports are meant to be part of their public interface.
+    pub fn add_submodel_with_address<S: ProtoModel>(
+        &mut self,
+        model: S,
+        mailbox: Mailbox<S::Model>,
+        name: impl Into<String>,
+    ) -> Address<S::Model> {
+        let address = mailbox.address();
+        self.add_submodel(model, mailbox, name);
+
+        address
+    }
+}
+
+/// The verbosity level of a log event emitted with [`Context::log_event`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Designates very serious errors.
+    Error,
+    /// Designates hazardous situations.
+    Warn,
+    /// Designates useful information.
+    Info,
+    /// Designates lower priority information.
+    Debug,
+    /// Designates very low priority, often extremely verbose, information.
+    Trace,
 }
 
 #[cfg(all(test, not(nexosim_loom)))]
@@ -530,10 +1475,14 @@ impl<M: Model> Context<M> {
     /// Creates a dummy context for testing purposes.
     pub(crate) fn new_dummy() -> Self {
         let dummy_address = Receiver::new(1).sender();
+        let origin_id = dummy_address.channel_id();
         Context::new(
             String::new(),
             GlobalScheduler::new_dummy(),
             Address(dummy_address),
+            origin_id,
+            ModelRng::new(SharedSeed::default(), 0),
+            Interceptors::default(),
         )
     }
 }
@@ -234,7 +234,13 @@
 //!
 //! Objects implementing the [`EventSink`] trait, such as [`EventSlot`] and
 //! [`EventBuffer`], are in turn similar to input ports. They can be connected
-//! to model outputs and collect events sent by such models.
+//! to model outputs and collect events sent by such models. A sink can be
+//! wrapped in a [`Timed`] adapter to also collect the simulation time at which
+//! each event was sent. When only aggregate values are of interest, a
+//! [`StatsSink`] maintains running statistics of numerical events without
+//! storing them. With the `csv` feature, a [`CsvSink`] writes events directly
+//! to a CSV file, and with the `tokio` feature, a [`TokioChannelSink`]
+//! forwards events to a Tokio channel for consumption by asynchronous tasks.
 //!
 //!
 //! # Connections
@@ -271,11 +277,23 @@ mod source;
 
 pub use input::markers;
 pub use input::{InputFn, ReplierFn};
-pub use output::{Output, Requestor, UniRequestor};
+pub use output::{LineError, LineId, LossCounter, Output, Requestor, TrySendError, UniRequestor};
 pub use sink::{
     blocking_event_queue::{BlockingEventQueue, BlockingEventQueueReader},
+    closure_sink::ClosureSink,
     event_buffer::EventBuffer,
+    event_relay::EventRelay,
     event_slot::EventSlot,
+    stats_sink::{Stats, StatsSink},
+    timed::Timed,
     EventSink, EventSinkStream, EventSinkWriter,
 };
-pub use source::{EventSource, QuerySource, ReplyReceiver};
+pub use source::{EventSource, ExternalEventSource, QuerySource, ReplyReceiver};
+
+#[cfg(feature = "csv")]
+pub use sink::csv_sink::{CsvSink, CsvSinkWriter, FlushPolicy};
+#[cfg(feature = "tokio")]
+pub use sink::tokio_channel_sink::{TokioChannelSink, TokioChannelSinkWriter};
+
+#[cfg(feature = "server")]
+pub(crate) use sink::Subscriber;
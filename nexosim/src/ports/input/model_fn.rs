@@ -6,6 +6,38 @@ use crate::model::{Context, Model};
 
 use super::markers;
 
+/// The future returned by a port method, instrumented with a span if the
+/// `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+type Traced<Fut> = tracing::instrument::Instrumented<Fut>;
+#[cfg(not(feature = "tracing"))]
+type Traced<Fut> = Fut;
+
+/// Instruments the future returned by port method `F` with a `port` span if
+/// the `tracing` feature is enabled.
+#[inline(always)]
+fn traced<F, Fut: Future>(fut: Fut) -> Traced<Fut> {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::Instrument::instrument(fut, port_span::<F>())
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        fut
+    }
+}
+
+/// Creates a `port` span for port method `F`.
+#[cfg(feature = "tracing")]
+fn port_span<F>() -> tracing::Span {
+    tracing::span!(
+        target: env!("CARGO_PKG_NAME"),
+        tracing::Level::TRACE,
+        "port",
+        method = std::any::type_name::<F>()
+    )
+}
+
 /// A function, method or closures that can be used as an *input port*.
 ///
 /// This trait is in particular implemented for any function or method with the
@@ -39,7 +71,12 @@ where
 {
     type Future = Ready<()>;
 
-    fn call(self, model: &'a mut M, _arg: (), _cx: &'a mut Context<M>) -> Self::Future {
+    fn call(self, model: &'a mut M, _arg: (), cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut ());
+
+        #[cfg(feature = "tracing")]
+        let _span = port_span::<F>().entered();
+
         self(model);
 
         ready(())
@@ -49,11 +86,17 @@ where
 impl<'a, M, T, F> InputFn<'a, M, T, markers::WithoutContext> for F
 where
     M: Model,
+    T: 'static,
     F: FnOnce(&'a mut M, T) + Send + 'static,
 {
     type Future = Ready<()>;
 
-    fn call(self, model: &'a mut M, arg: T, _cx: &'a mut Context<M>) -> Self::Future {
+    fn call(self, model: &'a mut M, mut arg: T, cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut arg);
+
+        #[cfg(feature = "tracing")]
+        let _span = port_span::<F>().entered();
+
         self(model, arg);
 
         ready(())
@@ -63,11 +106,17 @@ where
 impl<'a, M, T, F> InputFn<'a, M, T, markers::WithContext> for F
 where
     M: Model,
+    T: 'static,
     F: FnOnce(&'a mut M, T, &'a mut Context<M>) + Send + 'static,
 {
     type Future = Ready<()>;
 
-    fn call(self, model: &'a mut M, arg: T, cx: &'a mut Context<M>) -> Self::Future {
+    fn call(self, model: &'a mut M, mut arg: T, cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut arg);
+
+        #[cfg(feature = "tracing")]
+        let _span = port_span::<F>().entered();
+
         self(model, arg, cx);
 
         ready(())
@@ -80,36 +129,44 @@ where
     Fut: Future<Output = ()> + Send + 'a,
     F: FnOnce(&'a mut M) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
-    fn call(self, model: &'a mut M, _arg: (), _cx: &'a mut Context<M>) -> Self::Future {
-        self(model)
+    fn call(self, model: &'a mut M, _arg: (), cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut ());
+
+        traced::<F, _>(self(model))
     }
 }
 
 impl<'a, M, T, Fut, F> InputFn<'a, M, T, markers::AsyncWithoutContext> for F
 where
     M: Model,
+    T: 'static,
     Fut: Future<Output = ()> + Send + 'a,
     F: FnOnce(&'a mut M, T) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
-    fn call(self, model: &'a mut M, arg: T, _cx: &'a mut Context<M>) -> Self::Future {
-        self(model, arg)
+    fn call(self, model: &'a mut M, mut arg: T, cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut arg);
+
+        traced::<F, _>(self(model, arg))
     }
 }
 
 impl<'a, M, T, Fut, F> InputFn<'a, M, T, markers::AsyncWithContext> for F
 where
     M: Model,
+    T: 'static,
     Fut: Future<Output = ()> + Send + 'a,
     F: FnOnce(&'a mut M, T, &'a mut Context<M>) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
-    fn call(self, model: &'a mut M, arg: T, cx: &'a mut Context<M>) -> Self::Future {
-        self(model, arg, cx)
+    fn call(self, model: &'a mut M, mut arg: T, cx: &'a mut Context<M>) -> Self::Future {
+        cx.intercept(&mut arg);
+
+        traced::<F, _>(self(model, arg, cx))
     }
 }
 
@@ -141,10 +198,10 @@ where
     Fut: Future<Output = R> + Send + 'a,
     F: FnOnce(&'a mut M) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
     fn call(self, model: &'a mut M, _arg: (), _cx: &'a mut Context<M>) -> Self::Future {
-        self(model)
+        traced::<F, _>(self(model))
     }
 }
 
@@ -154,10 +211,10 @@ where
     Fut: Future<Output = R> + Send + 'a,
     F: FnOnce(&'a mut M, T) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
     fn call(self, model: &'a mut M, arg: T, _cx: &'a mut Context<M>) -> Self::Future {
-        self(model, arg)
+        traced::<F, _>(self(model, arg))
     }
 }
 
@@ -167,9 +224,9 @@ where
     Fut: Future<Output = R> + Send + 'a,
     F: FnOnce(&'a mut M, T, &'a mut Context<M>) -> Fut + Send + 'static,
 {
-    type Future = Fut;
+    type Future = Traced<Fut>;
 
     fn call(self, model: &'a mut M, arg: T, cx: &'a mut Context<M>) -> Self::Future {
-        self(model, arg, cx)
+        traced::<F, _>(self(model, arg, cx))
     }
 }
@@ -1,21 +1,28 @@
 mod broadcaster;
 mod sender;
 
+use std::any::type_name;
 use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_event::Event;
 
 use crate::model::Model;
 use crate::ports::EventSink;
 use crate::ports::{InputFn, ReplierFn};
-use crate::simulation::Address;
+use crate::simulation::{Address, PortLabel, PortTarget};
 use crate::util::cached_rw_lock::CachedRwLock;
 use crate::util::unwrap_or_throw::UnwrapOrThrow;
 
-use broadcaster::{EventBroadcaster, QueryBroadcaster};
+use broadcaster::{EventBroadcaster, QueryBroadcaster, TryBroadcastStatus};
 use sender::{FilterMapReplierSender, Sender};
 
 use self::sender::{
-    EventSinkSender, FilterMapEventSinkSender, FilterMapInputSender, InputSender,
-    MapEventSinkSender, MapInputSender, MapReplierSender, ReplierSender,
+    DelayedInputSender, EventSinkSender, FilterMapEventSinkSender, FilterMapInputSender,
+    InputSender, LossyInputSender, MapEventSinkSender, MapInputSender, MapReplierSender,
+    ReplierSender,
 };
 
 /// An output port.
@@ -30,6 +37,8 @@ use self::sender::{
 #[derive(Clone)]
 pub struct Output<T: Clone + Send + 'static> {
     broadcaster: CachedRwLock<EventBroadcaster<T>>,
+    pending_sends: Option<Arc<PendingSends>>,
+    label: PortLabel,
 }
 
 impl<T: Clone + Send + 'static> Output<T> {
@@ -38,6 +47,31 @@ impl<T: Clone + Send + 'static> Output<T> {
         Self::default()
     }
 
+    /// Creates a disconnected `Output` port that keeps track of the sends in
+    /// progress on this port and its clones so that they can be awaited with
+    /// [`Output::flush`].
+    ///
+    /// Keeping track of the sends in progress has a small cost on each send,
+    /// which is why it is only enabled for ports created with this
+    /// constructor.
+    pub fn new_flushable() -> Self {
+        Self {
+            broadcaster: CachedRwLock::new(EventBroadcaster::default()),
+            pending_sends: Some(Arc::new(PendingSends::new())),
+            label: PortLabel::new(type_name::<Self>()),
+        }
+    }
+
+    /// Sets the label identifying this port and its clones in the topology of
+    /// the simulation.
+    ///
+    /// The label applies to all connections of the port, including those made
+    /// before the label is set. See
+    /// [`Simulation::topology`](crate::simulation::Simulation::topology).
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label.set(label.into());
+    }
+
     /// Adds a connection to an input port of the model specified by the
     /// address.
     ///
@@ -50,18 +84,158 @@ impl<T: Clone + Send + 'static> Output<T> {
         F: for<'a> InputFn<'a, M, T, S> + Clone,
         S: Send + 'static,
     {
-        let sender = Box::new(InputSender::new(input, address.into().0));
+        let sender = Box::new(InputSender::new(
+            input,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
+        ));
+        self.broadcaster.write().unwrap().add(sender);
+    }
+
+    /// Adds a connection to the input port designated by the target.
+    ///
+    /// This is equivalent to [`Output::connect`], with the input port and the
+    /// address provided as a single [`PortTarget`] created with
+    /// [`Address::port`].
+    pub fn connect_to<M, F, S>(&mut self, target: PortTarget<M, F>)
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        S: Send + 'static,
+    {
+        self.connect(target.port, target.address);
+    }
+
+    /// Adds a connection to an input port of the model specified by the
+    /// address, delaying the delivery of each event by a fixed duration.
+    ///
+    /// Each event sent on this connection is scheduled for delivery `delay`
+    /// after it was sent, the delay being expressed in simulation time. The
+    /// other ports connected to the same output are not affected.
+    ///
+    /// Events sent on this connection are always delivered in the order they
+    /// were sent, including events sent at the same simulation time. A zero
+    /// delay is equivalent to a regular connection made with
+    /// [`Output::connect`].
+    ///
+    /// Events whose delivery time would exceed
+    /// [`MonotonicTime::MAX`](crate::time::MonotonicTime::MAX) are dropped
+    /// since the simulation can never reach that time. Sending on a delayed
+    /// connection outside of a simulation fails as if the target mailbox was
+    /// closed.
+    pub fn connect_delayed<M, F, S>(
+        &mut self,
+        input: F,
+        address: impl Into<Address<M>>,
+        delay: Duration,
+    ) where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        S: Send + 'static,
+    {
+        if delay.is_zero() {
+            self.connect(input, address);
+
+            return;
+        }
+
+        let sender = Box::new(DelayedInputSender::new(
+            input,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
+            delay,
+        ));
+        self.broadcaster.write().unwrap().add(sender);
+    }
+
+    /// Adds a lossy connection to an input port of the model specified by the
+    /// address.
+    ///
+    /// Unlike with a regular connection, an event sent on a lossy connection
+    /// is dropped if the mailbox of the target model is full at the time the
+    /// event is sent, rather than waiting until the mailbox has enough
+    /// capacity. This can be used to model unreliable links such as UDP
+    /// channels. Only this connection is affected: other ports connected to
+    /// the same output keep applying backpressure.
+    ///
+    /// The returned [`LossCounter`] keeps track of the number of dropped
+    /// events.
+    ///
+    /// The input port must be an asynchronous method of a model of type `M`
+    /// taking as argument a value of type `T` plus, optionally, a scheduler
+    /// reference.
+    pub fn connect_lossy<M, F, S>(
+        &mut self,
+        input: F,
+        address: impl Into<Address<M>>,
+    ) -> LossCounter
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        S: Send + 'static,
+    {
+        let counter = LossCounter::default();
+        let sender = Box::new(LossyInputSender::new(
+            input,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
+            counter.count.clone(),
+        ));
         self.broadcaster.write().unwrap().add(sender);
+
+        counter
     }
 
     /// Adds a connection to an event sink such as an
     /// [`EventSlot`](crate::ports::EventSlot) or
     /// [`EventBuffer`](crate::ports::EventBuffer).
-    pub fn connect_sink<S: EventSink<T>>(&mut self, sink: &S) {
+    ///
+    /// The returned line identifier can be used to remove the connection with
+    /// [`Output::disconnect_sink`].
+    pub fn connect_sink<S: EventSink<T>>(&mut self, sink: &S) -> LineId {
         let sender = Box::new(EventSinkSender::new(sink.writer()));
         self.broadcaster.write().unwrap().add(sender)
     }
 
+    /// Removes a connection to an event sink.
+    ///
+    /// The line identifier is the one returned by the method that made the
+    /// connection, such as [`Output::connect_sink`]. Events sent after the
+    /// disconnection are no longer forwarded to the sink, and since the list
+    /// of connected ports is shared, this also applies to all clones of this
+    /// port. An error is returned if the line does not exist, for instance
+    /// because it was already disconnected or because it was made through
+    /// another port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::{EventBuffer, Output};
+    ///
+    /// let mut output: Output<u64> = Output::new();
+    /// let sink = EventBuffer::new();
+    ///
+    /// let line_id = output.connect_sink(&sink);
+    /// assert!(output.is_connected());
+    ///
+    /// output.disconnect_sink(line_id).unwrap();
+    /// assert!(!output.is_connected());
+    /// assert!(output.disconnect_sink(line_id).is_err());
+    /// ```
+    pub fn disconnect_sink(&mut self, line_id: LineId) -> Result<(), LineError> {
+        if self.broadcaster.write().unwrap().remove(line_id) {
+            Ok(())
+        } else {
+            Err(LineError {})
+        }
+    }
+
     /// Adds an auto-converting connection to an input port of the model
     /// specified by the address.
     ///
@@ -79,7 +253,14 @@ impl<T: Clone + Send + 'static> Output<T> {
         U: Send + 'static,
         S: Send + 'static,
     {
-        let sender = Box::new(MapInputSender::new(map, input, address.into().0));
+        let sender = Box::new(MapInputSender::new(
+            map,
+            input,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
+        ));
         self.broadcaster.write().unwrap().add(sender);
     }
 
@@ -89,14 +270,17 @@ impl<T: Clone + Send + 'static> Output<T> {
     ///
     /// Events are mapped to another type using the closure provided in
     /// argument.
-    pub fn map_connect_sink<C, U, S>(&mut self, map: C, sink: &S)
+    ///
+    /// The returned line identifier can be used to remove the connection with
+    /// [`Output::disconnect_sink`].
+    pub fn map_connect_sink<C, U, S>(&mut self, map: C, sink: &S) -> LineId
     where
         C: Fn(&T) -> U + Send + Sync + 'static,
         U: Send + 'static,
         S: EventSink<U>,
     {
         let sender = Box::new(MapEventSinkSender::new(map, sink.writer()));
-        self.broadcaster.write().unwrap().add(sender);
+        self.broadcaster.write().unwrap().add(sender)
     }
 
     /// Adds an auto-converting, filtered connection to an input port of the
@@ -123,7 +307,10 @@ impl<T: Clone + Send + 'static> Output<T> {
         let sender = Box::new(FilterMapInputSender::new(
             filter_map,
             input,
-            address.into().0,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
         ));
         self.broadcaster.write().unwrap().add(sender);
     }
@@ -134,27 +321,145 @@ impl<T: Clone + Send + 'static> Output<T> {
     ///
     /// Events are mapped to another type using the closure provided in
     /// argument.
-    pub fn filter_map_connect_sink<C, U, S>(&mut self, filter_map: C, sink: &S)
+    ///
+    /// The returned line identifier can be used to remove the connection with
+    /// [`Output::disconnect_sink`].
+    pub fn filter_map_connect_sink<C, U, S>(&mut self, filter_map: C, sink: &S) -> LineId
     where
         C: Fn(&T) -> Option<U> + Send + Sync + 'static,
         U: Send + 'static,
         S: EventSink<U>,
     {
         let sender = Box::new(FilterMapEventSinkSender::new(filter_map, sink.writer()));
-        self.broadcaster.write().unwrap().add(sender);
+        self.broadcaster.write().unwrap().add(sender)
+    }
+
+    /// Returns `true` if at least one input port or sink is connected.
+    ///
+    /// This makes it possible to skip the construction of costly events when
+    /// nobody listens. Connections made through any clone of this port are
+    /// taken into account.
+    pub fn is_connected(&self) -> bool {
+        !self
+            .broadcaster
+            .read_shared(|broadcaster| broadcaster.is_empty())
+            .unwrap()
     }
 
     /// Broadcasts an event to all connected input ports.
     pub async fn send(&mut self, arg: T) {
+        // Sends in progress are only tracked if the port is flushable.
+        let _guard = self.pending_sends.as_deref().map(PendingSends::enter);
         let broadcaster = self.broadcaster.write_scratchpad().unwrap();
         broadcaster.broadcast(arg).await.unwrap_or_throw();
     }
+
+    /// Broadcasts an event to all connected input ports if none of the target
+    /// mailboxes is full, or returns the event otherwise.
+    ///
+    /// Unlike [`Output::send`], this method does not wait for the target
+    /// mailboxes to have enough capacity: if any of the mailboxes of the
+    /// connected input ports is full, the event is sent to none of the ports
+    /// and is returned in the error. Connections to event sinks and lossy
+    /// connections (see [`Output::connect_lossy`]) never prevent the event from
+    /// being sent.
+    ///
+    /// This method never waits, so it can be called from synchronous code.
+    /// Note, however, that other models may concurrently send events to the
+    /// same mailboxes, and that several connections may target the same
+    /// mailbox. Should a mailbox become full after the capacity check, the
+    /// event is only delivered to some of the ports and a
+    /// [`TrySendError::PartiallySent`] error is returned with the event and
+    /// the number of ports it could not be delivered to.
+    pub fn try_send(&mut self, arg: T) -> Result<(), TrySendError<T>> {
+        let broadcaster = self.broadcaster.write_scratchpad().unwrap();
+        match broadcaster.try_broadcast(&arg).unwrap_or_throw() {
+            TryBroadcastStatus::Sent => Ok(()),
+            TryBroadcastStatus::Full => Err(TrySendError::Full(arg)),
+            TryBroadcastStatus::PartiallySent(undelivered) => {
+                Err(TrySendError::PartiallySent(arg, undelivered))
+            }
+        }
+    }
+
+    /// Broadcasts a batch of events to all connected input ports.
+    ///
+    /// Each connected input port receives the whole batch as a single message,
+    /// so the target model processes the events contiguously and in iteration
+    /// order, without interleaving them with events sent concurrently by other
+    /// ports or by clones of this port. The ordering of the batch relative to
+    /// other events otherwise follows the usual
+    /// [ordering guarantees](crate#message-ordering-guarantees). The batch is
+    /// cloned for each connected port but the last one, to which it is moved.
+    ///
+    /// The ports are served in turn, so a full target mailbox delays the
+    /// delivery of the batch to the ports that follow it. Events sent to event
+    /// sinks and on lossy or delayed connections (see [`Output::connect_lossy`]
+    /// and [`Output::connect_delayed`]) are delivered one after the other.
+    pub async fn send_all(&mut self, args: impl IntoIterator<Item = T>) {
+        let args: Vec<T> = args.into_iter().collect();
+        if args.is_empty() {
+            return;
+        }
+
+        let _guard = self.pending_sends.as_deref().map(PendingSends::enter);
+        let broadcaster = self.broadcaster.write_scratchpad().unwrap();
+        broadcaster.broadcast_all(args).await.unwrap_or_throw();
+    }
+
+    /// Waits until all events being sent on this port or on any of its clones
+    /// have been accepted by their target mailboxes.
+    ///
+    /// Awaiting [`Output::send`] already guarantees that the event has been
+    /// accepted by all target mailboxes, so a model sending events in
+    /// sequence on a single port does not need to call this method. Clones of
+    /// a port may however be used concurrently by other models (see
+    /// [cloned ports](crate::ports#example-with-cloned-ports)), and this
+    /// method can then serve as a barrier ensuring that none of their sends
+    /// is still waiting for mailbox capacity.
+    ///
+    /// This method only concerns the enqueuing of events: it does not wait
+    /// for the events to be processed by the target models, so the usual
+    /// [ordering guarantees](crate#message-ordering-guarantees) are neither
+    /// strengthened nor weakened. Events sent on a delayed connection (see
+    /// [`Output::connect_delayed`]) are considered accepted once they have
+    /// been scheduled. Note that this method completes only when no send is
+    /// in progress, so it may keep waiting as long as clones of this port
+    /// start new sends.
+    ///
+    /// A model must not flush a port while one of the clones of this port may
+    /// be blocked sending an event to the mailbox of this very model: since
+    /// the model cannot process its mailbox until the flush completes, and
+    /// the flush cannot complete until the mailbox has room for the event,
+    /// the simulation would deadlock.
+    ///
+    /// Sends are only tracked on ports created with [`Output::new_flushable`]:
+    /// for other ports, this method returns immediately without waiting.
+    pub async fn flush(&mut self) {
+        let pending_sends = match self.pending_sends.as_deref() {
+            Some(pending_sends) => pending_sends,
+            None => return,
+        };
+
+        pending_sends
+            .signal
+            .wait_until(|| {
+                if pending_sends.count.load(Ordering::Acquire) == 0 {
+                    Some(())
+                } else {
+                    None
+                }
+            })
+            .await;
+    }
 }
 
 impl<T: Clone + Send + 'static> Default for Output<T> {
     fn default() -> Self {
         Self {
             broadcaster: CachedRwLock::new(EventBroadcaster::default()),
+            pending_sends: None,
+            label: PortLabel::new(type_name::<Self>()),
         }
     }
 }
@@ -169,6 +474,135 @@ impl<T: Clone + Send + 'static> fmt::Debug for Output<T> {
     }
 }
 
+/// A count of the sends in progress on an output port and its clones.
+struct PendingSends {
+    count: AtomicUsize,
+    signal: Event,
+}
+
+impl PendingSends {
+    fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            signal: Event::new(),
+        }
+    }
+
+    /// Registers a send in progress until the returned guard is dropped.
+    fn enter(&self) -> PendingSendGuard<'_> {
+        self.count.fetch_add(1, Ordering::Relaxed);
+
+        PendingSendGuard(self)
+    }
+}
+
+/// A guard that unregisters a send in progress when dropped, including when
+/// the send future is cancelled.
+struct PendingSendGuard<'a>(&'a PendingSends);
+
+impl Drop for PendingSendGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::Release) == 1 {
+            self.0.signal.notify_all();
+        }
+    }
+}
+
+/// An error returned by [`Output::try_send`] when the event could not be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The mailbox of at least one connected input port is full, so the event
+    /// was sent to none of the ports.
+    Full(T),
+    /// A mailbox became full during the broadcast, so the event was only
+    /// sent to some of the ports; the second field is the number of ports the
+    /// event was not sent to.
+    PartiallySent(T, usize),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the event that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(event) | Self::PartiallySent(event, _) => event,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("Full(..)"),
+            Self::PartiallySent(_, undelivered) => {
+                write!(f, "PartiallySent(.., {})", undelivered)
+            }
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("the mailbox of a connected input port is full"),
+            Self::PartiallySent(_, undelivered) => write!(
+                f,
+                "the event could not be sent to {} connected input port(s) with a full mailbox",
+                undelivered
+            ),
+        }
+    }
+}
+
+impl<T> std::error::Error for TrySendError<T> {}
+
+/// Unique identifier of a connection made on an output port.
+///
+/// Line identifiers are returned by the methods connecting an [`Output`] to an
+/// event sink, such as [`Output::connect_sink`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineId(pub(crate) u64);
+
+/// An error returned when the specified line cannot be found.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LineError {}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the specified line could not be found")
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// A counter of the events dropped by a lossy connection or event sink.
+///
+/// See [`Output::connect_lossy`].
+#[derive(Clone, Default)]
+pub struct LossCounter {
+    count: Arc<AtomicU64>,
+}
+
+impl LossCounter {
+    /// Returns the number of events dropped so far.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of dropped events.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn increment(&self) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for LossCounter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LossCounter")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
 /// A requestor port.
 ///
 /// `Requestor` ports can be connected to replier ports, i.e. to asynchronous
@@ -181,6 +615,7 @@ impl<T: Clone + Send + 'static> fmt::Debug for Output<T> {
 #[derive(Clone)]
 pub struct Requestor<T: Clone + Send + 'static, R: Send + 'static> {
     broadcaster: CachedRwLock<QueryBroadcaster<T, R>>,
+    label: PortLabel,
 }
 
 impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
@@ -189,6 +624,16 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
         Self::default()
     }
 
+    /// Sets the label identifying this port and its clones in the topology of
+    /// the simulation.
+    ///
+    /// The label applies to all connections of the port, including those made
+    /// before the label is set. See
+    /// [`Simulation::topology`](crate::simulation::Simulation::topology).
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label.set(label.into());
+    }
+
     /// Adds a connection to a replier port of the model specified by the
     /// address.
     ///
@@ -201,10 +646,30 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
         F: for<'a> ReplierFn<'a, M, T, R, S> + Clone,
         S: Send + 'static,
     {
-        let sender = Box::new(ReplierSender::new(replier, address.into().0));
+        let sender = Box::new(ReplierSender::new(
+            replier,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
+        ));
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Adds a connection to the replier port designated by the target.
+    ///
+    /// This is equivalent to [`Requestor::connect`], with the replier port and
+    /// the address provided as a single [`PortTarget`] created with
+    /// [`Address::port`].
+    pub fn connect_to<M, F, S>(&mut self, target: PortTarget<M, F>)
+    where
+        M: Model,
+        F: for<'a> ReplierFn<'a, M, T, R, S> + Clone,
+        S: Send + 'static,
+    {
+        self.connect(target.port, target.address);
+    }
+
     /// Adds an auto-converting connection to a replier port of the model
     /// specified by the address.
     ///
@@ -234,11 +699,82 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
             query_map,
             reply_map,
             replier,
-            address.into().0,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
         ));
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Adds a connection to a replier port of the model specified by the
+    /// address, wrapping its replies into a variant of the reply type.
+    ///
+    /// This is a special case of [`Requestor::map_connect`] where the query is
+    /// forwarded unchanged and the native reply of the replier port is mapped
+    /// to the reply type `R` with the provided closure, which is typically an
+    /// enum variant constructor. It makes it possible to aggregate replies of
+    /// heterogeneous types from several replier ports into a single typed
+    /// result.
+    ///
+    /// The replier port must be an asynchronous method of a model of type `M`
+    /// taking as argument a value of type `T` plus, optionally, a context
+    /// reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::Requestor;
+    /// use nexosim::simulation::Mailbox;
+    ///
+    /// pub enum Status {
+    ///     Pump(f64),
+    ///     Valve(bool),
+    /// }
+    ///
+    /// pub struct Pump {
+    ///     flow_rate: f64,
+    /// }
+    /// impl Pump {
+    ///     pub async fn flow_rate(&mut self) -> f64 {
+    ///         self.flow_rate
+    ///     }
+    /// }
+    /// impl Model for Pump {}
+    ///
+    /// pub struct Valve {
+    ///     is_open: bool,
+    /// }
+    /// impl Valve {
+    ///     pub async fn is_open(&mut self) -> bool {
+    ///         self.is_open
+    ///     }
+    /// }
+    /// impl Model for Valve {}
+    ///
+    /// let pump_mbox = Mailbox::<Pump>::new();
+    /// let valve_mbox = Mailbox::<Valve>::new();
+    ///
+    /// let mut status = Requestor::<(), Status>::new();
+    /// status.connect_variant(Status::Pump, Pump::flow_rate, &pump_mbox);
+    /// status.connect_variant(Status::Valve, Valve::is_open, &valve_mbox);
+    /// ```
+    pub fn connect_variant<M, D, F, Q, S>(
+        &mut self,
+        variant: D,
+        replier: F,
+        address: impl Into<Address<M>>,
+    ) where
+        M: Model,
+        D: Fn(Q) -> R + Send + Sync + 'static,
+        F: for<'a> ReplierFn<'a, M, T, Q, S> + Clone,
+        Q: Send + 'static,
+        S: Send + 'static,
+    {
+        self.map_connect(|arg: &T| arg.clone(), variant, replier, address);
+    }
+
     /// Adds an auto-converting, filtered connection to a replier port of the
     /// model specified by the address.
     ///
@@ -268,12 +804,31 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
             query_filter_map,
             reply_map,
             replier,
-            address.into().0,
+            address
+                .into()
+                .0
+                .with_port(self.label.clone(), type_name::<F>(), type_name::<T>()),
         ));
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Returns `true` if at least one replier port is connected.
+    ///
+    /// Connections made through any clone of this port are taken into
+    /// account. Since [`Requestor::send`] returns immediately when no replier
+    /// port is connected, this is mainly useful to skip the construction of
+    /// costly requests.
+    pub fn is_connected(&self) -> bool {
+        !self
+            .broadcaster
+            .read_shared(|broadcaster| broadcaster.is_empty())
+            .unwrap()
+    }
+
     /// Broadcasts a query to all connected replier ports.
+    ///
+    /// If no replier port is connected, an empty iterator is returned
+    /// immediately without sending any message.
     pub async fn send(&mut self, arg: T) -> impl Iterator<Item = R> + '_ {
         self.broadcaster
             .write_scratchpad()
@@ -282,12 +837,69 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
             .await
             .unwrap_or_throw()
     }
+
+    /// Broadcasts a query to all reachable replier ports and returns the
+    /// replies together with the number of unreachable replier ports.
+    ///
+    /// Unlike [`Requestor::send`], which fails the simulation when a replier
+    /// port cannot be reached, for instance because its model was removed,
+    /// this method skips unreachable replier ports and only collects the
+    /// replies of the others.
+    pub async fn send_best_effort(&mut self, arg: T) -> (impl Iterator<Item = R> + '_, usize) {
+        self.broadcaster
+            .write_scratchpad()
+            .unwrap()
+            .broadcast_best_effort(arg)
+            .await
+    }
+
+    /// Broadcasts a query to all connected replier ports and returns the
+    /// replies together with the index of the connection that produced them.
+    ///
+    /// Connections are indexed in the order in which they were established,
+    /// starting from 0. Filtered connections (see
+    /// [`Requestor::filter_map_connect`]) that did not forward the query do not
+    /// produce any reply, but are nevertheless accounted for in the indexing.
+    ///
+    /// This makes it possible to identify which replier produced a given
+    /// reply, for instance to diagnose divergent replies within a set of
+    /// identical models.
+    pub async fn send_enumerated(&mut self, arg: T) -> impl Iterator<Item = (usize, R)> + '_ {
+        self.broadcaster
+            .write_scratchpad()
+            .unwrap()
+            .broadcast_enumerated(arg)
+            .await
+            .unwrap_or_throw()
+    }
+
+    /// Broadcasts a query to all connected replier ports and collects the
+    /// replies into a container of type `C`.
+    ///
+    /// The replies are collected in the order in which the replier ports were
+    /// connected.
+    pub async fn send_collect<C: FromIterator<R>>(&mut self, arg: T) -> C {
+        self.send(arg).await.collect()
+    }
+
+    /// Broadcasts a query to all connected replier ports and folds the replies
+    /// with the provided closure, starting from an initial value.
+    ///
+    /// The replies are folded in the order in which the replier ports were
+    /// connected, without intermediate allocation.
+    pub async fn send_fold<B, F>(&mut self, arg: T, init: B, f: F) -> B
+    where
+        F: FnMut(B, R) -> B,
+    {
+        self.send(arg).await.fold(init, f)
+    }
 }
 
 impl<T: Clone + Send + 'static, R: Send + 'static> Default for Requestor<T, R> {
     fn default() -> Self {
         Self {
             broadcaster: CachedRwLock::new(QueryBroadcaster::default()),
+            label: PortLabel::new(type_name::<Self>()),
         }
     }
 }
@@ -306,9 +918,14 @@ impl<T: Clone + Send + 'static, R: Send + 'static> fmt::Debug for Requestor<T, R
 ///
 /// A `UniRequestor` port is connected to a replier port, i.e. to an
 /// asynchronous model method that returns a value.
+///
+/// The connection is established when the port is created and cannot be
+/// modified afterwards, so a `UniRequestor` can never be connected to more than
+/// one replier. Use a [`Requestor`] if several repliers are needed.
 #[derive(Clone)]
 pub struct UniRequestor<T: Clone + Send + 'static, R: Send + 'static> {
     sender: Box<dyn Sender<T, R>>,
+    label: PortLabel,
 }
 
 impl<T: Clone + Send + 'static, R: Send + 'static> UniRequestor<T, R> {
@@ -324,9 +941,16 @@ impl<T: Clone + Send + 'static, R: Send + 'static> UniRequestor<T, R> {
         F: for<'a> ReplierFn<'a, M, T, R, S> + Clone,
         S: Send + 'static,
     {
-        let sender = Box::new(ReplierSender::new(replier, address.into().0));
+        let label = PortLabel::new(type_name::<Self>());
+        let sender = Box::new(ReplierSender::new(
+            replier,
+            address
+                .into()
+                .0
+                .with_port(label.clone(), type_name::<F>(), type_name::<T>()),
+        ));
 
-        Self { sender }
+        Self { sender, label }
     }
 
     /// Creates an auto-converting `UniRequestor` port connected to a replier
@@ -354,14 +978,18 @@ impl<T: Clone + Send + 'static, R: Send + 'static> UniRequestor<T, R> {
         Q: Send + 'static,
         S: Send + 'static,
     {
+        let label = PortLabel::new(type_name::<Self>());
         let sender = Box::new(MapReplierSender::new(
             query_map,
             reply_map,
             replier,
-            address.into().0,
+            address
+                .into()
+                .0
+                .with_port(label.clone(), type_name::<F>(), type_name::<T>()),
         ));
 
-        Self { sender }
+        Self { sender, label }
     }
 
     /// Creates an auto-converting, filtered `UniRequestor` port connected to a
@@ -389,14 +1017,37 @@ impl<T: Clone + Send + 'static, R: Send + 'static> UniRequestor<T, R> {
         Q: Send + 'static,
         S: Send + 'static,
     {
+        let label = PortLabel::new(type_name::<Self>());
         let sender = Box::new(FilterMapReplierSender::new(
             query_filter_map,
             reply_map,
             replier,
-            address.into().0,
+            address
+                .into()
+                .0
+                .with_port(label.clone(), type_name::<F>(), type_name::<T>()),
         ));
 
-        Self { sender }
+        Self { sender, label }
+    }
+
+    /// Sets the label identifying this port and its clones in the topology of
+    /// the simulation.
+    ///
+    /// The label applies to all connections of the port, including those made
+    /// before the label is set. See
+    /// [`Simulation::topology`](crate::simulation::Simulation::topology).
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label.set(label.into());
+    }
+
+    /// Returns `true` since a `UniRequestor` is always connected to exactly
+    /// one replier port.
+    ///
+    /// This method is provided for consistency with
+    /// [`Requestor::is_connected`].
+    pub fn is_connected(&self) -> bool {
+        true
     }
 
     /// Sends a query to the connected replier port.
@@ -416,3 +1067,108 @@ impl<T: Clone + Send + 'static, R: Send + 'static> fmt::Debug for UniRequestor<T
         write!(f, "UniRequestor")
     }
 }
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use crate::ports::EventBuffer;
+    use crate::simulation::Mailbox;
+
+    use super::*;
+
+    struct TestModel;
+    impl TestModel {
+        async fn replier(&mut self, arg: u32) -> u32 {
+            arg
+        }
+    }
+    impl Model for TestModel {}
+
+    #[test]
+    fn output_is_connected() {
+        let mut output = Output::<u32>::new();
+        let clone = output.clone();
+        assert!(!output.is_connected());
+        assert!(!clone.is_connected());
+
+        let sink = EventBuffer::new();
+        output.connect_sink(&sink);
+        assert!(output.is_connected());
+        assert!(clone.is_connected());
+    }
+
+    #[test]
+    fn output_send_all() {
+        use futures_util::FutureExt;
+
+        let mut output = Output::<u32>::new();
+        let mut sink1 = EventBuffer::new();
+        let mut sink2 = EventBuffer::new();
+        output.connect_sink(&sink1);
+        output.connect_sink(&sink2);
+
+        // An empty batch is a no-op.
+        output.send_all([]).now_or_never().unwrap();
+        assert!(sink1.next().is_none());
+
+        output.send_all([1, 2, 3]).now_or_never().unwrap();
+        output.send(4).now_or_never().unwrap();
+        assert_eq!(sink1.by_ref().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(sink2.by_ref().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn output_flush() {
+        use futures_util::FutureExt;
+
+        let mut output = Output::<u32>::new_flushable();
+        let clone = output.clone();
+
+        // No send is in progress.
+        assert!(output.flush().now_or_never().is_some());
+
+        // A send is in progress on a clone.
+        let guard = clone.pending_sends.as_deref().unwrap().enter();
+        let mut flush = Box::pin(output.flush());
+        assert!((&mut flush).now_or_never().is_none());
+
+        // The send has completed.
+        drop(guard);
+        assert!(flush.now_or_never().is_some());
+    }
+
+    #[test]
+    fn output_flush_not_flushable() {
+        use futures_util::FutureExt;
+
+        // Flushing a port that does not track its sends is a no-op.
+        let mut output = Output::<u32>::new();
+        assert_eq!(output.flush().now_or_never(), Some(()));
+    }
+
+    #[test]
+    fn requestor_is_connected() {
+        let mut requestor = Requestor::<u32, u32>::new();
+        let clone = requestor.clone();
+        assert!(!requestor.is_connected());
+        assert!(!clone.is_connected());
+
+        let mailbox = Mailbox::<TestModel>::new();
+        requestor.connect(TestModel::replier, &mailbox);
+        assert!(requestor.is_connected());
+        assert!(clone.is_connected());
+
+        let uni_requestor = UniRequestor::<u32, u32>::new(TestModel::replier, &mailbox);
+        assert!(uni_requestor.is_connected());
+    }
+
+    #[test]
+    fn requestor_send_unconnected() {
+        use futures_util::FutureExt;
+
+        let mut requestor = Requestor::<u32, u32>::new();
+
+        // The query completes immediately without replies.
+        let replies = requestor.send(42).now_or_never().unwrap();
+        assert_eq!(replies.count(), 0);
+    }
+}
@@ -6,7 +6,8 @@ use std::task::{Context, Poll};
 use diatomic_waker::WakeSink;
 
 use super::sender::{RecycledFuture, Sender};
-use crate::channel::SendError;
+use super::LineId;
+use crate::channel::{SendError, TrySendError};
 use crate::util::task_set::TaskSet;
 
 /// An object that can efficiently broadcast messages to several addresses.
@@ -24,8 +25,15 @@ use crate::util::task_set::TaskSet;
 /// - the outputs of all sender futures are returned all at once rather than
 ///   with an asynchronous iterator (a.k.a. async stream).
 pub(super) struct BroadcasterInner<T: Clone, R> {
-    /// The list of senders with their associated line identifier.
+    /// The list of senders.
     senders: Vec<Box<dyn Sender<T, R>>>,
+    /// The line identifiers associated to the senders.
+    line_ids: Vec<LineId>,
+    /// The identifier of the next line.
+    next_line_id: u64,
+    /// The indices of the senders that produced a future during the last
+    /// broadcast for which indices were tracked, in the order of the outputs.
+    indices: Vec<usize>,
     /// Fields explicitly borrowed by the `BroadcastFuture`.
     shared: Shared<R>,
 }
@@ -38,15 +46,38 @@ impl<T: Clone, R> BroadcasterInner<T, R> {
     /// This method will panic if the total count of senders would reach
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
-    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) {
+    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) -> LineId {
         assert!(self.senders.len() < (u32::MAX as usize - 2));
+        let line_id = LineId(self.next_line_id);
+        self.next_line_id += 1;
         self.senders.push(sender);
+        self.line_ids.push(line_id);
         self.shared.outputs.push(None);
+        self.shared.failures.push(false);
 
         // The storage is alway an empty vector so we just book some capacity.
         if let Some(storage) = self.shared.storage.as_mut() {
             let _ = storage.try_reserve(self.senders.len());
         };
+
+        line_id
+    }
+
+    /// Removes the sender with the specified line identifier.
+    ///
+    /// Returns `false` if no sender with this identifier was found.
+    pub(super) fn remove(&mut self, line_id: LineId) -> bool {
+        match self.line_ids.iter().position(|id| *id == line_id) {
+            Some(idx) => {
+                self.senders.remove(idx);
+                self.line_ids.remove(idx);
+                self.shared.outputs.truncate(self.senders.len());
+                self.shared.failures.truncate(self.senders.len());
+
+                true
+            }
+            None => false,
+        }
     }
 
     /// Returns the number of connected senders.
@@ -54,31 +85,49 @@ impl<T: Clone, R> BroadcasterInner<T, R> {
         self.senders.len()
     }
 
+    /// Returns `true` if there are no connected senders.
+    pub(super) fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
     /// Return a list of futures broadcasting an event or query to multiple
     /// addresses.
+    ///
+    /// If `track_indices` is set, the indices of the senders that produced a
+    /// future are recorded.
     #[allow(clippy::type_complexity)]
     fn futures(
         &mut self,
         arg: T,
+        track_indices: bool,
     ) -> (
         &'_ mut Shared<R>,
         Vec<RecycledFuture<'_, Result<R, SendError>>>,
     ) {
         let mut futures = recycle_vec(self.shared.storage.take().unwrap_or_default());
+        if track_indices {
+            self.indices.clear();
+        }
 
         // Broadcast the message and collect all futures.
-        let mut iter = self.senders.iter_mut();
-        while let Some(sender) = iter.next() {
+        let mut iter = self.senders.iter_mut().enumerate();
+        while let Some((idx, sender)) = iter.next() {
             // Move the argument rather than clone it for the last future.
             if iter.len() == 0 {
                 if let Some(fut) = sender.send_owned(arg) {
                     futures.push(fut);
+                    if track_indices {
+                        self.indices.push(idx);
+                    }
                 }
                 break;
             }
 
             if let Some(fut) = sender.send(&arg) {
                 futures.push(fut);
+                if track_indices {
+                    self.indices.push(idx);
+                }
             }
         }
 
@@ -94,10 +143,14 @@ impl<T: Clone, R> Default for BroadcasterInner<T, R> {
 
         Self {
             senders: Vec::new(),
+            line_ids: Vec::new(),
+            next_line_id: 0,
+            indices: Vec::new(),
             shared: Shared {
                 wake_sink,
                 task_set: TaskSet::new(wake_src),
                 outputs: Vec::new(),
+                failures: Vec::new(),
                 storage: None,
             },
         }
@@ -108,6 +161,9 @@ impl<T: Clone, R> Clone for BroadcasterInner<T, R> {
     fn clone(&self) -> Self {
         Self {
             senders: self.senders.clone(),
+            line_ids: self.line_ids.clone(),
+            next_line_id: self.next_line_id,
+            indices: Vec::new(),
             shared: self.shared.clone(),
         }
     }
@@ -133,15 +189,27 @@ impl<T: Clone> EventBroadcaster<T> {
     /// This method will panic if the total count of senders would reach
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
-    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, ()>>) {
+    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, ()>>) -> LineId {
         self.inner.add(sender)
     }
 
+    /// Removes the sender with the specified line identifier.
+    ///
+    /// Returns `false` if no sender with this identifier was found.
+    pub(super) fn remove(&mut self, line_id: LineId) -> bool {
+        self.inner.remove(line_id)
+    }
+
     /// Returns the number of connected senders.
     pub(super) fn len(&self) -> usize {
         self.inner.len()
     }
 
+    /// Returns `true` if there are no connected senders.
+    pub(super) fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     /// Broadcasts an event to all addresses.
     pub(super) async fn broadcast(&mut self, arg: T) -> Result<(), SendError> {
         match self.inner.senders.as_mut_slice() {
@@ -156,15 +224,70 @@ impl<T: Clone> EventBroadcaster<T> {
 
             // Possibly multiple senders.
             _ => {
-                let (shared, mut futures) = self.inner.futures(arg);
+                let (shared, mut futures) = self.inner.futures(arg, false);
                 match futures.as_mut_slice() {
                     [] => Ok(()),
                     [fut] => fut.await,
-                    _ => BroadcastFuture::new(shared, futures).await,
+                    _ => BroadcastFuture::new(shared, futures, false)
+                        .await
+                        .map(|_| ()),
                 }
             }
         }
     }
+
+    /// Checks whether any of the addresses has a full mailbox.
+    pub(super) fn is_any_full(&self) -> bool {
+        self.inner.senders.iter().any(|sender| sender.is_full())
+    }
+
+    /// Broadcasts an event to all addresses without waiting.
+    ///
+    /// Nothing is sent if any of the addresses has a full mailbox. Addresses
+    /// whose mailbox becomes full after this check are skipped and counted in
+    /// the returned status.
+    pub(super) fn try_broadcast(&mut self, arg: &T) -> Result<TryBroadcastStatus, SendError> {
+        if self.is_any_full() {
+            return Ok(TryBroadcastStatus::Full);
+        }
+        let mut undelivered = 0;
+        for sender in self.inner.senders.iter_mut() {
+            match sender.try_send(arg) {
+                Ok(()) => {}
+                Err(TrySendError::Full) => undelivered += 1,
+                Err(TrySendError::Closed) => return Err(SendError),
+            }
+        }
+
+        if undelivered == 0 {
+            Ok(TryBroadcastStatus::Sent)
+        } else {
+            Ok(TryBroadcastStatus::PartiallySent(undelivered))
+        }
+    }
+
+    /// Broadcasts a batch of events to all addresses.
+    ///
+    /// The whole batch is sent to an address before the next address is
+    /// served, so the batch is cloned once per address rather than each event
+    /// being cloned once per address.
+    pub(super) async fn broadcast_all(&mut self, args: Vec<T>) -> Result<(), SendError>
+    where
+        T: Send,
+    {
+        let mut iter = self.inner.senders.iter_mut();
+        while let Some(sender) = iter.next() {
+            // Move the batch rather than clone it for the last sender.
+            if iter.len() == 0 {
+                sender.send_batch(args).await?;
+                break;
+            }
+
+            sender.send_batch(args.clone()).await?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: Clone> Default for EventBroadcaster<T> {
@@ -175,6 +298,19 @@ impl<T: Clone> Default for EventBroadcaster<T> {
     }
 }
 
+/// The outcome of a non-blocking event broadcast.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum TryBroadcastStatus {
+    /// The event was sent to all addresses.
+    Sent,
+    /// The event was sent to none of the addresses because at least one
+    /// mailbox was full.
+    Full,
+    /// The event could not be sent to the specified number of addresses
+    /// because their mailbox became full during the broadcast.
+    PartiallySent(usize),
+}
+
 /// An object that can efficiently broadcast queries to several replier ports.
 ///
 /// This is very similar to `source::broadcaster::QueryBroadcaster`, but
@@ -195,7 +331,7 @@ impl<T: Clone, R> QueryBroadcaster<T, R> {
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
     pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) {
-        self.inner.add(sender)
+        self.inner.add(sender);
     }
 
     /// Returns the number of connected senders.
@@ -203,57 +339,141 @@ impl<T: Clone, R> QueryBroadcaster<T, R> {
         self.inner.len()
     }
 
+    /// Returns `true` if there are no connected senders.
+    pub(super) fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     /// Broadcasts a query to all addresses and collect all responses.
     pub(super) async fn broadcast(
         &mut self,
         arg: T,
     ) -> Result<impl Iterator<Item = R> + '_, SendError> {
-        let output_count = match self.inner.senders.as_mut_slice() {
-            // No sender.
-            [] => 0,
+        let (output_count, _) = self.process(arg, false, false).await?;
+
+        Ok(self.outputs(output_count))
+    }
+
+    /// Broadcasts a query to all addresses and collect the responses from all
+    /// reachable addresses together with the number of unreachable addresses.
+    pub(super) async fn broadcast_best_effort(
+        &mut self,
+        arg: T,
+    ) -> (impl Iterator<Item = R> + '_, usize) {
+        // No error is returned when errors are skipped.
+        let (output_count, skipped_count) =
+            self.process(arg, true, false).await.unwrap_or_default();
+
+        // Outputs of unreachable addresses are missing.
+        let outputs = self
+            .inner
+            .shared
+            .outputs
+            .iter_mut()
+            .take(output_count)
+            .filter_map(|t| t.take());
+
+        (outputs, skipped_count)
+    }
+
+    /// Broadcasts a query to all addresses and collect all responses together
+    /// with the index of the address that produced them.
+    pub(super) async fn broadcast_enumerated(
+        &mut self,
+        arg: T,
+    ) -> Result<impl Iterator<Item = (usize, R)> + '_, SendError> {
+        let (output_count, _) = self.process(arg, false, true).await?;
+
+        // At this point all outputs should be available.
+        let outputs = self
+            .inner
+            .shared
+            .outputs
+            .iter_mut()
+            .take(output_count)
+            .map(|t| t.take().unwrap());
+
+        Ok(self.inner.indices.iter().copied().zip(outputs))
+    }
+
+    /// Broadcasts a query to all addresses and returns the number of output
+    /// slots and the number of unreachable addresses.
+    ///
+    /// If errors are skipped, the output slots of unreachable addresses are
+    /// left empty and no error is returned. If `track_indices` is set, the
+    /// indices of the addresses associated to the output slots are recorded.
+    async fn process(
+        &mut self,
+        arg: T,
+        skip_errors: bool,
+        track_indices: bool,
+    ) -> Result<(usize, usize), SendError> {
+        let counts = match self.inner.senders.as_mut_slice() {
+            // No sender: the broadcast completes immediately.
+            [] => (0, 0),
 
             // One sender at most.
             [sender] => {
+                if track_indices {
+                    self.inner.indices.clear();
+                }
                 if let Some(fut) = sender.send_owned(arg) {
-                    let output = fut.await?;
-                    self.inner.shared.outputs[0] = Some(output);
-
-                    1
+                    match fut.await {
+                        Ok(output) => {
+                            self.inner.shared.outputs[0] = Some(output);
+                            if track_indices {
+                                self.inner.indices.push(0);
+                            }
+
+                            (1, 0)
+                        }
+                        Err(_) if skip_errors => (0, 1),
+                        Err(e) => return Err(e),
+                    }
                 } else {
-                    0
+                    (0, 0)
                 }
             }
 
             // Possibly multiple senders.
             _ => {
-                let (shared, mut futures) = self.inner.futures(arg);
+                let (shared, mut futures) = self.inner.futures(arg, track_indices);
                 let output_count = futures.len();
 
-                match futures.as_mut_slice() {
-                    [] => {}
-                    [fut] => {
-                        let output = fut.await?;
-                        shared.outputs[0] = Some(output);
-                    }
-                    _ => {
-                        BroadcastFuture::new(shared, futures).await?;
-                    }
-                }
-
-                output_count
+                let skipped_count = match futures.as_mut_slice() {
+                    [] => 0,
+                    [fut] => match fut.await {
+                        Ok(output) => {
+                            shared.outputs[0] = Some(output);
+
+                            0
+                        }
+                        Err(_) if skip_errors => {
+                            shared.outputs[0] = None;
+
+                            1
+                        }
+                        Err(e) => return Err(e),
+                    },
+                    _ => BroadcastFuture::new(shared, futures, skip_errors).await?,
+                };
+
+                (output_count, skipped_count)
             }
         };
 
+        Ok(counts)
+    }
+
+    /// Returns an iterator over the outputs of the last broadcast.
+    fn outputs(&mut self, output_count: usize) -> impl Iterator<Item = R> + '_ {
         // At this point all outputs should be available.
-        let outputs = self
-            .inner
+        self.inner
             .shared
             .outputs
             .iter_mut()
             .take(output_count)
-            .map(|t| t.take().unwrap());
-
-        Ok(outputs)
+            .map(|t| t.take().unwrap())
     }
 }
 
@@ -281,6 +501,8 @@ struct Shared<R> {
     task_set: TaskSet,
     /// Outputs of the sender futures.
     outputs: Vec<Option<R>>,
+    /// Flags indicating the sender futures that have failed.
+    failures: Vec<bool>,
     /// Cached storage for the sender futures.
     ///
     /// When it exists, the cached storage is always an empty vector but it
@@ -301,6 +523,7 @@ impl<R> Clone for Shared<R> {
             wake_sink,
             task_set: TaskSet::new(wake_src),
             outputs,
+            failures: vec![false; self.failures.len()],
             storage: None,
         }
     }
@@ -322,6 +545,10 @@ pub(super) struct BroadcastFuture<'a, R> {
     futures: ManuallyDrop<Vec<RecycledFuture<'a, Result<R, SendError>>>>,
     /// The total count of futures that have not yet been polled to completion.
     pending_futures_count: usize,
+    /// Whether failed futures are skipped rather than failing the broadcast.
+    skip_errors: bool,
+    /// The total count of failed futures that were skipped.
+    skipped_futures_count: usize,
     /// State of completion of the future.
     state: FutureState,
 }
@@ -331,6 +558,7 @@ impl<'a, R> BroadcastFuture<'a, R> {
     fn new(
         shared: &'a mut Shared<R>,
         futures: Vec<RecycledFuture<'a, Result<R, SendError>>>,
+        skip_errors: bool,
     ) -> Self {
         let pending_futures_count = futures.len();
         shared.task_set.resize(pending_futures_count);
@@ -340,12 +568,17 @@ impl<'a, R> BroadcastFuture<'a, R> {
             // previous broadcast future was cancelled.
             output.take();
         }
+        for failure in shared.failures.iter_mut().take(pending_futures_count) {
+            *failure = false;
+        }
 
         BroadcastFuture {
             shared,
             futures: ManuallyDrop::new(futures),
             state: FutureState::Uninit,
             pending_futures_count,
+            skip_errors,
+            skipped_futures_count: 0,
         }
     }
 }
@@ -362,7 +595,9 @@ impl<R> Drop for BroadcastFuture<'_, R> {
 }
 
 impl<R> Future for BroadcastFuture<'_, R> {
-    type Output = Result<(), SendError>;
+    /// The number of failed futures that were skipped, or an error if a
+    /// future has failed and errors are not skipped.
+    type Output = Result<usize, SendError>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = &mut *self;
@@ -394,9 +629,14 @@ impl<R> Future for BroadcastFuture<'_, R> {
                         this.pending_futures_count -= 1;
                     }
                     Poll::Ready(Err(SendError)) => {
-                        this.state = FutureState::Completed;
+                        if !this.skip_errors {
+                            this.state = FutureState::Completed;
 
-                        return Poll::Ready(Err(SendError));
+                            return Poll::Ready(Err(SendError));
+                        }
+                        this.shared.failures[task_idx] = true;
+                        this.skipped_futures_count += 1;
+                        this.pending_futures_count -= 1;
                     }
                     Poll::Pending => {}
                 }
@@ -405,7 +645,7 @@ impl<R> Future for BroadcastFuture<'_, R> {
             if this.pending_futures_count == 0 {
                 this.state = FutureState::Completed;
 
-                return Poll::Ready(Ok(()));
+                return Poll::Ready(Ok(this.skipped_futures_count));
             }
 
             this.state = FutureState::Pending;
@@ -438,7 +678,7 @@ impl<R> Future for BroadcastFuture<'_, R> {
                 let output = &mut this.shared.outputs[task_idx];
 
                 // Do not poll completed futures.
-                if output.is_some() {
+                if output.is_some() || this.shared.failures[task_idx] {
                     continue;
                 }
 
@@ -452,9 +692,14 @@ impl<R> Future for BroadcastFuture<'_, R> {
                         this.pending_futures_count -= 1;
                     }
                     Poll::Ready(Err(SendError)) => {
-                        this.state = FutureState::Completed;
+                        if !this.skip_errors {
+                            this.state = FutureState::Completed;
 
-                        return Poll::Ready(Err(SendError));
+                            return Poll::Ready(Err(SendError));
+                        }
+                        this.shared.failures[task_idx] = true;
+                        this.skipped_futures_count += 1;
+                        this.pending_futures_count -= 1;
                     }
                     Poll::Pending => {}
                 }
@@ -463,7 +708,7 @@ impl<R> Future for BroadcastFuture<'_, R> {
             if this.pending_futures_count == 0 {
                 this.state = FutureState::Completed;
 
-                return Poll::Ready(Ok(()));
+                return Poll::Ready(Ok(this.skipped_futures_count));
             }
         }
     }
@@ -699,6 +944,58 @@ mod tests {
         assert_eq!(sum, N_RECV * MESSAGE * 2);
     }
 
+    #[test]
+    fn broadcast_query_best_effort() {
+        const N_RECV: usize = 4;
+        const N_UNREACHABLE: usize = 2;
+        const MESSAGE: usize = 42;
+
+        let mut mailboxes = Vec::new();
+        let mut broadcaster = QueryBroadcaster::default();
+        for _ in 0..N_RECV {
+            let mailbox = Receiver::new(10);
+            let address = mailbox.sender();
+            let sender = Box::new(ReplierSender::new(DoubleModel::double, address));
+
+            broadcaster.add(sender);
+            mailboxes.push(mailbox);
+        }
+
+        // Make some of the repliers unreachable.
+        for mailbox in mailboxes.drain(..N_UNREACHABLE) {
+            mailbox.close();
+        }
+
+        let th_broadcast = thread::spawn(move || {
+            let (iter, skipped) = block_on(broadcaster.broadcast_best_effort(MESSAGE));
+
+            (iter.sum::<usize>(), skipped)
+        });
+
+        let th_recv: Vec<_> = mailboxes
+            .into_iter()
+            .map(|mut mailbox| {
+                thread::spawn({
+                    let mut double_model = DoubleModel::new();
+
+                    move || {
+                        let mut dummy_cx = Context::new_dummy();
+                        block_on(mailbox.recv(&mut double_model, &mut dummy_cx)).unwrap();
+                        thread::sleep(std::time::Duration::from_millis(100));
+                    }
+                })
+            })
+            .collect();
+
+        let (sum, skipped) = th_broadcast.join().unwrap();
+        for th in th_recv {
+            th.join().unwrap();
+        }
+
+        assert_eq!(sum, (N_RECV - N_UNREACHABLE) * MESSAGE * 2);
+        assert_eq!(skipped, N_UNREACHABLE);
+    }
+
     #[test]
     fn broadcast_query_filter_map() {
         const N_RECV: usize = 4;
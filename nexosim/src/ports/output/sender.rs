@@ -1,17 +1,22 @@
-use std::future::Future;
+use std::future::{self, Future};
 use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use dyn_clone::DynClone;
 use recycle_box::{coerce_box, RecycleBox};
 
 use crate::channel;
-use crate::channel::SendError;
+use crate::channel::{SendError, TrySendError};
+use crate::executor;
+use crate::executor::SIMULATION_CONTEXT;
 use crate::model::Model;
 use crate::ports::{EventSinkWriter, InputFn, ReplierFn};
+use crate::simulation::{Address, DEFAULT_PRIORITY, GLOBAL_SCHEDULER_ORIGIN_ID};
 
 /// An event or query sender abstracting over the target model and input or
 /// replier method.
@@ -23,10 +28,82 @@ pub(super) trait Sender<T, R>: DynClone + Send {
     fn send_owned(&mut self, arg: T) -> Option<RecycledFuture<'_, Result<R, SendError>>> {
         self.send(&arg)
     }
+
+    /// Checks whether sending a message could require waiting for the target
+    /// mailbox to be emptied.
+    ///
+    /// This is only a hint since the mailbox may be concurrently accessed.
+    fn is_full(&self) -> bool {
+        false
+    }
+
+    /// Sends a message without waiting for the target mailbox to be emptied.
+    ///
+    /// The default implementation is meant for senders that never wait: the
+    /// send future, if any, is polled once and a pending future is reported as
+    /// a full mailbox.
+    fn try_send(&mut self, arg: &T) -> Result<(), TrySendError> {
+        match self.send(arg) {
+            None => Ok(()),
+            Some(mut fut) => {
+                let mut cx = Context::from_waker(futures_task::noop_waker_ref());
+                match Pin::new(&mut fut).poll(&mut cx) {
+                    Poll::Ready(Ok(_)) => Ok(()),
+                    Poll::Ready(Err(_)) => Err(TrySendError::Closed),
+                    Poll::Pending => Err(TrySendError::Full),
+                }
+            }
+        }
+    }
+
+    /// Asynchronously sends a batch of owned messages.
+    ///
+    /// The default implementation sends the messages one after the other, so
+    /// other messages may be interleaved with the batch.
+    fn send_batch(&mut self, args: Vec<T>) -> BatchFuture<'_>
+    where
+        T: Send,
+    {
+        Box::pin(async move {
+            for arg in args {
+                if let Some(fut) = self.send_owned(arg) {
+                    fut.await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
 }
 
 dyn_clone::clone_trait_object!(<T, R> Sender<T, R>);
 
+/// A future sending a batch of messages.
+pub(super) type BatchFuture<'a> = Pin<Box<dyn Future<Output = Result<(), SendError>> + Send + 'a>>;
+
+/// Sends a batch of events to an input port as a single message, so that the
+/// events are processed contiguously by the target model.
+fn send_input_batch<M, F, T, S>(
+    sender: &channel::Sender<M>,
+    func: F,
+    args: Vec<T>,
+) -> BatchFuture<'_>
+where
+    M: Model,
+    F: for<'a> InputFn<'a, M, T, S> + Clone,
+    T: Send + 'static,
+{
+    Box::pin(sender.send(move |model, scheduler, recycle_box| {
+        let fut = async move {
+            for arg in args {
+                func.clone().call(&mut *model, arg, &mut *scheduler).await;
+            }
+        };
+
+        coerce_box!(RecycleBox::recycle(recycle_box, fut))
+    }))
+}
+
 /// An object that can send events to an input port.
 pub(super) struct InputSender<M, F, T, S>
 where
@@ -76,6 +153,25 @@ where
 
         Some(RecycledFuture::new(&mut self.fut_storage, fut))
     }
+
+    fn is_full(&self) -> bool {
+        self.sender.is_full()
+    }
+
+    fn try_send(&mut self, arg: &T) -> Result<(), TrySendError> {
+        let func = self.func.clone();
+        let arg = arg.clone();
+
+        self.sender.try_send(move |model, scheduler, recycle_box| {
+            let fut = func.call(model, arg, scheduler);
+
+            coerce_box!(RecycleBox::recycle(recycle_box, fut))
+        })
+    }
+
+    fn send_batch(&mut self, args: Vec<T>) -> BatchFuture<'_> {
+        send_input_batch(&self.sender, self.func.clone(), args)
+    }
 }
 
 impl<M, F, T, S> Clone for InputSender<M, F, T, S>
@@ -94,6 +190,187 @@ where
     }
 }
 
+/// An object that can send events to an input port after a fixed delay.
+pub(super) struct DelayedInputSender<M, F, T, S>
+where
+    M: 'static,
+{
+    func: F,
+    sender: channel::Sender<M>,
+    delay: Duration,
+    fut_storage: Option<RecycleBox<()>>,
+    _phantom_closure: PhantomData<fn(&mut M, T)>,
+    _phantom_closure_marker: PhantomData<S>,
+}
+
+impl<M, F, T, S> DelayedInputSender<M, F, T, S>
+where
+    M: 'static,
+{
+    pub(super) fn new(func: F, sender: channel::Sender<M>, delay: Duration) -> Self {
+        Self {
+            func,
+            sender,
+            delay,
+            fut_storage: None,
+            _phantom_closure: PhantomData,
+            _phantom_closure_marker: PhantomData,
+        }
+    }
+}
+
+impl<M, F, T, S> Sender<T, ()> for DelayedInputSender<M, F, T, S>
+where
+    M: Model,
+    F: for<'a> InputFn<'a, M, T, S> + Clone,
+    T: Clone + Send + 'static,
+    S: Send + 'static,
+{
+    fn send(&mut self, arg: &T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        self.send_owned(arg.clone())
+    }
+
+    fn send_owned(&mut self, arg: T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        // All delayed events share the origin of the global scheduler, so
+        // events with the same deadline are delivered in the order they
+        // were sent.
+        let res = SIMULATION_CONTEXT.map(|cx| {
+            cx.scheduler.schedule_event_from(
+                self.delay,
+                self.func.clone(),
+                arg,
+                Address(self.sender.clone()),
+                DEFAULT_PRIORITY,
+                GLOBAL_SCHEDULER_ORIGIN_ID,
+            )
+        });
+
+        match res {
+            // The event is buffered in the scheduler queue so there is nothing
+            // to wait for.
+            Some(Ok(())) => None,
+            // The delivery time is beyond the largest representable simulation
+            // time, which the simulation can never reach: the event is
+            // dropped.
+            Some(Err(_)) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    target: env!("CARGO_PKG_NAME"),
+                    "event on a delayed connection dropped: delivery time out of range"
+                );
+
+                None
+            }
+            // Without a scheduler, the event cannot be delivered.
+            None => Some(RecycledFuture::new(
+                &mut self.fut_storage,
+                future::ready(Err(SendError)),
+            )),
+        }
+    }
+}
+
+impl<M, F, T, S> Clone for DelayedInputSender<M, F, T, S>
+where
+    M: 'static,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+            sender: self.sender.clone(),
+            delay: self.delay,
+            fut_storage: None,
+            _phantom_closure: PhantomData,
+            _phantom_closure_marker: PhantomData,
+        }
+    }
+}
+
+/// An object that can send events to an input port, dropping them if the
+/// target mailbox is full.
+pub(super) struct LossyInputSender<M, F, T, S>
+where
+    M: 'static,
+{
+    func: F,
+    sender: channel::Sender<M>,
+    drop_count: Arc<AtomicU64>,
+    fut_storage: Option<RecycleBox<()>>,
+    _phantom_closure: PhantomData<fn(&mut M, T)>,
+    _phantom_closure_marker: PhantomData<S>,
+}
+
+impl<M, F, T, S> LossyInputSender<M, F, T, S>
+where
+    M: 'static,
+{
+    pub(super) fn new(func: F, sender: channel::Sender<M>, drop_count: Arc<AtomicU64>) -> Self {
+        Self {
+            func,
+            sender,
+            drop_count,
+            fut_storage: None,
+            _phantom_closure: PhantomData,
+            _phantom_closure_marker: PhantomData,
+        }
+    }
+}
+
+impl<M, F, T, S> Sender<T, ()> for LossyInputSender<M, F, T, S>
+where
+    M: Model,
+    F: for<'a> InputFn<'a, M, T, S> + Clone,
+    T: Clone + Send + 'static,
+    S: Send,
+{
+    fn send(&mut self, arg: &T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        self.send_owned(arg.clone())
+    }
+
+    fn send_owned(&mut self, arg: T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        let func = self.func.clone();
+
+        let res = self.sender.try_send(move |model, scheduler, recycle_box| {
+            let fut = func.call(model, arg, scheduler);
+
+            coerce_box!(RecycleBox::recycle(recycle_box, fut))
+        });
+
+        match res {
+            // The message was sent without blocking so there is nothing to
+            // wait for.
+            Ok(()) => None,
+            Err(TrySendError::Full) => {
+                self.drop_count.fetch_add(1, Ordering::Relaxed);
+
+                None
+            }
+            Err(TrySendError::Closed) => Some(RecycledFuture::new(
+                &mut self.fut_storage,
+                future::ready(Err(SendError)),
+            )),
+        }
+    }
+}
+
+impl<M, F, T, S> Clone for LossyInputSender<M, F, T, S>
+where
+    M: 'static,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+            sender: self.sender.clone(),
+            drop_count: self.drop_count.clone(),
+            fut_storage: None,
+            _phantom_closure: PhantomData,
+            _phantom_closure_marker: PhantomData,
+        }
+    }
+}
+
 /// An object that can send mapped events to an input port.
 pub(super) struct MapInputSender<M, C, F, T, U, S>
 where
@@ -146,6 +423,27 @@ where
 
         Some(RecycledFuture::new(&mut self.fut_storage, fut))
     }
+
+    fn is_full(&self) -> bool {
+        self.sender.is_full()
+    }
+
+    fn try_send(&mut self, arg: &T) -> Result<(), TrySendError> {
+        let func = self.func.clone();
+        let arg = (self.map)(arg);
+
+        self.sender.try_send(move |model, scheduler, recycle_box| {
+            let fut = func.call(model, arg, scheduler);
+
+            coerce_box!(RecycleBox::recycle(recycle_box, fut))
+        })
+    }
+
+    fn send_batch(&mut self, args: Vec<T>) -> BatchFuture<'_> {
+        let args = args.iter().map(|arg| (self.map)(arg)).collect();
+
+        send_input_batch(&self.sender, self.func.clone(), args)
+    }
 }
 
 impl<M, C, F, T, U, S> Clone for MapInputSender<M, C, F, T, U, S>
@@ -219,6 +517,37 @@ where
             RecycledFuture::new(&mut self.fut_storage, fut)
         })
     }
+
+    fn is_full(&self) -> bool {
+        self.sender.is_full()
+    }
+
+    fn try_send(&mut self, arg: &T) -> Result<(), TrySendError> {
+        match (self.filter_map)(arg) {
+            Some(arg) => {
+                let func = self.func.clone();
+
+                self.sender.try_send(move |model, scheduler, recycle_box| {
+                    let fut = func.call(model, arg, scheduler);
+
+                    coerce_box!(RecycleBox::recycle(recycle_box, fut))
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn send_batch(&mut self, args: Vec<T>) -> BatchFuture<'_> {
+        let args: Vec<U> = args
+            .iter()
+            .filter_map(|arg| (self.filter_map)(arg))
+            .collect();
+        if args.is_empty() {
+            return Box::pin(future::ready(Ok(())));
+        }
+
+        send_input_batch(&self.sender, self.func.clone(), args)
+    }
 }
 
 impl<M, C, F, T, U, S> Clone for FilterMapInputSender<M, C, F, T, U, S>
@@ -269,7 +598,7 @@ where
         let writer = &mut self.writer;
 
         Some(RecycledFuture::new(&mut self.fut_storage, async move {
-            writer.write(arg);
+            write_to_sink(writer, arg);
 
             Ok(())
         }))
@@ -323,7 +652,7 @@ where
         let arg = (self.map)(arg);
 
         Some(RecycledFuture::new(&mut self.fut_storage, async move {
-            writer.write(arg);
+            write_to_sink(writer, arg);
 
             Ok(())
         }))
@@ -382,7 +711,7 @@ where
 
         (self.filter_map)(arg).map(|arg| {
             RecycledFuture::new(&mut self.fut_storage, async move {
-                writer.write(arg);
+                write_to_sink(writer, arg);
 
                 Ok(())
             })
@@ -405,6 +734,15 @@ where
     }
 }
 
+/// Writes an event to an event sink, together with the current simulation
+/// time if available.
+fn write_to_sink<T, W: EventSinkWriter<T>>(writer: &W, event: T) {
+    match executor::simulation_time() {
+        Some(time) => writer.write_timed(time, event),
+        None => writer.write(event),
+    }
+}
+
 /// An object that can send requests to a replier port and retrieve responses.
 pub(super) struct ReplierSender<M, F, T, R, S>
 where
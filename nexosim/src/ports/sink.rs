@@ -1,6 +1,16 @@
 pub(crate) mod blocking_event_queue;
+pub(crate) mod closure_sink;
+#[cfg(feature = "csv")]
+pub(crate) mod csv_sink;
 pub(crate) mod event_buffer;
+pub(crate) mod event_relay;
 pub(crate) mod event_slot;
+pub(crate) mod stats_sink;
+pub(crate) mod timed;
+#[cfg(feature = "tokio")]
+pub(crate) mod tokio_channel_sink;
+
+use crate::time::MonotonicTime;
 
 /// A simulation endpoint that can receive events sent by model outputs.
 ///
@@ -18,6 +28,17 @@ pub trait EventSink<T> {
 pub trait EventSinkWriter<T>: Clone + Send + Sync + 'static {
     /// Writes a value to the associated sink.
     fn write(&self, event: T);
+
+    /// Writes a value to the associated sink together with the simulation
+    /// time at which it was sent.
+    ///
+    /// This method is called instead of [`EventSinkWriter::write`] when an
+    /// event is sent by a model output. The default implementation drops the
+    /// time and forwards the event to [`EventSinkWriter::write`].
+    fn write_timed(&self, time: MonotonicTime, event: T) {
+        let _ = time;
+        self.write(event);
+    }
 }
 
 /// An iterator over collected events with the ability to pause and resume event
@@ -55,4 +76,45 @@ pub trait EventSinkStream: Iterator {
     {
         Iterator::try_fold(self, init, f)
     }
+
+    /// This is a hook that attaches a subscriber to which events are
+    /// forwarded as soon as they are written, instead of being collected.
+    ///
+    /// The subscriber is returned in the error if the event sink stream does
+    /// not support subscriptions, which is the default.
+    ///
+    /// It is not publicly implementable because it is only meant to be used
+    /// by the event sink streams of this crate.
+    #[doc(hidden)]
+    #[allow(private_interfaces)]
+    fn __subscribe(
+        &mut self,
+        subscriber: Subscriber<Self::Item>,
+    ) -> Result<(), Subscriber<Self::Item>> {
+        Err(subscriber)
+    }
+}
+
+/// A type-erased callback to which the events of an event sink are forwarded.
+pub(crate) struct Subscriber<T>(Box<dyn FnMut(T) -> bool + Send + 'static>);
+
+impl<T> Subscriber<T> {
+    /// Creates a subscriber from a closure.
+    ///
+    /// The closure should return `false` if the event could not be accepted,
+    /// in which case the subscription is cancelled.
+    #[cfg(feature = "server")]
+    pub(crate) fn new<F>(f: F) -> Self
+    where
+        F: FnMut(T) -> bool + Send + 'static,
+    {
+        Self(Box::new(f))
+    }
+
+    /// Forwards an event to the subscriber.
+    ///
+    /// Returns `false` if the subscription should be cancelled.
+    pub(crate) fn notify(&mut self, event: T) -> bool {
+        (self.0)(event)
+    }
 }
@@ -1,8 +1,9 @@
 use std::fmt;
 use std::iter::FusedIterator;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::{EventSink, EventSinkStream, EventSinkWriter};
 
@@ -81,6 +82,29 @@ pub struct BlockingEventQueueReader<T> {
     receiver: Receiver<T>,
 }
 
+impl<T> BlockingEventQueueReader<T> {
+    /// Waits for an event until the specified timeout elapses.
+    ///
+    /// This is similar to the iterator's `next` method, but an error is
+    /// returned if no event was received before the timeout. The error
+    /// distinguishes a timeout ([`RecvTimeoutError::Timeout`]) from the case
+    /// where all writer handles have been dropped
+    /// ([`RecvTimeoutError::Disconnected`]), which corresponds to `next`
+    /// returning `None`.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// Removes and returns all events currently in the queue, in
+    /// first-in-first-out order.
+    ///
+    /// Unlike the iterator's `next` method, this method never blocks: an empty
+    /// vector is returned if no event is available.
+    pub fn drain(&mut self) -> Vec<T> {
+        self.receiver.try_iter().collect()
+    }
+}
+
 impl<T> Iterator for BlockingEventQueueReader<T> {
     type Item = T;
 
@@ -143,3 +167,28 @@ impl<T> fmt::Debug for BlockingEventQueueWriter<T> {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocking_event_queue_next_timeout() {
+        let queue = BlockingEventQueue::new();
+        let writer = queue.writer();
+        let mut reader = queue.into_reader();
+
+        writer.write(1);
+        assert_eq!(reader.next_timeout(Duration::from_millis(10)), Ok(1));
+        assert_eq!(
+            reader.next_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        drop(writer);
+        assert_eq!(
+            reader.next_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::{EventSink, EventSinkWriter};
+
+/// An [`EventSink`] that invokes a closure on each event.
+///
+/// This is a lightweight alternative to a dedicated model when events only
+/// need to trigger a side effect, such as logging or forwarding events to an
+/// external system.
+///
+/// The closure is called synchronously by the model that sends the event,
+/// from within the simulation executor, so events sent by a given output are
+/// processed in order. If the same sink is connected to several outputs, calls
+/// to the closure are serialized.
+///
+/// The closure cannot be `async`. Since it blocks the sending model, it should
+/// return quickly; potentially long operations should rather be delegated to
+/// another thread or runtime. A panic in the closure is treated like a panic
+/// of the model that sent the event, which means that the simulation is
+/// aborted with an [`ExecutionError::Panic`] error.
+///
+/// [`ExecutionError::Panic`]: crate::simulation::ExecutionError::Panic
+///
+/// # Examples
+///
+/// ```
+/// use nexosim::ports::{ClosureSink, Output};
+///
+/// let mut output = Output::<u32>::default();
+/// output.connect_sink(&ClosureSink::new(|value: u32| println!("event: {}", value)));
+/// ```
+pub struct ClosureSink<F> {
+    inner: Arc<Mutex<F>>,
+}
+
+impl<F> ClosureSink<F> {
+    /// Creates a `ClosureSink` invoking the specified closure on each event.
+    pub fn new(f: F) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(f)),
+        }
+    }
+}
+
+impl<T, F> EventSink<T> for ClosureSink<F>
+where
+    T: Send + 'static,
+    F: FnMut(T) + Send + 'static,
+{
+    type Writer = ClosureSinkWriter<F>;
+
+    fn writer(&self) -> Self::Writer {
+        ClosureSinkWriter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for ClosureSink<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClosureSink").finish_non_exhaustive()
+    }
+}
+
+/// A producer handle of a `ClosureSink`.
+pub struct ClosureSinkWriter<F> {
+    inner: Arc<Mutex<F>>,
+}
+
+impl<T, F> EventSinkWriter<T> for ClosureSinkWriter<F>
+where
+    T: Send + 'static,
+    F: FnMut(T) + Send + 'static,
+{
+    /// Invokes the closure with the event.
+    fn write(&self, event: T) {
+        let mut f = self.inner.lock().unwrap();
+        (*f)(event);
+    }
+}
+
+impl<F> Clone for ClosureSinkWriter<F> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for ClosureSinkWriter<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClosureSinkWriter").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::time::MonotonicTime;
+
+use super::{EventSink, EventSinkWriter};
+
+/// The policy used by a [`CsvSink`] to flush rows to the file.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FlushPolicy {
+    /// Flushes the file after each event.
+    #[default]
+    EachEvent,
+    /// Flushes the file each time the specified number of events have been
+    /// written since the last flush.
+    Periodic(NonZeroUsize),
+}
+
+/// The shared state of a `CsvSink`.
+struct Inner {
+    writer: csv::Writer<File>,
+    timed: bool,
+    policy: FlushPolicy,
+    unflushed: usize,
+    error: Option<io::Error>,
+}
+
+impl Inner {
+    /// Writes a row, preceded by the time column if the sink is timed.
+    ///
+    /// Nothing is written after an error until the error is taken.
+    fn write_row<T: Serialize>(&mut self, time: Option<MonotonicTime>, event: T) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.try_write_row(time, event) {
+            self.error = Some(e);
+        }
+    }
+
+    fn try_write_row<T: Serialize>(
+        &mut self,
+        time: Option<MonotonicTime>,
+        event: T,
+    ) -> io::Result<()> {
+        if self.timed {
+            self.writer
+                .write_field(time.map(format_time).unwrap_or_default())?;
+        }
+        self.writer.serialize(event)?;
+
+        self.unflushed += 1;
+        let flush = match self.policy {
+            FlushPolicy::EachEvent => true,
+            FlushPolicy::Periodic(period) => self.unflushed >= period.get(),
+        };
+        if flush {
+            self.unflushed = 0;
+            self.writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An [`EventSink`] that writes events as rows of a CSV file.
+///
+/// Each event is serialized with [`serde`] into one row of the file, so rows
+/// are not buffered in memory beyond what the flushing policy permits. Events
+/// are expected to serialize to a flat record such as a tuple or a struct with
+/// scalar fields.
+///
+/// If the sink is created with [`CsvSink::new_timed`], the first column of
+/// each row contains the simulation time at which the event was sent by a
+/// model output, formatted as a decimal number of seconds since
+/// [`MonotonicTime::EPOCH`].
+///
+/// Writing events cannot fail from the point of view of the sender. Instead,
+/// the first I/O or serialization error is stored and no more rows are written
+/// until the error is retrieved with [`CsvSink::take_error`] or
+/// [`CsvSink::flush`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use nexosim::ports::{CsvSink, Output};
+///
+/// let sink = CsvSink::<(u32, f64)>::new_timed("pressure.csv", &["id", "pressure"]).unwrap();
+/// let mut output = Output::<(u32, f64)>::default();
+/// output.connect_sink(&sink);
+///
+/// // ... run the simulation ...
+///
+/// sink.flush().unwrap();
+/// ```
+pub struct CsvSink<T> {
+    inner: Arc<Mutex<Inner>>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize> CsvSink<T> {
+    /// Creates a sink that writes to the file at the specified path, creating
+    /// or truncating the file and writing the header row.
+    ///
+    /// The header row is omitted if `header` is empty.
+    pub fn new(path: impl AsRef<Path>, header: &[&str]) -> io::Result<Self> {
+        Self::with_header(path.as_ref(), header, false)
+    }
+
+    /// Creates a sink that writes to the file at the specified path, with the
+    /// simulation time in the first column.
+    ///
+    /// A `time` column name is prepended to the header row, which is omitted
+    /// if `header` is empty.
+    pub fn new_timed(path: impl AsRef<Path>, header: &[&str]) -> io::Result<Self> {
+        Self::with_header(path.as_ref(), header, true)
+    }
+
+    fn with_header(path: &Path, header: &[&str], timed: bool) -> io::Result<Self> {
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_path(path)?;
+
+        if !header.is_empty() {
+            if timed {
+                writer.write_field("time")?;
+            }
+            writer.write_record(header)?;
+            writer.flush()?;
+        }
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                writer,
+                timed,
+                policy: FlushPolicy::default(),
+                unflushed: 0,
+                error: None,
+            })),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Sets the flushing policy.
+    ///
+    /// The default policy is [`FlushPolicy::EachEvent`].
+    pub fn set_flush_policy(&self, policy: FlushPolicy) {
+        self.inner.lock().unwrap().policy = policy;
+    }
+
+    /// Flushes all rows written so far to the file.
+    ///
+    /// If an error occurred when writing a previous row, this error is taken
+    /// and returned instead.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(e) = inner.error.take() {
+            return Err(e);
+        }
+        inner.unflushed = 0;
+
+        inner.writer.flush()
+    }
+
+    /// Takes the error that occurred when writing a previous row, if any.
+    ///
+    /// Writing resumes with the next event once the error is taken.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.inner.lock().unwrap().error.take()
+    }
+}
+
+impl<T: Serialize + Send + 'static> EventSink<T> for CsvSink<T> {
+    type Writer = CsvSinkWriter<T>;
+
+    fn writer(&self) -> Self::Writer {
+        CsvSinkWriter {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CsvSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CsvSink").finish_non_exhaustive()
+    }
+}
+
+/// A writer handle of a `CsvSink`.
+pub struct CsvSinkWriter<T> {
+    inner: Arc<Mutex<Inner>>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + 'static> EventSinkWriter<T> for CsvSinkWriter<T> {
+    /// Writes the event as a new row.
+    ///
+    /// If the sink is timed, the time column of the row is left empty.
+    fn write(&self, event: T) {
+        self.inner.lock().unwrap().write_row(None, event);
+    }
+
+    /// Writes the event as a new row, preceded by the time if the sink is
+    /// timed.
+    fn write_timed(&self, time: MonotonicTime, event: T) {
+        self.inner.lock().unwrap().write_row(Some(time), event);
+    }
+}
+
+impl<T> Clone for CsvSinkWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CsvSinkWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CsvSinkWriter").finish_non_exhaustive()
+    }
+}
+
+/// Formats a timestamp as a decimal number of seconds since the epoch.
+fn format_time(time: MonotonicTime) -> String {
+    let secs = time.as_secs();
+    let nanos = time.subsec_nanos();
+
+    if secs >= 0 || nanos == 0 {
+        format!("{}.{:09}", secs, nanos)
+    } else {
+        format!("-{}.{:09}", -(secs + 1), 1_000_000_000 - nanos)
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn csv_sink_timed_rows() {
+        let path =
+            std::env::temp_dir().join(format!("nexosim_csv_sink_{}.csv", std::process::id()));
+
+        let sink = CsvSink::<(u32, f64)>::new_timed(&path, &["id", "value"]).unwrap();
+        sink.set_flush_policy(FlushPolicy::Periodic(NonZeroUsize::new(2).unwrap()));
+        let writer = sink.writer();
+        writer.write_timed(MonotonicTime::new(1, 500_000_000).unwrap(), (1, 0.5));
+        writer.write_timed(MonotonicTime::new(-2, 250_000_000).unwrap(), (2, 1.5));
+        sink.flush().unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            contents,
+            "time,id,value\n1.500000000,1,0.5\n-1.750000000,2,1.5\n"
+        );
+    }
+}
@@ -58,6 +58,34 @@ impl<T> EventBuffer<T> {
             }),
         }
     }
+
+    /// Removes and returns all events currently in the buffer, in
+    /// first-in-first-out order.
+    ///
+    /// This method never blocks: events written after the buffer was drained
+    /// are left in the buffer. This is equivalent to collecting the events
+    /// returned by the iterator until it returns `None`, except that the
+    /// buffer is only locked once and that events concurrently written to the
+    /// buffer cannot extend the draining indefinitely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::{EventBuffer, EventSink, EventSinkWriter};
+    ///
+    /// let mut buffer = EventBuffer::new();
+    /// let writer = buffer.writer();
+    /// writer.write(1);
+    /// writer.write(2);
+    ///
+    /// assert_eq!(buffer.drain(), vec![1, 2]);
+    /// assert!(buffer.drain().is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut buffer = self.inner.buffer.lock().unwrap();
+
+        std::mem::take(&mut *buffer).into()
+    }
 }
 
 impl<T: Send + 'static> EventSink<T> for EventBuffer<T> {
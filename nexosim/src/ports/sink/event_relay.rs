@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::{EventSink, EventSinkStream, EventSinkWriter, Subscriber};
+
+/// The shared data of an `EventRelay`.
+struct Inner<T> {
+    capacity: usize,
+    is_open: AtomicBool,
+    state: Mutex<State<T>>,
+}
+
+/// The mutable state of an `EventRelay`.
+struct State<T> {
+    buffer: VecDeque<T>,
+    subscriber: Option<Subscriber<T>>,
+}
+
+/// An iterator implementing [`EventSink`] and [`EventSinkStream`] that can
+/// forward events to a subscriber as soon as they are written.
+///
+/// As long as no subscriber is attached, an `EventRelay` behaves like an
+/// [`EventBuffer`](crate::ports::EventBuffer): events are stored in a
+/// fixed-capacity buffer, older events being overwritten if the maximum
+/// capacity is exceeded.
+///
+/// When registered in an
+/// [`EndpointRegistry`](crate::registry::EndpointRegistry), an `EventRelay`
+/// can be subscribed to by a remote client with the `StreamSink` RPC, in which
+/// case events are pushed to the client as they occur rather than buffered.
+/// Only one subscriber may be attached at a time: a new subscription replaces
+/// the previous one. Events buffered before the subscription remain available
+/// through the iterator.
+///
+/// If the subscriber cannot keep up with the rate of events, the subscription
+/// is cancelled and the relay falls back to buffering.
+pub struct EventRelay<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> EventRelay<T> {
+    /// Default buffer capacity when constructed with `new`.
+    pub const DEFAULT_CAPACITY: usize = 16;
+
+    /// Creates an open `EventRelay` with the default buffer capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates a closed `EventRelay` with the default buffer capacity.
+    pub fn new_closed() -> Self {
+        Self::with_capacity_closed(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates an open `EventRelay` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_state(capacity, true)
+    }
+
+    /// Creates a closed `EventRelay` with the specified buffer capacity.
+    pub fn with_capacity_closed(capacity: usize) -> Self {
+        Self::with_state(capacity, false)
+    }
+
+    /// Creates an `EventRelay` with the specified capacity and open state.
+    fn with_state(capacity: usize, is_open: bool) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                is_open: AtomicBool::new(is_open),
+                state: Mutex::new(State {
+                    buffer: VecDeque::new(),
+                    subscriber: None,
+                }),
+            }),
+        }
+    }
+}
+
+impl<T: Send + 'static> EventSink<T> for EventRelay<T> {
+    type Writer = EventRelayWriter<T>;
+
+    fn writer(&self) -> Self::Writer {
+        EventRelayWriter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Iterator for EventRelay<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.state.lock().unwrap().buffer.pop_front()
+    }
+}
+
+impl<T: Send + 'static> EventSinkStream for EventRelay<T> {
+    fn open(&mut self) {
+        self.inner.is_open.store(true, Ordering::Relaxed);
+    }
+
+    fn close(&mut self) {
+        self.inner.is_open.store(false, Ordering::Relaxed);
+    }
+
+    #[doc(hidden)]
+    #[allow(private_interfaces)]
+    fn __try_fold<B, F, E>(&mut self, init: B, f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> Result<B, E>,
+    {
+        let mut state = self.inner.state.lock().unwrap();
+        let mut drain = state.buffer.drain(..);
+
+        drain.try_fold(init, f)
+    }
+
+    #[doc(hidden)]
+    #[allow(private_interfaces)]
+    fn __subscribe(
+        &mut self,
+        subscriber: Subscriber<Self::Item>,
+    ) -> Result<(), Subscriber<Self::Item>> {
+        self.inner.state.lock().unwrap().subscriber = Some(subscriber);
+
+        Ok(())
+    }
+}
+
+impl<T> Default for EventRelay<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for EventRelay<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventRelay").finish_non_exhaustive()
+    }
+}
+
+/// A producer handle of an `EventRelay`.
+pub struct EventRelayWriter<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Send + 'static> EventSinkWriter<T> for EventRelayWriter<T> {
+    /// Forwards an event to the subscriber, if any, or pushes it onto the
+    /// buffer otherwise.
+    fn write(&self, event: T) {
+        if !self.inner.is_open.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut state = self.inner.state.lock().unwrap();
+        if let Some(subscriber) = &mut state.subscriber {
+            // If the subscriber cannot accept the event, the subscription is
+            // cancelled and the event is lost.
+            if !subscriber.notify(event) {
+                state.subscriber = None;
+            }
+
+            return;
+        }
+
+        if state.buffer.len() == self.inner.capacity {
+            state.buffer.pop_front();
+        }
+
+        state.buffer.push_back(event);
+    }
+}
+
+impl<T> Clone for EventRelayWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventRelayWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventRelayWriter").finish_non_exhaustive()
+    }
+}
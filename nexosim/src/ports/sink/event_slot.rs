@@ -2,12 +2,15 @@ use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, TryLockError, TryLockResult};
 
+use async_event::Event;
+
 use super::{EventSink, EventSinkStream, EventSinkWriter};
 
 /// The shared data of an `EventBuffer`.
 struct Inner<T> {
     is_open: AtomicBool,
     slot: Mutex<Option<T>>,
+    signal: Event,
 }
 
 /// An iterator implementing [`EventSink`] and [`EventSinkStream`] that only
@@ -27,6 +30,7 @@ impl<T> EventSlot<T> {
             inner: Arc::new(Inner {
                 is_open: AtomicBool::new(true),
                 slot: Mutex::new(None),
+                signal: Event::new(),
             }),
         }
     }
@@ -37,9 +41,29 @@ impl<T> EventSlot<T> {
             inner: Arc::new(Inner {
                 is_open: AtomicBool::new(false),
                 slot: Mutex::new(None),
+                signal: Event::new(),
             }),
         }
     }
+
+    /// Waits until the slot contains an event and takes it.
+    ///
+    /// The returned future resolves immediately if the slot already contains
+    /// an event. Otherwise, it resolves when an event is written to the slot.
+    /// This makes it possible to await simulation outputs from an external
+    /// asynchronous runtime without polling the slot with
+    /// [`next`](Iterator::next), which can be used interchangeably.
+    ///
+    /// Consistently with the slot semantics, if several events are written
+    /// before the slot is read, only the last one is returned. Note that the
+    /// future never resolves if the slot is closed or if no event is written
+    /// anymore.
+    pub async fn recv(&mut self) -> T {
+        self.inner
+            .signal
+            .wait_until(|| self.inner.slot.lock().unwrap().take())
+            .await
+    }
 }
 
 impl<T: Send + 'static> EventSink<T> for EventSlot<T> {
@@ -99,18 +123,15 @@ impl<T: Send + 'static> EventSinkWriter<T> for EventSlotWriter<T> {
             return;
         }
 
-        // Why do we just use `try_lock` and abandon if the lock is taken? The
-        // reason is that (i) the reader is never supposed to access the slot
-        // when the simulation runs and (ii) as a rule the simulator does not
-        // warrant fairness when concurrently writing to an input. Therefore, if
-        // the mutex is already locked when this writer attempts to lock it, it
-        // means another writer is concurrently writing an event, and that event
-        // is just as legitimate as ours so there is not need to overwrite it.
-        match self.inner.slot.try_lock() {
-            TryLockResult::Ok(mut v) => *v = Some(event),
-            TryLockResult::Err(TryLockError::WouldBlock) => {}
-            TryLockResult::Err(TryLockError::Poisoned(_)) => panic!(),
-        }
+        // Unlike with `next`, the reader may access the slot concurrently
+        // with the simulation when waiting with `recv`, so the lock must be
+        // acquired to make sure the event is not lost. This may in turn
+        // overwrite an event written concurrently by another writer, but as a
+        // rule the simulator does not warrant fairness when concurrently
+        // writing to an input, so that event is not more legitimate than ours.
+        *self.inner.slot.lock().unwrap() = Some(event);
+
+        self.inner.signal.notify_one();
     }
 }
 
@@ -127,3 +148,33 @@ impl<T> fmt::Debug for EventSlotWriter<T> {
         f.debug_struct("EventStreamWriter").finish_non_exhaustive()
     }
 }
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use futures_executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn event_slot_recv() {
+        let mut slot = EventSlot::new();
+        let writer = slot.writer();
+
+        // A value already in the slot is returned immediately.
+        writer.write(1);
+        writer.write(2);
+        assert_eq!(block_on(slot.recv()), 2);
+        assert_eq!(slot.next(), None);
+
+        // A value written later wakes up the reader.
+        let th = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            writer.write(3);
+        });
+        assert_eq!(block_on(slot.recv()), 3);
+        th.join().unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use super::{EventSink, EventSinkWriter};
+
+/// Running statistics of the events collected by a [`StatsSink`].
+///
+/// If no event was collected, `count` is zero and all other fields are NaN.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    /// Number of collected events.
+    pub count: u64,
+    /// Smallest collected value.
+    pub min: f64,
+    /// Largest collected value.
+    pub max: f64,
+    /// Arithmetic mean of the collected values.
+    pub mean: f64,
+    /// Population variance of the collected values.
+    pub variance: f64,
+}
+
+impl Stats {
+    /// Returns the population standard deviation of the collected values.
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// The running state of a `StatsSink`.
+#[derive(Clone, Copy)]
+struct Accumulator {
+    count: u64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// Sum of the squared deviations from the mean.
+    m2: f64,
+}
+
+impl Accumulator {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Updates the statistics with Welford's algorithm.
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn stats(&self) -> Stats {
+        if self.count == 0 {
+            return Stats {
+                count: 0,
+                min: f64::NAN,
+                max: f64::NAN,
+                mean: f64::NAN,
+                variance: f64::NAN,
+            };
+        }
+
+        Stats {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            variance: self.m2 / self.count as f64,
+        }
+    }
+}
+
+/// An [`EventSink`] that maintains running statistics of numerical events
+/// rather than storing them.
+///
+/// The count, minimum, maximum, mean and variance of the events are updated
+/// incrementally, so memory usage does not grow with the number of events.
+/// The mean and variance are computed with Welford's algorithm for numerical
+/// stability.
+///
+/// # Examples
+///
+/// ```
+/// use nexosim::ports::{Output, StatsSink};
+///
+/// let stats = StatsSink::<f32>::new();
+/// let mut output = Output::<f32>::default();
+/// output.connect_sink(&stats);
+///
+/// // ... run the simulation ...
+///
+/// let snapshot = stats.snapshot();
+/// println!("mean: {}, std dev: {}", snapshot.mean, snapshot.std_dev());
+/// ```
+pub struct StatsSink<T> {
+    inner: Arc<Mutex<Accumulator>>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Into<f64>> StatsSink<T> {
+    /// Creates a `StatsSink` with no collected events.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Accumulator::new())),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the statistics of the events collected so far.
+    pub fn snapshot(&self) -> Stats {
+        self.inner.lock().unwrap().stats()
+    }
+
+    /// Discards the statistics of the events collected so far.
+    pub fn reset(&self) {
+        *self.inner.lock().unwrap() = Accumulator::new();
+    }
+}
+
+impl<T: Into<f64> + Send + 'static> EventSink<T> for StatsSink<T> {
+    type Writer = StatsSinkWriter<T>;
+
+    fn writer(&self) -> Self::Writer {
+        StatsSinkWriter {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Into<f64>> Default for StatsSink<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for StatsSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StatsSink").finish_non_exhaustive()
+    }
+}
+
+/// A writer handle of a `StatsSink`.
+pub struct StatsSinkWriter<T> {
+    inner: Arc<Mutex<Accumulator>>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Into<f64> + Send + 'static> EventSinkWriter<T> for StatsSinkWriter<T> {
+    /// Updates the statistics with the event.
+    fn write(&self, event: T) {
+        self.inner.lock().unwrap().push(event.into());
+    }
+}
+
+impl<T> Clone for StatsSinkWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for StatsSinkWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StatsSinkWriter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_sink_running_statistics() {
+        let sink = StatsSink::<f64>::new();
+        assert_eq!(sink.snapshot().count, 0);
+        assert!(sink.snapshot().mean.is_nan());
+
+        let writer = sink.writer();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            writer.write(value);
+        }
+
+        let stats = sink.snapshot();
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.variance, 4.0);
+        assert_eq!(stats.std_dev(), 2.0);
+
+        sink.reset();
+        assert_eq!(sink.snapshot().count, 0);
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::executor;
+use crate::time::MonotonicTime;
+
+use super::{EventSink, EventSinkStream, EventSinkWriter};
+
+/// An adapter that timestamps the events written to an event sink.
+///
+/// A `Timed` sink wraps an event sink of `(MonotonicTime, T)` pairs, such as an
+/// [`EventBuffer<(MonotonicTime, T)>`](crate::ports::EventBuffer), and
+/// implements [`EventSink<T>`]: each event sent by a model output is stored
+/// together with the simulation time at which it was sent. If the wrapped sink
+/// is also an iterator or an [`EventSinkStream`], so is the `Timed` sink.
+///
+/// Since the simulation time is only known from within a simulation, events
+/// written to a `Timed` sink from outside a simulation, for instance when a
+/// writer is used directly or when an output port is used before it is
+/// associated to a model, are silently discarded.
+///
+/// # Examples
+///
+/// ```
+/// use nexosim::ports::{EventBuffer, Output, Timed};
+/// use nexosim::time::MonotonicTime;
+///
+/// let mut output = Output::<u32>::default();
+/// let sink = Timed::new(EventBuffer::<(MonotonicTime, u32)>::new());
+/// output.connect_sink(&sink);
+/// ```
+pub struct Timed<S> {
+    sink: S,
+}
+
+impl<S> Timed<S> {
+    /// Creates a `Timed` sink that wraps the specified sink.
+    pub fn new(sink: S) -> Self {
+        Self { sink }
+    }
+
+    /// Returns a reference to the wrapped sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns a mutable reference to the wrapped sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Returns the wrapped sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<T, S> EventSink<T> for Timed<S>
+where
+    T: Send + 'static,
+    S: EventSink<(MonotonicTime, T)>,
+{
+    type Writer = TimedWriter<S::Writer>;
+
+    fn writer(&self) -> Self::Writer {
+        TimedWriter {
+            writer: self.sink.writer(),
+        }
+    }
+}
+
+impl<S: Iterator> Iterator for Timed<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sink.next()
+    }
+}
+
+impl<S: EventSinkStream> EventSinkStream for Timed<S> {
+    fn open(&mut self) {
+        self.sink.open();
+    }
+
+    fn close(&mut self) {
+        self.sink.close();
+    }
+
+    #[doc(hidden)]
+    #[allow(private_interfaces)]
+    fn __try_fold<B, F, E>(&mut self, init: B, f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> Result<B, E>,
+    {
+        self.sink.__try_fold(init, f)
+    }
+}
+
+impl<S> fmt::Debug for Timed<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timed").finish_non_exhaustive()
+    }
+}
+
+/// A producer handle of a `Timed` sink.
+pub struct TimedWriter<W> {
+    writer: W,
+}
+
+impl<T, W> EventSinkWriter<T> for TimedWriter<W>
+where
+    T: Send + 'static,
+    W: EventSinkWriter<(MonotonicTime, T)>,
+{
+    /// Pushes an event timestamped with the current simulation time onto the
+    /// wrapped sink.
+    ///
+    /// The event is silently discarded if it is not written from within a
+    /// simulation, since the simulation time is then unknown.
+    fn write(&self, event: T) {
+        if let Some(time) = executor::simulation_time() {
+            self.writer.write((time, event));
+        }
+    }
+
+    /// Pushes a timestamped event onto the wrapped sink.
+    fn write_timed(&self, time: MonotonicTime, event: T) {
+        self.writer.write((time, event));
+    }
+}
+
+impl<W: Clone> Clone for TimedWriter<W> {
+    fn clone(&self) -> Self {
+        Self {
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl<W> fmt::Debug for TimedWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimedWriter").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use tokio::sync::mpsc;
+
+use crate::ports::LossCounter;
+
+use super::{EventSink, EventSinkWriter};
+
+/// An [`EventSink`] that forwards events to a bounded Tokio channel.
+///
+/// This sink makes it possible to consume events in asynchronous tasks of an
+/// application which embeds the simulation, by connecting model outputs to the
+/// [`Sender`](mpsc::Sender) side of a
+/// [`tokio::sync::mpsc::channel`](mpsc::channel).
+///
+/// Events are forwarded with a non-blocking `try_send`, so the simulation is
+/// never stalled by a slow consumer. An event is dropped if the channel is
+/// full or if the receiver was dropped at the time the event is written. The
+/// number of dropped events can be monitored with the [`LossCounter`] returned
+/// by [`TokioChannelSink::loss_counter`].
+///
+/// # Examples
+///
+/// ```
+/// use nexosim::ports::{Output, TokioChannelSink};
+///
+/// let (sender, mut receiver) = tokio::sync::mpsc::channel(64);
+/// let sink = TokioChannelSink::<u32>::new(sender);
+/// let mut output = Output::<u32>::default();
+/// output.connect_sink(&sink);
+///
+/// // The receiver can now be moved to an asynchronous task.
+/// ```
+pub struct TokioChannelSink<T> {
+    sender: mpsc::Sender<T>,
+    loss_counter: LossCounter,
+}
+
+impl<T> TokioChannelSink<T> {
+    /// Creates a sink that forwards events to the specified channel sender.
+    pub fn new(sender: mpsc::Sender<T>) -> Self {
+        Self {
+            sender,
+            loss_counter: LossCounter::default(),
+        }
+    }
+
+    /// Returns a counter of the events dropped so far because the channel was
+    /// full or closed.
+    pub fn loss_counter(&self) -> LossCounter {
+        self.loss_counter.clone()
+    }
+}
+
+impl<T: Send + 'static> EventSink<T> for TokioChannelSink<T> {
+    type Writer = TokioChannelSinkWriter<T>;
+
+    fn writer(&self) -> Self::Writer {
+        TokioChannelSinkWriter {
+            sender: self.sender.clone(),
+            loss_counter: self.loss_counter.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TokioChannelSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokioChannelSink")
+            .field("loss_counter", &self.loss_counter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A writer handle of a `TokioChannelSink`.
+pub struct TokioChannelSinkWriter<T> {
+    sender: mpsc::Sender<T>,
+    loss_counter: LossCounter,
+}
+
+impl<T: Send + 'static> EventSinkWriter<T> for TokioChannelSinkWriter<T> {
+    /// Forwards the event to the channel, or drops it if the channel is full
+    /// or closed.
+    fn write(&self, event: T) {
+        if self.sender.try_send(event).is_err() {
+            self.loss_counter.increment();
+        }
+    }
+}
+
+impl<T> Clone for TokioChannelSinkWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            loss_counter: self.loss_counter.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for TokioChannelSinkWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokioChannelSinkWriter")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokio_channel_sink_overflow() {
+        let (sender, mut receiver) = mpsc::channel(2);
+        let sink = TokioChannelSink::new(sender);
+        let counter = sink.loss_counter();
+        let writer = sink.writer();
+
+        writer.write(1);
+        writer.write(2);
+        writer.write(3); // dropped: the channel is full
+        assert_eq!(counter.count(), 1);
+        assert_eq!(receiver.try_recv(), Ok(1));
+
+        writer.write(4);
+        assert_eq!(receiver.try_recv(), Ok(2));
+        assert_eq!(receiver.try_recv(), Ok(4));
+
+        drop(receiver);
+        writer.write(5); // dropped: the channel is closed
+        assert_eq!(counter.count(), 2);
+    }
+}
@@ -2,14 +2,19 @@ mod broadcaster;
 mod sender;
 
 use std::fmt;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use crate::executor::SIMULATION_CONTEXT;
 use crate::model::Model;
 use crate::ports::InputFn;
 use crate::simulation::{
-    Action, ActionKey, Address, KeyedOnceAction, KeyedPeriodicAction, OnceAction, PeriodicAction,
+    Action, ActionInjector, ActionKey, Address, ExecutionError, ExternalSignal, KeyedOnceAction,
+    KeyedPeriodicAction, OnceAction, PeriodicAction, Scheduler, SchedulingError, DEFAULT_PRIORITY,
+    GLOBAL_SCHEDULER_ORIGIN_ID,
 };
+use crate::time::{MonotonicTime, SimDuration};
 use crate::util::slot;
 use crate::util::unwrap_or_throw::UnwrapOrThrow;
 
@@ -29,6 +34,7 @@ use super::ReplierFn;
 /// simulation control endpoint instantiated during bench assembly.
 pub struct EventSource<T: Clone + Send + 'static> {
     broadcaster: EventBroadcaster<T>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl<T: Clone + Send + 'static> EventSource<T> {
@@ -37,6 +43,47 @@ impl<T: Clone + Send + 'static> EventSource<T> {
         Self::default()
     }
 
+    /// Sets the minimum interval, in simulation time, between two events
+    /// broadcast by this source.
+    ///
+    /// This makes it possible to model a device that cannot accept commands
+    /// faster than some rate, irrespective of how fast the events are
+    /// submitted. The interval applies to all events sent by this source,
+    /// however their action was scheduled or processed.
+    ///
+    /// Events are never rejected. When an event is due less than the minimum
+    /// interval after the previous event, its broadcast is deferred to the
+    /// earliest time that honors the interval. Events submitted faster than
+    /// the interval are thus queued and spread out in submission order, one
+    /// interval apart, so a driver that keeps flooding the source creates an
+    /// ever-growing backlog in simulation time. Note that a deferred event can
+    /// no longer be cancelled. In the unlikely case where the backlog extends
+    /// beyond [`MonotonicTime::MAX`], the events that could never be processed
+    /// are discarded.
+    ///
+    /// A null interval disables rate limiting, which is the default behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::ports::EventSource;
+    ///
+    /// // A command source accepting at most one command every 10ms.
+    /// let source: EventSource<u8> =
+    ///     EventSource::new().with_min_interval(Duration::from_millis(10));
+    /// ```
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.rate_limiter = if min_interval.is_zero() {
+            None
+        } else {
+            Some(Arc::new(RateLimiter::new(min_interval)))
+        };
+
+        self
+    }
+
     /// Adds a connection to an input port of the model specified by the
     /// address.
     ///
@@ -107,7 +154,7 @@ impl<T: Clone + Send + 'static> EventSource<T> {
             fut.await.unwrap_or_throw();
         };
 
-        Action::new(OnceAction::new(fut))
+        Action::new(OnceAction::new(limit_rate(self.rate_limiter.clone(), fut)))
     }
 
     /// Returns a cancellable action and a cancellation key; when processed, the
@@ -115,6 +162,7 @@ impl<T: Clone + Send + 'static> EventSource<T> {
     pub fn keyed_event(&self, arg: T) -> (Action, ActionKey) {
         let action_key = ActionKey::new();
         let fut = self.broadcaster.broadcast(arg);
+        let rate_limiter = self.rate_limiter.clone();
 
         let action = Action::new(KeyedOnceAction::new(
             // Cancellation is ignored once the action is already spawned on the
@@ -122,8 +170,10 @@ impl<T: Clone + Send + 'static> EventSource<T> {
             // simulation step targeted by the action is running, but since an
             // event source is meant to be used outside the simulator, this
             // shouldn't be an issue in practice.
-            |_| async {
-                fut.await.unwrap_or_throw();
+            |_| {
+                limit_rate(rate_limiter, async {
+                    fut.await.unwrap_or_throw();
+                })
             },
             action_key.clone(),
         ));
@@ -139,7 +189,10 @@ impl<T: Clone + Send + 'static> EventSource<T> {
         Action::new(PeriodicAction::new(
             || async move {
                 let fut = source.broadcaster.broadcast(arg);
-                fut.await.unwrap_or_throw();
+                let fut = async {
+                    fut.await.unwrap_or_throw();
+                };
+                limit_rate(source.rate_limiter.clone(), fut).await;
             },
             period,
         ))
@@ -160,7 +213,10 @@ impl<T: Clone + Send + 'static> EventSource<T> {
             // practice.
             |_| async move {
                 let fut = source.broadcaster.broadcast(arg);
-                fut.await.unwrap_or_throw();
+                let fut = async {
+                    fut.await.unwrap_or_throw();
+                };
+                limit_rate(source.rate_limiter.clone(), fut).await;
             },
             period,
             action_key.clone(),
@@ -168,12 +224,58 @@ impl<T: Clone + Send + 'static> EventSource<T> {
 
         (action, action_key)
     }
+
+    /// Schedules a sequence of events with the specified offsets from the
+    /// current simulation time and returns the number of scheduled events.
+    ///
+    /// This is typically used to replay a recorded trace of timestamped
+    /// events. Offsets must be in chronological order, but several events may
+    /// share the same offset, in which case they are processed in sequence
+    /// order.
+    ///
+    /// The sequence is validated as a whole before any event is scheduled: an
+    /// error is returned and no event is scheduled if the offsets are not in
+    /// chronological order, if an offset is null or if the scheduling time of
+    /// an event cannot be represented as a [`MonotonicTime`].
+    pub fn schedule_from_iter<I>(
+        &self,
+        scheduler: &Scheduler,
+        events: I,
+    ) -> Result<usize, SchedulingError>
+    where
+        I: IntoIterator<Item = (Duration, T)>,
+    {
+        let events: Vec<_> = events.into_iter().collect();
+
+        if events.windows(2).any(|pair| pair[1].0 < pair[0].0) {
+            return Err(SchedulingError::NonMonotonicSchedule);
+        }
+        if events.first().is_some_and(|(offset, _)| offset.is_zero()) {
+            return Err(SchedulingError::InvalidScheduledTime);
+        }
+
+        let now = scheduler.time();
+        let actions = events
+            .into_iter()
+            .map(|(offset, arg)| {
+                SimDuration::from(offset)
+                    .checked_add_to(now)
+                    .map(|time| (time, self.event(arg)))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SchedulingError::InvalidScheduledTime)?;
+        let count = actions.len();
+        scheduler.schedule_batch(actions)?;
+
+        Ok(count)
+    }
 }
 
 impl<T: Clone + Send + 'static> Default for EventSource<T> {
     fn default() -> Self {
         Self {
             broadcaster: EventBroadcaster::default(),
+            rate_limiter: None,
         }
     }
 }
@@ -188,6 +290,164 @@ impl<T: Clone + Send + 'static> fmt::Debug for EventSource<T> {
     }
 }
 
+/// A limiter enforcing a minimum interval between the events of a source.
+struct RateLimiter {
+    min_interval: Duration,
+    /// Time slot reserved by the latest event, if any.
+    last_time: Mutex<Option<MonotonicTime>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter with the specified minimum interval.
+    fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_time: Mutex::new(None),
+        }
+    }
+
+    /// Reserves and returns the earliest time slot that is at or after the
+    /// specified time and honors the minimum interval.
+    ///
+    /// `None` is returned and no slot is reserved if the earliest time slot
+    /// cannot be represented as a `MonotonicTime`.
+    fn reserve(&self, now: MonotonicTime) -> Option<MonotonicTime> {
+        let mut last_time = self.last_time.lock().unwrap();
+        let time = match *last_time {
+            Some(last_time) => {
+                now.max(SimDuration::from(self.min_interval).checked_add_to(last_time)?)
+            }
+            None => now,
+        };
+        *last_time = Some(time);
+
+        Some(time)
+    }
+}
+
+/// Processes the broadcast future immediately if the rate limiter allows it,
+/// or defers it to the earliest allowed time otherwise.
+async fn limit_rate<F>(rate_limiter: Option<Arc<RateLimiter>>, fut: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    if let Some(rate_limiter) = rate_limiter {
+        if let Some(scheduler) = SIMULATION_CONTEXT.map(|ctx| ctx.scheduler.clone()) {
+            let now = scheduler.time();
+            let time = match rate_limiter.reserve(now) {
+                Some(time) => time,
+                // The event can never be processed.
+                None => return,
+            };
+            if time > now {
+                // The deadline is in the future so scheduling cannot fail.
+                scheduler
+                    .schedule_from(
+                        time,
+                        Action::new(OnceAction::new(fut)),
+                        DEFAULT_PRIORITY,
+                        GLOBAL_SCHEDULER_ORIGIN_ID,
+                    )
+                    .unwrap();
+
+                return;
+            }
+        }
+    }
+
+    fut.await;
+}
+
+/// A thread-safe handle to an [`EventSource`] that injects events into a
+/// running simulation.
+///
+/// An `ExternalEventSource` is obtained with
+/// [`SimInit::external_event_source`](crate::simulation::SimInit::external_event_source).
+/// It can be cloned and sent to other threads, making it possible for instance
+/// to feed a simulation running with
+/// [`Simulation::step_unbounded`](crate::simulation::Simulation::step_unbounded)
+/// from a co-simulation thread without resorting to a dedicated listener
+/// model.
+///
+/// Events are not sent directly to the connected models but are inserted into
+/// the scheduler queue at the current simulation time, so causality is
+/// preserved: an event is processed by the simulation at the earliest in the
+/// time slice that follows the one being processed, if any, and events sent
+/// from the same handle are processed in the order they were sent.
+///
+/// As long as an `ExternalEventSource` or one of its clones is alive,
+/// [`Simulation::step_unbounded`](crate::simulation::Simulation::step_unbounded)
+/// does not return once the simulation is idle, but waits until an event is
+/// sent.
+pub struct ExternalEventSource<T: Clone + Send + 'static> {
+    source: Arc<EventSource<T>>,
+    injector: ActionInjector,
+    signal: Arc<ExternalSignal>,
+}
+
+impl<T: Clone + Send + 'static> ExternalEventSource<T> {
+    /// Creates a new handle to the specified event source.
+    pub(crate) fn new(
+        source: EventSource<T>,
+        injector: ActionInjector,
+        signal: Arc<ExternalSignal>,
+    ) -> Self {
+        signal.register_source();
+
+        Self {
+            source: Arc::new(source),
+            injector,
+            signal,
+        }
+    }
+
+    /// Sends an event to all connected input ports at the current simulation
+    /// time.
+    ///
+    /// This method can be called concurrently with the execution of the
+    /// simulation. The event is discarded and an
+    /// [`ExecutionError::Terminated`] error is returned if the simulation and
+    /// all its [`Scheduler`]s have been dropped. Events sent to a simulation
+    /// which is no longer stepped, for instance after a fatal error, are never
+    /// processed.
+    pub fn send(&self, arg: T) -> Result<(), ExecutionError> {
+        self.injector
+            .inject(self.source.event(arg))
+            .map_err(|_| ExecutionError::Terminated)?;
+        self.signal.notify();
+
+        Ok(())
+    }
+}
+
+impl<T: Clone + Send + 'static> Clone for ExternalEventSource<T> {
+    fn clone(&self) -> Self {
+        self.signal.register_source();
+
+        Self {
+            source: self.source.clone(),
+            injector: self.injector.clone(),
+            signal: self.signal.clone(),
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Drop for ExternalEventSource<T> {
+    fn drop(&mut self) {
+        self.signal.unregister_source();
+    }
+}
+
+impl<T: Clone + Send + 'static> fmt::Debug for ExternalEventSource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "External event source ({} connected ports)",
+            self.source.broadcaster.len()
+        )
+    }
+}
+
 /// A query source port.
 ///
 /// The `QuerySource` port is similar to an
@@ -289,6 +549,11 @@ impl<T: Clone + Send + 'static, R: Send + 'static> QuerySource<T, R> {
         self.broadcaster.add(sender);
     }
 
+    /// Returns the number of connected replier ports.
+    pub(crate) fn connection_count(&self) -> usize {
+        self.broadcaster.len()
+    }
+
     /// Returns an action which, when processed, broadcasts a query to all
     /// connected replier ports.
     pub fn query(&self, arg: T) -> (Action, ReplyReceiver<R>) {
@@ -299,7 +564,7 @@ impl<T: Clone + Send + 'static, R: Send + 'static> QuerySource<T, R> {
             let _ = writer.write(replies);
         };
 
-        let action = Action::new(OnceAction::new(fut));
+        let action = Action::new_query(OnceAction::new(fut));
 
         (action, ReplyReceiver::<R>(reader))
     }
@@ -341,3 +606,36 @@ impl<R> fmt::Debug for ReplyReceiver<R> {
         write!(f, "Replies")
     }
 }
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_reserve() {
+        let limiter = RateLimiter::new(Duration::from_secs(2));
+        let t0 = MonotonicTime::EPOCH;
+
+        assert_eq!(limiter.reserve(t0), Some(t0));
+        assert_eq!(limiter.reserve(t0), Some(t0 + Duration::from_secs(2)));
+        assert_eq!(
+            limiter.reserve(t0 + Duration::from_secs(3)),
+            Some(t0 + Duration::from_secs(4))
+        );
+        assert_eq!(
+            limiter.reserve(t0 + Duration::from_secs(10)),
+            Some(t0 + Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn rate_limiter_reserve_overflow() {
+        let limiter = RateLimiter::new(Duration::from_secs(2));
+        let t_max = MonotonicTime::MAX;
+
+        assert_eq!(limiter.reserve(t_max), Some(t_max));
+        // The next slot lies beyond the representable range.
+        assert_eq!(limiter.reserve(t_max), None);
+        assert_eq!(limiter.reserve(t_max), None);
+    }
+}
@@ -3,7 +3,23 @@
 //! This module provides the `EndpointRegistry` object which associates each
 //! event sink, event source and query source in a simulation bench to a unique
 //! name.
+//!
+//! Event sinks, event sources and query sources live in separate namespaces:
+//! a name must be unique among endpoints of the same kind, but an event source
+//! and a query source may for instance share the same name.
+//!
+//! The endpoints of a registry can be described with
+//! [`EndpointRegistry::describe`], for instance to generate strongly-typed
+//! clients for a remotely-controlled simulation bench.
+//!
+//! It also provides the [`EventRecorder`] model and the [`replay`] function,
+//! which make it possible to capture in serialized form the events delivered
+//! to a model and to later replay them against an isolated instance of that
+//! model.
 
+mod codec;
+mod description;
+mod event_recorder;
 mod event_sink_registry;
 mod event_source_registry;
 mod query_source_registry;
@@ -12,6 +28,13 @@ use serde::{de::DeserializeOwned, ser::Serialize};
 
 use crate::ports::{EventSinkStream, EventSource, QuerySource};
 
+pub use codec::Codec;
+pub use description::{
+    EventSinkDescription, EventSourceDescription, QuerySourceDescription, RegistryDescription,
+};
+pub use event_recorder::{replay, EventRecorder, Record, RecordError, Recording, ReplayError};
+
+pub(crate) use codec::CodecError;
 pub(crate) use event_sink_registry::EventSinkRegistry;
 pub(crate) use event_source_registry::EventSourceRegistry;
 pub(crate) use query_source_registry::QuerySourceRegistry;
@@ -22,6 +45,7 @@ pub struct EndpointRegistry {
     pub(crate) event_sink_registry: EventSinkRegistry,
     pub(crate) event_source_registry: EventSourceRegistry,
     pub(crate) query_source_registry: QuerySourceRegistry,
+    pub(crate) codec: Codec,
 }
 
 impl EndpointRegistry {
@@ -30,6 +54,20 @@ impl EndpointRegistry {
         Self::default()
     }
 
+    /// Sets the codec used to decode events and queries and to encode replies
+    /// and sink events.
+    ///
+    /// The default codec is [`Codec::Cbor`].
+    pub fn set_codec(&mut self, codec: Codec) {
+        self.codec = codec;
+    }
+
+    /// Returns the codec used to decode events and queries and to encode
+    /// replies and sink events.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
     /// Adds an event source to the registry.
     ///
     /// If the specified name is already in use for another event source, the source
@@ -61,6 +99,48 @@ impl EndpointRegistry {
         self.query_source_registry.add(source, name)
     }
 
+    /// Adds a query source to the registry together with a reducer that
+    /// aggregates all replies server-side.
+    ///
+    /// When the query is processed remotely, the replies are passed to the
+    /// reducer before serialization and only the reduced value is returned to
+    /// the client, as the single element of the reply list. This can save
+    /// bandwidth when a query is broadcast to many models but the client only
+    /// needs an aggregate such as a count or a logical conjunction.
+    ///
+    /// If the specified name is already in use for another query source, the
+    /// source provided as argument is returned in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::QuerySource;
+    /// use nexosim::registry::EndpointRegistry;
+    ///
+    /// // A health-check query which replies are reduced to a single boolean.
+    /// let health_check = QuerySource::<(), bool>::new();
+    ///
+    /// let mut registry = EndpointRegistry::new();
+    /// registry
+    ///     .add_reduced_query_source(health_check, |mut replies| replies.all(|ok| ok), "all_ok")
+    ///     .unwrap();
+    /// ```
+    pub fn add_reduced_query_source<T, R, A, F>(
+        &mut self,
+        source: QuerySource<T, R>,
+        reducer: F,
+        name: impl Into<String>,
+    ) -> Result<(), QuerySource<T, R>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+        R: Send + 'static,
+        A: Serialize + 'static,
+        F: Fn(Box<dyn Iterator<Item = R> + '_>) -> A + Send + Sync + 'static,
+    {
+        self.query_source_registry
+            .add_reduced(source, reducer, name)
+    }
+
     /// Adds an event sink to the registry.
     ///
     /// If the specified name is already in use for another event sink, the
@@ -72,4 +152,343 @@ impl EndpointRegistry {
     {
         self.event_sink_registry.add(sink, name)
     }
+
+    /// Adds an event source to the registry and returns the registry.
+    ///
+    /// This is a chainable alternative to
+    /// [`add_event_source`](Self::add_event_source). If the specified name is
+    /// already in use for another event source, the source provided as
+    /// argument is returned in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::{EventBuffer, EventSource};
+    /// use nexosim::registry::EndpointRegistry;
+    ///
+    /// let registry = EndpointRegistry::new()
+    ///     .with_event_source(EventSource::<u64>::new(), "input")
+    ///     .unwrap()
+    ///     .with_event_sink(EventBuffer::<u64>::new(), "output")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(registry.sink_names().len(), 1);
+    /// ```
+    pub fn with_event_source<T>(
+        mut self,
+        source: EventSource<T>,
+        name: impl Into<String>,
+    ) -> Result<Self, EventSource<T>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+    {
+        self.add_event_source(source, name)?;
+
+        Ok(self)
+    }
+
+    /// Adds a query source to the registry and returns the registry.
+    ///
+    /// This is a chainable alternative to
+    /// [`add_query_source`](Self::add_query_source). If the specified name is
+    /// already in use for another query source, the source provided as
+    /// argument is returned in the error.
+    pub fn with_query_source<T, R>(
+        mut self,
+        source: QuerySource<T, R>,
+        name: impl Into<String>,
+    ) -> Result<Self, QuerySource<T, R>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+        R: Serialize + Send + 'static,
+    {
+        self.add_query_source(source, name)?;
+
+        Ok(self)
+    }
+
+    /// Adds an event sink to the registry and returns the registry.
+    ///
+    /// This is a chainable alternative to
+    /// [`add_event_sink`](Self::add_event_sink). If the specified name is
+    /// already in use for another event sink, the event sink provided as
+    /// argument is returned in the error.
+    pub fn with_event_sink<S>(mut self, sink: S, name: impl Into<String>) -> Result<Self, S>
+    where
+        S: EventSinkStream + Send + 'static,
+        S::Item: Serialize,
+    {
+        self.add_event_sink(sink, name)?;
+
+        Ok(self)
+    }
+
+    /// Returns the names of all registered event sources, sorted by name,
+    /// together with the name of their event type.
+    ///
+    /// Type names are as returned by [`std::any::type_name`] and are therefore
+    /// only meant for display purposes.
+    pub fn event_source_names(&self) -> Vec<(String, &'static str)> {
+        let mut names: Vec<_> = self
+            .event_source_registry
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.event_type_name()))
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    /// Returns the names of all registered query sources, sorted by name,
+    /// together with the name of their request type.
+    ///
+    /// Since query sources do not share their namespace with event sources, a
+    /// name may appear both in this list and in the list returned by
+    /// [`event_source_names`](Self::event_source_names).
+    ///
+    /// Type names are as returned by [`std::any::type_name`] and are therefore
+    /// only meant for display purposes.
+    pub fn query_source_names(&self) -> Vec<(String, &'static str)> {
+        let mut names: Vec<_> = self
+            .query_source_registry
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.request_type_name()))
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    /// Returns the names of all registered event sinks, sorted by name,
+    /// together with the name of their event type.
+    ///
+    /// Type names are as returned by [`std::any::type_name`] and are therefore
+    /// only meant for display purposes.
+    pub fn sink_names(&self) -> Vec<(String, &'static str)> {
+        let mut names: Vec<_> = self
+            .event_sink_registry
+            .iter()
+            .map(|(name, sink)| (name.to_string(), sink.event_type_name()))
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    /// Returns a structured description of all registered endpoints.
+    ///
+    /// The description contains the name of each endpoint together with the
+    /// name of its payload types, which is enough for a client to determine
+    /// how to encode events and requests and how to decode replies and sink
+    /// events. The same information is available remotely through the
+    /// `ListSources` and `ListSinks` methods of the gRPC server.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::{EventBuffer, EventSource, QuerySource};
+    /// use nexosim::registry::{Codec, EndpointRegistry};
+    ///
+    /// let registry = EndpointRegistry::new()
+    ///     .with_event_source(EventSource::<u64>::new(), "input")
+    ///     .unwrap()
+    ///     .with_query_source(QuerySource::<(), bool>::new(), "status")
+    ///     .unwrap()
+    ///     .with_event_sink(EventBuffer::<f64>::new(), "output")
+    ///     .unwrap();
+    ///
+    /// let description = registry.describe();
+    /// assert_eq!(description.codec, Codec::Cbor);
+    /// assert_eq!(description.event_sources[0].event_type, "u64");
+    /// assert_eq!(description.query_sources[0].reply_type, "bool");
+    /// assert_eq!(description.event_sinks[0].name, "output");
+    /// ```
+    pub fn describe(&self) -> RegistryDescription {
+        let mut event_sources: Vec<_> = self
+            .event_source_registry
+            .iter()
+            .map(|(name, source)| EventSourceDescription {
+                name: name.to_string(),
+                event_type: source.event_type_name(),
+            })
+            .collect();
+        event_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut query_sources: Vec<_> = self
+            .query_source_registry
+            .iter()
+            .map(|(name, source)| QuerySourceDescription {
+                name: name.to_string(),
+                request_type: source.request_type_name(),
+                reply_type: source.reply_type_name(),
+            })
+            .collect();
+        query_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut event_sinks: Vec<_> = self
+            .event_sink_registry
+            .iter()
+            .map(|(name, sink)| EventSinkDescription {
+                name: name.to_string(),
+                event_type: sink.event_type_name(),
+            })
+            .collect();
+        event_sinks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        RegistryDescription {
+            codec: self.codec,
+            event_sources,
+            query_sources,
+            event_sinks,
+        }
+    }
+
+    /// Returns a version number identifying the set of registered endpoints.
+    ///
+    /// The version is a hash of the sorted names of all endpoints together
+    /// with the names of their payload types. It only changes when an
+    /// endpoint is added, renamed or retyped, so a client can cache the
+    /// endpoint descriptions and skip fetching them again as long as the
+    /// version is unchanged. The version is reported to remote clients by the
+    /// `ListSources` RPC of the simulation server.
+    ///
+    /// The hash is computed with a fixed algorithm (64-bit FNV-1a) and is
+    /// therefore stable across runs and platforms. Since type names are as
+    /// returned by [`std::any::type_name`], however, the version may change
+    /// when the simulation bench is compiled with another version of the
+    /// compiler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::ports::{EventBuffer, EventSource};
+    /// use nexosim::registry::EndpointRegistry;
+    ///
+    /// let mut registry = EndpointRegistry::new();
+    /// registry.add_event_source(EventSource::<u64>::new(), "input").unwrap();
+    /// let version = registry.version();
+    ///
+    /// // The version is deterministic...
+    /// let mut other_registry = EndpointRegistry::new();
+    /// other_registry
+    ///     .add_event_source(EventSource::<u64>::new(), "input")
+    ///     .unwrap();
+    /// assert_eq!(other_registry.version(), version);
+    ///
+    /// // ...but changes when the set of endpoints changes.
+    /// registry.add_event_sink(EventBuffer::<u64>::new(), "output").unwrap();
+    /// assert_ne!(registry.version(), version);
+    /// ```
+    pub fn version(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        let description = self.describe();
+
+        // Each field is terminated by a null byte so that concatenations of
+        // distinct fields cannot collide.
+        let fields = description
+            .event_sources
+            .iter()
+            .flat_map(|source| ["event_source", source.name.as_str(), source.event_type])
+            .chain(description.query_sources.iter().flat_map(|source| {
+                [
+                    "query_source",
+                    source.name.as_str(),
+                    source.request_type,
+                    source.reply_type,
+                ]
+            }))
+            .chain(
+                description
+                    .event_sinks
+                    .iter()
+                    .flat_map(|sink| ["event_sink", sink.name.as_str(), sink.event_type]),
+            );
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for field in fields {
+            for byte in field.bytes().chain([0]) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        hash
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use crate::ports::{EventBuffer, EventSlot};
+
+    use super::*;
+
+    #[test]
+    fn endpoint_names() {
+        let mut registry = EndpointRegistry::new();
+        assert!(registry.event_source_names().is_empty());
+        assert!(registry.query_source_names().is_empty());
+        assert!(registry.sink_names().is_empty());
+
+        registry
+            .add_event_source(EventSource::<u32>::new(), "speed")
+            .unwrap();
+        registry
+            .add_event_source(EventSource::<bool>::new(), "enable")
+            .unwrap();
+        registry
+            .add_query_source(QuerySource::<(), f64>::new(), "temperature")
+            .unwrap();
+        registry
+            .add_event_sink(EventBuffer::<String>::new(), "log")
+            .unwrap();
+        registry
+            .add_event_sink(EventSlot::<u64>::new(), "counter")
+            .unwrap();
+
+        assert_eq!(
+            registry.event_source_names(),
+            vec![
+                ("enable".to_string(), std::any::type_name::<bool>()),
+                ("speed".to_string(), std::any::type_name::<u32>()),
+            ]
+        );
+        assert_eq!(
+            registry.query_source_names(),
+            vec![("temperature".to_string(), std::any::type_name::<()>())]
+        );
+        assert_eq!(
+            registry.sink_names(),
+            vec![
+                ("counter".to_string(), std::any::type_name::<u64>()),
+                ("log".to_string(), std::any::type_name::<String>()),
+            ]
+        );
+    }
+
+    #[test]
+    fn endpoint_namespaces() {
+        let mut registry = EndpointRegistry::new();
+
+        // Names are unique within a namespace...
+        registry
+            .add_event_source(EventSource::<u32>::new(), "setpoint")
+            .unwrap();
+        assert!(registry
+            .add_event_source(EventSource::<u32>::new(), "setpoint")
+            .is_err());
+
+        // ...but may be shared across namespaces.
+        registry
+            .add_query_source(QuerySource::<(), u32>::new(), "setpoint")
+            .unwrap();
+        registry
+            .add_event_sink(EventBuffer::<u32>::new(), "setpoint")
+            .unwrap();
+
+        assert_eq!(registry.event_source_names().len(), 1);
+        assert_eq!(registry.query_source_names().len(), 1);
+        assert_eq!(registry.sink_names().len(), 1);
+    }
 }
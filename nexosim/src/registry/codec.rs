@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The encoding of serialized events, queries and replies.
+///
+/// CBOR is the default codec as it is compact and fast to encode and decode.
+/// JSON is mostly meant for interoperability with clients that lack CBOR
+/// support and for human inspection of the payloads.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Codec {
+    /// The CBOR binary encoding, as implemented by the `ciborium` crate.
+    #[default]
+    Cbor,
+    /// The JSON text encoding, as implemented by the `serde_json` crate.
+    Json,
+}
+
+impl Codec {
+    /// Decodes a value encoded with this codec.
+    pub(crate) fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            Self::Cbor => ciborium::from_reader(bytes).map_err(CodecError::CborDecode),
+            Self::Json => serde_json::from_slice(bytes).map_err(CodecError::Json),
+        }
+    }
+
+    /// Encodes a value with this codec.
+    pub(crate) fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            Self::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(value, &mut buffer).map_err(CodecError::CborEncode)?;
+
+                Ok(buffer)
+            }
+            Self::Json => serde_json::to_vec(value).map_err(CodecError::Json),
+        }
+    }
+}
+
+/// An error returned when a value could not be encoded or decoded.
+#[derive(Debug)]
+pub(crate) enum CodecError {
+    /// The value could not be decoded from CBOR.
+    CborDecode(ciborium::de::Error<std::io::Error>),
+    /// The value could not be encoded to CBOR.
+    CborEncode(ciborium::ser::Error<std::io::Error>),
+    /// The value could not be encoded to or decoded from JSON.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CborDecode(e) => e.fmt(f),
+            Self::CborEncode(e) => e.fmt(f),
+            Self::Json(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for CodecError {}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    type Payload = (u32, String, Option<Vec<f64>>);
+
+    fn payload() -> Payload {
+        (42, "forty-two".to_string(), Some(vec![-1.5, 0.0, 2.25]))
+    }
+
+    #[test]
+    fn codec_round_trip() {
+        for codec in [Codec::Cbor, Codec::Json] {
+            let bytes = codec.encode(&payload()).unwrap();
+            let decoded: Payload = codec.decode(&bytes).unwrap();
+
+            assert_eq!(decoded, payload());
+        }
+    }
+
+    #[test]
+    fn codec_json_is_text() {
+        let bytes = Codec::Json.encode(&payload()).unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&bytes).unwrap(),
+            r#"[42,"forty-two",[-1.5,0.0,2.25]]"#
+        );
+    }
+
+    #[test]
+    fn codec_decode_errors() {
+        // Truncated payloads.
+        let bytes = Codec::Cbor.encode(&payload()).unwrap();
+        assert!(matches!(
+            Codec::Cbor.decode::<Payload>(&bytes[..bytes.len() - 1]),
+            Err(CodecError::CborDecode(_))
+        ));
+        let bytes = Codec::Json.encode(&payload()).unwrap();
+        assert!(matches!(
+            Codec::Json.decode::<Payload>(&bytes[..bytes.len() - 1]),
+            Err(CodecError::Json(_))
+        ));
+
+        // Type mismatch.
+        let bytes = Codec::Json.encode(&"not a number").unwrap();
+        assert!(matches!(
+            Codec::Json.decode::<u32>(&bytes),
+            Err(CodecError::Json(_))
+        ));
+
+        // Payloads encoded with the other codec.
+        let bytes = Codec::Cbor.encode(&payload()).unwrap();
+        assert!(Codec::Json.decode::<Payload>(&bytes).is_err());
+        let bytes = Codec::Json.encode(&payload()).unwrap();
+        assert!(Codec::Cbor.decode::<Payload>(&bytes).is_err());
+    }
+
+    #[test]
+    fn codec_json_encode_error() {
+        // JSON object keys must be strings.
+        let map = BTreeMap::from([((1u32, 2u32), 3u32)]);
+
+        assert!(Codec::Cbor.encode(&map).is_ok());
+        let error = Codec::Json.encode(&map).unwrap_err();
+        assert!(matches!(error, CodecError::Json(_)));
+        assert!(!error.to_string().is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use super::Codec;
+
+/// A machine-readable description of the endpoints of an
+/// [`EndpointRegistry`](super::EndpointRegistry).
+///
+/// This description is typically used to generate strongly-typed clients for
+/// a remotely-controlled simulation bench. All endpoints are sorted by name.
+///
+/// Type names are as returned by [`std::any::type_name`]. While they
+/// unambiguously identify the payload types within a given build, their exact
+/// format is not guaranteed to remain stable across compiler versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegistryDescription {
+    /// The codec used to encode and decode payloads.
+    pub codec: Codec,
+    /// The registered event sources.
+    pub event_sources: Vec<EventSourceDescription>,
+    /// The registered query sources.
+    pub query_sources: Vec<QuerySourceDescription>,
+    /// The registered event sinks.
+    pub event_sinks: Vec<EventSinkDescription>,
+}
+
+/// A description of a registered event source.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventSourceDescription {
+    /// The name under which the event source is registered.
+    pub name: String,
+    /// The name of the event type.
+    pub event_type: &'static str,
+}
+
+/// A description of a registered query source.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QuerySourceDescription {
+    /// The name under which the query source is registered.
+    pub name: String,
+    /// The name of the request type.
+    pub request_type: &'static str,
+    /// The name of the reply type.
+    ///
+    /// For query sources registered with a reducer, this is the type of the
+    /// reduced value.
+    pub reply_type: &'static str,
+}
+
+/// A description of a registered event sink.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventSinkDescription {
+    /// The name under which the event sink is registered.
+    pub name: String,
+    /// The name of the event type.
+    pub event_type: &'static str,
+}
@@ -0,0 +1,432 @@
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::model::{Context, Model};
+use crate::ports::EventSource;
+use crate::simulation::{Scheduler, SchedulingError};
+use crate::time::MonotonicTime;
+
+use super::Codec;
+
+/// A serialized event together with its delivery time.
+pub type Record = (MonotonicTime, Vec<u8>);
+
+/// A model that records all events it receives in serialized form.
+///
+/// An `EventRecorder` is meant to capture the events delivered to the input
+/// port of a model under scrutiny: it should be connected to the same output
+/// ports as this input port, and added to the bench under a name that refers
+/// to the observed model. The events are serialized with the configured codec
+/// and time-stamped with the simulation time of their delivery.
+///
+/// The records can be retrieved at any time through the [`Recording`] handle
+/// returned by [`EventRecorder::recording`], and later replayed against an
+/// isolated instance of the model with [`replay`].
+///
+/// Recording events cannot fail from the point of view of the sender.
+/// Instead, the first serialization error is stored and no more events are
+/// recorded until the error is retrieved with [`Recording::take_error`].
+pub struct EventRecorder<T> {
+    codec: Codec,
+    inner: Arc<Mutex<Inner>>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + 'static> EventRecorder<T> {
+    /// Creates a new recorder using the default codec.
+    pub fn new() -> Self {
+        Self::with_codec(Codec::default())
+    }
+
+    /// Creates a new recorder using the specified codec.
+    pub fn with_codec(codec: Codec) -> Self {
+        Self {
+            codec,
+            inner: Arc::new(Mutex::new(Inner::default())),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a handle to the records.
+    pub fn recording(&self) -> Recording {
+        Recording {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Records an event.
+    ///
+    /// The event is ignored if a previous serialization error was not
+    /// retrieved yet.
+    pub fn record(&mut self, event: T, cx: &mut Context<Self>) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.error.is_some() {
+            return;
+        }
+
+        match self.codec.encode(&event) {
+            Ok(bytes) => inner.records.push((cx.time(), bytes)),
+            Err(e) => {
+                inner.error = Some(RecordError {
+                    time: cx.time(),
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl<T: Serialize + Send + 'static> Model for EventRecorder<T> {}
+
+impl<T: Serialize + Send + 'static> Default for EventRecorder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for EventRecorder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventRecorder")
+            .field("codec", &self.codec)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state shared by an [`EventRecorder`] and its [`Recording`] handles.
+#[derive(Default)]
+struct Inner {
+    records: Vec<Record>,
+    error: Option<RecordError>,
+}
+
+/// A handle to the records of an [`EventRecorder`].
+#[derive(Clone)]
+pub struct Recording {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Recording {
+    /// Returns a copy of all records in chronological order.
+    pub fn records(&self) -> Vec<Record> {
+        self.inner.lock().unwrap().records.clone()
+    }
+
+    /// Removes and returns all records in chronological order.
+    pub fn take(&self) -> Vec<Record> {
+        std::mem::take(&mut self.inner.lock().unwrap().records)
+    }
+
+    /// Takes the error that occurred when recording a previous event, if any.
+    ///
+    /// Recording resumes with the next event once the error is taken.
+    pub fn take_error(&self) -> Option<RecordError> {
+        self.inner.lock().unwrap().error.take()
+    }
+}
+
+impl fmt::Debug for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("len", &self.inner.lock().unwrap().records.len())
+            .finish()
+    }
+}
+
+/// An error returned when an event could not be recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    time: MonotonicTime,
+    message: String,
+}
+
+impl RecordError {
+    /// Returns the delivery time of the event that could not be recorded.
+    pub fn time(&self) -> MonotonicTime {
+        self.time
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the event delivered at {} could not be serialized: {}",
+            self.time, self.message
+        )
+    }
+}
+
+impl Error for RecordError {}
+
+/// Schedules the replay of recorded events through an event source and
+/// returns the number of scheduled events.
+///
+/// Each event is scheduled relative to the current simulation time with the
+/// offset between its recorded delivery time and the specified `origin`, which
+/// would typically be the start time of the recorded simulation. Events
+/// recorded at the origin are processed at the current simulation time, at the
+/// next simulation step. The records must have been produced with the
+/// specified codec.
+///
+/// An error is returned and no event is scheduled if a record cannot be
+/// deserialized, if a record predates the origin or if the delivery times do
+/// not allow the events to be scheduled, see
+/// [`EventSource::schedule_from_iter`].
+pub fn replay<T>(
+    source: &EventSource<T>,
+    scheduler: &Scheduler,
+    records: &[Record],
+    origin: MonotonicTime,
+    codec: Codec,
+) -> Result<usize, ReplayError>
+where
+    T: DeserializeOwned + Clone + Send + 'static,
+{
+    let mut events = records
+        .iter()
+        .enumerate()
+        .map(|(idx, (time, bytes))| {
+            if *time < origin {
+                return Err(ReplayError::Scheduling(
+                    SchedulingError::InvalidScheduledTime,
+                ));
+            }
+            let event = codec
+                .decode(bytes)
+                .map_err(|_| ReplayError::Deserialization(idx))?;
+
+            Ok((time.duration_since(origin), event))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Events recorded at the origin cannot be scheduled since they are not in
+    // the future, so they are injected at the current time once the remaining
+    // events were successfully scheduled.
+    let origin_count = events
+        .iter()
+        .take_while(|(offset, _)| offset.is_zero())
+        .count();
+    let later_events = events.split_off(origin_count);
+    let count = source
+        .schedule_from_iter(scheduler, later_events)
+        .map_err(ReplayError::Scheduling)?;
+    for (_, event) in events {
+        scheduler.inject(source.event(event));
+    }
+
+    Ok(count + origin_count)
+}
+
+/// An error returned when recorded events cannot be replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The record at the specified index could not be deserialized.
+    Deserialization(usize),
+    /// The events could not be scheduled.
+    Scheduling(SchedulingError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deserialization(idx) => {
+                write!(f, "the record at index {} could not be deserialized", idx)
+            }
+            Self::Scheduling(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use std::time::Duration;
+
+    use serde::ser::Error as _;
+    use serde::Serializer;
+
+    use crate::simulation::{Mailbox, SimInit};
+
+    use super::*;
+
+    /// A value that cannot be serialized if odd.
+    #[derive(Clone)]
+    struct Even(u32);
+    impl Serialize for Even {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if self.0 % 2 != 0 {
+                return Err(S::Error::custom("odd value"));
+            }
+
+            serializer.serialize_u32(self.0)
+        }
+    }
+
+    struct Replayer {
+        received: Vec<(MonotonicTime, u32)>,
+    }
+    impl Replayer {
+        async fn input(&mut self, value: u32, cx: &mut Context<Self>) {
+            self.received.push((cx.time(), value));
+        }
+        async fn received(&mut self) -> Vec<(MonotonicTime, u32)> {
+            self.received.clone()
+        }
+    }
+    impl Model for Replayer {}
+
+    #[test]
+    fn event_recorder_record_and_replay() {
+        let t0 = MonotonicTime::EPOCH;
+        let recorder = EventRecorder::<Even>::with_codec(Codec::Json);
+        let recording = recorder.recording();
+        let mbox = Mailbox::new();
+        let mut source = EventSource::new();
+        source.connect(EventRecorder::record, &mbox);
+        let (mut simu, scheduler) = SimInit::new()
+            .add_model(recorder, mbox, "recorder")
+            .init(t0)
+            .unwrap();
+
+        scheduler
+            .schedule(Duration::from_secs(1), source.event(Even(2)))
+            .unwrap();
+        scheduler
+            .schedule(Duration::from_secs(3), source.event(Even(4)))
+            .unwrap();
+        simu.step_until(Duration::from_secs(5)).unwrap();
+
+        let records = recording.take();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, t0 + Duration::from_secs(1));
+        assert_eq!(records[1].0, t0 + Duration::from_secs(3));
+        assert!(recording.records().is_empty());
+        assert!(recording.take_error().is_none());
+
+        // Replay the records against a model accepting the serialized form
+        // of the events.
+        let t1 = MonotonicTime::new(100, 0).unwrap();
+        let replayer = Replayer {
+            received: Vec::new(),
+        };
+        let mbox = Mailbox::new();
+        let addr = mbox.address();
+        let mut source = EventSource::new();
+        source.connect(Replayer::input, &mbox);
+        let (mut simu, scheduler) = SimInit::new()
+            .add_model(replayer, mbox, "replayer")
+            .init(t1)
+            .unwrap();
+
+        assert_eq!(
+            replay(&source, &scheduler, &records, t0, Codec::Json),
+            Ok(2)
+        );
+        simu.step_until(Duration::from_secs(5)).unwrap();
+
+        let received = simu.process_query(Replayer::received, (), &addr).unwrap();
+        assert_eq!(
+            received,
+            vec![
+                (t1 + Duration::from_secs(1), 2),
+                (t1 + Duration::from_secs(3), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_recorder_replay_at_origin() {
+        let t0 = MonotonicTime::EPOCH;
+        let recorder = EventRecorder::<Even>::new();
+        let recording = recorder.recording();
+        let mbox = Mailbox::new();
+        let addr = mbox.address();
+        let mut simu = SimInit::new()
+            .add_model(recorder, mbox, "recorder")
+            .init(t0)
+            .unwrap()
+            .0;
+
+        simu.process_event(EventRecorder::record, Even(2), &addr)
+            .unwrap();
+        simu.process_event(EventRecorder::record, Even(4), &addr)
+            .unwrap();
+        simu.step_until(Duration::from_secs(1)).unwrap();
+        simu.process_event(EventRecorder::record, Even(6), &addr)
+            .unwrap();
+
+        let records = recording.take();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].0, t0);
+
+        // Events recorded at the origin are replayed at the current time.
+        let t1 = MonotonicTime::new(100, 0).unwrap();
+        let replayer = Replayer {
+            received: Vec::new(),
+        };
+        let mbox = Mailbox::new();
+        let addr = mbox.address();
+        let mut source = EventSource::new();
+        source.connect(Replayer::input, &mbox);
+        let (mut simu, scheduler) = SimInit::new()
+            .add_model(replayer, mbox, "replayer")
+            .init(t1)
+            .unwrap();
+
+        assert_eq!(
+            replay(&source, &scheduler, &records, t0, Codec::default()),
+            Ok(3)
+        );
+        simu.step().unwrap();
+        assert_eq!(simu.time(), t1);
+        simu.step().unwrap();
+        assert_eq!(simu.time(), t1 + Duration::from_secs(1));
+
+        let received = simu.process_query(Replayer::received, (), &addr).unwrap();
+        assert_eq!(
+            received,
+            vec![(t1, 2), (t1, 4), (t1 + Duration::from_secs(1), 6)]
+        );
+    }
+
+    #[test]
+    fn event_recorder_serialization_error() {
+        let t0 = MonotonicTime::EPOCH;
+        let recorder = EventRecorder::<Even>::new();
+        let recording = recorder.recording();
+        let mbox = Mailbox::new();
+        let addr = mbox.address();
+        let mut simu = SimInit::new()
+            .add_model(recorder, mbox, "recorder")
+            .init(t0)
+            .unwrap()
+            .0;
+
+        simu.process_event(EventRecorder::record, Even(2), &addr)
+            .unwrap();
+        simu.process_event(EventRecorder::record, Even(3), &addr)
+            .unwrap();
+        simu.step_until(Duration::from_secs(1)).unwrap();
+
+        // Events are ignored until the error is taken.
+        simu.process_event(EventRecorder::record, Even(4), &addr)
+            .unwrap();
+        assert_eq!(recording.records().len(), 1);
+
+        let error = recording.take_error().unwrap();
+        assert_eq!(error.time(), t0);
+        assert!(recording.take_error().is_none());
+
+        simu.process_event(EventRecorder::record, Even(6), &addr)
+            .unwrap();
+        let records = recording.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].0, t0 + Duration::from_secs(1));
+    }
+}
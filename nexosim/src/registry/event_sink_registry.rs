@@ -2,12 +2,11 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt;
 
-use ciborium;
 use serde::Serialize;
 
-use crate::ports::EventSinkStream;
+use crate::ports::{EventSinkStream, Subscriber};
 
-type SerializationError = ciborium::ser::Error<std::io::Error>;
+use super::{Codec, CodecError};
 
 /// A registry that holds all sinks meant to be accessed through remote
 /// procedure calls.
@@ -39,6 +38,12 @@ impl EventSinkRegistry {
     pub(crate) fn get_mut(&mut self, name: &str) -> Option<&mut dyn EventSinkStreamAny> {
         self.0.get_mut(name).map(|s| s.as_mut())
     }
+
+    /// Returns an iterator over the names and references to all sinks in the
+    /// registry, in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn EventSinkStreamAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for EventSinkRegistry {
@@ -59,8 +64,21 @@ pub(crate) trait EventSinkStreamAny: Send + 'static {
     /// Pauses the collection of new events.
     fn close(&mut self);
 
-    /// Encode and collect all events in a vector.
-    fn collect(&mut self) -> Result<Vec<Vec<u8>>, SerializationError>;
+    /// Encode with the specified codec and collect all events in a vector.
+    fn collect(&mut self, codec: Codec) -> Result<Vec<Vec<u8>>, CodecError>;
+
+    /// Attaches a subscriber to which all subsequent events are forwarded
+    /// after being encoded with the specified codec.
+    ///
+    /// The subscriber should return `false` if it can no longer accept
+    /// events, in which case the subscription is cancelled.
+    ///
+    /// Returns `false` if the sink does not support subscriptions.
+    fn subscribe(
+        &mut self,
+        codec: Codec,
+        subscriber: Box<dyn FnMut(Result<Vec<u8>, CodecError>) -> bool + Send>,
+    ) -> bool;
 }
 
 impl<E> EventSinkStreamAny for E
@@ -80,14 +98,24 @@ where
         self.close();
     }
 
-    fn collect(&mut self) -> Result<Vec<Vec<u8>>, SerializationError> {
+    fn collect(&mut self, codec: Codec) -> Result<Vec<Vec<u8>>, CodecError> {
         self.__try_fold(Vec::new(), |mut encoded_events, event| {
-            let mut buffer = Vec::new();
-            ciborium::into_writer(&event, &mut buffer).map(|_| {
+            codec.encode(&event).map(|buffer| {
                 encoded_events.push(buffer);
 
                 encoded_events
             })
         })
     }
+
+    fn subscribe(
+        &mut self,
+        codec: Codec,
+        mut subscriber: Box<dyn FnMut(Result<Vec<u8>, CodecError>) -> bool + Send>,
+    ) -> bool {
+        self.__subscribe(Subscriber::new(move |event| {
+            subscriber(codec.encode(&event))
+        }))
+        .is_ok()
+    }
 }
@@ -4,13 +4,12 @@ use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
-use ciborium;
 use serde::de::DeserializeOwned;
 
 use crate::ports::EventSource;
 use crate::simulation::{Action, ActionKey};
 
-type DeserializationError = ciborium::de::Error<std::io::Error>;
+use super::{Codec, CodecError};
 
 /// A registry that holds all sources and sinks meant to be accessed through
 /// remote procedure calls.
@@ -45,6 +44,12 @@ impl EventSourceRegistry {
     pub(crate) fn get(&self, name: &str) -> Option<&dyn EventSourceAny> {
         self.0.get(name).map(|s| s.as_ref())
     }
+
+    /// Returns an iterator over the names and references to all event sources
+    /// in the registry, in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn EventSourceAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for EventSourceRegistry {
@@ -53,43 +58,46 @@ impl fmt::Debug for EventSourceRegistry {
     }
 }
 
-/// A type-erased `EventSource` that operates on CBOR-encoded serialized events.
+/// A type-erased `EventSource` that operates on serialized events.
 pub(crate) trait EventSourceAny: Send + Sync + 'static {
     /// Returns an action which, when processed, broadcasts an event to all
     /// connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
-    fn event(&self, serialized_arg: &[u8]) -> Result<Action, DeserializationError>;
+    /// The argument is expected to be encoded with the specified codec.
+    fn event(&self, serialized_arg: &[u8], codec: Codec) -> Result<Action, CodecError>;
 
     /// Returns a cancellable action and a cancellation key; when processed, the
     /// action broadcasts an event to all connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is expected to be encoded with the specified codec.
     fn keyed_event(
         &self,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError>;
 
     /// Returns a periodically recurring action which, when processed,
     /// broadcasts an event to all connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is expected to be encoded with the specified codec.
     fn periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<Action, DeserializationError>;
+        codec: Codec,
+    ) -> Result<Action, CodecError>;
 
     /// Returns a cancellable, periodically recurring action and a cancellation
     /// key; when processed, the action broadcasts an event to all connected
     /// input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is expected to be encoded with the specified codec.
     fn keyed_periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError>;
 
     /// Human-readable name of the event type, as returned by
     /// `any::type_name`.
@@ -100,29 +108,39 @@ impl<T> EventSourceAny for Arc<EventSource<T>>
 where
     T: DeserializeOwned + Clone + Send + 'static,
 {
-    fn event(&self, serialized_arg: &[u8]) -> Result<Action, DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| EventSource::event(self, arg))
+    fn event(&self, serialized_arg: &[u8], codec: Codec) -> Result<Action, CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| EventSource::event(self, arg))
     }
     fn keyed_event(
         &self,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| EventSource::keyed_event(self, arg))
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| EventSource::keyed_event(self, arg))
     }
     fn periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<Action, DeserializationError> {
-        ciborium::from_reader(serialized_arg)
+        codec: Codec,
+    ) -> Result<Action, CodecError> {
+        codec
+            .decode(serialized_arg)
             .map(|arg| EventSource::periodic_event(self, period, arg))
     }
     fn keyed_periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| self.keyed_periodic_event(period, arg))
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| self.keyed_periodic_event(period, arg))
     }
     fn event_type_name(&self) -> &'static str {
         std::any::type_name::<T>()
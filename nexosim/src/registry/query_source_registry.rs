@@ -1,16 +1,15 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
-use ciborium;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::ports::{QuerySource, ReplyReceiver};
 use crate::simulation::Action;
 
-type DeserializationError = ciborium::de::Error<std::io::Error>;
-type SerializationError = ciborium::ser::Error<std::io::Error>;
+use super::{Codec, CodecError};
 
 /// A registry that holds all sources and sinks meant to be accessed through
 /// remote procedure calls.
@@ -41,11 +40,47 @@ impl QuerySourceRegistry {
         }
     }
 
+    /// Adds a query source to the registry together with a reducer that
+    /// aggregates all replies into a single value.
+    ///
+    /// If the specified name is already in use for another query source, the
+    /// source provided as argument is returned in the error.
+    pub(crate) fn add_reduced<T, R, A, F>(
+        &mut self,
+        source: QuerySource<T, R>,
+        reducer: F,
+        name: impl Into<String>,
+    ) -> Result<(), QuerySource<T, R>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+        R: Send + 'static,
+        A: Serialize + 'static,
+        F: Fn(Box<dyn Iterator<Item = R> + '_>) -> A + Send + Sync + 'static,
+    {
+        match self.0.entry(name.into()) {
+            Entry::Vacant(s) => {
+                s.insert(Box::new(ReducedQuerySource {
+                    source,
+                    reducer: Arc::new(reducer),
+                }));
+
+                Ok(())
+            }
+            Entry::Occupied(_) => Err(source),
+        }
+    }
+
     /// Returns a mutable reference to the specified query source if it is in
     /// the registry.
     pub(crate) fn get(&self, name: &str) -> Option<&dyn QuerySourceAny> {
         self.0.get(name).map(|s| s.as_ref())
     }
+
+    /// Returns an iterator over the names and references to all query sources
+    /// in the registry, in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn QuerySourceAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for QuerySourceRegistry {
@@ -54,18 +89,19 @@ impl fmt::Debug for QuerySourceRegistry {
     }
 }
 
-/// A type-erased `QuerySource` that operates on CBOR-encoded serialized queries
-/// and returns CBOR-encoded replies.
+/// A type-erased `QuerySource` that operates on serialized queries and returns
+/// serialized replies.
 pub(crate) trait QuerySourceAny: Send + Sync + 'static {
     /// Returns an action which, when processed, broadcasts a query to all
     /// connected replier ports.
     ///
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is expected to be encoded with the specified codec.
     fn query(
         &self,
         arg: &[u8],
-    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), CodecError>;
 
     /// Human-readable name of the request type, as returned by
     /// `any::type_name`.
@@ -84,8 +120,9 @@ where
     fn query(
         &self,
         arg: &[u8],
-    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), DeserializationError> {
-        ciborium::from_reader(arg).map(|arg| {
+        codec: Codec,
+    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), CodecError> {
+        codec.decode(arg).map(|arg| {
             let (action, reply_recv) = self.query(arg);
             let reply_recv: Box<dyn ReplyReceiverAny> = Box::new(reply_recv);
 
@@ -102,22 +139,60 @@ where
     }
 }
 
-/// A type-erased `ReplyReceiver` that returns CBOR-encoded replies.
+/// A query source with a reducer that aggregates all replies into a single
+/// value.
+struct ReducedQuerySource<T: Clone + Send + 'static, R: Send + 'static, F> {
+    source: QuerySource<T, R>,
+    reducer: Arc<F>,
+}
+
+impl<T, R, A, F> QuerySourceAny for ReducedQuerySource<T, R, F>
+where
+    T: DeserializeOwned + Clone + Send + 'static,
+    R: Send + 'static,
+    A: Serialize + 'static,
+    F: Fn(Box<dyn Iterator<Item = R> + '_>) -> A + Send + Sync + 'static,
+{
+    fn query(
+        &self,
+        arg: &[u8],
+        codec: Codec,
+    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), CodecError> {
+        codec.decode(arg).map(|arg| {
+            let (action, reply_recv) = self.source.query(arg);
+            let reply_recv: Box<dyn ReplyReceiverAny> = Box::new(ReducedReplyReceiver {
+                reply_recv,
+                reducer: self.reducer.clone(),
+            });
+
+            (action, reply_recv)
+        })
+    }
+
+    fn request_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn reply_type_name(&self) -> &'static str {
+        std::any::type_name::<A>()
+    }
+}
+
+/// A type-erased `ReplyReceiver` that returns serialized replies.
 pub(crate) trait ReplyReceiverAny {
-    /// Take the replies, if any, encode them and collect them in a vector.
-    fn take_collect(&mut self) -> Option<Result<Vec<Vec<u8>>, SerializationError>>;
+    /// Take the replies, if any, encode them with the specified codec and
+    /// collect them in a vector.
+    fn take_collect(&mut self, codec: Codec) -> Option<Result<Vec<Vec<u8>>, CodecError>>;
 }
 
 impl<R: Serialize + 'static> ReplyReceiverAny for ReplyReceiver<R> {
-    fn take_collect(&mut self) -> Option<Result<Vec<Vec<u8>>, SerializationError>> {
+    fn take_collect(&mut self, codec: Codec) -> Option<Result<Vec<Vec<u8>>, CodecError>> {
         let replies = self.take()?;
 
         let encoded_replies = (move || {
             let mut encoded_replies = Vec::new();
             for reply in replies {
-                let mut encoded_reply = Vec::new();
-                ciborium::into_writer(&reply, &mut encoded_reply)?;
-                encoded_replies.push(encoded_reply);
+                encoded_replies.push(codec.encode(&reply)?);
             }
 
             Ok(encoded_replies)
@@ -126,3 +201,135 @@ impl<R: Serialize + 'static> ReplyReceiverAny for ReplyReceiver<R> {
         Some(encoded_replies)
     }
 }
+
+/// A `ReplyReceiver` that aggregates all replies into a single value.
+struct ReducedReplyReceiver<R, F> {
+    reply_recv: ReplyReceiver<R>,
+    reducer: Arc<F>,
+}
+
+impl<R, A, F> ReplyReceiverAny for ReducedReplyReceiver<R, F>
+where
+    R: 'static,
+    A: Serialize + 'static,
+    F: Fn(Box<dyn Iterator<Item = R> + '_>) -> A,
+{
+    fn take_collect(&mut self, codec: Codec) -> Option<Result<Vec<Vec<u8>>, CodecError>> {
+        let replies = self.reply_recv.take()?;
+        let reply = (self.reducer)(Box::new(replies));
+
+        Some(codec.encode(&reply).map(|reply| vec![reply]))
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use crate::model::Model;
+    use crate::simulation::{Mailbox, SimInit, Simulation};
+    use crate::time::MonotonicTime;
+
+    use super::*;
+
+    struct Sensor {
+        value: u32,
+    }
+    impl Sensor {
+        async fn read(&mut self, scale: u32) -> u32 {
+            self.value * scale
+        }
+    }
+    impl Model for Sensor {}
+
+    /// Builds a simulation with 3 sensors connected to the query source.
+    fn sensor_bench(source: &mut QuerySource<u32, u32>) -> Simulation {
+        let mut sim_init = SimInit::new();
+        for value in [1, 2, 3] {
+            let mbox = Mailbox::new();
+            source.connect(Sensor::read, &mbox);
+            sim_init = sim_init.add_model(Sensor { value }, mbox, format!("sensor{}", value));
+        }
+
+        sim_init.init(MonotonicTime::EPOCH).unwrap().0
+    }
+
+    /// Processes a query with the specified source and returns the encoded
+    /// replies.
+    fn process_query(
+        simulation: &mut Simulation,
+        source: &dyn QuerySourceAny,
+        scale: u32,
+        codec: Codec,
+    ) -> Vec<Vec<u8>> {
+        let (action, mut replies) = source.query(&codec.encode(&scale).unwrap(), codec).unwrap();
+        simulation.process(action).unwrap();
+
+        replies.take_collect(codec).unwrap().unwrap()
+    }
+
+    #[test]
+    fn query_source_replies() {
+        let mut source = QuerySource::new();
+        let mut simulation = sensor_bench(&mut source);
+
+        let mut registry = QuerySourceRegistry::default();
+        registry.add(source, "read").unwrap();
+        let source = registry.get("read").unwrap();
+
+        for codec in [Codec::Cbor, Codec::Json] {
+            let mut replies: Vec<u32> = process_query(&mut simulation, source, 10, codec)
+                .iter()
+                .map(|reply| codec.decode(reply).unwrap())
+                .collect();
+            replies.sort();
+
+            assert_eq!(replies, vec![10, 20, 30]);
+        }
+    }
+
+    #[test]
+    fn reduced_query_source_replies() {
+        let mut source = QuerySource::new();
+        let mut simulation = sensor_bench(&mut source);
+
+        // Reduce the replies to their count and sum.
+        let mut registry = QuerySourceRegistry::default();
+        registry
+            .add_reduced(
+                source,
+                |replies| replies.fold((0usize, 0u32), |(n, sum), r| (n + 1, sum + r)),
+                "count_and_sum",
+            )
+            .unwrap();
+        let source = registry.get("count_and_sum").unwrap();
+
+        // The reply type is the type of the reduced value.
+        assert_eq!(source.request_type_name(), std::any::type_name::<u32>());
+        assert_eq!(
+            source.reply_type_name(),
+            std::any::type_name::<(usize, u32)>()
+        );
+
+        for codec in [Codec::Cbor, Codec::Json] {
+            let replies = process_query(&mut simulation, source, 10, codec);
+
+            assert_eq!(replies.len(), 1);
+            assert_eq!(codec.decode::<(usize, u32)>(&replies[0]).unwrap(), (3, 60));
+        }
+    }
+
+    #[test]
+    fn reduced_query_source_name_in_use() {
+        let mut registry = QuerySourceRegistry::default();
+        registry
+            .add(QuerySource::<u32, u32>::new(), "read")
+            .unwrap();
+
+        assert!(registry
+            .add_reduced(
+                QuerySource::<u32, u32>::new(),
+                |replies| replies.count(),
+                "read"
+            )
+            .is_err());
+    }
+}
@@ -1,11 +1,41 @@
 //! Simulation management through remote procedure calls.
+//!
+//! A simulation server is started with [`run`] or, on Unix platforms,
+//! `run_local`. A [`ServerBuilder`] makes it possible to customize the server,
+//! for instance to secure connections with TLS, to retry binding an address
+//! in use or to change the capacity of the scheduling request queues.
+//!
+//! # Sessions
+//!
+//! A single server can host several independent simulations, each within its
+//! own session. An `Init` request with the `new_session` flag set builds a new
+//! simulation bench with the bench generator provided to the server and returns
+//! the identifier of the newly created session. All other requests specify the
+//! identifier of the session they target and are rejected with a
+//! `SESSION_NOT_FOUND` error if no such session is active.
+//!
+//! The server also hosts a default session with identifier 0, which always
+//! exists and is (re)initialized in place by `Init` requests without the
+//! `new_session` flag. Clients that ignore sessions altogether, and therefore
+//! leave all session identifiers to 0, thus keep working as with a
+//! single-simulation server.
+//!
+//! A `Reset` request rebuilds the bench of a session in place, keeping its
+//! identifier. Sessions are not tied to client connections: a session remains
+//! active until it is closed with a `CloseSession` request or until the server
+//! stops. Closing a session drops its simulation bench as soon as pending
+//! requests targeting this session have completed. Session identifiers are never
+//! reused, so clients should close the sessions they no longer need to release
+//! their resources. Closing the default session merely drops its simulation
+//! bench.
 
 mod codegen;
 mod key_registry;
 mod run;
 mod services;
 
-pub use run::run;
+pub use run::{run, RetryPolicy, ServerBuilder};
+pub use tonic::transport::{Certificate, Identity, ServerTlsConfig};
 
 #[cfg(unix)]
 pub use run::run_local;
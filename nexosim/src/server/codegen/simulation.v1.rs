@@ -6,6 +6,13 @@ pub struct Error {
     #[prost(string, tag = "2")]
     pub message: ::prost::alloc::string::String,
 }
+/// An event key remains valid for as long as the server runs the same simulation
+/// bench, regardless of client reconnections, until the event is cancelled or, for
+/// non-periodic events, processed. Keys are not preserved across an `Init` or a
+/// `Reset`: these requests build a new bench with an empty scheduler, so events
+/// scheduled by clients, including periodic events, must be scheduled again.
+/// Keys issued for a previous bench are never reused and are rejected with an
+/// `INVALID_KEY` error.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct EventKey {
     #[prost(uint64, tag = "1")]
@@ -13,13 +20,29 @@ pub struct EventKey {
     #[prost(uint64, tag = "2")]
     pub subkey2: u64,
 }
+/// An `Init` request with the `new_session` flag set creates a new, independent
+/// simulation session and returns its identifier. All other requests carry the
+/// `session_id` of the session they target; requests with an unknown session
+/// identifier are rejected with a `SESSION_NOT_FOUND` error. A session is not
+/// bound to a client connection: it remains active until it is explicitly closed
+/// with `CloseSession` or until the server stops.
+///
+/// The default session, with identifier 0, always exists. An `Init` request
+/// without the `new_session` flag (re)initializes the default session in place,
+/// so clients that leave all session identifiers to 0 are unaware of sessions.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct InitRequest {
     #[prost(bytes = "vec", tag = "2")]
     pub cfg: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "3")]
+    pub new_session: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct InitReply {
+    /// This field is hoisted because it is only meaningful upon success. It is
+    /// zero if an error is returned or if the default session was initialized.
+    #[prost(uint64, tag = "2")]
+    pub session_id: u64,
     /// Always returns exactly 1 variant.
     #[prost(oneof = "init_reply::Result", tags = "1, 100")]
     pub result: ::core::option::Option<init_reply::Result>,
@@ -36,7 +59,32 @@ pub mod init_reply {
     }
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct HaltRequest {}
+pub struct ResetRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResetReply {
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "reset_reply::Result", tags = "1, 100")]
+    pub result: ::core::option::Option<reset_reply::Result>,
+}
+/// Nested message and enum types in `ResetReply`.
+pub mod reset_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Time(::prost_types::Timestamp),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct HaltRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct HaltReply {
     /// Always returns exactly 1 variant.
@@ -55,7 +103,10 @@ pub mod halt_reply {
     }
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TimeRequest {}
+pub struct TimeRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TimeReply {
     /// Always returns exactly 1 variant.
@@ -74,7 +125,36 @@ pub mod time_reply {
     }
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct StepRequest {}
+pub struct PendingEventsRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingEventsReply {
+    /// This field is hoisted because it is optional. It is always absent if no
+    /// event is pending or if an error is returned.
+    #[prost(message, optional, tag = "1")]
+    pub next_event_time: ::core::option::Option<::prost_types::Timestamp>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "pending_events_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<pending_events_reply::Result>,
+}
+/// Nested message and enum types in `PendingEventsReply`.
+pub mod pending_events_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(uint64, tag = "10")]
+        Count(u64),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StepRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StepReply {
     /// Always returns exactly 1 variant.
@@ -94,6 +174,8 @@ pub mod step_reply {
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct StepUntilRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
     /// Always returns exactly 1 variant.
     #[prost(oneof = "step_until_request::Deadline", tags = "1, 2")]
     pub deadline: ::core::option::Option<step_until_request::Deadline>,
@@ -136,6 +218,8 @@ pub struct ScheduleEventRequest {
     pub period: ::core::option::Option<::prost_types::Duration>,
     #[prost(bool, tag = "6")]
     pub with_key: bool,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
     /// Expects exactly 1 variant.
     #[prost(oneof = "schedule_event_request::Deadline", tags = "1, 2")]
     pub deadline: ::core::option::Option<schedule_event_request::Deadline>,
@@ -174,6 +258,8 @@ pub mod schedule_event_reply {
 pub struct CancelEventRequest {
     #[prost(message, optional, tag = "1")]
     pub key: ::core::option::Option<EventKey>,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CancelEventReply {
@@ -192,12 +278,16 @@ pub mod cancel_event_reply {
         Error(super::Error),
     }
 }
+/// The session identifier is ignored when the request is part of a
+/// `ProcessEventsRequest`.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessEventRequest {
     #[prost(string, tag = "1")]
     pub source_name: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "2")]
     pub event: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessEventReply {
@@ -216,12 +306,53 @@ pub mod process_event_reply {
         Error(super::Error),
     }
 }
+/// Events are processed in order, each to completion before the next one. Unless
+/// `continue_on_error` is set, processing stops at the first failed event.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessEventsRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<ProcessEventRequest>,
+    #[prost(bool, tag = "2")]
+    pub continue_on_error: bool,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessEventsReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned. It contains one
+    /// reply per processed event, so if processing stopped at a failed event,
+    /// that event is the last one.
+    #[prost(message, repeated, tag = "1")]
+    pub replies: ::prost::alloc::vec::Vec<ProcessEventReply>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "process_events_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<process_events_reply::Result>,
+}
+/// Nested message and enum types in `ProcessEventsReply`.
+pub mod process_events_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessQueryRequest {
     #[prost(string, tag = "1")]
     pub source_name: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "2")]
     pub request: ::prost::alloc::vec::Vec<u8>,
+    /// Optional wall clock time allocated for the processing of the query. If
+    /// the query is not processed in time, a `QUERY_TIMEOUT` error is returned
+    /// and the simulation is terminated.
+    #[prost(message, optional, tag = "3")]
+    pub timeout: ::core::option::Option<::prost_types::Duration>,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessQueryReply {
@@ -248,6 +379,8 @@ pub mod process_query_reply {
 pub struct ReadEventsRequest {
     #[prost(string, tag = "1")]
     pub sink_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReadEventsReply {
@@ -274,6 +407,8 @@ pub mod read_events_reply {
 pub struct OpenSinkRequest {
     #[prost(string, tag = "1")]
     pub sink_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OpenSinkReply {
@@ -296,6 +431,8 @@ pub mod open_sink_reply {
 pub struct CloseSinkRequest {
     #[prost(string, tag = "1")]
     pub sink_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CloseSinkReply {
@@ -314,13 +451,145 @@ pub mod close_sink_reply {
         Error(super::Error),
     }
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamSinkRequest {
+    #[prost(string, tag = "1")]
+    pub sink_name: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+/// A stream reply is sent for each event. The stream is terminated after an
+/// error reply, or if the client does not consume events fast enough.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamSinkReply {
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "stream_sink_reply::Result", tags = "1, 100")]
+    pub result: ::core::option::Option<stream_sink_reply::Result>,
+}
+/// Nested message and enum types in `StreamSinkReply`.
+pub mod stream_sink_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(bytes = "vec", tag = "1")]
+        Event(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventSourceInfo {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuerySourceInfo {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub request_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub reply_type: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventSinkInfo {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListSourcesRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSourcesReply {
+    /// These fields are hoisted because protobuf3 does not support `repeated`
+    /// within a `oneof`. They are Always empty if an error is returned
+    #[prost(message, repeated, tag = "1")]
+    pub event_sources: ::prost::alloc::vec::Vec<EventSourceInfo>,
+    #[prost(message, repeated, tag = "2")]
+    pub query_sources: ::prost::alloc::vec::Vec<QuerySourceInfo>,
+    /// Version of the endpoint registry, which changes whenever the set of
+    /// endpoints or their types change. It is zero if an error is returned.
+    #[prost(uint64, tag = "3")]
+    pub registry_version: u64,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "list_sources_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<list_sources_reply::Result>,
+}
+/// Nested message and enum types in `ListSourcesReply`.
+pub mod list_sources_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListSinksRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSinksReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned
+    #[prost(message, repeated, tag = "1")]
+    pub sinks: ::prost::alloc::vec::Vec<EventSinkInfo>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "list_sinks_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<list_sinks_reply::Result>,
+}
+/// Nested message and enum types in `ListSinksReply`.
+pub mod list_sinks_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Closing a session drops its simulation bench. The session identifier is
+/// never reused, except for the default session which is merely reverted to its
+/// uninitialized state.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CloseSessionRequest {
+    #[prost(uint64, tag = "99")]
+    pub session_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseSessionReply {
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "close_session_reply::Result", tags = "1, 100")]
+    pub result: ::core::option::Option<close_session_reply::Result>,
+}
+/// Nested message and enum types in `CloseSessionReply`.
+pub mod close_session_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
 /// A convenience message type for custom transport implementation.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AnyRequest {
     /// Expects exactly 1 variant.
     #[prost(
         oneof = "any_request::Request",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18"
     )]
     pub request: ::core::option::Option<any_request::Request>,
 }
@@ -353,6 +622,18 @@ pub mod any_request {
         OpenSinkRequest(super::OpenSinkRequest),
         #[prost(message, tag = "12")]
         CloseSinkRequest(super::CloseSinkRequest),
+        #[prost(message, tag = "13")]
+        ListSourcesRequest(super::ListSourcesRequest),
+        #[prost(message, tag = "14")]
+        ListSinksRequest(super::ListSinksRequest),
+        #[prost(message, tag = "15")]
+        ResetRequest(super::ResetRequest),
+        #[prost(message, tag = "16")]
+        ProcessEventsRequest(super::ProcessEventsRequest),
+        #[prost(message, tag = "17")]
+        PendingEventsRequest(super::PendingEventsRequest),
+        #[prost(message, tag = "18")]
+        CloseSessionRequest(super::CloseSessionRequest),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -365,6 +646,7 @@ pub enum ErrorCode {
     InvalidDeadline = 4,
     InvalidMessage = 5,
     InvalidKey = 6,
+    Busy = 7,
     InitializerPanic = 10,
     SimulationNotStarted = 11,
     SimulationHalted = 12,
@@ -377,8 +659,21 @@ pub enum ErrorCode {
     SimulationOutOfSync = 19,
     SimulationBadQuery = 20,
     SimulationTimeOutOfRange = 21,
+    SimulationWouldBlock = 22,
+    SimulationNoRealTimeClock = 23,
     SourceNotFound = 30,
     SinkNotFound = 31,
+    SinkNotStreamable = 32,
+    ModelNotFound = 33,
+    ModelConnected = 34,
+    InvalidModel = 35,
+    ModelPanic = 36,
+    QueryTimeout = 37,
+    SimulationWallClockTimeout = 38,
+    SimulationCancelled = 39,
+    SessionNotFound = 40,
+    InvariantViolated = 41,
+    SimulationClockAhead = 42,
 }
 impl ErrorCode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -394,6 +689,7 @@ impl ErrorCode {
             Self::InvalidDeadline => "INVALID_DEADLINE",
             Self::InvalidMessage => "INVALID_MESSAGE",
             Self::InvalidKey => "INVALID_KEY",
+            Self::Busy => "BUSY",
             Self::InitializerPanic => "INITIALIZER_PANIC",
             Self::SimulationNotStarted => "SIMULATION_NOT_STARTED",
             Self::SimulationHalted => "SIMULATION_HALTED",
@@ -406,8 +702,21 @@ impl ErrorCode {
             Self::SimulationOutOfSync => "SIMULATION_OUT_OF_SYNC",
             Self::SimulationBadQuery => "SIMULATION_BAD_QUERY",
             Self::SimulationTimeOutOfRange => "SIMULATION_TIME_OUT_OF_RANGE",
+            Self::SimulationWouldBlock => "SIMULATION_WOULD_BLOCK",
+            Self::SimulationNoRealTimeClock => "SIMULATION_NO_REAL_TIME_CLOCK",
             Self::SourceNotFound => "SOURCE_NOT_FOUND",
             Self::SinkNotFound => "SINK_NOT_FOUND",
+            Self::SinkNotStreamable => "SINK_NOT_STREAMABLE",
+            Self::ModelNotFound => "MODEL_NOT_FOUND",
+            Self::ModelConnected => "MODEL_CONNECTED",
+            Self::InvalidModel => "INVALID_MODEL",
+            Self::ModelPanic => "MODEL_PANIC",
+            Self::QueryTimeout => "QUERY_TIMEOUT",
+            Self::SimulationWallClockTimeout => "SIMULATION_WALL_CLOCK_TIMEOUT",
+            Self::SimulationCancelled => "SIMULATION_CANCELLED",
+            Self::SessionNotFound => "SESSION_NOT_FOUND",
+            Self::InvariantViolated => "INVARIANT_VIOLATED",
+            Self::SimulationClockAhead => "SIMULATION_CLOCK_AHEAD",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -420,6 +729,7 @@ impl ErrorCode {
             "INVALID_DEADLINE" => Some(Self::InvalidDeadline),
             "INVALID_MESSAGE" => Some(Self::InvalidMessage),
             "INVALID_KEY" => Some(Self::InvalidKey),
+            "BUSY" => Some(Self::Busy),
             "INITIALIZER_PANIC" => Some(Self::InitializerPanic),
             "SIMULATION_NOT_STARTED" => Some(Self::SimulationNotStarted),
             "SIMULATION_HALTED" => Some(Self::SimulationHalted),
@@ -432,8 +742,21 @@ impl ErrorCode {
             "SIMULATION_OUT_OF_SYNC" => Some(Self::SimulationOutOfSync),
             "SIMULATION_BAD_QUERY" => Some(Self::SimulationBadQuery),
             "SIMULATION_TIME_OUT_OF_RANGE" => Some(Self::SimulationTimeOutOfRange),
+            "SIMULATION_WOULD_BLOCK" => Some(Self::SimulationWouldBlock),
+            "SIMULATION_NO_REAL_TIME_CLOCK" => Some(Self::SimulationNoRealTimeClock),
             "SOURCE_NOT_FOUND" => Some(Self::SourceNotFound),
             "SINK_NOT_FOUND" => Some(Self::SinkNotFound),
+            "SINK_NOT_STREAMABLE" => Some(Self::SinkNotStreamable),
+            "MODEL_NOT_FOUND" => Some(Self::ModelNotFound),
+            "MODEL_CONNECTED" => Some(Self::ModelConnected),
+            "INVALID_MODEL" => Some(Self::InvalidModel),
+            "MODEL_PANIC" => Some(Self::ModelPanic),
+            "QUERY_TIMEOUT" => Some(Self::QueryTimeout),
+            "SIMULATION_WALL_CLOCK_TIMEOUT" => Some(Self::SimulationWallClockTimeout),
+            "SIMULATION_CANCELLED" => Some(Self::SimulationCancelled),
+            "SESSION_NOT_FOUND" => Some(Self::SessionNotFound),
+            "INVARIANT_VIOLATED" => Some(Self::InvariantViolated),
+            "SIMULATION_CLOCK_AHEAD" => Some(Self::SimulationClockAhead),
             _ => None,
         }
     }
@@ -455,6 +778,10 @@ pub mod simulation_server {
             &self,
             request: tonic::Request<super::InitRequest>,
         ) -> std::result::Result<tonic::Response<super::InitReply>, tonic::Status>;
+        async fn reset(
+            &self,
+            request: tonic::Request<super::ResetRequest>,
+        ) -> std::result::Result<tonic::Response<super::ResetReply>, tonic::Status>;
         async fn halt(
             &self,
             request: tonic::Request<super::HaltRequest>,
@@ -463,6 +790,13 @@ pub mod simulation_server {
             &self,
             request: tonic::Request<super::TimeRequest>,
         ) -> std::result::Result<tonic::Response<super::TimeReply>, tonic::Status>;
+        async fn pending_events(
+            &self,
+            request: tonic::Request<super::PendingEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PendingEventsReply>,
+            tonic::Status,
+        >;
         async fn step(
             &self,
             request: tonic::Request<super::StepRequest>,
@@ -492,6 +826,10 @@ pub mod simulation_server {
             tonic::Response<super::ProcessEventReply>,
             tonic::Status,
         >;
+        async fn process_events(
+            &self,
+            request: tonic::Request<super::ProcessEventsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ProcessEventsReply>, tonic::Status>;
         async fn process_query(
             &self,
             request: tonic::Request<super::ProcessQueryRequest>,
@@ -511,6 +849,34 @@ pub mod simulation_server {
             &self,
             request: tonic::Request<super::CloseSinkRequest>,
         ) -> std::result::Result<tonic::Response<super::CloseSinkReply>, tonic::Status>;
+        /// Server streaming response type for the StreamSink method.
+        type StreamSinkStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::StreamSinkReply, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn stream_sink(
+            &self,
+            request: tonic::Request<super::StreamSinkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamSinkStream>,
+            tonic::Status,
+        >;
+        async fn list_sources(
+            &self,
+            request: tonic::Request<super::ListSourcesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListSourcesReply>, tonic::Status>;
+        async fn list_sinks(
+            &self,
+            request: tonic::Request<super::ListSinksRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListSinksReply>, tonic::Status>;
+        async fn close_session(
+            &self,
+            request: tonic::Request<super::CloseSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CloseSessionReply>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SimulationServer<T> {
@@ -631,6 +997,51 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
+                "/simulation.v1.Simulation/Reset" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResetSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ResetRequest>
+                    for ResetSvc<T> {
+                        type Response = super::ResetReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ResetRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::reset(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ResetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/simulation.v1.Simulation/Halt" => {
                     #[allow(non_camel_case_types)]
                     struct HaltSvc<T: Simulation>(pub Arc<T>);
@@ -717,6 +1128,49 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
+                "/simulation.v1.Simulation/PendingEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct PendingEventsSvc<T: Simulation>(pub Arc<T>);
+                    impl<T: Simulation> tonic::server::UnaryService<super::PendingEventsRequest>
+                    for PendingEventsSvc<T> {
+                        type Response = super::PendingEventsReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PendingEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::pending_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PendingEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/simulation.v1.Simulation/Step" => {
                     #[allow(non_camel_case_types)]
                     struct StepSvc<T: Simulation>(pub Arc<T>);
@@ -940,6 +1394,51 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
+                "/simulation.v1.Simulation/ProcessEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProcessEventsSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ProcessEventsRequest>
+                    for ProcessEventsSvc<T> {
+                        type Response = super::ProcessEventsReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ProcessEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::process_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ProcessEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/simulation.v1.Simulation/ProcessQuery" => {
                     #[allow(non_camel_case_types)]
                     struct ProcessQuerySvc<T: Simulation>(pub Arc<T>);
@@ -1120,6 +1619,187 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
+                "/simulation.v1.Simulation/StreamSink" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamSinkSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::ServerStreamingService<super::StreamSinkRequest>
+                    for StreamSinkSvc<T> {
+                        type Response = super::StreamSinkReply;
+                        type ResponseStream = T::StreamSinkStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamSinkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::stream_sink(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StreamSinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/ListSources" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSourcesSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ListSourcesRequest>
+                    for ListSourcesSvc<T> {
+                        type Response = super::ListSourcesReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListSourcesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::list_sources(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListSourcesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/ListSinks" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSinksSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ListSinksRequest>
+                    for ListSinksSvc<T> {
+                        type Response = super::ListSinksReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListSinksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::list_sinks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListSinksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/CloseSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct CloseSessionSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::CloseSessionRequest>
+                    for CloseSessionSvc<T> {
+                        type Response = super::CloseSessionReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CloseSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::close_session(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CloseSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
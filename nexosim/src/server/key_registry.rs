@@ -5,12 +5,30 @@ use crate::util::indexed_priority_queue::{IndexedPriorityQueue, InsertKey};
 pub(crate) type KeyRegistryId = InsertKey;
 
 /// A collection of `ActionKey`s indexed by a unique identifier.
+///
+/// Identifiers remain valid as long as the key is in the registry. They are
+/// never reused by the registry itself, nor by the registries created with
+/// [`KeyRegistry::successor`], so a stale identifier cannot refer to an
+/// unrelated key. Keys are not carried over to a successor, however, since
+/// the actions they refer to belong to the replaced simulation bench.
 #[derive(Default)]
 pub(crate) struct KeyRegistry {
     keys: IndexedPriorityQueue<MonotonicTime, ActionKey>,
 }
 
 impl KeyRegistry {
+    /// Creates an empty registry which identifiers never collide with the
+    /// identifiers issued by this registry or its predecessors.
+    ///
+    /// This is meant to be used when the simulation bench is replaced, so
+    /// that identifiers issued for the previous bench are rejected rather than
+    /// aliased to keys of the new bench.
+    pub(crate) fn successor(&self) -> Self {
+        Self {
+            keys: IndexedPriorityQueue::with_first_epoch(self.keys.next_epoch()),
+        }
+    }
+
     /// Inserts an `ActionKey` into the registry.
     ///
     /// The provided expiration deadline is the latest time at which the key is
@@ -1,35 +1,297 @@
 //! Simulation server.
 
-use std::net::SocketAddr;
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener};
 #[cfg(unix)]
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc as std_mpsc;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::time::Duration;
+use std::{fmt, io, iter, thread};
 
 use serde::de::DeserializeOwned;
-use tonic::{transport::Server, Request, Response, Status};
+use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
+use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{Server, ServerTlsConfig};
+use tonic::{Request, Response, Status};
 
 use crate::registry::EndpointRegistry;
-use crate::simulation::{Simulation, SimulationError};
+use crate::simulation::{Scheduler, Simulation, SimulationError};
 
 use super::codegen::simulation::*;
 use super::key_registry::KeyRegistry;
-use super::services::InitService;
+use super::services::{session_not_found_error, ErrorReply, InitService};
 use super::services::{ControllerService, MonitorService, SchedulerService};
 
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests;
+
+/// Identifier of a simulation session.
+type SessionId = u64;
+
+/// Identifier of the default session.
+///
+/// The default session always exists so that clients unaware of sessions can
+/// keep using the server as a single-simulation server.
+const DEFAULT_SESSION_ID: SessionId = 0;
+
+/// Capacity of the channel feeding each sink stream.
+///
+/// A sink stream is closed if the client lets more than this number of events
+/// accumulate.
+const STREAM_SINK_CAPACITY: usize = 1024;
+
+/// Default capacity of the queue of pending scheduling requests of a session.
+///
+/// See [`ServerBuilder::set_scheduling_capacity`].
+const SCHEDULING_QUEUE_CAPACITY: usize = 1024;
+
 /// Runs a simulation from a network server.
 ///
 /// The first argument is a closure that takes an initialization configuration
 /// and is called every time the simulation is (re)started by the remote client.
 /// It must create a new simulation, complemented by a registry that exposes the
 /// public event and query interface.
+///
+/// This is a shorthand for [`ServerBuilder::run`] with the default server
+/// settings.
 pub fn run<F, I>(sim_gen: F, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>>
 where
     F: FnMut(I) -> Result<(Simulation, EndpointRegistry), SimulationError> + Send + 'static,
     I: DeserializeOwned,
 {
-    run_service(GrpcSimulationService::new(sim_gen), addr)
+    ServerBuilder::new(sim_gen).run(addr)
+}
+
+/// Runs a simulation locally from a Unix Domain Sockets server.
+///
+/// The first argument is a closure that takes an initialization configuration
+/// and is called every time the simulation is (re)started by the remote client.
+/// It must create a new simulation, complemented by a registry that exposes the
+/// public event and query interface.
+///
+/// This is a shorthand for [`ServerBuilder::run_local`] with the default
+/// server settings.
+#[cfg(unix)]
+pub fn run_local<F, I, P>(sim_gen: F, path: P) -> Result<(), Box<dyn std::error::Error>>
+where
+    F: FnMut(I) -> Result<(Simulation, EndpointRegistry), SimulationError> + Send + 'static,
+    I: DeserializeOwned,
+    P: AsRef<Path>,
+{
+    ServerBuilder::new(sim_gen).run_local(path)
+}
+
+/// A builder for a simulation server with custom settings.
+///
+/// All settings are optional and can be combined freely. The server is
+/// started with [`ServerBuilder::run`] for a network server or with
+/// [`ServerBuilder::run_local`] for a Unix Domain Sockets server.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use nexosim::registry::EndpointRegistry;
+/// use nexosim::server::{Identity, RetryPolicy, ServerBuilder, ServerTlsConfig};
+/// use nexosim::simulation::{SimInit, Simulation, SimulationError};
+/// use nexosim::time::MonotonicTime;
+///
+/// fn bench(_cfg: ()) -> Result<(Simulation, EndpointRegistry), SimulationError> {
+///     let (simulation, _scheduler) = SimInit::new().init(MonotonicTime::EPOCH)?;
+///
+///     Ok((simulation, EndpointRegistry::new()))
+/// }
+///
+/// let cert = std::fs::read("server.pem")?;
+/// let key = std::fs::read("server.key")?;
+/// let tls_config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+///
+/// // Retry up to 5 times, waiting 100ms, 200ms, 400ms, 800ms and 1s.
+/// let retry_policy = RetryPolicy::new(5, Duration::from_millis(100))
+///     .set_max_delay(Duration::from_secs(1));
+///
+/// ServerBuilder::new(bench)
+///     .set_tls_config(tls_config)
+///     .set_retry_policy(retry_policy)
+///     .set_scheduling_capacity(64)
+///     .run("0.0.0.0:41633".parse()?)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ServerBuilder {
+    init_service: InitService,
+    tls_config: Option<ServerTlsConfig>,
+    retry_policy: Option<RetryPolicy>,
+    scheduling_capacity: usize,
+}
+
+impl ServerBuilder {
+    /// Creates a builder with the default server settings.
+    ///
+    /// The argument is a closure that takes an initialization configuration
+    /// and is called every time the simulation is (re)started by the remote
+    /// client. It must create a new simulation, complemented by a registry
+    /// that exposes the public event and query interface.
+    pub fn new<F, I>(sim_gen: F) -> Self
+    where
+        F: FnMut(I) -> Result<(Simulation, EndpointRegistry), SimulationError> + Send + 'static,
+        I: DeserializeOwned,
+    {
+        Self {
+            init_service: InitService::new(sim_gen),
+            tls_config: None,
+            retry_policy: None,
+            scheduling_capacity: SCHEDULING_QUEUE_CAPACITY,
+        }
+    }
+
+    /// Secures all connections with TLS.
+    ///
+    /// The server identity is typically built from a PEM-encoded certificate
+    /// and private key with
+    /// [`Identity::from_pem`](crate::server::Identity::from_pem), which
+    /// accepts the file contents as bytes. Mutual TLS may optionally be enabled
+    /// by also providing the PEM-encoded certificate of the authority that
+    /// signed the client certificates with
+    /// [`ServerTlsConfig::client_ca_root`].
+    ///
+    /// This setting is ignored by [`ServerBuilder::run_local`].
+    pub fn set_tls_config(mut self, tls_config: ServerTlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+
+        self
+    }
+
+    /// Retries to bind the server address according to the provided policy if
+    /// the address is in use.
+    ///
+    /// This typically happens when a supervised server is restarted before the
+    /// operating system has released the address of the previous instance. An
+    /// error is returned if the address is still in use after the last retry.
+    ///
+    /// This setting is ignored by [`ServerBuilder::run_local`].
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Sets the capacity of the queue of pending scheduling requests of each
+    /// session.
+    ///
+    /// The `ScheduleEvent` requests of a session are queued and processed one
+    /// at a time, in the order they were received, by a thread dedicated to
+    /// this session, so that bursts of requests do not stall the server. A
+    /// request is counted as pending from the moment it is queued until it has
+    /// been processed. When the queue is full, new scheduling requests are
+    /// immediately rejected with a `BUSY` error code rather than being queued,
+    /// and may be retried by the client later.
+    ///
+    /// The capacity is clamped to at least 1. The default capacity is 1024.
+    pub fn set_scheduling_capacity(mut self, capacity: usize) -> Self {
+        self.scheduling_capacity = capacity;
+
+        self
+    }
+
+    /// Runs the simulation from a network server.
+    pub fn run(self, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
+        run_service(
+            GrpcSimulationService::new(self.init_service, self.scheduling_capacity),
+            addr,
+            self.tls_config,
+            self.retry_policy,
+        )
+    }
+
+    /// Runs the simulation locally from a Unix Domain Sockets server.
+    ///
+    /// The TLS configuration and the retry policy, if any, are ignored.
+    #[cfg(unix)]
+    pub fn run_local<P: AsRef<Path>>(self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        run_local_service(
+            GrpcSimulationService::new(self.init_service, self.scheduling_capacity),
+            path.as_ref(),
+        )
+    }
+}
+
+impl fmt::Debug for ServerBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerBuilder")
+            .field("is_tls", &self.tls_config.is_some())
+            .field("retry_policy", &self.retry_policy)
+            .field("scheduling_capacity", &self.scheduling_capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A policy for retrying to bind the address of a network server.
+///
+/// The delay between two attempts starts at the specified initial delay and
+/// is doubled after each retry, up to an optional maximum delay.
+///
+/// See [`ServerBuilder::set_retry_policy`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the specified maximum number of retries and
+    /// delay before the first retry.
+    pub fn new(max_retries: u32, initial_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_delay,
+            max_delay: None,
+        }
+    }
+
+    /// Sets an upper bound on the delay between two attempts.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+
+        self
+    }
+
+    /// Returns the successive delays before each retry.
+    fn delays(&self) -> impl Iterator<Item = Duration> {
+        let max_delay = self.max_delay;
+
+        iter::successors(Some(self.initial_delay), move |delay| {
+            let delay = delay.saturating_mul(2);
+
+            Some(match max_delay {
+                Some(max_delay) => delay.min(max_delay),
+                None => delay,
+            })
+        })
+        .take(self.max_retries as usize)
+    }
+
+    /// Binds a TCP listener to the specified address, retrying as long as the
+    /// address is in use and the maximum number of retries is not exceeded.
+    fn bind(&self, addr: SocketAddr) -> io::Result<TcpListener> {
+        let mut delays = self.delays();
+
+        loop {
+            match TcpListener::bind(addr) {
+                Err(e) if e.kind() == io::ErrorKind::AddrInUse => match delays.next() {
+                    Some(delay) => thread::sleep(delay),
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+        }
+    }
 }
 
 /// Monomorphization of the network server.
@@ -39,42 +301,50 @@ where
 fn run_service(
     service: GrpcSimulationService,
     addr: SocketAddr,
+    tls_config: Option<ServerTlsConfig>,
+    retry_policy: Option<RetryPolicy>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Use 2 threads so that even if the controller service is blocked due to
-    // ongoing simulation execution, other services can still be used
-    // concurrently.
+    // The listener is bound upfront when binding should be retried since
+    // `Server::serve` does not make it possible to identify binding errors.
+    let listener = match retry_policy {
+        Some(retry_policy) => {
+            let listener = retry_policy.bind(addr)?;
+            listener.set_nonblocking(true)?;
+
+            Some(listener)
+        }
+        None => None,
+    };
+
+    // Requests which may block, such as simulation steps, are processed on the
+    // blocking thread pool, so 2 worker threads are enough to keep the server
+    // responsive.
     let rt = tokio::runtime::Builder::new_multi_thread()
         .worker_threads(2)
         .enable_io()
         .build()?;
 
+    let mut server = Server::builder();
+    if let Some(tls_config) = tls_config {
+        server = server.tls_config(tls_config)?;
+    }
+
     rt.block_on(async move {
-        Server::builder()
-            .add_service(simulation_server::SimulationServer::new(service))
-            .serve(addr)
-            .await?;
+        let router = server.add_service(simulation_server::SimulationServer::new(service));
+        match listener {
+            Some(listener) => {
+                let listener = tokio::net::TcpListener::from_std(listener)?;
+                let incoming = TcpIncoming::from_listener(listener, false, None)
+                    .map_err(|e| e as Box<dyn std::error::Error>)?;
+                router.serve_with_incoming(incoming).await?;
+            }
+            None => router.serve(addr).await?,
+        }
 
         Ok(())
     })
 }
 
-/// Runs a simulation locally from a Unix Domain Sockets server.
-///
-/// The first argument is a closure that takes an initialization configuration
-/// and is called every time the simulation is (re)started by the remote client.
-/// It must create a new simulation, complemented by a registry that exposes the
-/// public event and query interface.
-#[cfg(unix)]
-pub fn run_local<F, I, P>(sim_gen: F, path: P) -> Result<(), Box<dyn std::error::Error>>
-where
-    F: FnMut(I) -> Result<(Simulation, EndpointRegistry), SimulationError> + Send + 'static,
-    I: DeserializeOwned,
-    P: AsRef<Path>,
-{
-    let path = path.as_ref();
-    run_local_service(GrpcSimulationService::new(sim_gen), path)
-}
-
 /// Monomorphization of the Unix Domain Sockets server.
 ///
 /// Keeping this as a separate monomorphized fragment can even triple
@@ -113,9 +383,9 @@ fn run_local_service(
     // (Re-)Create the socket.
     fs::create_dir_all(path.parent().unwrap())?;
 
-    // Use 2 threads so that even if the controller service is blocked due to
-    // ongoing simulation execution, other services can still be used
-    // concurrently.
+    // Requests which may block, such as simulation steps, are processed on the
+    // blocking thread pool, so 2 worker threads are enough to keep the server
+    // responsive.
     let rt = tokio::runtime::Builder::new_multi_thread()
         .worker_threads(2)
         .enable_io()
@@ -134,30 +404,97 @@ fn run_local_service(
     })
 }
 
-struct GrpcSimulationService {
+/// A scheduling request waiting in a scheduling queue.
+struct SchedulingJob {
+    request: ScheduleEventRequest,
+    reply_sender: oneshot::Sender<ScheduleEventReply>,
+    _permit: OwnedSemaphorePermit,
+}
+
+/// A bounded FIFO queue of scheduling requests.
+///
+/// The requests are processed one at a time by a dedicated thread, which exits
+/// once the queue is dropped and all pending requests have been processed.
+struct SchedulingQueue {
+    sender: std_mpsc::Sender<SchedulingJob>,
+    permits: Arc<Semaphore>,
+}
+
+impl SchedulingQueue {
+    /// Creates a queue that can hold at most `capacity` pending requests and
+    /// spawns the thread that processes them.
+    fn new(scheduler_service: Arc<Mutex<SchedulerService>>, capacity: usize) -> Self {
+        let (sender, receiver) = std_mpsc::channel::<SchedulingJob>();
+
+        thread::spawn(move || {
+            for job in receiver {
+                let reply = scheduler_service
+                    .lock()
+                    .unwrap()
+                    .schedule_event(job.request);
+
+                // The requester may have given up waiting for the reply.
+                let _ = job.reply_sender.send(reply);
+            }
+        });
+
+        Self {
+            sender,
+            permits: Arc::new(Semaphore::new(capacity.clamp(1, Semaphore::MAX_PERMITS))),
+        }
+    }
+
+    /// Pushes a request to the back of the queue and returns a receiver for
+    /// its reply, or returns a `BUSY` error reply if the queue is full.
+    fn push(
+        &self,
+        request: ScheduleEventRequest,
+    ) -> Result<oneshot::Receiver<ScheduleEventReply>, ScheduleEventReply> {
+        // A permit is held from the moment the request is queued until it has
+        // been processed.
+        let permit = self
+            .permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| SchedulerService::schedule_event_busy_reply())?;
+
+        let (reply_sender, reply_receiver) = oneshot::channel();
+        let job = SchedulingJob {
+            request,
+            reply_sender,
+            _permit: permit,
+        };
+
+        // The processing thread only exits after the queue is dropped.
+        self.sender.send(job).unwrap();
+
+        Ok(reply_receiver)
+    }
+}
+
+/// The state of a simulation session.
+struct Session {
     init_service: Mutex<InitService>,
     controller_service: Mutex<ControllerService>,
     monitor_service: Mutex<MonitorService>,
-    scheduler_service: Mutex<SchedulerService>,
+    scheduler_service: Arc<Mutex<SchedulerService>>,
+    scheduling_queue: SchedulingQueue,
 }
 
-impl GrpcSimulationService {
-    /// Creates a new `GrpcSimulationService` without any active simulation.
+impl Session {
+    /// Creates a new session without any active simulation.
     ///
-    /// The argument is a closure that takes an initialization configuration and
-    /// is called every time the simulation is (re)started by the remote client.
-    /// It must create a new simulation, complemented by a registry that exposes
-    /// the public event and query interface.
-    pub(crate) fn new<F, I>(sim_gen: F) -> Self
-    where
-        F: FnMut(I) -> Result<(Simulation, EndpointRegistry), SimulationError> + Send + 'static,
-        I: DeserializeOwned,
-    {
+    /// At most `scheduling_capacity` scheduling requests can be pending at any
+    /// time.
+    fn new(init_service: InitService, scheduling_capacity: usize) -> Self {
+        let scheduler_service = Arc::new(Mutex::new(SchedulerService::NotStarted));
+
         Self {
-            init_service: Mutex::new(InitService::new(sim_gen)),
+            init_service: Mutex::new(init_service),
             controller_service: Mutex::new(ControllerService::NotStarted),
             monitor_service: Mutex::new(MonitorService::NotStarted),
-            scheduler_service: Mutex::new(SchedulerService::NotStarted),
+            scheduling_queue: SchedulingQueue::new(scheduler_service.clone(), scheduling_capacity),
+            scheduler_service,
         }
     }
 
@@ -180,6 +517,121 @@ impl GrpcSimulationService {
     fn scheduler(&self) -> MutexGuard<'_, SchedulerService> {
         self.scheduler_service.lock().unwrap()
     }
+
+    /// Installs a new simulation bench, replacing any previous one.
+    fn start(&self, bench: (Simulation, Scheduler, EndpointRegistry)) {
+        let (simulation, scheduler, endpoint_registry) = bench;
+
+        let registry_version = endpoint_registry.version();
+        let event_source_registry = Arc::new(endpoint_registry.event_source_registry);
+        let query_source_registry = endpoint_registry.query_source_registry;
+        let event_sink_registry = endpoint_registry.event_sink_registry;
+        let codec = endpoint_registry.codec;
+
+        // Make sure that the keys issued for the previous bench, if any, are
+        // not mistaken for keys of the new bench.
+        let key_registry = match &*self.scheduler() {
+            SchedulerService::Started { key_registry, .. } => key_registry.successor(),
+            SchedulerService::NotStarted => KeyRegistry::default(),
+        };
+
+        *self.controller() = ControllerService::Started {
+            simulation,
+            event_source_registry: event_source_registry.clone(),
+            query_source_registry,
+            registry_version,
+            codec,
+        };
+        *self.monitor() = MonitorService::Started {
+            event_sink_registry,
+            codec,
+        };
+        *self.scheduler() = SchedulerService::Started {
+            scheduler,
+            event_source_registry,
+            key_registry,
+            codec,
+        };
+    }
+}
+
+/// A gRPC service hosting any number of independent simulation sessions.
+///
+/// A new session is created by each successful `Init` request with the
+/// `new_session` flag set and is identified by the session identifier returned
+/// in the reply. All other requests are routed to the session designated by
+/// their session identifier. A session remains active, irrespectively of client
+/// connections, until it is closed with a `CloseSession` request, at which
+/// point its simulation bench is dropped once all requests targeting this
+/// session have completed.
+///
+/// The default session always exists: it is initialized in place by `Init`
+/// requests without the `new_session` flag and closing it only drops its
+/// simulation bench.
+struct GrpcSimulationService {
+    init_service: InitService,
+    sessions: Mutex<HashMap<SessionId, Arc<Session>>>,
+    next_session_id: AtomicU64,
+    scheduling_capacity: usize,
+}
+
+impl GrpcSimulationService {
+    /// Creates a new `GrpcSimulationService` with an uninitialized default
+    /// session.
+    ///
+    /// The initializer is forked for each session. At most
+    /// `scheduling_capacity` scheduling requests can be pending at any time in
+    /// each session.
+    fn new(init_service: InitService, scheduling_capacity: usize) -> Self {
+        let default_session = Session::new(init_service.fork(), scheduling_capacity);
+
+        Self {
+            init_service,
+            sessions: Mutex::new(HashMap::from([(
+                DEFAULT_SESSION_ID,
+                Arc::new(default_session),
+            )])),
+            next_session_id: AtomicU64::new(DEFAULT_SESSION_ID + 1),
+            scheduling_capacity,
+        }
+    }
+
+    /// Locks the session map and returns the mutex guard.
+    fn sessions(&self) -> MutexGuard<'_, HashMap<SessionId, Arc<Session>>> {
+        self.sessions.lock().unwrap()
+    }
+
+    /// Returns the session with the specified identifier or, if no such
+    /// session exists, an error reply.
+    fn session<R: ErrorReply>(&self, session_id: SessionId) -> Result<Arc<Session>, R> {
+        self.sessions()
+            .get(&session_id)
+            .cloned()
+            .ok_or_else(|| R::from_error(session_not_found_error(session_id)))
+    }
+
+    /// Processes a request with the specified session or, if no such session
+    /// exists, returns an error reply.
+    ///
+    /// The request is processed on the blocking thread pool since it may take
+    /// arbitrarily long to complete, for instance when stepping a simulation,
+    /// and must not prevent the server workers from serving other sessions.
+    async fn with_session<R, F>(&self, session_id: SessionId, f: F) -> Result<Response<R>, Status>
+    where
+        R: ErrorReply + Send + 'static,
+        F: FnOnce(&Session) -> R + Send + 'static,
+    {
+        let session = match self.session(session_id) {
+            Ok(session) => session,
+            Err(reply) => return Ok(Response::new(reply)),
+        };
+
+        let reply = tokio::task::spawn_blocking(move || f(&session))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(reply))
+    }
 }
 
 #[tonic::async_trait]
@@ -187,44 +639,97 @@ impl simulation_server::Simulation for GrpcSimulationService {
     async fn init(&self, request: Request<InitRequest>) -> Result<Response<InitReply>, Status> {
         let request = request.into_inner();
 
-        let (reply, bench) = self.initializer().init(request);
-
-        if let Some((simulation, scheduler, endpoint_registry)) = bench {
-            let event_source_registry = Arc::new(endpoint_registry.event_source_registry);
-            let query_source_registry = endpoint_registry.query_source_registry;
-            let event_sink_registry = endpoint_registry.event_sink_registry;
-
-            *self.controller() = ControllerService::Started {
-                simulation,
-                event_source_registry: event_source_registry.clone(),
-                query_source_registry,
-            };
-            *self.monitor() = MonitorService::Started {
-                event_sink_registry,
-            };
-            *self.scheduler() = SchedulerService::Started {
-                scheduler,
-                event_source_registry,
-                key_registry: KeyRegistry::default(),
-            };
+        // The default session is initialized in place; its previous bench, if
+        // any, is kept if the initialization fails.
+        if !request.new_session {
+            return self
+                .with_session(DEFAULT_SESSION_ID, move |session| {
+                    let (reply, bench) = session.initializer().init(request);
+
+                    if let Some(bench) = bench {
+                        session.start(bench);
+                    }
+
+                    reply
+                })
+                .await;
+        }
+
+        let mut init_service = self.init_service.fork();
+        let scheduling_capacity = self.scheduling_capacity;
+
+        // Building a bench may take arbitrarily long, so it is done on the
+        // blocking thread pool.
+        let (mut reply, session) = tokio::task::spawn_blocking(move || {
+            let (reply, bench) = init_service.init(request);
+            let session = bench.map(|bench| {
+                let session = Session::new(init_service, scheduling_capacity);
+                session.start(bench);
+
+                session
+            });
+
+            (reply, session)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+        if let Some(session) = session {
+            let session_id = self.next_session_id.fetch_add(1, Ordering::Relaxed);
+            self.sessions().insert(session_id, Arc::new(session));
+            reply.session_id = session_id;
         }
 
         Ok(Response::new(reply))
     }
+    async fn reset(&self, request: Request<ResetRequest>) -> Result<Response<ResetReply>, Status> {
+        let request = request.into_inner();
+
+        self.with_session(request.session_id, move |session| {
+            let (reply, bench) = session.initializer().reset(request);
+
+            if let Some(bench) = bench {
+                session.start(bench);
+            }
+
+            reply
+        })
+        .await
+    }
     async fn halt(&self, request: Request<HaltRequest>) -> Result<Response<HaltReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().halt(request)))
+        self.with_session(request.session_id, move |session| {
+            session.scheduler().halt(request)
+        })
+        .await
     }
     async fn time(&self, request: Request<TimeRequest>) -> Result<Response<TimeReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().time(request)))
+        self.with_session(request.session_id, move |session| {
+            session.scheduler().time(request)
+        })
+        .await
+    }
+    async fn pending_events(
+        &self,
+        request: Request<PendingEventsRequest>,
+    ) -> Result<Response<PendingEventsReply>, Status> {
+        let request = request.into_inner();
+
+        self.with_session(request.session_id, move |session| {
+            session.scheduler().pending_events(request)
+        })
+        .await
     }
     async fn step(&self, request: Request<StepRequest>) -> Result<Response<StepReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().step(request)))
+        self.with_session(request.session_id, move |session| {
+            session.controller().step(request)
+        })
+        .await
     }
     async fn step_until(
         &self,
@@ -232,7 +737,10 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<StepUntilReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().step_until(request)))
+        self.with_session(request.session_id, move |session| {
+            session.controller().step_until(request)
+        })
+        .await
     }
     async fn schedule_event(
         &self,
@@ -240,7 +748,22 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ScheduleEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().schedule_event(request)))
+        let session = match self.session(request.session_id) {
+            Ok(session) => session,
+            Err(reply) => return Ok(Response::new(reply)),
+        };
+
+        // The request is processed by the thread of the scheduling queue so
+        // that the server worker is not blocked while waiting for the
+        // scheduler; it is rejected rather than queued if the queue is full.
+        let reply = match session.scheduling_queue.push(request) {
+            Ok(reply_receiver) => reply_receiver
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?,
+            Err(busy_reply) => busy_reply,
+        };
+
+        Ok(Response::new(reply))
     }
     async fn cancel_event(
         &self,
@@ -248,7 +771,10 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<CancelEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().cancel_event(request)))
+        self.with_session(request.session_id, move |session| {
+            session.scheduler().cancel_event(request)
+        })
+        .await
     }
     async fn process_event(
         &self,
@@ -256,7 +782,21 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ProcessEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().process_event(request)))
+        self.with_session(request.session_id, move |session| {
+            session.controller().process_event(request)
+        })
+        .await
+    }
+    async fn process_events(
+        &self,
+        request: Request<ProcessEventsRequest>,
+    ) -> Result<Response<ProcessEventsReply>, Status> {
+        let request = request.into_inner();
+
+        self.with_session(request.session_id, move |session| {
+            session.controller().process_events(request)
+        })
+        .await
     }
     async fn process_query(
         &self,
@@ -264,7 +804,10 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ProcessQueryReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().process_query(request)))
+        self.with_session(request.session_id, move |session| {
+            session.controller().process_query(request)
+        })
+        .await
     }
     async fn read_events(
         &self,
@@ -272,7 +815,10 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ReadEventsReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().read_events(request)))
+        self.with_session(request.session_id, move |session| {
+            session.monitor().read_events(request)
+        })
+        .await
     }
     async fn open_sink(
         &self,
@@ -280,7 +826,10 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<OpenSinkReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().open_sink(request)))
+        self.with_session(request.session_id, move |session| {
+            session.monitor().open_sink(request)
+        })
+        .await
     }
     async fn close_sink(
         &self,
@@ -288,6 +837,82 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<CloseSinkReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().close_sink(request)))
+        self.with_session(request.session_id, move |session| {
+            session.monitor().close_sink(request)
+        })
+        .await
+    }
+
+    type StreamSinkStream = ReceiverStream<Result<StreamSinkReply, Status>>;
+
+    async fn stream_sink(
+        &self,
+        request: Request<StreamSinkRequest>,
+    ) -> Result<Response<Self::StreamSinkStream>, Status> {
+        let request = request.into_inner();
+
+        let (sender, receiver) = mpsc::channel(STREAM_SINK_CAPACITY);
+        match self.session::<StreamSinkReply>(request.session_id) {
+            Ok(session) => {
+                tokio::task::spawn_blocking(move || session.monitor().stream_sink(request, sender))
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?;
+            }
+            Err(reply) => {
+                // The channel was just created so it cannot be full.
+                let _ = sender.try_send(Ok(reply));
+            }
+        }
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+    async fn list_sources(
+        &self,
+        request: Request<ListSourcesRequest>,
+    ) -> Result<Response<ListSourcesReply>, Status> {
+        let request = request.into_inner();
+
+        self.with_session(request.session_id, move |session| {
+            session.controller().list_sources(request)
+        })
+        .await
+    }
+    async fn list_sinks(
+        &self,
+        request: Request<ListSinksRequest>,
+    ) -> Result<Response<ListSinksReply>, Status> {
+        let request = request.into_inner();
+
+        self.with_session(request.session_id, move |session| {
+            session.monitor().list_sinks(request)
+        })
+        .await
+    }
+    async fn close_session(
+        &self,
+        request: Request<CloseSessionRequest>,
+    ) -> Result<Response<CloseSessionReply>, Status> {
+        let request = request.into_inner();
+
+        // The default session cannot be removed and is replaced by an
+        // uninitialized session instead. The closed session is dropped after
+        // the session map is unlocked.
+        let session = if request.session_id == DEFAULT_SESSION_ID {
+            let default_session = Session::new(self.init_service.fork(), self.scheduling_capacity);
+
+            self.sessions()
+                .insert(DEFAULT_SESSION_ID, Arc::new(default_session))
+        } else {
+            self.sessions().remove(&request.session_id)
+        };
+
+        let reply = match session {
+            Some(_) => close_session_reply::Result::Empty(()),
+            None => close_session_reply::Result::Error(session_not_found_error(request.session_id)),
+        };
+
+        Ok(Response::new(CloseSessionReply {
+            result: Some(reply),
+        }))
     }
 }
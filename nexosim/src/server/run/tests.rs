@@ -0,0 +1,749 @@
+//! Tests of the gRPC service, driven through its `Simulation` trait.
+
+use std::time::Duration;
+
+use futures_util::FutureExt;
+use serde::ser::Error as _;
+use serde::{Serialize, Serializer};
+
+use crate::model::Model;
+use crate::ports::{EventBuffer, EventRelay, EventSource, Output, QuerySource};
+use crate::server::services::monotonic_to_timestamp;
+use crate::simulation::{Mailbox, SimInit};
+use crate::time::MonotonicTime;
+
+use super::simulation_server::Simulation as _;
+use super::*;
+
+/// A model that accumulates the values it receives.
+struct Counter {
+    count: u64,
+    output: Output<u64>,
+}
+impl Counter {
+    async fn add(&mut self, value: u64) {
+        self.count += value;
+        self.output.send(self.count).await;
+    }
+    async fn count(&mut self) -> u64 {
+        self.count
+    }
+    async fn slow_count(&mut self, delay: Duration) -> u64 {
+        std::thread::sleep(delay);
+
+        self.count
+    }
+}
+impl Model for Counter {}
+
+/// A count which can only be serialized if it is even.
+struct EvenCount(u64);
+impl Serialize for EvenCount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % 2 != 0 {
+            return Err(S::Error::custom("odd count"));
+        }
+
+        serializer.serialize_u64(self.0)
+    }
+}
+
+/// Builds a bench with a single counter initialized to the value provided as
+/// configuration.
+fn counter_bench(initial_count: u64) -> Result<(Simulation, EndpointRegistry), SimulationError> {
+    let mut counter = Counter {
+        count: initial_count,
+        output: Output::default(),
+    };
+    let mbox = Mailbox::new();
+
+    let sink = EventBuffer::new();
+    counter.output.connect_sink(&sink);
+    let relay = EventRelay::new();
+    counter
+        .output
+        .map_connect_sink(|count| EvenCount(*count), &relay);
+    let mut add = EventSource::new();
+    add.connect(Counter::add, &mbox);
+    let mut count = QuerySource::new();
+    count.connect(Counter::count, &mbox);
+    let mut slow_count = QuerySource::new();
+    slow_count.connect(Counter::slow_count, &mbox);
+
+    let mut registry = EndpointRegistry::new();
+    registry.add_event_source(add, "add").unwrap();
+    registry.add_query_source(count, "count").unwrap();
+    registry.add_query_source(slow_count, "slow_count").unwrap();
+    registry.add_event_sink(sink, "count").unwrap();
+    registry.add_event_sink(relay, "even_count").unwrap();
+
+    let (simulation, _scheduler) = SimInit::new()
+        .add_model(counter, mbox, "counter")
+        .init(MonotonicTime::EPOCH)?;
+
+    Ok((simulation, registry))
+}
+
+/// Creates a service that generates counter benches.
+fn counter_service(scheduling_capacity: usize) -> GrpcSimulationService {
+    GrpcSimulationService::new(InitService::new(counter_bench), scheduling_capacity)
+}
+
+/// Creates a multi-threaded runtime for the service.
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()
+        .unwrap()
+}
+
+/// Serializes a value to CBOR.
+fn cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    ciborium::into_writer(value, &mut buffer).unwrap();
+
+    buffer
+}
+
+/// Initializes a counter bench in a new session and returns the session
+/// identifier.
+async fn init(service: &GrpcSimulationService, initial_count: u64) -> SessionId {
+    let reply = service
+        .init(Request::new(InitRequest {
+            cfg: cbor(&initial_count),
+            new_session: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(reply.result, Some(init_reply::Result::Empty(())));
+
+    reply.session_id
+}
+
+/// Returns a request to schedule the addition of 1 to a counter in 1s.
+fn schedule_add_request(session_id: SessionId) -> ScheduleEventRequest {
+    ScheduleEventRequest {
+        deadline: Some(schedule_event_request::Deadline::Duration(
+            prost_types::Duration {
+                seconds: 1,
+                nanos: 0,
+            },
+        )),
+        source_name: "add".to_string(),
+        event: cbor(&1u64),
+        session_id,
+        ..Default::default()
+    }
+}
+
+/// Adds a value to the counter of a session.
+async fn process_add(service: &GrpcSimulationService, session_id: SessionId, value: u64) {
+    let reply = service
+        .process_event(Request::new(ProcessEventRequest {
+            source_name: "add".to_string(),
+            event: cbor(&value),
+            session_id,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(reply.result, Some(process_event_reply::Result::Empty(())));
+}
+
+/// Queries the value of the counter of a session.
+async fn query_count(service: &GrpcSimulationService, session_id: SessionId) -> u64 {
+    let reply = service
+        .process_query(Request::new(ProcessQueryRequest {
+            source_name: "count".to_string(),
+            request: cbor(&()),
+            session_id,
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(reply.result, Some(process_query_reply::Result::Empty(())));
+    assert_eq!(reply.replies.len(), 1);
+
+    ciborium::from_reader(reply.replies[0].as_slice()).unwrap()
+}
+
+/// Queries the value of the counter of a session after a wall clock delay,
+/// with a timeout.
+async fn query_slow_count(
+    service: &GrpcSimulationService,
+    session_id: SessionId,
+    delay: Duration,
+    timeout: prost_types::Duration,
+) -> ProcessQueryReply {
+    service
+        .process_query(Request::new(ProcessQueryRequest {
+            source_name: "slow_count".to_string(),
+            request: cbor(&delay),
+            timeout: Some(timeout),
+            session_id,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+/// Requests the simulation time of a session and returns the error, if any.
+async fn time_error(service: &GrpcSimulationService, session_id: SessionId) -> Option<Error> {
+    let reply = service
+        .time(Request::new(TimeRequest { session_id }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    match reply.result {
+        Some(time_reply::Result::Error(error)) => Some(error),
+        _ => None,
+    }
+}
+
+/// Requests the reset of a session.
+async fn reset(service: &GrpcSimulationService, session_id: SessionId) -> reset_reply::Result {
+    service
+        .reset(Request::new(ResetRequest { session_id }))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap()
+}
+
+/// Subscribes to a sink of a session and returns the receiver of the stream.
+async fn stream_sink(
+    service: &GrpcSimulationService,
+    session_id: SessionId,
+    sink_name: &str,
+) -> mpsc::Receiver<Result<StreamSinkReply, Status>> {
+    service
+        .stream_sink(Request::new(StreamSinkRequest {
+            sink_name: sink_name.to_string(),
+            session_id,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .into_inner()
+}
+
+/// Asserts that an error has the expected code.
+fn assert_error_code(error: Option<&Error>, code: ErrorCode) {
+    match error {
+        Some(error) => assert_eq!(error.code, code as i32, "{}", error.message),
+        None => panic!("an error with code {:?} was expected", code),
+    }
+}
+
+#[test]
+fn schedule_event_busy() {
+    const CAPACITY: usize = 2;
+
+    let service = counter_service(CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 0).await;
+        let session = service.session::<InitReply>(session_id).unwrap();
+
+        // Block the thread that processes the scheduling queue.
+        let scheduler_guard = session.scheduler();
+
+        // Fill the queue without waiting for the replies.
+        for _ in 0..CAPACITY {
+            assert!(service
+                .schedule_event(Request::new(schedule_add_request(session_id)))
+                .now_or_never()
+                .is_none());
+        }
+
+        // The queue is full.
+        let reply = service
+            .schedule_event(Request::new(schedule_add_request(session_id)))
+            .await
+            .unwrap()
+            .into_inner();
+        let error = match &reply.result {
+            Some(schedule_event_reply::Result::Error(error)) => Some(error),
+            _ => None,
+        };
+        assert_error_code(error, ErrorCode::Busy);
+
+        // New requests are accepted once the pending requests are processed.
+        drop(scheduler_guard);
+        let mut is_accepted = false;
+        for _ in 0..100 {
+            let reply = service
+                .schedule_event(Request::new(schedule_add_request(session_id)))
+                .await
+                .unwrap()
+                .into_inner();
+            if reply.result == Some(schedule_event_reply::Result::Empty(())) {
+                is_accepted = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(is_accepted);
+
+        // The requests that were queued were processed even though their
+        // replies were never awaited.
+        let reply = service
+            .pending_events(Request::new(PendingEventsRequest { session_id }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            reply.result,
+            Some(pending_events_reply::Result::Count(CAPACITY as u64 + 1))
+        );
+    });
+}
+
+#[test]
+fn init_new_sessions() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id1 = init(&service, 1).await;
+        let session_id2 = init(&service, 2).await;
+
+        // Each new session gets its own identifier and its own bench.
+        assert_ne!(session_id1, DEFAULT_SESSION_ID);
+        assert_ne!(session_id2, DEFAULT_SESSION_ID);
+        assert_ne!(session_id1, session_id2);
+        assert_eq!(query_count(&service, session_id1).await, 1);
+        assert_eq!(query_count(&service, session_id2).await, 2);
+
+        // The default session is left untouched.
+        assert_error_code(
+            time_error(&service, DEFAULT_SESSION_ID).await.as_ref(),
+            ErrorCode::SimulationNotStarted,
+        );
+    });
+}
+
+#[test]
+fn init_default_session() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        // The default session exists before any initialization.
+        assert_error_code(
+            time_error(&service, DEFAULT_SESSION_ID).await.as_ref(),
+            ErrorCode::SimulationNotStarted,
+        );
+
+        // Initialization and re-initialization of the default session.
+        for initial_count in [3, 4] {
+            let reply = service
+                .init(Request::new(InitRequest {
+                    cfg: cbor(&initial_count),
+                    new_session: false,
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(reply.result, Some(init_reply::Result::Empty(())));
+            assert_eq!(reply.session_id, DEFAULT_SESSION_ID);
+            assert_eq!(
+                query_count(&service, DEFAULT_SESSION_ID).await,
+                initial_count
+            );
+        }
+    });
+}
+
+#[test]
+fn unknown_session() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 0).await;
+        let unknown_session_id = session_id + 1;
+
+        assert!(time_error(&service, session_id).await.is_none());
+        assert_error_code(
+            time_error(&service, unknown_session_id).await.as_ref(),
+            ErrorCode::SessionNotFound,
+        );
+
+        let reply = service
+            .schedule_event(Request::new(schedule_add_request(unknown_session_id)))
+            .await
+            .unwrap()
+            .into_inner();
+        let error = match &reply.result {
+            Some(schedule_event_reply::Result::Error(error)) => Some(error),
+            _ => None,
+        };
+        assert_error_code(error, ErrorCode::SessionNotFound);
+    });
+}
+
+#[test]
+fn close_session() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id1 = init(&service, 1).await;
+        let session_id2 = init(&service, 2).await;
+
+        let close = |session_id| {
+            service
+                .close_session(Request::new(CloseSessionRequest { session_id }))
+                .map(|reply| reply.unwrap().into_inner().result)
+        };
+
+        assert_eq!(
+            close(session_id1).await,
+            Some(close_session_reply::Result::Empty(()))
+        );
+
+        // The closed session is gone but the other one is still active.
+        assert_error_code(
+            time_error(&service, session_id1).await.as_ref(),
+            ErrorCode::SessionNotFound,
+        );
+        assert_eq!(query_count(&service, session_id2).await, 2);
+
+        // A session cannot be closed twice.
+        match close(session_id1).await {
+            Some(close_session_reply::Result::Error(error)) => {
+                assert_error_code(Some(&error), ErrorCode::SessionNotFound)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        // Closing the default session only reverts it to an uninitialized
+        // state.
+        let reply = service
+            .init(Request::new(InitRequest {
+                cfg: cbor(&0u64),
+                new_session: false,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.result, Some(init_reply::Result::Empty(())));
+        for _ in 0..2 {
+            assert_eq!(
+                close(DEFAULT_SESSION_ID).await,
+                Some(close_session_reply::Result::Empty(()))
+            );
+            assert_error_code(
+                time_error(&service, DEFAULT_SESSION_ID).await.as_ref(),
+                ErrorCode::SimulationNotStarted,
+            );
+        }
+    });
+}
+
+#[test]
+fn reset_session() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 5).await;
+        let other_session_id = init(&service, 5).await;
+
+        // Alter the state of the counter and of the scheduler.
+        process_add(&service, session_id, 1).await;
+        service
+            .schedule_event(Request::new(schedule_add_request(session_id)))
+            .await
+            .unwrap();
+        service
+            .schedule_event(Request::new(schedule_add_request(session_id)))
+            .await
+            .unwrap();
+        let reply = service
+            .step(Request::new(StepRequest { session_id }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(reply.result, Some(step_reply::Result::Time(_))));
+        assert_eq!(query_count(&service, session_id).await, 8);
+        process_add(&service, other_session_id, 1).await;
+
+        // The bench is rebuilt with the initial configuration.
+        assert_eq!(
+            reset(&service, session_id).await,
+            reset_reply::Result::Time(monotonic_to_timestamp(MonotonicTime::EPOCH).unwrap())
+        );
+        assert_eq!(query_count(&service, session_id).await, 5);
+        let reply = service
+            .pending_events(Request::new(PendingEventsRequest { session_id }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.result, Some(pending_events_reply::Result::Count(0)));
+
+        // Other sessions are not affected.
+        assert_eq!(query_count(&service, other_session_id).await, 6);
+    });
+}
+
+#[test]
+fn reset_errors() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        // The default session was never initialized.
+        match reset(&service, DEFAULT_SESSION_ID).await {
+            reset_reply::Result::Error(error) => {
+                assert_error_code(Some(&error), ErrorCode::SimulationNotStarted)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        // Unknown session.
+        let session_id = init(&service, 0).await;
+        match reset(&service, session_id + 1).await {
+            reset_reply::Result::Error(error) => {
+                assert_error_code(Some(&error), ErrorCode::SessionNotFound)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn stream_sink_events() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 0).await;
+        let mut stream = stream_sink(&service, session_id, "even_count").await;
+
+        process_add(&service, session_id, 2).await;
+        process_add(&service, session_id, 2).await;
+
+        for count in [2u64, 4] {
+            let reply = stream.recv().await.unwrap().unwrap();
+            assert_eq!(
+                reply.result,
+                Some(stream_sink_reply::Result::Event(cbor(&count)))
+            );
+        }
+    });
+}
+
+#[test]
+fn stream_sink_stops_after_error() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 0).await;
+        let mut stream = stream_sink(&service, session_id, "even_count").await;
+
+        // The second event cannot be serialized.
+        process_add(&service, session_id, 2).await;
+        process_add(&service, session_id, 1).await;
+        process_add(&service, session_id, 1).await;
+
+        let reply = stream.recv().await.unwrap().unwrap();
+        assert_eq!(
+            reply.result,
+            Some(stream_sink_reply::Result::Event(cbor(&2u64)))
+        );
+        match stream.recv().await.unwrap().unwrap().result {
+            Some(stream_sink_reply::Result::Error(error)) => {
+                assert_error_code(Some(&error), ErrorCode::InvalidMessage)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        // The stream is closed and the subsequent events are buffered.
+        assert!(stream.recv().await.is_none());
+        let reply = service
+            .read_events(Request::new(ReadEventsRequest {
+                sink_name: "even_count".to_string(),
+                session_id,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.events, vec![cbor(&4u64)]);
+    });
+}
+
+#[test]
+fn stream_sink_errors() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 0).await;
+
+        for (sink_name, code) in [
+            ("count", ErrorCode::SinkNotStreamable),
+            ("unknown", ErrorCode::SinkNotFound),
+        ] {
+            let mut stream = stream_sink(&service, session_id, sink_name).await;
+            match stream.recv().await.unwrap().unwrap().result {
+                Some(stream_sink_reply::Result::Error(error)) => {
+                    assert_error_code(Some(&error), code)
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+            assert!(stream.recv().await.is_none());
+        }
+    });
+}
+
+#[test]
+fn list_sources_registry_version() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+    let (_, registry) = counter_bench(0).unwrap();
+    let expected_version = registry.version();
+
+    runtime().block_on(async {
+        let list_sources = |session_id| {
+            service
+                .list_sources(Request::new(ListSourcesRequest { session_id }))
+                .map(|reply| reply.unwrap().into_inner())
+        };
+
+        // The version does not depend on the configuration of the bench.
+        for initial_count in [0, 1] {
+            let session_id = init(&service, initial_count).await;
+            let reply = list_sources(session_id).await;
+            assert_eq!(reply.result, Some(list_sources_reply::Result::Empty(())));
+            assert_eq!(reply.registry_version, expected_version);
+        }
+
+        // No version is reported if the simulation is not started.
+        let reply = list_sources(DEFAULT_SESSION_ID).await;
+        assert_eq!(reply.registry_version, 0);
+        match reply.result {
+            Some(list_sources_reply::Result::Error(error)) => {
+                assert_error_code(Some(&error), ErrorCode::SimulationNotStarted)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn retry_policy_delays() {
+    let ms = Duration::from_millis;
+
+    let policy = RetryPolicy::new(4, ms(10));
+    assert_eq!(
+        policy.delays().collect::<Vec<_>>(),
+        [ms(10), ms(20), ms(40), ms(80)]
+    );
+
+    let policy = policy.set_max_delay(ms(30));
+    assert_eq!(
+        policy.delays().collect::<Vec<_>>(),
+        [ms(10), ms(20), ms(30), ms(30)]
+    );
+
+    assert_eq!(RetryPolicy::new(0, ms(10)).delays().count(), 0);
+}
+
+#[test]
+fn retry_policy_bind_released_address() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        drop(listener);
+    });
+
+    // The address is eventually released.
+    let policy =
+        RetryPolicy::new(100, Duration::from_millis(5)).set_max_delay(Duration::from_millis(20));
+    assert!(policy.bind(addr).is_ok());
+
+    th.join().unwrap();
+}
+
+#[test]
+fn retry_policy_bind_exhausted() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let policy = RetryPolicy::new(2, Duration::from_millis(5));
+    let start = std::time::Instant::now();
+    let error = policy.bind(addr).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+    // Both retries have been attempted.
+    assert!(start.elapsed() >= Duration::from_millis(15));
+
+    drop(listener);
+}
+
+#[test]
+fn process_query_timeout() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 3).await;
+
+        // The query completes in time.
+        let timeout = prost_types::Duration {
+            seconds: 10,
+            nanos: 0,
+        };
+        let reply = query_slow_count(&service, session_id, Duration::ZERO, timeout).await;
+        assert_eq!(reply.result, Some(process_query_reply::Result::Empty(())));
+        assert_eq!(reply.replies, vec![cbor(&3u64)]);
+
+        // The query times out and the simulation is terminated.
+        let timeout = prost_types::Duration {
+            seconds: 0,
+            nanos: 50_000_000,
+        };
+        let reply =
+            query_slow_count(&service, session_id, Duration::from_millis(500), timeout).await;
+        assert!(reply.replies.is_empty());
+        match reply.result {
+            Some(process_query_reply::Result::Error(error)) => {
+                assert_error_code(Some(&error), ErrorCode::QueryTimeout)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let reply = service
+            .step(Request::new(StepRequest { session_id }))
+            .await
+            .unwrap()
+            .into_inner();
+        match reply.result {
+            Some(step_reply::Result::Error(error)) => {
+                assert_error_code(Some(&error), ErrorCode::SimulationTerminated)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    });
+}
+
+#[test]
+fn process_query_invalid_timeout() {
+    let service = counter_service(SCHEDULING_QUEUE_CAPACITY);
+
+    runtime().block_on(async {
+        let session_id = init(&service, 3).await;
+
+        for (seconds, nanos) in [(0, 0), (-1, 0), (0, -1)] {
+            let timeout = prost_types::Duration { seconds, nanos };
+            let reply = query_slow_count(&service, session_id, Duration::ZERO, timeout).await;
+            match reply.result {
+                Some(process_query_reply::Result::Error(error)) => {
+                    assert_error_code(Some(&error), ErrorCode::InvalidDeadline)
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        // The simulation is still usable.
+        assert_eq!(query_count(&service, session_id).await, 3);
+    });
+}
@@ -8,7 +8,7 @@ use std::time::Duration;
 use prost_types::Timestamp;
 use tai_time::MonotonicTime;
 
-use super::codegen::simulation::{Error, ErrorCode};
+use super::codegen::simulation::*;
 use crate::simulation::{ExecutionError, SchedulingError, SimulationError};
 
 pub(crate) use controller_service::ControllerService;
@@ -32,6 +32,67 @@ fn simulation_not_started_error() -> Error {
     )
 }
 
+/// An error returned when the queue of pending scheduling requests is full.
+fn scheduler_busy_error() -> Error {
+    to_error(
+        ErrorCode::Busy,
+        "too many scheduling requests are pending, try again later",
+    )
+}
+
+/// An error returned when a request targets a session that does not exist or
+/// was closed.
+pub(crate) fn session_not_found_error(session_id: u64) -> Error {
+    to_error(
+        ErrorCode::SessionNotFound,
+        format!("no active session with identifier {}", session_id),
+    )
+}
+
+/// A Protobuf reply that can be built from an error.
+pub(crate) trait ErrorReply {
+    /// Creates a reply containing the specified error.
+    fn from_error(error: Error) -> Self;
+}
+
+macro_rules! impl_error_reply {
+    ($($reply:ident => $reply_mod:ident),* $(,)?) => {
+        $(
+            impl ErrorReply for $reply {
+                #[allow(clippy::needless_update)]
+                fn from_error(error: Error) -> Self {
+                    Self {
+                        result: Some($reply_mod::Result::Error(error)),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_error_reply!(
+    InitReply => init_reply,
+    ResetReply => reset_reply,
+    HaltReply => halt_reply,
+    TimeReply => time_reply,
+    PendingEventsReply => pending_events_reply,
+    StepReply => step_reply,
+    StepUntilReply => step_until_reply,
+    ScheduleEventReply => schedule_event_reply,
+    CancelEventReply => cancel_event_reply,
+    ProcessEventReply => process_event_reply,
+    ProcessEventsReply => process_events_reply,
+    ProcessQueryReply => process_query_reply,
+    ReadEventsReply => read_events_reply,
+    OpenSinkReply => open_sink_reply,
+    CloseSinkReply => close_sink_reply,
+    StreamSinkReply => stream_sink_reply,
+    ListSourcesReply => list_sources_reply,
+    ListSinksReply => list_sinks_reply,
+    CloseSessionReply => close_session_reply,
+);
+
 /// Map an `ExecutionError` to a Protobuf error.
 fn map_execution_error(error: ExecutionError) -> Error {
     let error_code = match error {
@@ -39,12 +100,21 @@ fn map_execution_error(error: ExecutionError) -> Error {
         ExecutionError::MessageLoss(_) => ErrorCode::SimulationMessageLoss,
         ExecutionError::NoRecipient { .. } => ErrorCode::SimulationNoRecipient,
         ExecutionError::Panic { .. } => ErrorCode::SimulationPanic,
+        ExecutionError::ModelPanic { .. } => ErrorCode::ModelPanic,
         ExecutionError::Timeout => ErrorCode::SimulationTimeout,
+        ExecutionError::WallClockTimeout => ErrorCode::SimulationWallClockTimeout,
         ExecutionError::OutOfSync(_) => ErrorCode::SimulationOutOfSync,
+        ExecutionError::ClockAhead(_) => ErrorCode::SimulationClockAhead,
         ExecutionError::BadQuery => ErrorCode::SimulationBadQuery,
-        ExecutionError::Halted => ErrorCode::SimulationHalted,
+        ExecutionError::Halted(_) => ErrorCode::SimulationHalted,
         ExecutionError::Terminated => ErrorCode::SimulationTerminated,
         ExecutionError::InvalidDeadline(_) => ErrorCode::InvalidDeadline,
+        ExecutionError::ModelNotFound(_) => ErrorCode::ModelNotFound,
+        ExecutionError::ModelConnected(_) => ErrorCode::ModelConnected,
+        ExecutionError::WouldBlock => ErrorCode::SimulationWouldBlock,
+        ExecutionError::ValidationFailure(_) => ErrorCode::InvalidModel,
+        ExecutionError::Cancelled => ErrorCode::SimulationCancelled,
+        ExecutionError::InvariantViolated { .. } => ErrorCode::InvariantViolated,
     };
 
     let error_message = error.to_string();
@@ -57,6 +127,9 @@ fn map_scheduling_error(error: SchedulingError) -> Error {
     let error_code = match error {
         SchedulingError::InvalidScheduledTime => ErrorCode::InvalidDeadline,
         SchedulingError::NullRepetitionPeriod => ErrorCode::InvalidPeriod,
+        SchedulingError::NoRealTimeClock => ErrorCode::SimulationNoRealTimeClock,
+        SchedulingError::ExpiredActionKey => ErrorCode::InvalidKey,
+        SchedulingError::NonMonotonicSchedule => ErrorCode::InvalidTime,
     };
 
     let error_message = error.to_string();
@@ -3,13 +3,14 @@ use std::sync::Arc;
 
 use prost_types::Timestamp;
 
-use crate::registry::{EventSourceRegistry, QuerySourceRegistry};
-use crate::simulation::Simulation;
+use crate::registry::{Codec, EventSourceRegistry, QuerySourceRegistry};
+use crate::simulation::{ExecutionError, Simulation};
+use crate::time::SimDuration;
 
 use super::super::codegen::simulation::*;
 use super::{
     map_execution_error, monotonic_to_timestamp, simulation_not_started_error,
-    timestamp_to_monotonic, to_error, to_positive_duration,
+    timestamp_to_monotonic, to_error, to_positive_duration, to_strictly_positive_duration,
 };
 
 /// Protobuf-based simulation controller.
@@ -23,6 +24,8 @@ pub(crate) enum ControllerService {
         simulation: Simulation,
         event_source_registry: Arc<EventSourceRegistry>,
         query_source_registry: QuerySourceRegistry,
+        registry_version: u64,
+        codec: Codec,
     },
 }
 
@@ -93,9 +96,14 @@ impl ControllerService {
                             "the specified deadline lies in the past",
                         ))?;
 
-                        simulation
-                            .step_until(duration)
-                            .map_err(map_execution_error)?;
+                        let time = SimDuration::from(duration)
+                            .checked_add_to(simulation.time())
+                            .ok_or(to_error(
+                                ErrorCode::SimulationTimeOutOfRange,
+                                "the specified deadline is out of range",
+                            ))?;
+
+                        simulation.step_until(time).map_err(map_execution_error)?;
                     }
                 };
 
@@ -126,29 +134,9 @@ impl ControllerService {
             Self::Started {
                 simulation,
                 event_source_registry,
+                codec,
                 ..
-            } => move || -> Result<(), Error> {
-                let source_name = &request.source_name;
-                let event = &request.event;
-
-                let source = event_source_registry.get(source_name).ok_or(to_error(
-                    ErrorCode::SourceNotFound,
-                    "no source is registered with the name '{}'".to_string(),
-                ))?;
-
-                let event = source.event(event).map_err(|e| {
-                    to_error(
-                        ErrorCode::InvalidMessage,
-                        format!(
-                            "the event could not be deserialized as type '{}': {}",
-                            source.event_type_name(),
-                            e
-                        ),
-                    )
-                })?;
-
-                simulation.process(event).map_err(map_execution_error)
-            }(),
+            } => process_event(simulation, event_source_registry, *codec, &request),
             Self::NotStarted => Err(simulation_not_started_error()),
         };
 
@@ -160,6 +148,56 @@ impl ControllerService {
         }
     }
 
+    /// Broadcasts a batch of events from event sources immediately, blocking
+    /// until completion.
+    ///
+    /// Events are processed in order, each event being processed to
+    /// completion before the next one is broadcast. Unless the
+    /// `continue_on_error` flag is set, processing stops at the first error.
+    /// The reply contains one result per processed event, so the index of the
+    /// failed event, if any, is that of the last result.
+    ///
+    /// Simulation time remains unchanged.
+    pub(crate) fn process_events(&mut self, request: ProcessEventsRequest) -> ProcessEventsReply {
+        match self {
+            Self::Started {
+                simulation,
+                event_source_registry,
+                codec,
+                ..
+            } => {
+                let mut replies = Vec::with_capacity(request.requests.len());
+                for event_request in &request.requests {
+                    let result =
+                        process_event(simulation, event_source_registry, *codec, event_request);
+                    let is_err = result.is_err();
+
+                    replies.push(ProcessEventReply {
+                        result: Some(match result {
+                            Ok(()) => process_event_reply::Result::Empty(()),
+                            Err(error) => process_event_reply::Result::Error(error),
+                        }),
+                    });
+
+                    if is_err && !request.continue_on_error {
+                        break;
+                    }
+                }
+
+                ProcessEventsReply {
+                    replies,
+                    result: Some(process_events_reply::Result::Empty(())),
+                }
+            }
+            Self::NotStarted => ProcessEventsReply {
+                replies: Vec::new(),
+                result: Some(process_events_reply::Result::Error(
+                    simulation_not_started_error(),
+                )),
+            },
+        }
+    }
+
     /// Broadcasts a query from a query source immediately, blocking until
     /// completion.
     ///
@@ -169,9 +207,19 @@ impl ControllerService {
             Self::Started {
                 simulation,
                 query_source_registry,
+                codec,
                 ..
             } => move || -> Result<Vec<Vec<u8>>, Error> {
                 let source_name = &request.source_name;
+                let timeout = request
+                    .timeout
+                    .map(|timeout| {
+                        to_strictly_positive_duration(timeout).ok_or(to_error(
+                            ErrorCode::InvalidDeadline,
+                            "the specified query timeout is not strictly positive",
+                        ))
+                    })
+                    .transpose()?;
                 let request = &request.request;
 
                 let source = query_source_registry.get(source_name).ok_or(to_error(
@@ -179,7 +227,7 @@ impl ControllerService {
                     "no source is registered with the name '{}'".to_string(),
                 ))?;
 
-                let (query, mut promise) = source.query(request).map_err(|e| {
+                let (query, mut promise) = source.query(request, *codec).map_err(|e| {
                     to_error(
                         ErrorCode::InvalidMessage,
                         format!(
@@ -190,9 +238,20 @@ impl ControllerService {
                     )
                 })?;
 
-                simulation.process(query).map_err(map_execution_error)?;
+                match timeout {
+                    Some(timeout) => match simulation.process_with_timeout(query, timeout) {
+                        (Err(ExecutionError::Timeout), true) => {
+                            return Err(to_error(
+                                ErrorCode::QueryTimeout,
+                                "the query has failed to complete within the allocated time",
+                            ));
+                        }
+                        (result, _) => result.map_err(map_execution_error)?,
+                    },
+                    None => simulation.process(query).map_err(map_execution_error)?,
+                }
 
-                let replies = promise.take_collect().ok_or(to_error(
+                let replies = promise.take_collect(*codec).ok_or(to_error(
                     ErrorCode::SimulationBadQuery,
                     "a reply to the query was expected but none was available; maybe the target model was not added to the simulation?".to_string(),
                 ))?;
@@ -222,6 +281,82 @@ impl ControllerService {
             },
         }
     }
+
+    /// Lists all registered event and query sources.
+    pub(crate) fn list_sources(&mut self, _request: ListSourcesRequest) -> ListSourcesReply {
+        match self {
+            Self::Started {
+                event_source_registry,
+                query_source_registry,
+                registry_version,
+                ..
+            } => {
+                let mut event_sources: Vec<_> = event_source_registry
+                    .iter()
+                    .map(|(name, source)| EventSourceInfo {
+                        name: name.to_string(),
+                        event_type: source.event_type_name().to_string(),
+                    })
+                    .collect();
+                event_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let mut query_sources: Vec<_> = query_source_registry
+                    .iter()
+                    .map(|(name, source)| QuerySourceInfo {
+                        name: name.to_string(),
+                        request_type: source.request_type_name().to_string(),
+                        reply_type: source.reply_type_name().to_string(),
+                    })
+                    .collect();
+                query_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+                ListSourcesReply {
+                    event_sources,
+                    query_sources,
+                    registry_version: *registry_version,
+                    result: Some(list_sources_reply::Result::Empty(())),
+                }
+            }
+            Self::NotStarted => ListSourcesReply {
+                event_sources: Vec::new(),
+                query_sources: Vec::new(),
+                registry_version: 0,
+                result: Some(list_sources_reply::Result::Error(
+                    simulation_not_started_error(),
+                )),
+            },
+        }
+    }
+}
+
+/// Broadcasts an event from an event source immediately, blocking until
+/// completion.
+fn process_event(
+    simulation: &mut Simulation,
+    event_source_registry: &EventSourceRegistry,
+    codec: Codec,
+    request: &ProcessEventRequest,
+) -> Result<(), Error> {
+    let source_name = &request.source_name;
+    let event = &request.event;
+
+    let source = event_source_registry.get(source_name).ok_or(to_error(
+        ErrorCode::SourceNotFound,
+        "no source is registered with the name '{}'".to_string(),
+    ))?;
+
+    let event = source.event(event, codec).map_err(|e| {
+        to_error(
+            ErrorCode::InvalidMessage,
+            format!(
+                "the event could not be deserialized as type '{}': {}",
+                source.event_type_name(),
+                e
+            ),
+        )
+    })?;
+
+    simulation.process(event).map_err(map_execution_error)
 }
 
 impl fmt::Debug for ControllerService {
@@ -1,4 +1,5 @@
 use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use ciborium;
 use serde::de::DeserializeOwned;
@@ -6,7 +7,7 @@ use serde::de::DeserializeOwned;
 use crate::registry::EndpointRegistry;
 use crate::simulation::{Scheduler, Simulation, SimulationError};
 
-use super::{map_simulation_error, to_error};
+use super::{map_simulation_error, monotonic_to_timestamp, simulation_not_started_error, to_error};
 
 use super::super::codegen::simulation::*;
 
@@ -18,8 +19,12 @@ type SimGen = Box<dyn FnMut(&[u8]) -> Result<InitResult, DeserializationError> +
 ///
 /// An `InitService` creates a new simulation bench based on a serialized
 /// initialization configuration.
+///
+/// The bench generator may be shared between several `InitService`s, one per
+/// simulation session, in which case benches are generated one at a time.
 pub(crate) struct InitService {
-    sim_gen: SimGen,
+    sim_gen: Arc<Mutex<SimGen>>,
+    cfg: Option<Vec<u8>>,
 }
 
 impl InitService {
@@ -42,7 +47,17 @@ impl InitService {
         };
 
         Self {
-            sim_gen: Box::new(sim_gen),
+            sim_gen: Arc::new(Mutex::new(Box::new(sim_gen))),
+            cfg: None,
+        }
+    }
+
+    /// Creates a new `InitService` that shares the bench generator of this
+    /// service but has no initialization configuration.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            sim_gen: self.sim_gen.clone(),
+            cfg: None,
         }
     }
 
@@ -51,7 +66,87 @@ impl InitService {
         &mut self,
         request: InitRequest,
     ) -> (InitReply, Option<(Simulation, Scheduler, EndpointRegistry)>) {
-        let reply = panic::catch_unwind(AssertUnwindSafe(|| (self.sim_gen)(&request.cfg)))
+        let (reply, bench) = match self.build(&request.cfg) {
+            Ok((simulation, registry)) => {
+                self.cfg = Some(request.cfg);
+                let scheduler = simulation.scheduler();
+                (
+                    init_reply::Result::Empty(()),
+                    Some((simulation, scheduler, registry)),
+                )
+            }
+            Err(e) => (init_reply::Result::Error(e), None),
+        };
+
+        (
+            InitReply {
+                result: Some(reply),
+            },
+            bench,
+        )
+    }
+
+    /// Re-initializes the simulation with the configuration of the last
+    /// successful initialization.
+    ///
+    /// The simulation bench is rebuilt from scratch by the bench generator, so
+    /// the state of the new simulation is exactly the same as the state of the
+    /// previous simulation right after its initialization. Upon success, the
+    /// reply contains the start time of the new simulation.
+    ///
+    /// If no simulation was successfully initialized yet, a
+    /// `SimulationNotStarted` error is returned. If the re-initialization
+    /// fails, the previous simulation bench is left in place.
+    pub(crate) fn reset(
+        &mut self,
+        _request: ResetRequest,
+    ) -> (
+        ResetReply,
+        Option<(Simulation, Scheduler, EndpointRegistry)>,
+    ) {
+        let reply = move || -> Result<(Simulation, EndpointRegistry), Error> {
+            let cfg = self.cfg.take().ok_or_else(simulation_not_started_error)?;
+            let bench = self.build(&cfg);
+            self.cfg = Some(cfg);
+
+            bench
+        }();
+
+        let (reply, bench) = match reply {
+            Ok((simulation, registry)) => match monotonic_to_timestamp(simulation.time()) {
+                Some(timestamp) => {
+                    let scheduler = simulation.scheduler();
+                    (
+                        reset_reply::Result::Time(timestamp),
+                        Some((simulation, scheduler, registry)),
+                    )
+                }
+                None => (
+                    reset_reply::Result::Error(to_error(
+                        ErrorCode::SimulationTimeOutOfRange,
+                        "the start time of the simulation is out of range",
+                    )),
+                    None,
+                ),
+            },
+            Err(e) => (reset_reply::Result::Error(e), None),
+        };
+
+        (
+            ResetReply {
+                result: Some(reply),
+            },
+            bench,
+        )
+    }
+
+    /// Builds a simulation bench from a serialized configuration.
+    fn build(&mut self, cfg: &[u8]) -> Result<(Simulation, EndpointRegistry), Error> {
+        // A panic in the generator poisons the mutex but leaves the generator
+        // usable, so poisoning can be ignored.
+        let mut sim_gen = self.sim_gen.lock().unwrap_or_else(PoisonError::into_inner);
+
+        panic::catch_unwind(AssertUnwindSafe(|| (*sim_gen)(cfg)))
             .map_err(|payload| {
                 let panic_msg: Option<&str> = if let Some(s) = payload.downcast_ref::<&str>() {
                     Some(s)
@@ -83,24 +178,6 @@ impl InitService {
                     )
                 })
                 .and_then(|init_result| init_result.map_err(map_simulation_error))
-            });
-
-        let (reply, bench) = match reply {
-            Ok((simulation, registry)) => {
-                let scheduler = simulation.scheduler();
-                (
-                    init_reply::Result::Empty(()),
-                    Some((simulation, scheduler, registry)),
-                )
-            }
-            Err(e) => (init_reply::Result::Error(e), None),
-        };
-
-        (
-            InitReply {
-                result: Some(reply),
-            },
-            bench,
-        )
+            })
     }
 }
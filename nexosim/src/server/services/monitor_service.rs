@@ -1,6 +1,9 @@
 use std::fmt;
 
-use crate::registry::EventSinkRegistry;
+use tokio::sync::mpsc;
+use tonic::Status;
+
+use crate::registry::{Codec, CodecError, EventSinkRegistry};
 
 use super::super::codegen::simulation::*;
 use super::{simulation_not_started_error, to_error};
@@ -12,6 +15,7 @@ use super::{simulation_not_started_error, to_error};
 pub(crate) enum MonitorService {
     Started {
         event_sink_registry: EventSinkRegistry,
+        codec: Codec,
     },
     NotStarted,
 }
@@ -22,6 +26,7 @@ impl MonitorService {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
+                codec,
             } => move || -> Result<Vec<Vec<u8>>, Error> {
                 let sink_name = &request.sink_name;
 
@@ -30,7 +35,7 @@ impl MonitorService {
                     format!("no sink is registered with the name '{}'", sink_name),
                 ))?;
 
-                sink.collect().map_err(|e| {
+                sink.collect(*codec).map_err(|e| {
                     to_error(
                         ErrorCode::InvalidMessage,
                         format!(
@@ -56,11 +61,119 @@ impl MonitorService {
         }
     }
 
+    /// Subscribes to an event sink.
+    ///
+    /// Upon success, the sink is opened and all subsequent events are encoded
+    /// and pushed to the provided channel as they occur. If an event cannot
+    /// be encoded, an error reply is pushed to the channel instead. In this
+    /// case, or if the channel is full because the client does not keep up
+    /// with the event rate, the subscription is cancelled, the sender is
+    /// dropped and the stream is thus closed; the sink then reverts to
+    /// buffering events, which can be read with `read_events`.
+    ///
+    /// If the subscription fails, an error reply is sent to the channel and the
+    /// sender is dropped.
+    pub(crate) fn stream_sink(
+        &mut self,
+        request: StreamSinkRequest,
+        sender: mpsc::Sender<Result<StreamSinkReply, Status>>,
+    ) {
+        let reply = match self {
+            Self::Started {
+                event_sink_registry,
+                codec,
+            } => move || -> Result<(), Error> {
+                let sink_name = &request.sink_name;
+
+                let sink = event_sink_registry.get_mut(sink_name).ok_or(to_error(
+                    ErrorCode::SinkNotFound,
+                    format!("no sink is registered with the name '{}'", sink_name),
+                ))?;
+
+                let event_type_name = sink.event_type_name();
+                let stream_sender = sender.clone();
+                let subscriber = Box::new(move |event: Result<Vec<u8>, CodecError>| {
+                    // The stream is terminated after an error reply.
+                    let (result, is_valid) = match event {
+                        Ok(event) => (stream_sink_reply::Result::Event(event), true),
+                        Err(e) => (
+                            stream_sink_reply::Result::Error(to_error(
+                                ErrorCode::InvalidMessage,
+                                format!(
+                                    "the event could not be serialized from type '{}': {}",
+                                    event_type_name, e
+                                ),
+                            )),
+                            false,
+                        ),
+                    };
+
+                    let is_sent = stream_sender
+                        .try_send(Ok(StreamSinkReply {
+                            result: Some(result),
+                        }))
+                        .is_ok();
+
+                    is_sent && is_valid
+                });
+
+                if !sink.subscribe(*codec, subscriber) {
+                    return Err(to_error(
+                        ErrorCode::SinkNotStreamable,
+                        format!("the sink '{}' does not support streaming", sink_name),
+                    ));
+                }
+                sink.open();
+
+                Ok(())
+            }(),
+            Self::NotStarted => Err(simulation_not_started_error()),
+        };
+
+        if let Err(error) = reply {
+            // The channel was just created so it cannot be full.
+            let _ = sender.try_send(Ok(StreamSinkReply {
+                result: Some(stream_sink_reply::Result::Error(error)),
+            }));
+        }
+    }
+
+    /// Lists all registered event sinks.
+    pub(crate) fn list_sinks(&mut self, _request: ListSinksRequest) -> ListSinksReply {
+        match self {
+            Self::Started {
+                event_sink_registry,
+                ..
+            } => {
+                let mut sinks: Vec<_> = event_sink_registry
+                    .iter()
+                    .map(|(name, sink)| EventSinkInfo {
+                        name: name.to_string(),
+                        event_type: sink.event_type_name().to_string(),
+                    })
+                    .collect();
+                sinks.sort_by(|a, b| a.name.cmp(&b.name));
+
+                ListSinksReply {
+                    sinks,
+                    result: Some(list_sinks_reply::Result::Empty(())),
+                }
+            }
+            Self::NotStarted => ListSinksReply {
+                sinks: Vec::new(),
+                result: Some(list_sinks_reply::Result::Error(
+                    simulation_not_started_error(),
+                )),
+            },
+        }
+    }
+
     /// Opens an event sink.
     pub(crate) fn open_sink(&mut self, request: OpenSinkRequest) -> OpenSinkReply {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
+                ..
             } => {
                 let sink_name = &request.sink_name;
 
@@ -88,6 +201,7 @@ impl MonitorService {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
+                ..
             } => {
                 let sink_name = &request.sink_name;
 
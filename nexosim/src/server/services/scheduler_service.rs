@@ -1,14 +1,15 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::registry::EventSourceRegistry;
+use crate::registry::{Codec, EventSourceRegistry};
 use crate::server::key_registry::{KeyRegistry, KeyRegistryId};
 use crate::simulation::Scheduler;
+use crate::time::SimDuration;
 
 use super::super::codegen::simulation::*;
 use super::{
-    map_scheduling_error, monotonic_to_timestamp, simulation_not_started_error,
-    timestamp_to_monotonic, to_error, to_strictly_positive_duration,
+    map_scheduling_error, monotonic_to_timestamp, scheduler_busy_error,
+    simulation_not_started_error, timestamp_to_monotonic, to_error, to_strictly_positive_duration,
 };
 
 /// Protobuf-based simulation scheduler.
@@ -21,6 +22,7 @@ pub(crate) enum SchedulerService {
         scheduler: Scheduler,
         event_source_registry: Arc<EventSourceRegistry>,
         key_registry: KeyRegistry,
+        codec: Codec,
     },
 }
 
@@ -46,6 +48,38 @@ impl SchedulerService {
         }
     }
 
+    /// Returns the number of pending scheduled events and the time of the
+    /// next one, if any.
+    pub(crate) fn pending_events(&mut self, _request: PendingEventsRequest) -> PendingEventsReply {
+        let (next_event_time, reply) = match self {
+            Self::Started { scheduler, .. } => {
+                let count = scheduler.pending_event_count() as u64;
+                match scheduler.next_event_time().map(monotonic_to_timestamp) {
+                    Some(None) => (
+                        None,
+                        pending_events_reply::Result::Error(to_error(
+                            ErrorCode::SimulationTimeOutOfRange,
+                            "the time of the next event is out of range",
+                        )),
+                    ),
+                    next_event_time => (
+                        next_event_time.flatten(),
+                        pending_events_reply::Result::Count(count),
+                    ),
+                }
+            }
+            Self::NotStarted => (
+                None,
+                pending_events_reply::Result::Error(simulation_not_started_error()),
+            ),
+        };
+
+        PendingEventsReply {
+            next_event_time,
+            result: Some(reply),
+        }
+    }
+
     /// Schedules an event at a future time.
     pub(crate) fn schedule_event(&mut self, request: ScheduleEventRequest) -> ScheduleEventReply {
         let reply = match self {
@@ -53,6 +87,7 @@ impl SchedulerService {
                 scheduler,
                 event_source_registry,
                 key_registry,
+                codec,
             } => move || -> Result<Option<KeyRegistryId>, Error> {
                 let source_name = &request.source_name;
                 let event = &request.event;
@@ -73,15 +108,15 @@ impl SchedulerService {
                 ))?;
 
                 let (action, action_key) = match (with_key, period) {
-                    (false, None) => source.event(event).map(|action| (action, None)),
+                    (false, None) => source.event(event, *codec).map(|action| (action, None)),
                     (false, Some(period)) => source
-                        .periodic_event(period, event)
+                        .periodic_event(period, event, *codec)
                         .map(|action| (action, None)),
                     (true, None) => source
-                        .keyed_event(event)
+                        .keyed_event(event, *codec)
                         .map(|(action, key)| (action, Some(key))),
                     (true, Some(period)) => source
-                        .keyed_periodic_event(period, event)
+                        .keyed_periodic_event(period, event, *codec)
                         .map(|(action, key)| (action, Some(key))),
                 }
                 .map_err(|e| {
@@ -112,7 +147,12 @@ impl SchedulerService {
                             "the specified scheduling deadline is not in the future",
                         ))?;
 
-                        scheduler.time() + duration
+                        SimDuration::from(duration)
+                            .checked_add_to(scheduler.time())
+                            .ok_or(to_error(
+                                ErrorCode::SimulationTimeOutOfRange,
+                                "the specified scheduling deadline is out of range",
+                            ))?
                     }
                 };
 
@@ -152,6 +192,14 @@ impl SchedulerService {
         }
     }
 
+    /// Returns the reply to a scheduling request rejected because too many
+    /// scheduling requests are pending.
+    pub(crate) fn schedule_event_busy_reply() -> ScheduleEventReply {
+        ScheduleEventReply {
+            result: Some(schedule_event_reply::Result::Error(scheduler_busy_error())),
+        }
+    }
+
     /// Cancels a keyed event.
     pub(crate) fn cancel_event(&mut self, request: CancelEventRequest) -> CancelEventReply {
         let reply = match self {
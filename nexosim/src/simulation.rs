@@ -74,41 +74,59 @@
 //!
 //! Deadlocks are reported as [`ExecutionError::Deadlock`] errors, which
 //! identify all involved models and the count of unprocessed messages (events
-//! or requests) in their mailboxes.
+//! or requests) in their mailboxes. Models that are blocked while attempting to
+//! send a message to a saturated mailbox are reported as well, even if their
+//! own mailbox is empty.
+mod description_registry;
+mod interceptor;
 mod mailbox;
+mod panic_registry;
 mod scheduler;
 mod sim_init;
+mod topology;
 
 pub(crate) use scheduler::{
-    GlobalScheduler, KeyedOnceAction, KeyedPeriodicAction, OnceAction, PeriodicAction,
+    ActionInjector, ExternalSignal, GlobalScheduler, HaltSignal, KeyedOnceAction,
+    KeyedPeriodicAction, OnceAction, PeriodicAction, SharedWallClockRef, DEFAULT_PRIORITY,
+    GLOBAL_SCHEDULER_ORIGIN_ID,
 };
 
-pub use mailbox::{Address, Mailbox};
+pub use mailbox::{Address, Mailbox, PortTarget};
 pub use scheduler::{Action, ActionKey, AutoActionKey, Scheduler, SchedulingError};
-pub use sim_init::SimInit;
+pub use sim_init::{ModelEntry, SimConfig, SimInit};
+pub use topology::{Connection, Topology};
+
+pub(crate) use topology::PortLabel;
+
+pub(crate) use description_registry::DescriptionRegistry;
+pub(crate) use interceptor::Interceptors;
+pub(crate) use panic_registry::PanicRegistry;
 
 use std::any::{Any, TypeId};
 use std::cell::Cell;
 use std::error::Error;
 use std::fmt;
 use std::future::Future;
+use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::task::Poll;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{panic, task};
 
+use futures_channel::oneshot;
 use pin_project::pin_project;
 use recycle_box::{coerce_box, RecycleBox};
 
 use scheduler::SchedulerQueue;
 
-use crate::channel::{ChannelObserver, SendError};
+use crate::channel::{ChannelObserver, Observer, SendError};
 use crate::executor::{Executor, ExecutorError, Signal};
 use crate::model::{BuildContext, Context, Model, ProtoModel};
-use crate::ports::{InputFn, ReplierFn};
-use crate::time::{AtomicTime, Clock, Deadline, MonotonicTime, SyncStatus};
+use crate::ports::{InputFn, QuerySource, ReplierFn};
+use crate::time::{AtomicTime, Clock, Deadline, MonotonicTime, SimDuration, SyncStatus};
+use crate::util::rng::{ModelRng, SharedSeed};
 use crate::util::seq_futures::SeqFuture;
 use crate::util::slot;
 
@@ -131,9 +149,12 @@ thread_local! { pub(crate) static CURRENT_MODEL_ID: Cell<ModelId> = const { Cell
 ///
 /// Events and queries can be scheduled immediately, *i.e.* for the current
 /// simulation time, using [`process_event`](Simulation::process_event) and
-/// [`send_query`](Simulation::process_query). Calling these methods will block
-/// until all computations triggered by such event or query have completed. In
-/// the case of queries, the response is returned.
+/// [`process_query`](Simulation::process_query). Calling these methods will
+/// block until all computations triggered by such event or query have
+/// completed. In the case of queries, the response is returned. Queries can be
+/// broadcast to several replier ports with
+/// [`process_query_source`](Simulation::process_query_source), in which case
+/// all responses are returned.
 ///
 /// Events can also be scheduled at a future simulation time using one of the
 /// [`schedule_*`](Scheduler::schedule_event) method. These methods queue an
@@ -157,10 +178,25 @@ pub struct Simulation {
     time: AtomicTime,
     clock: Box<dyn Clock>,
     clock_tolerance: Option<Duration>,
+    wall_clock_ref: SharedWallClockRef,
     timeout: Duration,
-    observers: Vec<(String, Box<dyn ChannelObserver>)>,
+    wall_clock_deadline: Option<Instant>,
+    observers: Vec<ModelObserver>,
+    abort_signal: Signal,
     model_names: Vec<String>,
-    is_halted: Arc<AtomicBool>,
+    rng_seed: SharedSeed,
+    panic_registry: Arc<PanicRegistry>,
+    description_registry: Arc<DescriptionRegistry>,
+    halt_signal: Arc<HaltSignal>,
+    external_signal: Arc<ExternalSignal>,
+    time_hook: Option<Box<dyn FnMut(MonotonicTime) + Send>>,
+    terminate_hooks: Vec<Box<dyn FnOnce(&SimulationReport) + Send>>,
+    // Must be dropped after the executor so the model waiting for the release
+    // is not woken outside the executor.
+    inspection_release: Option<multishot::Sender<()>>,
+    action_count: u64,
+    deterministic_ordering: bool,
+    check_invariants: bool,
     is_terminated: bool,
 }
 
@@ -174,20 +210,43 @@ impl Simulation {
         clock: Box<dyn Clock + 'static>,
         clock_tolerance: Option<Duration>,
         timeout: Duration,
-        observers: Vec<(String, Box<dyn ChannelObserver>)>,
+        wall_clock_deadline: Option<Instant>,
+        observers: Vec<ModelObserver>,
+        abort_signal: Signal,
         model_names: Vec<String>,
-        is_halted: Arc<AtomicBool>,
+        rng_seed: SharedSeed,
+        panic_registry: Arc<PanicRegistry>,
+        description_registry: Arc<DescriptionRegistry>,
+        halt_signal: Arc<HaltSignal>,
+        external_signal: Arc<ExternalSignal>,
+        deterministic_ordering: bool,
+        check_invariants: bool,
     ) -> Self {
+        let wall_clock_ref = Arc::new(Mutex::new(clock.wall_clock_ref()));
+
         Self {
             executor,
             scheduler_queue,
             time,
             clock,
             clock_tolerance,
+            wall_clock_ref,
             timeout,
+            wall_clock_deadline,
             observers,
+            abort_signal,
             model_names,
-            is_halted,
+            rng_seed,
+            panic_registry,
+            description_registry,
+            halt_signal,
+            external_signal,
+            time_hook: None,
+            terminate_hooks: Vec::new(),
+            inspection_release: None,
+            action_count: 0,
+            deterministic_ordering,
+            check_invariants,
             is_terminated: false,
         }
     }
@@ -206,11 +265,227 @@ impl Simulation {
         self.timeout = timeout;
     }
 
+    /// Sets a hook called at the start of each simulation time slice.
+    ///
+    /// The hook is called with the new simulation time by all methods that
+    /// advance the simulation time, such as [`Simulation::step`] or
+    /// [`Simulation::step_until`], each time the simulation moves to the time
+    /// of the next scheduled actions. It is called on the thread controlling
+    /// the simulation, before the clock is synchronized and before the actions
+    /// scheduled for that time are processed. This makes it possible, for
+    /// instance, to synchronize the simulation with an external simulator in
+    /// lock-step.
+    ///
+    /// The simulation time is only updated once the hook returns, so that it
+    /// is left unchanged if the hook cancels all the actions that were due. If
+    /// the hook schedules or cancels actions such that the next scheduled
+    /// actions move to another time, the hook is called again with that time.
+    ///
+    /// Methods that do not advance the simulation time, such as
+    /// [`Simulation::process_event`], do not call the hook. Likewise, the hook
+    /// is not called when the simulation time is moved to a deadline for which
+    /// no action is scheduled.
+    ///
+    /// Any previously set hook is replaced.
+    pub fn set_time_hook(&mut self, hook: impl FnMut(MonotonicTime) + Send + 'static) {
+        self.time_hook = Some(Box::new(hook));
+    }
+
+    /// Adds a hook called by [`Simulation::finish`] once the simulation has
+    /// run to completion.
+    ///
+    /// The hook is called with the final report of the simulation, before the
+    /// models are dropped. It can be used, for instance, to collect the final
+    /// value of observables read from event sinks or to flush logs. Hooks are
+    /// called in the order in which they were added.
+    ///
+    /// Hooks are not called if [`Simulation::finish`] returns an error or if
+    /// the simulation is dropped without calling [`Simulation::finish`].
+    pub fn add_terminate_hook(&mut self, hook: impl FnOnce(&SimulationReport) + Send + 'static) {
+        self.terminate_hooks.push(Box::new(hook));
+    }
+
     /// Returns the current simulation time.
     pub fn time(&self) -> MonotonicTime {
         self.time.read()
     }
 
+    /// Returns the number of pending scheduled events.
+    ///
+    /// Cancelled events are not counted and periodic events are counted once.
+    ///
+    /// See also [`Scheduler::pending_event_count`].
+    pub fn pending_event_count(&self) -> usize {
+        scheduler::pending_action_count(&self.scheduler_queue.lock().unwrap())
+    }
+
+    /// Returns `true` if the simulation bench is quiescent.
+    ///
+    /// The bench is quiescent when no actions remain in the scheduler queue,
+    /// all model mailboxes are empty and no model is being executed. Since
+    /// models are only executed during a call to a `Simulation` method, the
+    /// latter condition is always fulfilled when this method is called.
+    ///
+    /// Note that a [`Scheduler`] handle used concurrently from another thread
+    /// may schedule new events at any time, so the result may be outdated as
+    /// soon as it is returned unless all such handles are known to be inactive.
+    pub fn is_quiescent(&self) -> bool {
+        self.is_idle() && self.observers.iter().all(|entry| entry.observer.len() == 0)
+    }
+
+    /// Returns a report on the current state of the simulation.
+    ///
+    /// This method has no effect on the simulation.
+    pub fn report(&self) -> SimulationReport {
+        SimulationReport {
+            time: self.time.read(),
+            action_count: self.action_count,
+            model_count: self.model_names.len(),
+        }
+    }
+
+    /// Runs the simulation to completion and returns a final report.
+    ///
+    /// All scheduled actions are processed as if by calling
+    /// [`Simulation::step_unbounded`] before the report is generated. The
+    /// hooks added with [`Simulation::add_terminate_hook`] are then called
+    /// with the report, after which the simulation is consumed, which drops
+    /// all models. Use [`Simulation::report`] to get a report without stepping
+    /// the simulation.
+    ///
+    /// The report does not include the values of observables since these are
+    /// read from event sinks that are owned by the caller; a terminate hook
+    /// can be used to collect them together with the report.
+    ///
+    /// Note that this method never returns if the simulation is kept alive
+    /// indefinitely, for instance by a periodic action.
+    pub fn finish(mut self) -> Result<SimulationReport, ExecutionError> {
+        self.step_unbounded()?;
+
+        let report = self.report();
+        for hook in self.terminate_hooks.drain(..) {
+            hook(&report);
+        }
+
+        Ok(report)
+    }
+
+    /// Returns the connection graph between the models of the simulation.
+    ///
+    /// All connections from an output or requestor port to an input or
+    /// replier port of a model of the simulation are reported, irrespective of
+    /// whether they were already used, as long as the source port or one of its
+    /// clones is alive. The source ports are identified by their label (see
+    /// [`Connection`]). Connections from event sources, query sources and to
+    /// event sinks are not reported.
+    ///
+    /// This method has no effect on the simulation.
+    pub fn topology(&self) -> Topology {
+        let connections =
+            self.observers
+                .iter()
+                .flat_map(|entry| {
+                    entry.observer.connections().into_iter().map(
+                        |(source_port, port, event_type)| Connection {
+                            source_port,
+                            target: entry.name.clone(),
+                            port,
+                            event_type,
+                        },
+                    )
+                })
+                .collect();
+
+        Topology::new(connections)
+    }
+
+    /// Returns a manifest of the configuration of all models.
+    ///
+    /// The manifest is a JSON object which maps the fully qualified names of
+    /// all models that reported a configuration snapshot with
+    /// [`Model::describe`] to their snapshot. It is meant to be attached to
+    /// simulation results to document the parameters of the run. If several
+    /// models share the same name, only the snapshot of the last added model
+    /// is kept.
+    ///
+    /// This method has no effect on the simulation.
+    #[cfg(feature = "manifest")]
+    pub fn manifest(&self) -> serde_json::Value {
+        self.description_registry.manifest(&self.model_names)
+    }
+
+    /// Returns the connection graph between the models of the simulation in
+    /// the Graphviz DOT format.
+    ///
+    /// Nodes are labeled by model name or by source port label, and edges by
+    /// the type of the events or requests carried by the connection. See
+    /// [`Simulation::topology`] for the connections that are reported.
+    ///
+    /// This method has no effect on the simulation.
+    pub fn to_dot(&self) -> String {
+        self.topology().to_dot(&self.model_names)
+    }
+
+    /// Replaces the simulation clock.
+    ///
+    /// The new clock is immediately synchronized with the current simulation
+    /// time, which makes it possible for instance to run a simulation as fast
+    /// as possible during a warm-up phase with a [`NoClock`] and to then
+    /// switch to real time with an [`AutoSystemClock`], which takes the
+    /// current simulation time as its reference upon first synchronization.
+    /// Clocks with a predefined reference, such as a
+    /// [`SystemClock`](crate::time::SystemClock), must be given a reference
+    /// consistent with the current simulation time: if the current simulation
+    /// time would only be reached by the new clock in the future of the wall
+    /// clock by more than the tolerance specified with
+    /// [`SimInit::set_clock_tolerance`] (or at all if no tolerance was
+    /// specified), the clock is rejected with an
+    /// [`ExecutionError::ClockAhead`] error and the previous clock is kept.
+    ///
+    /// Since this method requires exclusive access to the simulation, the
+    /// clock can only be replaced between steps and never in the middle of a
+    /// time slice. Wall clock deadlines subsequently requested with
+    /// [`Scheduler::schedule_at_wall_clock`] are translated to simulation time
+    /// using the reference of the new clock, including for schedulers obtained
+    /// before the replacement.
+    ///
+    /// If a synchronization tolerance was specified with
+    /// [`SimInit::set_clock_tolerance`], an [`ExecutionError::OutOfSync`]
+    /// error is returned if the new clock lags behind the simulation time by
+    /// more than the tolerance.
+    ///
+    /// [`NoClock`]: crate::time::NoClock
+    /// [`AutoSystemClock`]: crate::time::AutoSystemClock
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        // Reject clocks which would stall the simulation until their
+        // reference catches up with the current simulation time.
+        if let Some((simulation_ref, wall_clock_ref)) = clock.wall_clock_ref() {
+            let now = self.time.read();
+            let target = SimDuration::between(simulation_ref, now)
+                .and_then(|elapsed| wall_clock_ref.checked_add(elapsed.into()));
+            if let Some(target) = target {
+                if let Some(lead) = target.checked_duration_since(Instant::now()) {
+                    if lead > self.clock_tolerance.unwrap_or(Duration::ZERO) {
+                        return Err(ExecutionError::ClockAhead(lead));
+                    }
+                }
+            }
+        }
+
+        self.clock = Box::new(clock);
+        let result = self.synchronize_clock(self.time.read());
+
+        // The clock reference is only queried after the first synchronization
+        // since some clocks are initialized at this point.
+        *self.wall_clock_ref.lock().unwrap() = self.clock.wall_clock_ref();
+
+        result
+    }
+
     /// Advances simulation time to that of the next scheduled event, processing
     /// that event as well as all other events scheduled for the same time.
     ///
@@ -221,6 +496,53 @@ impl Simulation {
         self.step_to_next(None).map(|_| ())
     }
 
+    /// Advances simulation time to that of the next scheduled event like
+    /// [`Simulation::step`], returning a report of the processed time slice.
+    ///
+    /// If no event is scheduled, the simulation time is left unchanged and the
+    /// returned report contains the current simulation time and null event and
+    /// query counts.
+    pub fn step_observed(&mut self) -> Result<StepReport, ExecutionError> {
+        let report = self.step_to_next(None)?.unwrap_or(StepReport {
+            time: self.time.read(),
+            event_count: 0,
+            query_count: 0,
+        });
+
+        Ok(report)
+    }
+
+    /// Attempts to advance simulation time to that of the next scheduled event
+    /// like [`Simulation::step_observed`], but only if the step need not wait
+    /// for the clock.
+    ///
+    /// If the configured clock is a real-time clock (see
+    /// [`Clock::wall_clock_ref`]) and the wall clock time corresponding to the
+    /// next scheduled event has not been reached yet, the simulation is left
+    /// unchanged and an [`ExecutionError::WouldBlock`] error is returned. This
+    /// makes it possible to integrate a simulation into an external event loop,
+    /// such as that of a GUI, by polling it cooperatively. Clocks that do not
+    /// provide a wall clock reference are assumed to never block.
+    ///
+    /// This is a best-effort method: once started, the step runs to completion
+    /// like [`Simulation::step`], so the call still blocks while the models
+    /// process the events, including when a model waits for room in a full
+    /// mailbox. The time needed to do so is not bounded, particularly with the
+    /// multi-threaded executor where the models run concurrently on other
+    /// threads. Likewise, another thread may schedule an earlier event between
+    /// the check and the step, in which case the step is performed anyway.
+    pub fn try_step(&mut self) -> Result<StepReport, ExecutionError> {
+        if let Some(next_time) = self.next_action_time() {
+            if let Some(wall_time) = self.wall_time(next_time) {
+                if wall_time > Instant::now() {
+                    return Err(ExecutionError::WouldBlock);
+                }
+            }
+        }
+
+        self.step_observed()
+    }
+
     /// Iteratively advances the simulation time until the specified deadline,
     /// as if by calling [`Simulation::step`] repeatedly.
     ///
@@ -240,18 +562,243 @@ impl Simulation {
     /// Iteratively advances the simulation time, as if by calling
     /// [`Simulation::step`] repeatedly.
     ///
-    /// This method blocks until all events scheduled have completed.
+    /// This method blocks until all events scheduled have completed. If
+    /// [`ExternalEventSource`](crate::ports::ExternalEventSource)s were
+    /// created for the simulation, however, the simulation does not return
+    /// once idle but waits for events sent by these sources; it only returns
+    /// once idle after all external event sources have been dropped.
     pub fn step_unbounded(&mut self) -> Result<(), ExecutionError> {
-        self.step_until_unchecked(None)
+        loop {
+            self.step_until_unchecked(None)?;
+            if !self.external_signal.wait() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Iteratively advances the simulation time until either no more events
+    /// are scheduled or the specified token is cancelled, as if by calling
+    /// [`Simulation::step`] repeatedly.
+    ///
+    /// The token is checked between time slices, including before the first
+    /// one: a time slice during which the token is cancelled runs to
+    /// completion, but no further time slice is processed and
+    /// [`ExecutionError::Cancelled`] is returned. Unlike a halt requested with
+    /// [`Scheduler::halt`], a cancellation is not a fatal error: the
+    /// simulation can be resumed with another token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    ///
+    /// use nexosim::simulation::{CancellationToken, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// let (mut simu, _scheduler) = SimInit::new().init(MonotonicTime::EPOCH).unwrap();
+    ///
+    /// let token = CancellationToken::new();
+    /// let handle = token.clone();
+    /// thread::spawn(move || {
+    ///     // ... wait for some external condition ...
+    ///     handle.cancel();
+    /// });
+    ///
+    /// // Returns when idle or when cancelled by the other thread.
+    /// let _ = simu.step_unbounded_cancellable(&token);
+    /// ```
+    pub fn step_unbounded_cancellable(
+        &mut self,
+        token: &CancellationToken,
+    ) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        loop {
+            if token.is_cancelled() {
+                return Err(ExecutionError::Cancelled);
+            }
+            if self.step_to_next(None)?.is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Iteratively advances the simulation time until either no more events
+    /// are scheduled or the specified wall clock deadline is reached,
+    /// whichever comes first, as if by calling [`Simulation::step`]
+    /// repeatedly.
+    ///
+    /// [`StepOutcome::Idle`] is returned if all scheduled events have
+    /// completed and [`StepOutcome::DeadlineReached`] if the wall clock
+    /// deadline was reached. In both cases, the simulation time upon
+    /// completion is that of the last processed event.
+    ///
+    /// The deadline is only checked between time slices: a time slice that is
+    /// started before the deadline runs to completion even if this takes the
+    /// wall clock past the deadline. If the configured clock is a real-time
+    /// clock (see [`Clock::wall_clock_ref`]), however, no time slice is started
+    /// if its wall clock time lies beyond the deadline, so the method does not
+    /// wait for the clock past the deadline.
+    pub fn step_unbounded_until(
+        &mut self,
+        wall_deadline: Instant,
+    ) -> Result<StepOutcome, ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        loop {
+            if Instant::now() >= wall_deadline {
+                return Ok(StepOutcome::DeadlineReached);
+            }
+            let next_time = match self.next_action_time() {
+                Some(time) => time,
+                None => return Ok(StepOutcome::Idle),
+            };
+            if let Some(wall_time) = self.wall_time(next_time) {
+                if wall_time > wall_deadline {
+                    return Ok(StepOutcome::DeadlineReached);
+                }
+            }
+
+            self.step_to_next(None)?;
+        }
+    }
+
+    /// Iteratively advances the simulation time until either no more events
+    /// are scheduled or the specified deadline is reached, whichever comes
+    /// first, as if by calling [`Simulation::step`] repeatedly.
+    ///
+    /// If all scheduled events complete before the deadline, the simulation
+    /// time upon completion is that of the last processed event and
+    /// [`StepOutcome::Idle`] is returned. Otherwise, the simulation time upon
+    /// completion is equal to the deadline and [`StepOutcome::DeadlineReached`]
+    /// is returned.
+    ///
+    /// As with [`Simulation::step_until`], processing is gated by the
+    /// configured simulation clock.
+    pub fn step_until_idle_or(
+        &mut self,
+        deadline: impl Deadline,
+    ) -> Result<StepOutcome, ExecutionError> {
+        let now = self.time.read();
+        let target_time = deadline.into_time(now);
+        if target_time < now {
+            return Err(ExecutionError::InvalidDeadline(target_time));
+        }
+
+        loop {
+            match self
+                .step_to_next(Some(target_time))?
+                .map(|report| report.time)
+            {
+                // The target time was reached exactly.
+                Some(time) if time == target_time => return Ok(StepOutcome::DeadlineReached),
+                // No actions are scheduled before or at the target time.
+                None => {
+                    if self.is_idle() {
+                        return Ok(StepOutcome::Idle);
+                    }
+
+                    return self
+                        .step_until_unchecked(Some(target_time))
+                        .map(|()| StepOutcome::DeadlineReached);
+                }
+                // The target time was not reached yet.
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Iteratively advances the simulation time as long as the specified
+    /// condition holds, as if by calling [`Simulation::step`] repeatedly.
+    ///
+    /// The condition is evaluated before each time slice, including before
+    /// the first one. [`StepOutcome::Interrupted`] is returned as soon as the
+    /// condition evaluates to `false`, and [`StepOutcome::Idle`] if all
+    /// scheduled events have completed while the condition still held. In
+    /// both cases, the simulation time upon completion is that of the last
+    /// processed event.
+    ///
+    /// The condition only sees the state of the simulation at time slice
+    /// boundaries, never in the middle of a time slice: a time slice during
+    /// which the monitored state crosses a threshold always runs to
+    /// completion, but no further time slice is processed. The state of the
+    /// models can be observed by the condition through endpoints such as
+    /// [`EventSlot`](crate::ports::EventSlot)s captured by the closure.
+    ///
+    /// As with [`Simulation::step`], processing is gated by the configured
+    /// simulation clock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::simulation::{SimInit, StepOutcome};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// let t0 = MonotonicTime::EPOCH;
+    /// let (mut simu, _scheduler) = SimInit::new().init(t0).unwrap();
+    ///
+    /// // No event is scheduled so the simulation is idle right away.
+    /// let outcome = simu
+    ///     .step_while(|simu| simu.time() < t0 + Duration::from_secs(10))
+    ///     .unwrap();
+    /// assert_eq!(outcome, StepOutcome::Idle);
+    /// ```
+    pub fn step_while(
+        &mut self,
+        mut condition: impl FnMut(&Simulation) -> bool,
+    ) -> Result<StepOutcome, ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        loop {
+            if !condition(self) {
+                return Ok(StepOutcome::Interrupted);
+            }
+            if self.step_to_next(None)?.is_none() {
+                return Ok(StepOutcome::Idle);
+            }
+        }
+    }
+
+    /// Processes an action immediately like [`Simulation::process`], with a
+    /// specific timeout.
+    ///
+    /// The specified timeout only applies if it is shorter than the timeout
+    /// of the simulation, if any. The returned boolean is `true` if the
+    /// specified timeout was applied.
+    #[cfg(feature = "server")]
+    pub(crate) fn process_with_timeout(
+        &mut self,
+        action: Action,
+        timeout: Duration,
+    ) -> (Result<(), ExecutionError>, bool) {
+        if !self.timeout.is_zero() && self.timeout <= timeout {
+            return (self.process(action), false);
+        }
+
+        let timeout = std::mem::replace(&mut self.timeout, timeout);
+        let result = self.process(action);
+        self.timeout = timeout;
+
+        (result, true)
     }
 
     /// Processes an action immediately, blocking until completion.
     ///
     /// Simulation time remains unchanged. The periodicity of the action, if
-    /// any, is ignored.
+    /// any, is ignored. If invariant checking is enabled, the invariants of all
+    /// models are checked once the action has completed.
     pub fn process(&mut self, action: Action) -> Result<(), ExecutionError> {
         action.spawn_and_forget(&self.executor);
-        self.run()
+        self.run()?;
+        self.verify_invariants()
     }
 
     /// Processes an event immediately, blocking until completion.
@@ -286,7 +833,8 @@ impl Simulation {
         };
 
         self.executor.spawn_and_forget(fut);
-        self.run()
+        self.run()?;
+        self.verify_invariants()
     }
 
     /// Processes a query immediately, blocking until completion.
@@ -330,37 +878,383 @@ impl Simulation {
 
         self.executor.spawn_and_forget(fut);
         self.run()?;
+        self.verify_invariants()?;
 
         reply_reader
             .try_read()
             .map_err(|_| ExecutionError::BadQuery)
     }
 
+    /// Broadcasts a query immediately to all replier ports connected to a
+    /// query source, blocking until completion.
+    ///
+    /// Simulation time remains unchanged. The replies are returned in the
+    /// order in which the replier ports were connected to the source. If the
+    /// query source is not connected to any replier port, an
+    /// [`ExecutionError::BadQuery`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::QuerySource;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// pub struct Tank {
+    ///     level: f64,
+    /// }
+    /// impl Tank {
+    ///     pub async fn level(&mut self) -> f64 {
+    ///         self.level
+    ///     }
+    /// }
+    /// impl Model for Tank {}
+    ///
+    /// let tank1_mbox = Mailbox::new();
+    /// let tank2_mbox = Mailbox::new();
+    /// let mut levels = QuerySource::new();
+    /// levels.connect(Tank::level, &tank1_mbox);
+    /// levels.connect(Tank::level, &tank2_mbox);
+    ///
+    /// let (mut simu, _scheduler) = SimInit::new()
+    ///     .add_model(Tank { level: 1.5 }, tank1_mbox, "tank1")
+    ///     .add_model(Tank { level: 2.0 }, tank2_mbox, "tank2")
+    ///     .init(MonotonicTime::EPOCH)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(simu.process_query_source(&levels, ()).unwrap(), vec![1.5, 2.0]);
+    /// ```
+    pub fn process_query_source<T, R>(
+        &mut self,
+        source: &QuerySource<T, R>,
+        arg: T,
+    ) -> Result<Vec<R>, ExecutionError>
+    where
+        T: Clone + Send + 'static,
+        R: Send + 'static,
+    {
+        if source.connection_count() == 0 {
+            return Err(ExecutionError::BadQuery);
+        }
+
+        let (action, mut reply_receiver) = source.query(arg);
+        self.process(action)?;
+
+        reply_receiver
+            .take()
+            .map(|replies| replies.collect())
+            .ok_or(ExecutionError::BadQuery)
+    }
+
+    /// Adds a model and its mailbox to a started simulation.
+    ///
+    /// The model is built and its [`Model::init`] method is executed
+    /// immediately, at the current simulation time: any event scheduled by
+    /// `init` is thus scheduled relative to the time at which the model was
+    /// added rather than relative to the start time of the simulation. The
+    /// address of the model is returned once initialization has completed.
+    ///
+    /// Since the models already in the simulation cannot be accessed anymore,
+    /// connections to the new model can only be made beforehand through the
+    /// ports of the new model or through ports that were kept by the caller,
+    /// such as [`EventSource`](crate::ports::EventSource)s.
+    ///
+    /// The `name` argument follows the same rules as for
+    /// [`SimInit::add_model`]. If the validation of the model or of any of its
+    /// submodels fails (see [`ProtoModel::validate`]), an
+    /// [`ExecutionError::ValidationFailure`] error is returned and the model
+    /// is dropped without being initialized.
+    pub fn add_model<P: ProtoModel>(
+        &mut self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+    ) -> Result<Address<P::Model>, ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let mut name = name.into();
+        if name.is_empty() {
+            name = String::from("<unknown>");
+        };
+        let address = mailbox.address();
+        let scheduler = GlobalScheduler::new(
+            self.scheduler_queue.clone(),
+            self.time.reader(),
+            self.halt_signal.clone(),
+        );
+
+        let mut pending_models = PendingModels::default();
+        let mut validation_failures = Vec::new();
+        add_model(
+            model,
+            mailbox,
+            name,
+            scheduler,
+            &mut pending_models,
+            &self.abort_signal,
+            &mut self.model_names,
+            &self.rng_seed,
+            &self.panic_registry,
+            &self.description_registry,
+            self.deterministic_ordering,
+            false,
+            None,
+            &mut validation_failures,
+        );
+        if !validation_failures.is_empty() {
+            return Err(ExecutionError::ValidationFailure(validation_failures));
+        }
+
+        pending_models.spawn_all(&self.executor, &mut self.observers);
+        self.run()?;
+
+        Ok(address)
+    }
+
+    /// Removes a model from a started simulation.
+    ///
+    /// The model is designated by the name provided to
+    /// [`SimInit::add_model`] or [`Simulation::add_model`]; if several models
+    /// share the same name, the model that was added first is removed.
+    /// Submodels cannot be removed individually, but are removed together
+    /// with their parent model.
+    ///
+    /// Removal is only possible if no other entity holds the address of the
+    /// model, which means that all output ports, requestors, event sources and
+    /// query sources connected to the model must have been dropped and that no
+    /// event or query targeting the model may remain scheduled, including
+    /// events scheduled by the model itself. Otherwise, an
+    /// [`ExecutionError::ModelConnected`] error is returned and the model
+    /// remains in the simulation. Connections to submodels are not checked
+    /// since they are normally internal to the parent model.
+    ///
+    /// Upon removal the mailboxes of the model and of its submodels are
+    /// closed, [`Model::terminate`] is called on each of them and the models
+    /// are then dropped. Since this method can only be called between
+    /// simulation steps, the mailbox of the model is always empty at this
+    /// point and no message is lost.
+    pub fn remove_model(&mut self, name: &str) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let start = self
+            .observers
+            .iter()
+            .position(|entry| !entry.is_submodel && entry.name == name)
+            .ok_or_else(|| ExecutionError::ModelNotFound(name.to_string()))?;
+
+        // The only address that may remain is that held by the model's own
+        // context.
+        if self.observers[start].observer.sender_count() > 1 {
+            return Err(ExecutionError::ModelConnected(name.to_string()));
+        }
+
+        // Submodels are registered right after their parent model.
+        let end = self.observers[start + 1..]
+            .iter()
+            .position(|entry| !entry.is_submodel)
+            .map_or(self.observers.len(), |len| start + 1 + len);
+
+        // Closing the mailboxes makes the model tasks terminate the models
+        // and return once woken up.
+        for entry in self.observers.drain(start..end) {
+            entry.observer.close();
+        }
+
+        self.run()
+    }
+
+    /// Gives read-only access to a model between simulation steps.
+    ///
+    /// The model is designated by the name provided to [`SimInit::add_model`]
+    /// or [`Simulation::add_model`]; if several models share the same name,
+    /// the model that was added first is inspected. Submodels cannot be
+    /// inspected. An [`ExecutionError::ModelNotFound`] error is returned if no
+    /// model with this name and type was found.
+    ///
+    /// The returned guard mutably borrows the simulation, so it is statically
+    /// guaranteed that no simulation step can be in progress while the model
+    /// is inspected. In practice, the model is parked while processing a
+    /// dedicated message until the simulation is resumed. Messages that were
+    /// sent to the model remain in its mailbox and are processed once the
+    /// simulation is resumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// #[derive(Default)]
+    /// pub struct Counter {
+    ///     count: u64,
+    /// }
+    /// impl Counter {
+    ///     pub fn increment(&mut self) {
+    ///         self.count += 1;
+    ///     }
+    /// }
+    /// impl Model for Counter {}
+    ///
+    /// let mbox = Mailbox::new();
+    /// let addr = mbox.address();
+    /// let (mut simu, _scheduler) = SimInit::new()
+    ///     .add_model(Counter::default(), mbox, "counter")
+    ///     .init(MonotonicTime::EPOCH)
+    ///     .unwrap();
+    ///
+    /// simu.process_event(Counter::increment, (), &addr).unwrap();
+    ///
+    /// let counter = simu.inspect_model::<Counter>("counter").unwrap();
+    /// assert_eq!(counter.count, 1);
+    /// ```
+    pub fn inspect_model<M: Model>(
+        &mut self,
+        name: &str,
+    ) -> Result<ModelGuard<'_, M>, ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let sender = self
+            .observers
+            .iter()
+            .find(|entry| !entry.is_submodel && entry.name == name)
+            .and_then(|entry| entry.observer.as_any().downcast_ref::<Observer<M>>())
+            .map(|observer| observer.sender())
+            .ok_or_else(|| ExecutionError::ModelNotFound(name.to_string()))?;
+
+        /// A pointer to a model parked for inspection.
+        struct ModelPtr<M>(*const M);
+
+        // Safety: the pointer is only dereferenced by the simulation thread
+        // while the model is parked.
+        unsafe impl<M: Send> Send for ModelPtr<M> {}
+
+        let (ptr_writer, mut ptr_reader) = slot::slot();
+        let mut release_receiver = multishot::Receiver::new();
+        let release_sender = release_receiver.sender().unwrap();
+
+        let fut = async move {
+            // Ignore send errors.
+            let _ = sender
+                .send(
+                    move |model: &mut M,
+                          _,
+                          recycle_box: RecycleBox<()>|
+                          -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                        let fut = async move {
+                            let _ = ptr_writer.write(ModelPtr(&*model as *const M));
+
+                            // Park the model until the simulation is resumed.
+                            let _ = release_receiver.recv().await;
+                        };
+
+                        coerce_box!(RecycleBox::recycle(recycle_box, fut))
+                    },
+                )
+                .await;
+        };
+
+        self.executor.spawn_and_forget(fut);
+        let result = self.run();
+
+        // The release signal must not be dropped before the executor, even if
+        // the model could not be parked.
+        self.inspection_release = Some(release_sender);
+        result?;
+
+        let ptr = ptr_reader
+            .try_read()
+            .map_err(|_| ExecutionError::ModelNotFound(name.to_string()))?;
+
+        // Safety: the model is parked until the release signal is sent, which
+        // only happens when the executor is run again. This requires a
+        // mutable borrow of the simulation, which is held by the guard.
+        let model = unsafe { &*ptr.0 };
+
+        Ok(ModelGuard { model })
+    }
+
     /// Runs the executor.
     fn run(&mut self) -> Result<(), ExecutionError> {
+        self.run_with(false)
+    }
+
+    /// Runs the executor, optionally distributing all spawned tasks upfront
+    /// across the worker threads.
+    fn run_with(&mut self, is_distributed: bool) -> Result<(), ExecutionError> {
+        // Release the model parked for inspection, if any.
+        if let Some(release) = self.inspection_release.take() {
+            self.executor.spawn_and_forget(async move {
+                release.send(());
+            });
+        }
+
         if self.is_terminated {
             return Err(ExecutionError::Terminated);
         }
 
-        if self.is_halted.load(Ordering::Relaxed) {
+        if let Some(reason) = self.halt_signal.reason() {
             self.is_terminated = true;
-            return Err(ExecutionError::Halted);
+            return Err(ExecutionError::Halted(reason));
+        }
+
+        // The wall clock limit is enforced by the executor as a timeout
+        // whenever the remaining budget is shorter than the step timeout.
+        let mut timeout = self.timeout;
+        let mut is_wall_clock_limited = false;
+        if let Some(deadline) = self.wall_clock_deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                self.is_terminated = true;
+                return Err(ExecutionError::WallClockTimeout);
+            }
+            if timeout.is_zero() || remaining < timeout {
+                timeout = remaining;
+                is_wall_clock_limited = true;
+            }
         }
 
-        self.executor.run(self.timeout).map_err(|e| {
+        let result = if is_distributed {
+            self.executor.run_distributed(timeout)
+        } else {
+            self.executor.run(timeout)
+        };
+
+        result.map_err(|e| {
             self.is_terminated = true;
 
             match e {
                 ExecutorError::UnprocessedMessages(msg_count) => {
                     let mut deadlock_info = Vec::new();
-                    for (model, observer) in &self.observers {
-                        let mailbox_size = observer.len();
+                    let mut blocked_senders = Vec::new();
+                    for entry in &self.observers {
+                        let mailbox_size = entry.observer.len();
                         if mailbox_size != 0 {
                             deadlock_info.push(DeadlockInfo {
-                                model: model.clone(),
+                                model: entry.name.clone(),
                                 mailbox_size,
                             });
                         }
+                        blocked_senders.extend(entry.observer.blocked_senders());
+                    }
+
+                    // Models blocked on a full mailbox are also involved even
+                    // though their own mailbox may be empty.
+                    for model_id in blocked_senders {
+                        let model = &self.model_names[model_id];
+                        if !deadlock_info.iter().any(|info| &info.model == model) {
+                            deadlock_info.push(DeadlockInfo {
+                                model: model.clone(),
+                                mailbox_size: 0,
+                            });
+                        }
                     }
 
                     if deadlock_info.is_empty() {
@@ -369,6 +1263,7 @@ impl Simulation {
                         ExecutionError::Deadlock(deadlock_info)
                     }
                 }
+                ExecutorError::Timeout if is_wall_clock_limited => ExecutionError::WallClockTimeout,
                 ExecutorError::Timeout => ExecutionError::Timeout,
                 ExecutorError::Panic(model_id, payload) => {
                     let model = model_id
@@ -388,44 +1283,67 @@ impl Simulation {
                     panic::resume_unwind(payload);
                 }
             }
-        })
+        })?;
+
+        // Report panics caught in models, if any.
+        if let Some((model_id, payload)) = self.panic_registry.take() {
+            let model = self.model_names[model_id].clone();
+
+            // A `SendError` means that a message could not be delivered,
+            // which is a fatal error even if the panic was caught.
+            if (*payload).type_id() == TypeId::of::<SendError>() {
+                self.is_terminated = true;
+
+                return Err(ExecutionError::NoRecipient { model: Some(model) });
+            }
+
+            return Err(ExecutionError::ModelPanic {
+                model,
+                info: panic_message(&*payload)
+                    .unwrap_or("<non-string payload>")
+                    .to_string(),
+            });
+        }
+
+        Ok(())
     }
 
     /// Advances simulation time to that of the next scheduled action if its
     /// scheduling time does not exceed the specified bound, processing that
     /// action as well as all other actions scheduled for the same time.
     ///
-    /// If at least one action was found that satisfied the time bound, the
-    /// corresponding new simulation time is returned.
+    /// If at least one action was found that satisfied the time bound, a report
+    /// with the corresponding new simulation time is returned.
     fn step_to_next(
         &mut self,
         upper_time_bound: Option<MonotonicTime>,
-    ) -> Result<Option<MonotonicTime>, ExecutionError> {
+    ) -> Result<Option<StepReport>, ExecutionError> {
         if self.is_terminated {
             return Err(ExecutionError::Terminated);
         }
 
-        if self.is_halted.load(Ordering::Relaxed) {
+        if let Some(reason) = self.halt_signal.reason() {
             self.is_terminated = true;
-            return Err(ExecutionError::Halted);
+            return Err(ExecutionError::Halted(reason));
         }
 
         // Function pulling the next action. If the action is periodic, it is
         // immediately re-scheduled.
         fn pull_next_action(scheduler_queue: &mut MutexGuard<SchedulerQueue>) -> Action {
-            let ((time, channel_id), action) = scheduler_queue.pull().unwrap();
+            let ((time, priority, channel_id), action) = scheduler_queue.pull().unwrap();
             if let Some((action_clone, period)) = action.next() {
-                scheduler_queue.insert((time + period, channel_id), action_clone);
+                scheduler_queue.insert((time + period, priority, channel_id), action_clone);
             }
 
             action
         }
 
-        let upper_time_bound = upper_time_bound.unwrap_or(MonotonicTime::MAX);
-
-        // Closure returning the next key which time stamp is no older than the
-        // upper bound, if any. Cancelled actions are pulled and discarded.
-        let peek_next_key = |scheduler_queue: &mut MutexGuard<SchedulerQueue>| {
+        // Function returning the next key which time stamp is no older than
+        // the upper bound, if any. Cancelled actions are pulled and discarded.
+        fn peek_next_key(
+            scheduler_queue: &mut MutexGuard<SchedulerQueue>,
+            upper_time_bound: MonotonicTime,
+        ) -> Option<(MonotonicTime, i32, usize)> {
             loop {
                 match scheduler_queue.peek() {
                     Some((&key, action)) if key.0 <= upper_time_bound => {
@@ -438,19 +1356,48 @@ impl Simulation {
                     _ => break None,
                 }
             }
-        };
+        }
+
+        let upper_time_bound = upper_time_bound.unwrap_or(MonotonicTime::MAX);
 
         // Move to the next scheduled time.
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
-        let mut current_key = match peek_next_key(&mut scheduler_queue) {
+        let mut current_key = match peek_next_key(&mut scheduler_queue, upper_time_bound) {
             Some(key) => key,
             None => return Ok(None),
         };
+
+        // Call the time hook with the queue's mutex released since the hook may
+        // schedule or cancel actions, in which case the next key is updated.
+        // The simulation time is only updated once the next key is settled.
+        if let Some(time_hook) = &mut self.time_hook {
+            loop {
+                drop(scheduler_queue);
+                time_hook(current_key.0);
+                scheduler_queue = self.scheduler_queue.lock().unwrap();
+                match peek_next_key(&mut scheduler_queue, upper_time_bound) {
+                    Some(key) if key.0 == current_key.0 => {
+                        current_key = key;
+                        break;
+                    }
+                    Some(key) => current_key = key,
+                    None => return Ok(None),
+                }
+            }
+        }
         self.time.write(current_key.0);
+        let mut event_count = 0;
+        let mut query_count = 0;
+        let mut is_synchronized = false;
 
         loop {
             let action = pull_next_action(&mut scheduler_queue);
-            let mut next_key = peek_next_key(&mut scheduler_queue);
+            if action.is_query() {
+                query_count += 1;
+            } else {
+                event_count += 1;
+            }
+            let mut next_key = peek_next_key(&mut scheduler_queue, upper_time_bound);
             if next_key != Some(current_key) {
                 // Since there are no other actions with the same origin and the
                 // same time, the action is spawned immediately.
@@ -463,8 +1410,13 @@ impl Simulation {
                 action_sequence.push(action.into_future());
                 loop {
                     let action = pull_next_action(&mut scheduler_queue);
+                    if action.is_query() {
+                        query_count += 1;
+                    } else {
+                        event_count += 1;
+                    }
                     action_sequence.push(action.into_future());
-                    next_key = peek_next_key(&mut scheduler_queue);
+                    next_key = peek_next_key(&mut scheduler_queue, upper_time_bound);
                     if next_key != Some(current_key) {
                         break;
                     }
@@ -476,31 +1428,148 @@ impl Simulation {
             }
 
             current_key = match next_key {
-                // If the next action is scheduled at the same time, update the
-                // key and continue.
-                Some(k) if k.0 == current_key.0 => k,
+                // If the next action is scheduled at the same time and with the
+                // same priority, update the key and continue.
+                Some(k) if k.0 == current_key.0 && k.1 == current_key.1 => k,
+                // If the next action is scheduled at the same time but with a
+                // higher priority value, wait until all actions with the
+                // current priority have completed before continuing.
+                Some(k) if k.0 == current_key.0 => {
+                    drop(scheduler_queue); // make sure the queue's mutex is released.
+
+                    if !is_synchronized {
+                        self.synchronize_clock(current_key.0)?;
+                        is_synchronized = true;
+                    }
+                    self.run()?;
+
+                    // Pending actions may have been cancelled in the meantime.
+                    scheduler_queue = self.scheduler_queue.lock().unwrap();
+                    match peek_next_key(&mut scheduler_queue, upper_time_bound) {
+                        Some(k) if k.0 == current_key.0 => k,
+                        _ => {
+                            drop(scheduler_queue);
+                            self.verify_invariants()?;
+                            self.action_count += (event_count + query_count) as u64;
+
+                            return Ok(Some(StepReport {
+                                time: current_key.0,
+                                event_count,
+                                query_count,
+                            }));
+                        }
+                    }
+                }
                 // Otherwise wait until all actions have completed and return.
                 _ => {
                     drop(scheduler_queue); // make sure the queue's mutex is released.
 
                     let current_time = current_key.0;
-                    if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(current_time) {
-                        if let Some(tolerance) = &self.clock_tolerance {
-                            if &lag > tolerance {
-                                self.is_terminated = true;
-
-                                return Err(ExecutionError::OutOfSync(lag));
-                            }
-                        }
+                    if !is_synchronized {
+                        self.synchronize_clock(current_time)?;
                     }
                     self.run()?;
-
-                    return Ok(Some(current_time));
+                    self.verify_invariants()?;
+                    self.action_count += (event_count + query_count) as u64;
+
+                    return Ok(Some(StepReport {
+                        time: current_time,
+                        event_count,
+                        query_count,
+                    }));
                 }
             };
         }
     }
 
+    /// Requests all models and submodels to check their invariants if
+    /// invariant checking is enabled.
+    ///
+    /// If several models report a violation, only the first model in
+    /// registration order is reported.
+    fn verify_invariants(&mut self) -> Result<(), ExecutionError> {
+        if !self.check_invariants {
+            return Ok(());
+        }
+
+        let mut results = Vec::with_capacity(self.observers.len());
+        for entry in &self.observers {
+            let (result_writer, result_reader) = slot::slot();
+            self.executor
+                .spawn_and_forget(entry.observer.check_invariants(result_writer));
+            results.push(result_reader);
+        }
+        self.run()?;
+
+        for (entry, mut result) in self.observers.iter().zip(results) {
+            if let Ok(Err(message)) = result.try_read() {
+                self.is_terminated = true;
+
+                return Err(ExecutionError::InvariantViolated {
+                    model: entry.name.clone(),
+                    message,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the time of the next non-cancelled action in the scheduler
+    /// queue, if any.
+    ///
+    /// Cancelled actions are pulled and discarded.
+    fn next_action_time(&self) -> Option<MonotonicTime> {
+        scheduler::next_action_time(&mut self.scheduler_queue.lock().unwrap())
+    }
+
+    /// Returns the wall clock time at which the clock will be synchronized
+    /// with the specified simulation time, if the clock is a real-time clock
+    /// and the simulation time lies in the future of the clock reference.
+    fn wall_time(&self, time: MonotonicTime) -> Option<Instant> {
+        let (ref_time, ref_instant) = self.clock.wall_clock_ref()?;
+
+        // Times in the past of the clock reference never block.
+        if time > ref_time {
+            Some(ref_instant + time.duration_since(ref_time))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if no actions remain in the scheduler queue.
+    ///
+    /// Cancelled actions are pulled and discarded.
+    fn is_idle(&self) -> bool {
+        self.next_action_time().is_none()
+    }
+
+    /// Synchronizes the clock with the specified simulation time.
+    ///
+    /// An error is returned if the synchronization lag exceeds the tolerance
+    /// or if the wall clock limit was exceeded while waiting for the clock.
+    fn synchronize_clock(&mut self, time: MonotonicTime) -> Result<(), ExecutionError> {
+        if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(time) {
+            if let Some(tolerance) = &self.clock_tolerance {
+                if &lag > tolerance {
+                    self.is_terminated = true;
+
+                    return Err(ExecutionError::OutOfSync(lag));
+                }
+            }
+        }
+
+        if let Some(deadline) = self.wall_clock_deadline {
+            if Instant::now() >= deadline {
+                self.is_terminated = true;
+
+                return Err(ExecutionError::WallClockTimeout);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Iteratively advances simulation time and processes all actions scheduled
     /// up to the specified target time.
     ///
@@ -515,7 +1584,10 @@ impl Simulation {
         target_time: Option<MonotonicTime>,
     ) -> Result<(), ExecutionError> {
         loop {
-            match self.step_to_next(target_time) {
+            match self
+                .step_to_next(target_time)
+                .map(|report| report.map(|r| r.time))
+            {
                 // The target time was reached exactly.
                 Ok(time) if time == target_time => return Ok(()),
                 // No actions are scheduled before or at the target time.
@@ -523,15 +1595,7 @@ impl Simulation {
                     if let Some(target_time) = target_time {
                         // Update the simulation time.
                         self.time.write(target_time);
-                        if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(target_time) {
-                            if let Some(tolerance) = &self.clock_tolerance {
-                                if &lag > tolerance {
-                                    self.is_terminated = true;
-
-                                    return Err(ExecutionError::OutOfSync(lag));
-                                }
-                            }
-                        }
+                        self.synchronize_clock(target_time)?;
                     }
                     return Ok(());
                 }
@@ -543,12 +1607,12 @@ impl Simulation {
     }
 
     /// Returns a scheduler handle.
-    #[cfg(feature = "server")]
     pub(crate) fn scheduler(&self) -> Scheduler {
         Scheduler::new(
             self.scheduler_queue.clone(),
             self.time.reader(),
-            self.is_halted.clone(),
+            self.halt_signal.clone(),
+            self.wall_clock_ref.clone(),
         )
     }
 }
@@ -561,6 +1625,104 @@ impl fmt::Debug for Simulation {
     }
 }
 
+/// A summary of a simulation step returned by [`Simulation::step_observed`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StepReport {
+    /// The simulation time upon completion of the step.
+    pub time: MonotonicTime,
+    /// The number of scheduled events that were processed during the step.
+    ///
+    /// Messages sent by models to other models as a consequence of these
+    /// events are not included in this count.
+    pub event_count: usize,
+    /// The number of scheduled queries that were processed during the step.
+    ///
+    /// Messages sent by models to other models as a consequence of these
+    /// queries are not included in this count.
+    pub query_count: usize,
+}
+
+/// A report on the state of a simulation returned by [`Simulation::report`]
+/// and [`Simulation::finish`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SimulationReport {
+    /// The simulation time.
+    pub time: MonotonicTime,
+    /// The total number of scheduled actions (events or queries) processed
+    /// since the simulation was started.
+    ///
+    /// Actions processed immediately, for instance with
+    /// [`Simulation::process_event`], are not included in this count.
+    pub action_count: u64,
+    /// The number of models and submodels added to the simulation, including
+    /// those that were removed.
+    pub model_count: usize,
+}
+
+/// The condition that terminated a call to
+/// [`Simulation::step_until_idle_or`], [`Simulation::step_unbounded_until`]
+/// or [`Simulation::step_while`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StepOutcome {
+    /// All scheduled events have completed before the deadline or before the
+    /// stepping condition ceased to hold.
+    Idle,
+    /// The deadline was reached.
+    DeadlineReached,
+    /// The stepping condition ceased to hold.
+    Interrupted,
+}
+
+/// A token used to cooperatively cancel a call to
+/// [`Simulation::step_unbounded_cancellable`].
+///
+/// Clones of a token share the same cancellation state, so a clone can be
+/// moved to another thread or to a model to request the cancellation. Once
+/// cancelled, a token remains cancelled.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and all its clones.
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A read-only access to a model returned by [`Simulation::inspect_model`].
+///
+/// The simulation cannot be stepped while the guard is alive.
+pub struct ModelGuard<'a, M> {
+    model: &'a M,
+}
+
+impl<M> Deref for ModelGuard<'_, M> {
+    type Target = M;
+
+    fn deref(&self) -> &M {
+        self.model
+    }
+}
+
+impl<M> fmt::Debug for ModelGuard<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelGuard").finish_non_exhaustive()
+    }
+}
+
 /// Information regarding a deadlocked model.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct DeadlockInfo {
@@ -573,14 +1735,31 @@ pub struct DeadlockInfo {
     pub mailbox_size: usize,
 }
 
+/// Information regarding a model which validation has failed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationInfo {
+    /// The fully qualified name of the model.
+    ///
+    /// This is the name of the model, if relevant prepended by the
+    /// dot-separated names of all parent models.
+    pub model: String,
+    /// The error message returned by [`ProtoModel::validate`].
+    pub message: String,
+}
+
 /// An error returned upon simulation execution failure.
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum ExecutionError {
     /// The simulation has been intentionally stopped.
-    Halted,
+    ///
+    /// The payload is the reason provided with
+    /// [`Scheduler::halt_with_reason`], or an empty string if the simulation
+    /// was halted with [`Scheduler::halt`].
+    Halted(String),
     /// The simulation has been terminated due to an earlier deadlock, message
-    /// loss, missing recipient, model panic, timeout or synchronization loss.
+    /// loss, missing recipient, model panic, timeout, synchronization loss or
+    /// invariant violation.
     Terminated,
     /// The simulation has deadlocked due to the enlisted models.
     ///
@@ -629,6 +1808,25 @@ pub enum ExecutionError {
         /// [`panic_any`](std::panic::panic_any).
         payload: Box<dyn Any + Send + 'static>,
     },
+    /// A panic was caught in a model input or replier method and the
+    /// simulation was configured to survive model panics.
+    ///
+    /// Unlike [`ExecutionError::Panic`], this is not a fatal error: the
+    /// panicking message is discarded and the simulation can be resumed. If
+    /// several panics were caught during the same call, only the first one is
+    /// reported.
+    ///
+    /// See [`SimInit::catch_model_panics`].
+    ModelPanic {
+        /// The fully qualified name of the panicking model.
+        ///
+        /// The fully qualified name is made of the unqualified model name, if
+        /// relevant prepended by the dot-separated names of all parent models.
+        model: String,
+        /// The panic message, or `<non-string payload>` if the panic payload
+        /// was not a string.
+        info: String,
+    },
     /// The simulation step has failed to complete within the allocated time.
     ///
     /// This is a fatal error: any subsequent attempt to run the simulation will
@@ -636,6 +1834,13 @@ pub enum ExecutionError {
     ///
     /// See also [`SimInit::set_timeout`] and [`Simulation::set_timeout`].
     Timeout,
+    /// The simulation has exceeded its total wall clock time limit.
+    ///
+    /// This is a fatal error: any subsequent attempt to run the simulation will
+    /// return an [`ExecutionError::Terminated`] error.
+    ///
+    /// See also [`SimInit::set_wall_clock_limit`].
+    WallClockTimeout,
     /// The simulation has lost synchronization with the clock and lags behind
     /// by the duration given in the payload.
     ///
@@ -644,8 +1849,14 @@ pub enum ExecutionError {
     ///
     /// See also [`SimInit::set_clock_tolerance`].
     OutOfSync(Duration),
+    /// The clock provided to [`Simulation::set_clock`] would only reach the
+    /// current simulation time after the duration given in the payload.
+    ///
+    /// This is a non-fatal error: the previous clock remains in use.
+    ClockAhead(Duration),
     /// The query did not obtain a response because the mailbox targeted by the
-    /// query was not found in the simulation.
+    /// query was not found in the simulation, or because the query source is
+    /// not connected to any replier port.
     ///
     /// This is a non-fatal error.
     BadQuery,
@@ -654,12 +1865,66 @@ pub enum ExecutionError {
     ///
     /// This is a non-fatal error.
     InvalidDeadline(MonotonicTime),
+    /// No model with the specified name was found in the simulation.
+    ///
+    /// This is a non-fatal error.
+    ModelNotFound(String),
+    /// The model could not be removed because its address is still held by
+    /// other entities.
+    ///
+    /// This is a non-fatal error.
+    ModelConnected(String),
+    /// The simulation step was not performed because it could not have
+    /// completed without blocking.
+    ///
+    /// This is a non-fatal error.
+    ///
+    /// See also [`Simulation::try_step`].
+    WouldBlock,
+    /// The validation of one or more models has failed.
+    ///
+    /// This error is returned by [`SimInit::init`] before any model is
+    /// initialized, or by [`Simulation::add_model`], in which case it is a
+    /// non-fatal error.
+    ///
+    /// See also [`ProtoModel::validate`].
+    ValidationFailure(Vec<ValidationInfo>),
+    /// The simulation was stopped because its cancellation token was
+    /// cancelled.
+    ///
+    /// This is a non-fatal error.
+    ///
+    /// See also [`Simulation::step_unbounded_cancellable`].
+    Cancelled,
+    /// A model has reported an invariant violation at the end of a time
+    /// slice.
+    ///
+    /// If several models report a violation within the same time slice, only
+    /// the first one is reported.
+    ///
+    /// This is a fatal error: any subsequent attempt to run the simulation will
+    /// return an [`ExecutionError::Terminated`] error.
+    ///
+    /// See also [`SimInit::check_invariants`] and [`Model::check_invariants`].
+    InvariantViolated {
+        /// The name of the model.
+        model: String,
+        /// The message returned by [`Model::check_invariants`].
+        message: String,
+    },
 }
 
 impl fmt::Display for ExecutionError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Halted => f.write_str("the simulation has been intentionally stopped"),
+            Self::Halted(reason) => {
+                f.write_str("the simulation has been intentionally stopped")?;
+                if !reason.is_empty() {
+                    write!(f, ": {}", reason)?;
+                }
+
+                Ok(())
+            }
             Self::Terminated => f.write_str("the simulation has been terminated"),
             Self::Deadlock(list) => {
                 f.write_str(
@@ -696,16 +1961,18 @@ impl fmt::Display for ExecutionError {
                 }
             }
             Self::Panic{model, payload} => {
-                let msg: &str = if let Some(s) = payload.downcast_ref::<&str>() {
-                    s
-                } else if let Some(s) = payload.downcast_ref::<String>() {
-                    s
+                let msg = if let Some(msg) = panic_message(&**payload) {
+                    msg
                 } else {
                     return write!(f, "model '{}' has panicked", model);
                 };
                 write!(f, "model '{}' has panicked with the message: '{}'", model, msg)
             }
+            Self::ModelPanic{model, info} => {
+                write!(f, "a panic was caught in model '{}' with the message: '{}'", model, info)
+            }
             Self::Timeout => f.write_str("the simulation step has failed to complete within the allocated time"),
+            Self::WallClockTimeout => f.write_str("the simulation has exceeded its wall clock time limit"),
             Self::OutOfSync(lag) => {
                 write!(
                     f,
@@ -713,7 +1980,14 @@ impl fmt::Display for ExecutionError {
                     lag
                 )
             }
-            Self::BadQuery => f.write_str("the query did not return any response; was the target mailbox added to the simulation?"),
+            Self::ClockAhead(lead) => {
+                write!(
+                    f,
+                    "the new clock is ahead of the simulation time by '{:?}'",
+                    lead
+                )
+            }
+            Self::BadQuery => f.write_str("the query did not return any response; was the target mailbox added to the simulation or the query source connected?"),
             Self::InvalidDeadline(time) => {
                 write!(
                     f,
@@ -721,6 +1995,41 @@ impl fmt::Display for ExecutionError {
                     time
                 )
             }
+            Self::ModelNotFound(name) => {
+                write!(f, "no model named '{}' was found in the simulation", name)
+            }
+            Self::ModelConnected(name) => {
+                write!(
+                    f,
+                    "model '{}' cannot be removed because it is still connected",
+                    name
+                )
+            }
+            Self::WouldBlock => f.write_str(
+                "the simulation step was not performed because it would have blocked",
+            ),
+            Self::Cancelled => f.write_str("the simulation has been cancelled"),
+            Self::InvariantViolated { model, message } => {
+                write!(
+                    f,
+                    "model '{}' has violated an invariant: {}",
+                    model, message
+                )
+            }
+            Self::ValidationFailure(list) => {
+                f.write_str("the validation of the following models has failed: ")?;
+                let mut first_item = true;
+                for info in list {
+                    if first_item {
+                        first_item = false;
+                    } else {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "'{}' ({})", info.model, info.message)?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -766,39 +2075,119 @@ impl From<SchedulingError> for SimulationError {
     }
 }
 
+/// Returns the message of a panic payload, if it is a string.
+fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        Some(s)
+    } else {
+        payload.downcast_ref::<String>().map(|s| s.as_str())
+    }
+}
+
 /// Adds a model and its mailbox to the simulation bench.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn add_model<P: ProtoModel>(
     model: P,
     mailbox: Mailbox<P::Model>,
     name: String,
     scheduler: GlobalScheduler,
-    executor: &Executor,
+    pending_models: &mut PendingModels,
     abort_signal: &Signal,
     model_names: &mut Vec<String>,
+    rng_seed: &SharedSeed,
+    panic_registry: &Arc<PanicRegistry>,
+    description_registry: &Arc<DescriptionRegistry>,
+    deterministic_ordering: bool,
+    is_submodel: bool,
+    init_turn: Option<InitTurn>,
+    validation_failures: &mut Vec<ValidationInfo>,
 ) {
     #[cfg(feature = "tracing")]
     let span = tracing::span!(target: env!("CARGO_PKG_NAME"), tracing::Level::INFO, "model", name);
 
+    // The mailbox is registered before the submodels are built so that
+    // submodels are registered right after their parent model.
+    pending_models.observers.push(ModelObserver {
+        name: name.clone(),
+        observer: Box::new(mailbox.0.observer()),
+        is_submodel,
+    });
+
     let mut build_cx = BuildContext::new(
         &mailbox,
         &name,
         &scheduler,
-        executor,
+        pending_models,
         abort_signal,
         model_names,
+        rng_seed,
+        panic_registry,
+        description_registry,
+        deterministic_ordering,
+        validation_failures,
     );
+    let validation = model.validate(&build_cx);
     let model = model.build(&mut build_cx);
+    if let Err(message) = validation {
+        validation_failures.push(ValidationInfo {
+            model: name.clone(),
+            message,
+        });
+    }
 
+    let model_index = model_names.len();
+    #[cfg(feature = "manifest")]
+    let description = model.describe();
     let address = mailbox.address();
-    let mut receiver = mailbox.0;
+    let Mailbox(mut receiver, interceptors) = mailbox;
     let abort_signal = abort_signal.clone();
-    let mut cx = Context::new(name.clone(), scheduler, address);
+    let rng = ModelRng::new(rng_seed.clone(), model_index);
+    // The only requirement for the origin ID is that it must be (i) specific
+    // to each model and (ii) different from 0 (which is reserved for the
+    // global scheduler). The channel ID of the model mailbox fulfills this
+    // requirement. With deterministic ordering, the origin ID is instead
+    // derived from the model index so that the order of same-time actions
+    // scheduled by different models is reproducible; this assumes that all
+    // models use the same scheme.
+    let origin_id = if deterministic_ordering {
+        model_index + 1
+    } else {
+        address.0.channel_id()
+    };
+    let mut cx = Context::new(
+        name.clone(),
+        scheduler,
+        address,
+        origin_id,
+        rng,
+        interceptors,
+    );
+    let panic_registry = panic_registry.clone();
     let fut = async move {
-        let mut model = model.init(&mut cx).await.0;
-        while !abort_signal.is_set() && receiver.recv(&mut model, &mut cx).await.is_ok() {}
+        let mut model = match init_turn {
+            Some(init_turn) => init_turn.run(model.init(&mut cx)).await.0,
+            None => model.init(&mut cx).await.0,
+        };
+        while !abort_signal.is_set() {
+            if panic_registry.is_enabled() {
+                match receiver.recv_catch_unwind(&mut model, &mut cx).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(payload)) => panic_registry.add(model_index, payload),
+                    Err(_) => break,
+                }
+            } else if receiver.recv(&mut model, &mut cx).await.is_err() {
+                break;
+            }
+        }
+
+        // Unless the simulation was aborted, the loop only exits when the
+        // mailbox is closed, i.e. when the model is removed.
+        if !abort_signal.is_set() {
+            model.terminate(&mut cx).await;
+        }
     };
 
-    let model_id = ModelId::new(model_names.len());
+    let model_id = ModelId::new(model_index);
     model_names.push(name);
 
     #[cfg(not(feature = "tracing"))]
@@ -806,7 +2195,89 @@ pub(crate) fn add_model<P: ProtoModel>(
     #[cfg(feature = "tracing")]
     let fut = ModelFuture::new(fut, model_id, span);
 
-    executor.spawn_and_forget(fut);
+    #[cfg(feature = "manifest")]
+    let description_registry = description_registry.clone();
+    pending_models.push(move |executor| {
+        #[cfg(feature = "manifest")]
+        if let Some(description) = description {
+            description_registry.add(model_index, description);
+        }
+        executor.spawn_and_forget(fut);
+    });
+}
+
+/// Models that were built but not yet spawned on the executor.
+///
+/// Spawning and registration are deferred until a model and all its
+/// submodels are built, so that a model rejected by validation is dropped
+/// without ever being initialized.
+#[derive(Default)]
+pub(crate) struct PendingModels {
+    spawns: Vec<Box<dyn FnOnce(&Executor) + Send>>,
+    observers: Vec<ModelObserver>,
+}
+
+impl PendingModels {
+    /// Adds a model given its spawning closure.
+    fn push(&mut self, spawn: impl FnOnce(&Executor) + Send + 'static) {
+        self.spawns.push(Box::new(spawn));
+    }
+
+    /// Registers the mailboxes of all pending models and spawns the models on
+    /// the executor.
+    pub(crate) fn spawn_all(self, executor: &Executor, observers: &mut Vec<ModelObserver>) {
+        observers.extend(self.observers);
+        for spawn in self.spawns {
+            spawn(executor);
+        }
+    }
+}
+
+impl fmt::Debug for PendingModels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingModels")
+            .field("len", &self.spawns.len())
+            .finish()
+    }
+}
+
+/// A turn in the sequential initialization of models.
+pub(crate) struct InitTurn {
+    previous: Option<oneshot::Receiver<()>>,
+    next: oneshot::Sender<()>,
+}
+
+impl InitTurn {
+    /// Creates a turn which starts once the previous turn, if any, has ended
+    /// and which ends the next turn when completed.
+    pub(crate) fn new(previous: Option<oneshot::Receiver<()>>, next: oneshot::Sender<()>) -> Self {
+        Self { previous, next }
+    }
+
+    /// Runs a model initializer during this turn.
+    ///
+    /// The next turn starts when the initializer completes or if it is
+    /// dropped, so a panicking model does not prevent the initialization of
+    /// subsequent models.
+    async fn run<F: Future>(self, init: F) -> F::Output {
+        if let Some(previous) = self.previous {
+            // The sender may have been dropped rather than used, which
+            // equally ends the previous turn.
+            let _ = previous.await;
+        }
+        let output = init.await;
+        let _ = self.next.send(());
+
+        output
+    }
+}
+
+/// The mailbox observer of a model or submodel.
+pub(crate) struct ModelObserver {
+    /// The fully qualified name of the model.
+    name: String,
+    observer: Box<dyn ChannelObserver>,
+    is_submodel: bool,
 }
 
 /// A unique index assigned to a model instance.
@@ -825,7 +2296,7 @@ impl ModelId {
 
         Self(id)
     }
-    fn get(&self) -> Option<usize> {
+    pub(crate) fn get(&self) -> Option<usize> {
         if self.0 != usize::MAX {
             Some(self.0)
         } else {
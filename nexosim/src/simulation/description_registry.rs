@@ -0,0 +1,41 @@
+#[cfg(feature = "manifest")]
+use std::sync::Mutex;
+
+/// A registry of the configuration snapshots reported by models with
+/// [`Model::describe`](crate::model::Model::describe).
+///
+/// The registry is always empty unless the `manifest` feature is enabled.
+#[derive(Debug, Default)]
+pub(crate) struct DescriptionRegistry {
+    #[cfg(feature = "manifest")]
+    descriptions: Mutex<Vec<(usize, serde_json::Value)>>,
+}
+
+#[cfg(feature = "manifest")]
+impl DescriptionRegistry {
+    /// Records the configuration snapshot of the specified model.
+    pub(crate) fn add(&self, model_id: usize, description: serde_json::Value) {
+        self.descriptions
+            .lock()
+            .unwrap()
+            .push((model_id, description));
+    }
+
+    /// Returns a JSON object which maps the names of all models that reported
+    /// a configuration snapshot to their snapshot.
+    ///
+    /// The model names are indexed by model identifier. If several models
+    /// share the same name, only the snapshot of the last added model is
+    /// kept.
+    pub(crate) fn manifest(&self, model_names: &[String]) -> serde_json::Value {
+        let manifest = self
+            .descriptions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(model_id, description)| (model_names[*model_id].clone(), description.clone()))
+            .collect();
+
+        serde_json::Value::Object(manifest)
+    }
+}
@@ -0,0 +1,55 @@
+use std::any::Any;
+use std::sync::Mutex;
+
+/// A type-erased interceptor closure for events of type `T`.
+type InterceptorFn<T> = Box<dyn FnMut(&mut T) + Send + 'static>;
+
+/// A closure called with the event type name before any event is delivered.
+#[cfg(feature = "dev-hooks")]
+type DispatchHookFn = Box<dyn FnMut(&'static str) + Send + 'static>;
+
+/// A list of closures that observe or modify the events delivered to the input
+/// ports of a model.
+///
+/// The closures are type-erased and are only applied to events with a matching
+/// type, in the order in which they were added.
+#[derive(Default)]
+pub(crate) struct Interceptors {
+    // The mutex is only ever accessed mutably and is only needed to make the
+    // model context `Sync`.
+    interceptors: Mutex<Vec<Box<dyn Any + Send + 'static>>>,
+    #[cfg(feature = "dev-hooks")]
+    dispatch_hooks: Mutex<Vec<DispatchHookFn>>,
+}
+
+impl Interceptors {
+    /// Adds an interceptor for events of type `T`.
+    pub(crate) fn add<T: 'static>(&mut self, interceptor: impl FnMut(&mut T) + Send + 'static) {
+        let interceptor: InterceptorFn<T> = Box::new(interceptor);
+
+        self.interceptors
+            .get_mut()
+            .unwrap()
+            .push(Box::new(interceptor));
+    }
+
+    /// Adds a hook called for events of any type.
+    #[cfg(feature = "dev-hooks")]
+    pub(crate) fn add_dispatch_hook(&mut self, hook: impl FnMut(&'static str) + Send + 'static) {
+        self.dispatch_hooks.get_mut().unwrap().push(Box::new(hook));
+    }
+
+    /// Applies in order all interceptors for events of type `T`.
+    pub(crate) fn apply<T: 'static>(&mut self, arg: &mut T) {
+        #[cfg(feature = "dev-hooks")]
+        for hook in self.dispatch_hooks.get_mut().unwrap().iter_mut() {
+            hook(std::any::type_name::<T>());
+        }
+
+        for interceptor in self.interceptors.get_mut().unwrap().iter_mut() {
+            if let Some(interceptor) = interceptor.downcast_mut::<InterceptorFn<T>>() {
+                interceptor(arg);
+            }
+        }
+    }
+}
@@ -3,13 +3,19 @@ use std::fmt;
 use crate::channel::{Receiver, Sender};
 use crate::model::Model;
 
+use super::Interceptors;
+
 /// A model mailbox.
 ///
 /// A mailbox is an entity associated to a model instance that collects all
 /// messages sent to that model. The size of its internal buffer can be
 /// optionally specified at construction time using
 /// [`with_capacity`](Mailbox::with_capacity).
-pub struct Mailbox<M: Model>(pub(crate) Receiver<M>);
+///
+/// Messages are normally processed in FIFO order. A mailbox created with
+/// [`new_prioritized`](Mailbox::new_prioritized) additionally supports a
+/// high-priority class of messages, see [`Address::high_priority`].
+pub struct Mailbox<M: Model>(pub(crate) Receiver<M>, pub(crate) Interceptors);
 
 impl<M: Model> Mailbox<M> {
     /// Default capacity when created with `new` or `Default::default`.
@@ -17,7 +23,10 @@ impl<M: Model> Mailbox<M> {
 
     /// Creates a new mailbox with capacity `Self::DEFAULT_CAPACITY`.
     pub fn new() -> Self {
-        Self(Receiver::new(Self::DEFAULT_CAPACITY))
+        Self(
+            Receiver::new(Self::DEFAULT_CAPACITY),
+            Interceptors::default(),
+        )
     }
 
     /// Creates a new mailbox with the specified capacity.
@@ -27,7 +36,109 @@ impl<M: Model> Mailbox<M> {
     /// The constructor will panic if the requested capacity is 0 or is greater
     /// than `usize::MAX/2 + 1`.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(Receiver::new(capacity))
+        Self(Receiver::new(capacity), Interceptors::default())
+    }
+
+    /// Creates a new mailbox with a regular and a high-priority class of
+    /// messages, each with capacity `Self::DEFAULT_CAPACITY`.
+    ///
+    /// Messages sent through an address obtained with
+    /// [`Address::high_priority`] belong to the high-priority class and all
+    /// other messages to the regular class. The model always processes
+    /// pending high-priority messages before regular messages, so the
+    /// ordering guarantees are as follows:
+    ///
+    /// * messages of the same class are processed in FIFO order, as with a
+    ///   regular mailbox,
+    /// * a high-priority message is processed before all regular messages
+    ///   that are pending when the model is ready to process its next message,
+    ///   even if these were sent earlier.
+    ///
+    /// A message which processing has started is never preempted, however.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::Output;
+    /// use nexosim::simulation::Mailbox;
+    ///
+    /// pub struct Controller {
+    ///     pub commands: Output<bool>,
+    ///     pub data: Output<Vec<u8>>,
+    /// }
+    /// impl Model for Controller {}
+    ///
+    /// pub struct Instrument {}
+    /// impl Instrument {
+    ///     pub fn abort(&mut self, _: bool) {}
+    ///     pub fn store(&mut self, _: Vec<u8>) {}
+    /// }
+    /// impl Model for Instrument {}
+    ///
+    /// let mut controller = Controller {
+    ///     commands: Output::default(),
+    ///     data: Output::default(),
+    /// };
+    /// let instrument_mbox = Mailbox::new_prioritized();
+    /// let instrument_addr = instrument_mbox.address();
+    ///
+    /// // Commands jump ahead of pending bulk data.
+    /// controller
+    ///     .commands
+    ///     .connect(Instrument::abort, instrument_addr.high_priority());
+    /// controller.data.connect(Instrument::store, &instrument_addr);
+    /// ```
+    pub fn new_prioritized() -> Self {
+        Self(
+            Receiver::new_prioritized(Self::DEFAULT_CAPACITY),
+            Interceptors::default(),
+        )
+    }
+
+    /// Installs an interceptor for all events of type `T` delivered to the
+    /// input ports of the model.
+    ///
+    /// The interceptor is called by the model right before an event of type
+    /// `T` is processed by an input port, irrespective of the sender of the
+    /// event, and may observe or modify the event. This makes it possible,
+    /// for instance, to log all events received by a model or to inject
+    /// faults without altering the model or its connections.
+    ///
+    /// Interceptors are called in the order in which events are processed by
+    /// the model. If several interceptors are installed for the same event
+    /// type, they are applied in the order in which they were installed.
+    /// Requests sent to replier ports are not intercepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::simulation::Mailbox;
+    ///
+    /// pub struct Heater {
+    ///     power: f64,
+    /// }
+    /// impl Heater {
+    ///     pub fn set_power(&mut self, power: f64) {
+    ///         self.power = power;
+    ///     }
+    /// }
+    /// impl Model for Heater {}
+    ///
+    /// // Clamp the requested power and log it.
+    /// let heater_mbox = Mailbox::<Heater>::new().with_interceptor(|power: &mut f64| {
+    ///     *power = power.clamp(0.0, 100.0);
+    ///     println!("heater power set to {}", power);
+    /// });
+    /// ```
+    pub fn with_interceptor<T: 'static>(
+        mut self,
+        interceptor: impl FnMut(&mut T) + Send + 'static,
+    ) -> Self {
+        self.1.add(interceptor);
+
+        self
     }
 
     /// Returns a handle to this mailbox.
@@ -61,6 +172,65 @@ impl<M: Model> fmt::Debug for Mailbox<M> {
 /// `Address::clone` or `Mailbox::address` as appropriate.
 pub struct Address<M: Model>(pub(crate) Sender<M>);
 
+impl<M: Model> Address<M> {
+    /// Returns a connection target designating the specified input or replier
+    /// port of the model.
+    ///
+    /// The target can be passed as a single argument to
+    /// [`Output::connect_to`](crate::ports::Output::connect_to) or
+    /// [`Requestor::connect_to`](crate::ports::Requestor::connect_to), which
+    /// is equivalent to passing the port and the address separately to the
+    /// `connect` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::Output;
+    /// use nexosim::simulation::Mailbox;
+    ///
+    /// #[derive(Default)]
+    /// pub struct Sensor {
+    ///     pub output: Output<f64>,
+    /// }
+    /// impl Model for Sensor {}
+    ///
+    /// pub struct Display {}
+    /// impl Display {
+    ///     pub fn show(&mut self, value: f64) {
+    ///         println!("{}", value);
+    ///     }
+    /// }
+    /// impl Model for Display {}
+    ///
+    /// let mut sensor = Sensor::default();
+    /// let display_mbox = Mailbox::<Display>::new();
+    /// sensor
+    ///     .output
+    ///     .connect_to(display_mbox.address().port(Display::show));
+    /// ```
+    pub fn port<F>(&self, port: F) -> PortTarget<M, F> {
+        PortTarget {
+            port,
+            address: self.clone(),
+        }
+    }
+
+    /// Returns an address through which messages are sent in the
+    /// high-priority class of the mailbox.
+    ///
+    /// The returned address can be used wherever a regular address is
+    /// accepted, for instance to mark a connection of an output port as high
+    /// priority or to schedule high-priority events. See
+    /// [`Mailbox::new_prioritized`] for the ordering guarantees.
+    ///
+    /// If the mailbox was not created with [`Mailbox::new_prioritized`], the
+    /// returned address behaves like a regular address.
+    pub fn high_priority(&self) -> Address<M> {
+        Address(self.0.clone().with_high_priority())
+    }
+}
+
 impl<M: Model> Clone for Address<M> {
     fn clone(&self) -> Self {
         Self(self.0.clone())
@@ -94,3 +264,28 @@ impl<M: Model> fmt::Debug for Address<M> {
             .finish_non_exhaustive()
     }
 }
+
+/// An input or replier port of a specific model instance.
+///
+/// A `PortTarget` is created with [`Address::port`].
+pub struct PortTarget<M: Model, F> {
+    pub(crate) port: F,
+    pub(crate) address: Address<M>,
+}
+
+impl<M: Model, F: Clone> Clone for PortTarget<M, F> {
+    fn clone(&self) -> Self {
+        Self {
+            port: self.port.clone(),
+            address: self.address.clone(),
+        }
+    }
+}
+
+impl<M: Model, F> fmt::Debug for PortTarget<M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortTarget")
+            .field("mailbox_id", &self.address.0.channel_id().to_string())
+            .finish_non_exhaustive()
+    }
+}
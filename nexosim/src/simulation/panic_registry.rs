@@ -0,0 +1,51 @@
+use std::any::{Any, TypeId};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::channel::SendError;
+
+/// A registry of the panics caught during the execution of model input and
+/// replier methods.
+#[derive(Debug, Default)]
+pub(crate) struct PanicRegistry {
+    is_enabled: AtomicBool,
+    panics: Mutex<Vec<(usize, Box<dyn Any + Send + 'static>)>>,
+}
+
+impl PanicRegistry {
+    /// Enables the catching of model panics.
+    pub(crate) fn enable(&self) {
+        self.is_enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether model panics should be caught.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.is_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Records a panic caught in the specified model.
+    pub(crate) fn add(&self, model_id: usize, payload: Box<dyn Any + Send + 'static>) {
+        self.panics.lock().unwrap().push((model_id, payload));
+    }
+
+    /// Clears the registry and returns the identifier of the model and the
+    /// payload of the most relevant recorded panic, if any.
+    ///
+    /// Panics originating from a `SendError` are typically a consequence of a
+    /// panic in another model, for instance if a replier has panicked while
+    /// processing a request, so the first panic which does not originate from
+    /// a `SendError` is returned in priority.
+    pub(crate) fn take(&self) -> Option<(usize, Box<dyn Any + Send + 'static>)> {
+        let mut panics = std::mem::take(&mut *self.panics.lock().unwrap());
+        let idx = panics
+            .iter()
+            .position(|(_, payload)| (**payload).type_id() != TypeId::of::<SendError>())
+            .unwrap_or(0);
+
+        if panics.is_empty() {
+            None
+        } else {
+            Some(panics.swap_remove(idx))
+        }
+    }
+}
@@ -3,10 +3,10 @@ use std::error::Error;
 use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fmt, ptr};
 
 use pin_project::pin_project;
@@ -17,27 +17,42 @@ use crate::executor::Executor;
 use crate::model::Model;
 use crate::ports::InputFn;
 use crate::simulation::Address;
-use crate::time::{AtomicTimeReader, Deadline, MonotonicTime};
+use crate::time::{AtomicTimeReader, Deadline, MonotonicTime, TimeHandle};
 use crate::util::priority_queue::PriorityQueue;
 
-#[cfg(all(test, not(nexosim_loom)))]
+#[cfg(any(all(test, not(nexosim_loom)), feature = "dev-hooks"))]
 use crate::{time::TearableAtomicTime, util::sync_cell::SyncCell};
 
-const GLOBAL_SCHEDULER_ORIGIN_ID: usize = 0;
+/// Origin identifier of actions scheduled by the global scheduler.
+pub(crate) const GLOBAL_SCHEDULER_ORIGIN_ID: usize = 0;
+
+/// Priority of actions scheduled without an explicit priority.
+pub(crate) const DEFAULT_PRIORITY: i32 = 0;
+
+/// A simulation time and a wall clock instant which refer to the same point in
+/// time, shared with the simulation so it can be updated if the clock changes.
+pub(crate) type SharedWallClockRef = Arc<Mutex<Option<(MonotonicTime, Instant)>>>;
 
 /// A global simulation scheduler.
 ///
 /// A `Scheduler` can be `Clone`d and sent to other threads.
 #[derive(Clone)]
-pub struct Scheduler(GlobalScheduler);
+pub struct Scheduler {
+    inner: GlobalScheduler,
+    wall_clock_ref: SharedWallClockRef,
+}
 
 impl Scheduler {
     pub(crate) fn new(
         scheduler_queue: Arc<Mutex<SchedulerQueue>>,
         time: AtomicTimeReader,
-        is_halted: Arc<AtomicBool>,
+        halt_signal: Arc<HaltSignal>,
+        wall_clock_ref: SharedWallClockRef,
     ) -> Self {
-        Self(GlobalScheduler::new(scheduler_queue, time, is_halted))
+        Self {
+            inner: GlobalScheduler::new(scheduler_queue, time, halt_signal),
+            wall_clock_ref,
+        }
     }
 
     /// Returns the current simulation time.
@@ -55,7 +70,29 @@ impl Scheduler {
     /// }
     /// ```
     pub fn time(&self) -> MonotonicTime {
-        self.0.time()
+        self.inner.time()
+    }
+
+    /// Returns a lightweight handle to the current simulation time.
+    ///
+    /// The handle can be sent to other threads and read concurrently with the
+    /// execution of the simulation.
+    pub fn time_handle(&self) -> TimeHandle {
+        TimeHandle::new(self.inner.time.clone())
+    }
+
+    /// Returns the number of pending scheduled events.
+    ///
+    /// Cancelled events are not counted and periodic events are counted once.
+    pub fn pending_event_count(&self) -> usize {
+        pending_action_count(&self.inner.scheduler_queue.lock().unwrap())
+    }
+
+    /// Returns the time of the next pending scheduled event, if any.
+    ///
+    /// Cancelled events are ignored.
+    pub fn next_event_time(&self) -> Option<MonotonicTime> {
+        next_action_time(&mut self.inner.scheduler_queue.lock().unwrap())
     }
 
     /// Schedules an action at a future time.
@@ -67,8 +104,71 @@ impl Scheduler {
     /// model, these events are guaranteed to be processed according to the
     /// scheduling order of the actions.
     pub fn schedule(&self, deadline: impl Deadline, action: Action) -> Result<(), SchedulingError> {
-        self.0
-            .schedule_from(deadline, action, GLOBAL_SCHEDULER_ORIGIN_ID)
+        self.inner.schedule_from(
+            deadline,
+            action,
+            DEFAULT_PRIORITY,
+            GLOBAL_SCHEDULER_ORIGIN_ID,
+        )
+    }
+
+    /// Schedules a batch of actions at future times.
+    ///
+    /// This is equivalent to calling [`Scheduler::schedule`] for each action of
+    /// the batch, except that the scheduler queue is only locked once and
+    /// that the new actions are inserted in a single pass. This is typically
+    /// used to load a long stimulus trace during bench initialization.
+    ///
+    /// The scheduling times need not be in chronological order. Actions
+    /// scheduled for the same time are processed in the order of the batch.
+    ///
+    /// An error is returned and no action is scheduled if any of the specified
+    /// times is not in the future of the current simulation time.
+    pub fn schedule_batch<I>(&self, actions: I) -> Result<(), SchedulingError>
+    where
+        I: IntoIterator<Item = (MonotonicTime, Action)>,
+    {
+        self.inner
+            .schedule_batch_from(actions, DEFAULT_PRIORITY, GLOBAL_SCHEDULER_ORIGIN_ID)
+    }
+
+    /// Inserts an action at the current simulation time.
+    ///
+    /// The action is processed at the next simulation step, after the actions
+    /// already scheduled for the current time.
+    pub(crate) fn inject(&self, action: Action) {
+        // The scheduler queue must always be locked when reading the time (see
+        // `GlobalScheduler::schedule_from`).
+        let mut scheduler_queue = self.inner.scheduler_queue.lock().unwrap();
+        let now = self.inner.time();
+        scheduler_queue.insert((now, DEFAULT_PRIORITY, GLOBAL_SCHEDULER_ORIGIN_ID), action);
+    }
+
+    /// Schedules an action at a future time with the specified priority.
+    ///
+    /// An error is returned if the specified time is not in the future of the
+    /// current simulation time.
+    ///
+    /// Among all actions scheduled for the same simulation time, those with a
+    /// lower priority value are processed first: all actions with a given
+    /// priority, together with all events and queries they transitively
+    /// trigger, are guaranteed to complete before any action with a higher
+    /// priority value is processed. Actions scheduled without an explicit
+    /// priority have a priority of 0.
+    ///
+    /// The ordering guarantee of [`Scheduler::schedule`] only applies to
+    /// actions with the same priority: if multiple actions with the same
+    /// priority send events at the same simulation time to the same model,
+    /// these events are processed according to the scheduling order of the
+    /// actions.
+    pub fn schedule_with_priority(
+        &self,
+        deadline: impl Deadline,
+        priority: i32,
+        action: Action,
+    ) -> Result<(), SchedulingError> {
+        self.inner
+            .schedule_from(deadline, action, priority, GLOBAL_SCHEDULER_ORIGIN_ID)
     }
 
     /// Schedules an event at a future time.
@@ -91,8 +191,14 @@ impl Scheduler {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.0
-            .schedule_event_from(deadline, func, arg, address, GLOBAL_SCHEDULER_ORIGIN_ID)
+        self.inner.schedule_event_from(
+            deadline,
+            func,
+            arg,
+            address,
+            DEFAULT_PRIORITY,
+            GLOBAL_SCHEDULER_ORIGIN_ID,
+        )
     }
 
     /// Schedules a cancellable event at a future time and returns an event key.
@@ -115,8 +221,13 @@ impl Scheduler {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.0
-            .schedule_keyed_event_from(deadline, func, arg, address, GLOBAL_SCHEDULER_ORIGIN_ID)
+        self.inner.schedule_keyed_event_from(
+            deadline,
+            func,
+            arg,
+            address,
+            GLOBAL_SCHEDULER_ORIGIN_ID,
+        )
     }
 
     /// Schedules a periodically recurring event at a future time.
@@ -140,7 +251,7 @@ impl Scheduler {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.0.schedule_periodic_event_from(
+        self.inner.schedule_periodic_event_from(
             deadline,
             period,
             func,
@@ -172,7 +283,7 @@ impl Scheduler {
         T: Send + Clone + 'static,
         S: Send + 'static,
     {
-        self.0.schedule_keyed_periodic_event_from(
+        self.inner.schedule_keyed_periodic_event_from(
             deadline,
             period,
             func,
@@ -182,9 +293,102 @@ impl Scheduler {
         )
     }
 
+    /// Moves a pending keyed action to a new time.
+    ///
+    /// The action key remains valid after rescheduling, so the action can be
+    /// subsequently cancelled or rescheduled again.
+    ///
+    /// An error is returned if the specified time is not in the future of the
+    /// current simulation time, or if the action is no longer pending because
+    /// it was already processed or cancelled. For periodic actions, only the
+    /// next occurrence is moved and later occurrences follow with the original
+    /// period.
+    pub fn reschedule(
+        &self,
+        key: &ActionKey,
+        deadline: impl Deadline,
+    ) -> Result<(), SchedulingError> {
+        self.inner.reschedule(key, deadline)
+    }
+
+    /// Schedules an event at the simulation time that corresponds to the
+    /// specified wall clock instant.
+    ///
+    /// The wall clock instant is translated to simulation time using the
+    /// time reference of the real-time [`Clock`](crate::time::Clock) set with
+    /// [`SimInit::set_clock`](crate::simulation::SimInit::set_clock). The
+    /// translation preserves the nanosecond resolution of [`Instant`] and
+    /// therefore involves no rounding beyond that of the platform's monotonic
+    /// clock.
+    ///
+    /// An error is returned if the simulation clock is not a real-time clock,
+    /// or if the translated time is not in the future of the current
+    /// simulation time. In particular, an instant which wall clock time has
+    /// not yet elapsed may still translate to a simulation time in the past if
+    /// the simulation runs ahead of the wall clock, which can occur if a clock
+    /// synchronization tolerance was set.
+    ///
+    /// Events scheduled for the same time and targeting the same model are
+    /// guaranteed to be processed according to the scheduling order.
+    pub fn schedule_at_wall_clock<M, F, T, S>(
+        &self,
+        instant: Instant,
+        func: F,
+        arg: T,
+        address: impl Into<Address<M>>,
+    ) -> Result<(), SchedulingError>
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        let time = self.wall_clock_to_time(instant)?;
+
+        self.inner.schedule_event_from(
+            time,
+            func,
+            arg,
+            address,
+            DEFAULT_PRIORITY,
+            GLOBAL_SCHEDULER_ORIGIN_ID,
+        )
+    }
+
     /// Requests the simulation to stop when advancing to the next step.
+    ///
+    /// This is equivalent to calling [`Scheduler::halt_with_reason`] with an
+    /// empty reason.
     pub fn halt(&mut self) {
-        self.0.halt()
+        self.inner.halt(String::new())
+    }
+
+    /// Requests the simulation to stop when advancing to the next step,
+    /// specifying the reason for the halt.
+    ///
+    /// The reason is returned in the [`ExecutionError::Halted`] error
+    /// produced by the simulation. If the simulation was already requested to
+    /// halt, the original reason is preserved.
+    ///
+    /// [`ExecutionError::Halted`]: crate::simulation::ExecutionError::Halted
+    pub fn halt_with_reason(&mut self, reason: impl Into<String>) {
+        self.inner.halt(reason.into())
+    }
+
+    /// Translates a wall clock instant to simulation time.
+    fn wall_clock_to_time(&self, instant: Instant) -> Result<MonotonicTime, SchedulingError> {
+        let (simulation_ref, wall_clock_ref) = self
+            .wall_clock_ref
+            .lock()
+            .unwrap()
+            .ok_or(SchedulingError::NoRealTimeClock)?;
+
+        let time = match instant.checked_duration_since(wall_clock_ref) {
+            Some(delta) => simulation_ref.checked_add(delta),
+            None => simulation_ref.checked_sub(wall_clock_ref.duration_since(instant)),
+        };
+
+        time.ok_or(SchedulingError::InvalidScheduledTime)
     }
 }
 
@@ -203,55 +407,115 @@ impl fmt::Debug for Scheduler {
 #[derive(Debug)]
 #[must_use = "dropping this key immediately cancels the associated action"]
 pub struct AutoActionKey {
-    is_cancelled: Arc<AtomicBool>,
+    key: ActionKey,
 }
 
 impl Drop for AutoActionKey {
     fn drop(&mut self) {
-        self.is_cancelled.store(true, Ordering::Relaxed);
+        self.key.set_cancelled();
     }
 }
 
+/// Flag set in the state of an [`ActionKey`] when the action is cancelled.
+const CANCELLED: u8 = 0b01;
+/// Flag set in the state of an [`ActionKey`] when a non-periodic action is
+/// processed.
+const PROCESSED: u8 = 0b10;
+
 /// Handle to a scheduled action.
 ///
-/// An `ActionKey` can be used to cancel a scheduled action.
+/// An `ActionKey` can be used to cancel a scheduled action or to check whether
+/// it is still pending.
 #[derive(Clone, Debug)]
 #[must_use = "prefer unkeyed scheduling methods if the action is never cancelled"]
 pub struct ActionKey {
-    is_cancelled: Arc<AtomicBool>,
+    state: Arc<AtomicU8>,
 }
 
 impl ActionKey {
     /// Creates a key for a pending action.
     pub(crate) fn new() -> Self {
         Self {
-            is_cancelled: Arc::new(AtomicBool::new(false)),
+            state: Arc::new(AtomicU8::new(0)),
         }
     }
 
     /// Checks whether the action was cancelled.
     pub(crate) fn is_cancelled(&self) -> bool {
-        self.is_cancelled.load(Ordering::Relaxed)
+        self.state.load(Ordering::Relaxed) & CANCELLED != 0
+    }
+
+    /// Checks whether the action is still pending, i.e. whether it was
+    /// neither cancelled nor processed.
+    ///
+    /// A non-periodic action is considered processed as soon as the simulation
+    /// has advanced to its scheduled time and started processing it, so this
+    /// method returns `false` from that point on even if the action is then
+    /// cancelled before the event actually reaches the model. A periodic
+    /// action remains pending until it is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use nexosim::simulation::ActionKey;
+    ///
+    /// // A watchdog that is re-armed whenever it is kicked, unless its timeout
+    /// // is still armed.
+    /// #[derive(Default)]
+    /// pub struct Watchdog {
+    ///     timeout_key: Option<ActionKey>,
+    /// }
+    ///
+    /// impl Watchdog {
+    ///     pub fn kick(&mut self, _: (), cx: &mut Context<Self>) {
+    ///         if self.timeout_key.as_ref().is_some_and(|key| key.is_pending()) {
+    ///             return;
+    ///         }
+    ///         self.timeout_key = cx
+    ///             .schedule_keyed_event(Duration::from_secs(1), Self::timeout, ())
+    ///             .ok();
+    ///     }
+    ///
+    ///     fn timeout(&mut self) {
+    ///         // ...
+    ///     }
+    /// }
+    ///
+    /// impl Model for Watchdog {}
+    /// ```
+    pub fn is_pending(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == 0
     }
 
     /// Cancels the associated action.
     pub fn cancel(self) {
-        self.is_cancelled.store(true, Ordering::Relaxed);
+        self.set_cancelled();
     }
 
     /// Converts action key to a managed key.
     pub fn into_auto(self) -> AutoActionKey {
-        AutoActionKey {
-            is_cancelled: self.is_cancelled,
-        }
+        AutoActionKey { key: self }
+    }
+
+    /// Marks the action as processed.
+    fn set_processed(&self) {
+        self.state.fetch_or(PROCESSED, Ordering::Relaxed);
+    }
+
+    /// Marks the action as cancelled.
+    fn set_cancelled(&self) {
+        self.state.fetch_or(CANCELLED, Ordering::Relaxed);
     }
 }
 
 impl PartialEq for ActionKey {
     /// Implements equality by considering clones to be equivalent, rather than
-    /// keys with the same `is_cancelled` value.
+    /// keys with the same state.
     fn eq(&self, other: &Self) -> bool {
-        ptr::eq(&*self.is_cancelled, &*other.is_cancelled)
+        ptr::eq(&*self.state, &*other.state)
     }
 }
 
@@ -259,12 +523,12 @@ impl Eq for ActionKey {}
 
 impl Hash for ActionKey {
     /// Implements `Hash`` by considering clones to be equivalent, rather than
-    /// keys with the same `is_cancelled` value.
+    /// keys with the same state.
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
-        ptr::hash(&*self.is_cancelled, state)
+        ptr::hash(&*self.state, state)
     }
 }
 
@@ -276,6 +540,15 @@ pub enum SchedulingError {
     InvalidScheduledTime,
     /// The repetition period is zero.
     NullRepetitionPeriod,
+    /// The simulation is not synchronized with a real-time clock, so wall
+    /// clock instants cannot be translated to simulation time.
+    NoRealTimeClock,
+    /// The action key refers to an action which is no longer pending, either
+    /// because it was already processed or because it was cancelled.
+    ExpiredActionKey,
+    /// The scheduled times of a sequence of actions are not in chronological
+    /// order.
+    NonMonotonicSchedule,
 }
 
 impl fmt::Display for SchedulingError {
@@ -286,6 +559,18 @@ impl fmt::Display for SchedulingError {
                 "the scheduled time should be in the future of the current simulation time"
             ),
             Self::NullRepetitionPeriod => write!(fmt, "the repetition period cannot be zero"),
+            Self::NoRealTimeClock => write!(
+                fmt,
+                "wall clock scheduling requires the simulation to be synchronized with a real-time clock"
+            ),
+            Self::ExpiredActionKey => write!(
+                fmt,
+                "the action key refers to an action that was already processed or cancelled"
+            ),
+            Self::NonMonotonicSchedule => write!(
+                fmt,
+                "the scheduled times should be in chronological order"
+            ),
         }
     }
 }
@@ -302,12 +587,29 @@ impl Error for SchedulingError {}
 /// [`Simulation::process`](crate::simulation::Simulation::process).
 pub struct Action {
     inner: Box<dyn ActionInner>,
+    is_query: bool,
 }
 
 impl Action {
-    /// Creates a new `Action` from an `ActionInner`.
+    /// Creates a new `Action` that sends an event from an `ActionInner`.
     pub(crate) fn new<S: ActionInner>(s: S) -> Self {
-        Self { inner: Box::new(s) }
+        Self {
+            inner: Box::new(s),
+            is_query: false,
+        }
+    }
+
+    /// Creates a new `Action` that sends a query from an `ActionInner`.
+    pub(crate) fn new_query<S: ActionInner>(s: S) -> Self {
+        Self {
+            inner: Box::new(s),
+            is_query: true,
+        }
+    }
+
+    /// Reports whether the action sends a query rather than an event.
+    pub(crate) fn is_query(&self) -> bool {
+        self.is_query
     }
 
     /// Reports whether the action was cancelled.
@@ -315,12 +617,23 @@ impl Action {
         self.inner.is_cancelled()
     }
 
+    /// Returns the key of the action, if any.
+    pub(crate) fn key(&self) -> Option<&ActionKey> {
+        self.inner.key()
+    }
+
     /// If this is a periodic action, returns a boxed clone of this action and
     /// its repetition period; otherwise returns `None`.
     pub(crate) fn next(&self) -> Option<(Action, Duration)> {
-        self.inner
-            .next()
-            .map(|(inner, period)| (Self { inner }, period))
+        self.inner.next().map(|(inner, period)| {
+            (
+                Self {
+                    inner,
+                    is_query: self.is_query,
+                },
+                period,
+            )
+        })
     }
 
     /// Returns a boxed future that performs the action.
@@ -351,29 +664,96 @@ impl fmt::Debug for Action {
 /// scheduler). The preservation of this ordering is implemented by the event
 /// loop, which aggregate events with the same origin into single sequential
 /// futures, thus ensuring that they are not executed concurrently.
-pub(crate) type SchedulerQueue = PriorityQueue<(MonotonicTime, usize), Action>;
+///
+/// The priority is placed between the time and the origin ID so that all
+/// actions with the same time and priority are contiguous in the queue, which
+/// lets the event loop process each priority level to completion before moving
+/// to the next one.
+pub(crate) type SchedulerQueue = PriorityQueue<(MonotonicTime, i32, usize), Action>;
+
+/// Returns the number of non-cancelled actions in the scheduler queue.
+pub(crate) fn pending_action_count(scheduler_queue: &SchedulerQueue) -> usize {
+    scheduler_queue
+        .values()
+        .filter(|action| !action.is_cancelled())
+        .count()
+}
+
+/// Returns the time of the next non-cancelled action in the scheduler queue,
+/// if any.
+///
+/// Cancelled actions are pulled and discarded.
+pub(crate) fn next_action_time(scheduler_queue: &mut SchedulerQueue) -> Option<MonotonicTime> {
+    loop {
+        match scheduler_queue.peek() {
+            Some((_, action)) if action.is_cancelled() => {
+                scheduler_queue.pull();
+            }
+            next => break next.map(|(key, _)| key.0),
+        }
+    }
+}
+
+/// A shared request to stop the simulation, together with its reason.
+#[derive(Default)]
+pub(crate) struct HaltSignal {
+    is_halted: AtomicBool,
+    reason: Mutex<String>,
+}
+
+impl HaltSignal {
+    /// Creates a new, unset halt signal.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the halt signal unless it was already set, in which case the
+    /// original reason is preserved.
+    pub(crate) fn halt(&self, reason: String) {
+        let mut current_reason = self.reason.lock().unwrap();
+        if !self.is_halted.load(Ordering::Relaxed) {
+            *current_reason = reason;
+            self.is_halted.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the reason for the halt if the signal is set.
+    pub(crate) fn reason(&self) -> Option<String> {
+        if !self.is_halted.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        Some(self.reason.lock().unwrap().clone())
+    }
+}
 
 /// Internal implementation of the global scheduler.
 #[derive(Clone)]
 pub(crate) struct GlobalScheduler {
     scheduler_queue: Arc<Mutex<SchedulerQueue>>,
     time: AtomicTimeReader,
-    is_halted: Arc<AtomicBool>,
+    halt_signal: Arc<HaltSignal>,
 }
 
 impl GlobalScheduler {
     pub(crate) fn new(
         scheduler_queue: Arc<Mutex<SchedulerQueue>>,
         time: AtomicTimeReader,
-        is_halted: Arc<AtomicBool>,
+        halt_signal: Arc<HaltSignal>,
     ) -> Self {
         Self {
             scheduler_queue,
             time,
-            is_halted,
+            halt_signal,
         }
     }
 
+    /// Returns a thread-safe handle to inject actions at the current
+    /// simulation time.
+    pub(crate) fn injector(&self) -> ActionInjector {
+        ActionInjector::new(&self.scheduler_queue, self.time.clone())
+    }
+
     /// Returns the current simulation time.
     pub(crate) fn time(&self) -> MonotonicTime {
         // We use `read` rather than `try_read` because the scheduler can be
@@ -384,11 +764,13 @@ impl GlobalScheduler {
         self.time.read()
     }
 
-    /// Schedules an action identified by its origin at a future time.
+    /// Schedules an action identified by its origin at a future time with the
+    /// specified priority.
     pub(crate) fn schedule_from(
         &self,
         deadline: impl Deadline,
         action: Action,
+        priority: i32,
         origin_id: usize,
     ) -> Result<(), SchedulingError> {
         // The scheduler queue must always be locked when reading the time,
@@ -401,23 +783,61 @@ impl GlobalScheduler {
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
 
         let now = self.time();
-        let time = deadline.into_time(now);
-        if now >= time {
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+
+        scheduler_queue.insert((time, priority, origin_id), action);
+
+        Ok(())
+    }
+
+    /// Schedules a batch of actions identified by their origin at future times
+    /// with the specified priority.
+    ///
+    /// No action is scheduled if any of the scheduling times is not in the
+    /// future.
+    pub(crate) fn schedule_batch_from<I>(
+        &self,
+        actions: I,
+        priority: i32,
+        origin_id: usize,
+    ) -> Result<(), SchedulingError>
+    where
+        I: IntoIterator<Item = (MonotonicTime, Action)>,
+    {
+        // The actions are collected before the queue is locked to keep the
+        // critical section as short as possible.
+        let actions: Vec<_> = actions.into_iter().collect();
+
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+
+        let now = self.time();
+        if actions.iter().any(|(time, _)| now >= *time) {
             return Err(SchedulingError::InvalidScheduledTime);
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert_all(
+            actions
+                .into_iter()
+                .map(|(time, action)| ((time, priority, origin_id), action)),
+        );
 
         Ok(())
     }
 
-    /// Schedules an event identified by its origin at a future time.
+    /// Schedules an event identified by its origin at a future time with the
+    /// specified priority.
     pub(crate) fn schedule_event_from<M, F, T, S>(
         &self,
         deadline: impl Deadline,
         func: F,
         arg: T,
         address: impl Into<Address<M>>,
+        priority: i32,
         origin_id: usize,
     ) -> Result<(), SchedulingError>
     where
@@ -433,12 +853,12 @@ impl GlobalScheduler {
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
-        if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
-        }
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, priority, origin_id), action);
 
         Ok(())
     }
@@ -470,12 +890,12 @@ impl GlobalScheduler {
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
-        if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
-        }
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
 
         Ok(event_key)
     }
@@ -510,12 +930,12 @@ impl GlobalScheduler {
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
-        if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
-        }
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
 
         Ok(())
     }
@@ -552,19 +972,179 @@ impl GlobalScheduler {
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
-        if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
+
+        Ok(event_key)
+    }
+
+    /// Schedules a cancellable, periodically recurring event identified by its
+    /// origin at a future time and returns an event key.
+    ///
+    /// Once the key is cancelled, an `on_cancel` event is delivered to the
+    /// target model in place of the next periodic event.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn schedule_keyed_periodic_event_with_cancel_from<M, F, T, S, G, U>(
+        &self,
+        deadline: impl Deadline,
+        period: Duration,
+        func: F,
+        arg: T,
+        on_cancel: G,
+        address: impl Into<Address<M>>,
+        origin_id: usize,
+    ) -> Result<ActionKey, SchedulingError>
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+        G: for<'a> InputFn<'a, M, (), U> + Clone,
+        U: Send + 'static,
+    {
+        if period.is_zero() {
+            return Err(SchedulingError::NullRepetitionPeriod);
         }
+        let sender = address.into().0;
+        let event_key = ActionKey::new();
+        let action = Action::new(KeyedPeriodicActionWithCancel::new(
+            |ek, is_notified| {
+                send_keyed_event_with_cancel(ek, is_notified, func, arg, on_cancel, sender)
+            },
+            period,
+            event_key.clone(),
+        ));
 
-        scheduler_queue.insert((time, origin_id), action);
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let now = self.time();
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
 
         Ok(event_key)
     }
 
-    /// Requests the simulation to stop when advancing to the next step.
-    pub(crate) fn halt(&mut self) {
-        self.is_halted.store(true, Ordering::Relaxed);
+    /// Schedules an event identified by its origin at a future time, which
+    /// delivery is conditioned by a guard evaluated on the target model.
+    pub(crate) fn schedule_conditional_event_from<M, G, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        guard: G,
+        func: F,
+        arg: T,
+        address: impl Into<Address<M>>,
+        origin_id: usize,
+    ) -> Result<(), SchedulingError>
+    where
+        M: Model,
+        G: Fn(&M) -> bool + Send + 'static,
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        let sender = address.into().0;
+        let action = Action::new(OnceAction::new(process_conditional_event(
+            guard, func, arg, sender,
+        )));
+
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let now = self.time();
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
+
+        Ok(())
+    }
+
+    /// Schedules a periodically recurring event identified by its origin at a
+    /// future time, which delivery is conditioned by a guard evaluated on the
+    /// target model at each period.
+    pub(crate) fn schedule_conditional_periodic_event_from<M, G, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        period: Duration,
+        guard: G,
+        func: F,
+        arg: T,
+        address: impl Into<Address<M>>,
+        origin_id: usize,
+    ) -> Result<(), SchedulingError>
+    where
+        M: Model,
+        G: Fn(&M) -> bool + Clone + Send + 'static,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        if period.is_zero() {
+            return Err(SchedulingError::NullRepetitionPeriod);
+        }
+        let sender = address.into().0;
+        let action = Action::new(PeriodicAction::new(
+            || process_conditional_event(guard, func, arg, sender),
+            period,
+        ));
+
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let now = self.time();
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+
+        scheduler_queue.insert((time, DEFAULT_PRIORITY, origin_id), action);
+
+        Ok(())
+    }
+
+    /// Moves a pending keyed action to a new time, preserving its priority and
+    /// origin.
+    pub(crate) fn reschedule(
+        &self,
+        key: &ActionKey,
+        deadline: impl Deadline,
+    ) -> Result<(), SchedulingError> {
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let now = self.time();
+        let time = match deadline.checked_into_time(now) {
+            Some(time) if time > now => time,
+            _ => return Err(SchedulingError::InvalidScheduledTime),
+        };
+        if key.is_cancelled() {
+            return Err(SchedulingError::ExpiredActionKey);
+        }
+
+        let ((_, priority, origin_id), action) = scheduler_queue
+            .extract(|action| action.key() == Some(key))
+            .ok_or(SchedulingError::ExpiredActionKey)?;
+
+        scheduler_queue.insert((time, priority, origin_id), action);
+
+        Ok(())
+    }
+
+    /// Requests the simulation to stop when advancing to the next step,
+    /// specifying the reason for the halt.
+    pub(crate) fn halt(&mut self, reason: String) {
+        self.halt_signal.halt(reason);
     }
 }
 
@@ -581,6 +1161,9 @@ pub(crate) trait ActionInner: Send + 'static {
     /// Reports whether the action was cancelled.
     fn is_cancelled(&self) -> bool;
 
+    /// Returns the key of the action, if any.
+    fn key(&self) -> Option<&ActionKey>;
+
     /// If this is a periodic action, returns a boxed clone of this action and
     /// its repetition period; otherwise returns `None`.
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)>;
@@ -637,6 +1220,9 @@ where
     fn is_cancelled(&self) -> bool {
         false
     }
+    fn key(&self) -> Option<&ActionKey> {
+        None
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         None
     }
@@ -681,6 +1267,9 @@ where
     fn is_cancelled(&self) -> bool {
         false
     }
+    fn key(&self) -> Option<&ActionKey> {
+        None
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         let event = Box::new(Self::new(self.gen.clone(), self.period));
 
@@ -726,13 +1315,18 @@ where
     fn is_cancelled(&self) -> bool {
         self.event_key.is_cancelled()
     }
+    fn key(&self) -> Option<&ActionKey> {
+        Some(&self.event_key)
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         None
     }
     fn into_future(self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        self.event_key.set_processed();
         Box::pin((self.gen)(self.event_key))
     }
     fn spawn_and_forget(self: Box<Self>, executor: &Executor) {
+        self.event_key.set_processed();
         executor.spawn_and_forget((self.gen)(self.event_key));
     }
 }
@@ -775,6 +1369,9 @@ where
     fn is_cancelled(&self) -> bool {
         self.event_key.is_cancelled()
     }
+    fn key(&self) -> Option<&ActionKey> {
+        Some(&self.event_key)
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         let event = Box::new(Self::new(
             self.gen.clone(),
@@ -792,6 +1389,211 @@ where
     }
 }
 
+/// An object that can be converted to a future performing a periodic action
+/// that can be cancelled, and which performs a final notification action once
+/// cancelled.
+///
+/// The action is only discarded by the scheduler once the notification has
+/// been performed.
+pub(crate) struct KeyedPeriodicActionWithCancel<G, F>
+where
+    G: (FnOnce(ActionKey, Arc<AtomicBool>) -> F) + Clone + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    /// A clonable generator for associated future.
+    gen: G,
+    /// The repetition period.
+    period: Duration,
+    /// The event cancellation key.
+    event_key: ActionKey,
+    /// A flag set once the cancellation has been notified.
+    is_notified: Arc<AtomicBool>,
+}
+
+impl<G, F> KeyedPeriodicActionWithCancel<G, F>
+where
+    G: (FnOnce(ActionKey, Arc<AtomicBool>) -> F) + Clone + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    /// Constructs a new `KeyedPeriodicActionWithCancel`.
+    pub(crate) fn new(gen: G, period: Duration, event_key: ActionKey) -> Self {
+        Self {
+            gen,
+            period,
+            event_key,
+            is_notified: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<G, F> ActionInner for KeyedPeriodicActionWithCancel<G, F>
+where
+    G: (FnOnce(ActionKey, Arc<AtomicBool>) -> F) + Clone + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn is_cancelled(&self) -> bool {
+        self.is_notified.load(Ordering::Relaxed)
+    }
+    fn key(&self) -> Option<&ActionKey> {
+        Some(&self.event_key)
+    }
+    fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
+        // A cancelled action is not repeated since the cancellation is
+        // notified by this occurrence.
+        if self.event_key.is_cancelled() {
+            return None;
+        }
+
+        let event = Box::new(Self {
+            gen: self.gen.clone(),
+            period: self.period,
+            event_key: self.event_key.clone(),
+            is_notified: self.is_notified.clone(),
+        });
+
+        Some((event, self.period))
+    }
+    fn into_future(self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin((self.gen)(self.event_key, self.is_notified))
+    }
+    fn spawn_and_forget(self: Box<Self>, executor: &Executor) {
+        executor.spawn_and_forget((self.gen)(self.event_key, self.is_notified));
+    }
+}
+
+/// A handle inserting actions into the scheduler queue at the current
+/// simulation time.
+///
+/// Unlike a [`GlobalScheduler`], an `ActionInjector` does not keep the
+/// scheduler queue alive.
+#[derive(Clone)]
+pub(crate) struct ActionInjector {
+    scheduler_queue: Weak<Mutex<SchedulerQueue>>,
+    time: AtomicTimeReader,
+}
+
+impl ActionInjector {
+    pub(crate) fn new(
+        scheduler_queue: &Arc<Mutex<SchedulerQueue>>,
+        time: AtomicTimeReader,
+    ) -> Self {
+        Self {
+            scheduler_queue: Arc::downgrade(scheduler_queue),
+            time,
+        }
+    }
+
+    /// Inserts an action at the current simulation time.
+    ///
+    /// The action is returned if the scheduler queue no longer exists.
+    pub(crate) fn inject(&self, action: Action) -> Result<(), Action> {
+        let scheduler_queue = match self.scheduler_queue.upgrade() {
+            Some(scheduler_queue) => scheduler_queue,
+            None => return Err(action),
+        };
+
+        // The scheduler queue must always be locked when reading the time (see
+        // `GlobalScheduler::schedule_from`).
+        let mut scheduler_queue = scheduler_queue.lock().unwrap();
+        let now = self.time.read();
+        scheduler_queue.insert((now, DEFAULT_PRIORITY, GLOBAL_SCHEDULER_ORIGIN_ID), action);
+
+        Ok(())
+    }
+
+    /// Inserts an event targeting a model input at the current simulation
+    /// time.
+    ///
+    /// The action is returned if the scheduler queue no longer exists.
+    pub(crate) fn inject_event<M, F, T, S>(
+        &self,
+        func: F,
+        arg: T,
+        sender: Sender<M>,
+    ) -> Result<(), Action>
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + Clone + 'static,
+        S: Send + 'static,
+    {
+        self.inject(Action::new(OnceAction::new(process_event(
+            func, arg, sender,
+        ))))
+    }
+}
+
+impl fmt::Debug for ActionInjector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionInjector").finish_non_exhaustive()
+    }
+}
+
+/// A signal used to wake up a simulation waiting for events injected by
+/// external event sources.
+#[derive(Default)]
+pub(crate) struct ExternalSignal {
+    state: Mutex<ExternalSignalState>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct ExternalSignalState {
+    /// The number of live external event sources.
+    source_count: usize,
+    /// Whether an event was injected since the last wake-up.
+    is_notified: bool,
+}
+
+impl ExternalSignal {
+    /// Registers a new external event source.
+    pub(crate) fn register_source(&self) {
+        self.state.lock().unwrap().source_count += 1;
+    }
+
+    /// Unregisters an external event source, waking up the simulation if this
+    /// was the last source.
+    pub(crate) fn unregister_source(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.source_count -= 1;
+        if state.source_count == 0 {
+            self.condvar.notify_all();
+        }
+    }
+
+    /// Notifies the simulation that an event was injected.
+    pub(crate) fn notify(&self) {
+        self.state.lock().unwrap().is_notified = true;
+        self.condvar.notify_all();
+    }
+
+    /// Blocks until an event is injected or until no external event source
+    /// remains.
+    ///
+    /// Returns `true` if an event was injected since the last call, even if
+    /// no external source remains, and `false` otherwise.
+    pub(crate) fn wait(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.is_notified {
+                state.is_notified = false;
+
+                return true;
+            }
+            if state.source_count == 0 {
+                return false;
+            }
+            state = self.condvar.wait(state).unwrap();
+        }
+    }
+}
+
+impl fmt::Debug for ExternalSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalSignal").finish_non_exhaustive()
+    }
+}
+
 /// Asynchronously sends a non-cancellable event to a model input.
 pub(crate) async fn process_event<M, F, T, S>(func: F, arg: T, sender: Sender<M>)
 where
@@ -813,6 +1615,38 @@ where
         .await;
 }
 
+/// Asynchronously sends a non-cancellable event to a model input, provided
+/// that the guard evaluated on the model at delivery time returns `true`.
+pub(crate) async fn process_conditional_event<M, G, F, T, S>(
+    guard: G,
+    func: F,
+    arg: T,
+    sender: Sender<M>,
+) where
+    M: Model,
+    G: Fn(&M) -> bool + Send + 'static,
+    F: for<'a> InputFn<'a, M, T, S>,
+    T: Send + 'static,
+{
+    let _ = sender
+        .send(
+            move |model: &mut M,
+                  scheduler,
+                  recycle_box: RecycleBox<()>|
+                  -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                let fut = async move {
+                    // Only perform the call if the guard still holds.
+                    if guard(&*model) {
+                        func.call(model, arg, scheduler).await;
+                    }
+                };
+
+                coerce_box!(RecycleBox::recycle(recycle_box, fut))
+            },
+        )
+        .await;
+}
+
 /// Asynchronously sends a cancellable event to a model input.
 pub(crate) async fn send_keyed_event<M, F, T, S>(
     event_key: ActionKey,
@@ -843,13 +1677,53 @@ pub(crate) async fn send_keyed_event<M, F, T, S>(
         .await;
 }
 
-#[cfg(all(test, not(nexosim_loom)))]
+/// Asynchronously sends a cancellable event to a model input, or a
+/// notification event to another input if the event was cancelled and the
+/// cancellation was not notified yet.
+pub(crate) async fn send_keyed_event_with_cancel<M, F, T, S, G, U>(
+    event_key: ActionKey,
+    is_notified: Arc<AtomicBool>,
+    func: F,
+    arg: T,
+    on_cancel: G,
+    sender: Sender<M>,
+) where
+    M: Model,
+    F: for<'a> InputFn<'a, M, T, S>,
+    T: Send + Clone + 'static,
+    G: for<'a> InputFn<'a, M, (), U>,
+    U: Send + 'static,
+{
+    let _ = sender
+        .send(
+            move |model: &mut M,
+                  scheduler,
+                  recycle_box: RecycleBox<()>|
+                  -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                let fut = async move {
+                    // The cancellation is checked upon delivery so that a
+                    // cancellation by the model itself is taken into account
+                    // by the next occurrence of the event.
+                    if !event_key.is_cancelled() {
+                        func.call(model, arg, scheduler).await;
+                    } else if !is_notified.swap(true, Ordering::Relaxed) {
+                        on_cancel.call(model, (), scheduler).await;
+                    }
+                };
+
+                coerce_box!(RecycleBox::recycle(recycle_box, fut))
+            },
+        )
+        .await;
+}
+
+#[cfg(any(all(test, not(nexosim_loom)), feature = "dev-hooks"))]
 impl GlobalScheduler {
     /// Creates a dummy scheduler for testing purposes.
     pub(crate) fn new_dummy() -> Self {
         let dummy_priority_queue = Arc::new(Mutex::new(PriorityQueue::new()));
         let dummy_time = SyncCell::new(TearableAtomicTime::new(MonotonicTime::EPOCH)).reader();
-        let dummy_halter = Arc::new(AtomicBool::new(false));
+        let dummy_halter = Arc::new(HaltSignal::new());
         GlobalScheduler::new(dummy_priority_queue, dummy_time, dummy_halter)
     }
 }
@@ -1,18 +1,21 @@
 use std::fmt;
-use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures_channel::oneshot;
 
-use crate::channel::ChannelObserver;
 use crate::executor::{Executor, SimulationContext};
 use crate::model::ProtoModel;
+use crate::ports::{EventSource, ExternalEventSource};
 use crate::time::{AtomicTime, Clock, MonotonicTime, NoClock, SyncStatus, TearableAtomicTime};
 use crate::util::priority_queue::PriorityQueue;
+use crate::util::rng::SharedSeed;
 use crate::util::sync_cell::SyncCell;
 
 use super::{
-    add_model, ExecutionError, GlobalScheduler, Mailbox, Scheduler, SchedulerQueue, Signal,
-    Simulation,
+    add_model, ActionInjector, DescriptionRegistry, ExecutionError, ExternalSignal,
+    GlobalScheduler, HaltSignal, InitTurn, Mailbox, ModelObserver, PanicRegistry, PendingModels,
+    Scheduler, SchedulerQueue, Signal, Simulation, ValidationInfo,
 };
 
 /// Builder for a multi-threaded, discrete-event simulation.
@@ -20,13 +23,23 @@ pub struct SimInit {
     executor: Executor,
     scheduler_queue: Arc<Mutex<SchedulerQueue>>,
     time: AtomicTime,
-    is_halted: Arc<AtomicBool>,
+    halt_signal: Arc<HaltSignal>,
+    external_signal: Arc<ExternalSignal>,
     clock: Box<dyn Clock + 'static>,
     clock_tolerance: Option<Duration>,
     timeout: Duration,
-    observers: Vec<(String, Box<dyn ChannelObserver>)>,
+    wall_clock_limit: Duration,
+    observers: Vec<ModelObserver>,
     abort_signal: Signal,
     model_names: Vec<String>,
+    rng_seed: SharedSeed,
+    panic_registry: Arc<PanicRegistry>,
+    description_registry: Arc<DescriptionRegistry>,
+    validation_failures: Vec<ValidationInfo>,
+    deterministic_ordering: bool,
+    check_invariants: bool,
+    parallel_init: bool,
+    last_init_turn: Option<oneshot::Receiver<()>>,
 }
 
 impl SimInit {
@@ -49,9 +62,15 @@ impl SimInit {
             num_threads.clamp(1, usize::BITS as usize)
         };
         let time = SyncCell::new(TearableAtomicTime::new(MonotonicTime::EPOCH));
+        let scheduler_queue = Arc::new(Mutex::new(PriorityQueue::new()));
+        let halt_signal = Arc::new(HaltSignal::new());
         let simulation_context = SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: time.reader(),
+            scheduler: GlobalScheduler::new(
+                scheduler_queue.clone(),
+                time.reader(),
+                halt_signal.clone(),
+            ),
         };
 
         let abort_signal = Signal::new();
@@ -63,18 +82,55 @@ impl SimInit {
 
         Self {
             executor,
-            scheduler_queue: Arc::new(Mutex::new(PriorityQueue::new())),
+            scheduler_queue,
             time,
-            is_halted: Arc::new(AtomicBool::new(false)),
+            halt_signal,
+            external_signal: Arc::new(ExternalSignal::default()),
             clock: Box::new(NoClock::new()),
             clock_tolerance: None,
             timeout: Duration::ZERO,
+            wall_clock_limit: Duration::ZERO,
             observers: Vec::new(),
             abort_signal,
             model_names: Vec::new(),
+            rng_seed: SharedSeed::default(),
+            panic_registry: Arc::new(PanicRegistry::default()),
+            description_registry: Arc::new(DescriptionRegistry::default()),
+            validation_failures: Vec::new(),
+            deterministic_ordering: false,
+            check_invariants: false,
+            parallel_init: false,
+            last_init_turn: None,
         }
     }
 
+    /// Creates a builder for a simulation with the specified configuration.
+    ///
+    /// See [`SimConfig`] for the settings that can be captured in a
+    /// configuration. Settings can still be modified on the returned builder.
+    pub fn with_config(config: &SimConfig) -> Self {
+        let mut sim_init = Self::with_num_threads(config.num_threads);
+        if let Some(clock_factory) = &config.clock_factory {
+            sim_init.clock = clock_factory();
+        }
+        sim_init.clock_tolerance = config.clock_tolerance;
+        sim_init.timeout = config.timeout;
+        sim_init.wall_clock_limit = config.wall_clock_limit;
+        if let Some(seed) = config.seed {
+            sim_init = sim_init.set_seed(seed);
+        }
+        if config.deterministic_ordering {
+            sim_init = sim_init.deterministic_ordering();
+        }
+        if config.catch_model_panics {
+            sim_init = sim_init.catch_model_panics();
+        }
+        sim_init.check_invariants = config.check_invariants;
+        sim_init.parallel_init = config.parallel_init;
+
+        sim_init
+    }
+
     /// Adds a model and its mailbox to the simulation bench.
     ///
     /// The `name` argument needs not be unique. The use of the dot character in
@@ -82,36 +138,144 @@ impl SimInit {
     /// fully qualified name of a submodel. If an empty string is provided, it
     /// is replaced by the string `<unknown>`.
     pub fn add_model<P: ProtoModel>(
+        self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+    ) -> Self {
+        self.add_model_with_turn(model, mailbox, name.into(), None)
+    }
+
+    /// Adds a model and its mailbox to the simulation bench, opting out of
+    /// parallel initialization.
+    ///
+    /// This method behaves like [`SimInit::add_model`], but the
+    /// [`Model::init`] method of the model is only executed once the
+    /// initializers of all models previously added with this method have
+    /// completed. Models added with this method are thus initialized one at a
+    /// time and in the order they were added, even if
+    /// [`SimInit::parallel_init`] is enabled; they are still initialized
+    /// concurrently with the other models. The submodels of the model are not
+    /// affected.
+    ///
+    /// Note that a model waiting for its turn does not process messages. A
+    /// model sending more messages to a model added later with this method
+    /// than its mailbox can hold would therefore block its own initialization
+    /// and cause a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, InitializedModel, Model};
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// pub struct Device {}
+    ///
+    /// impl Model for Device {
+    ///     async fn init(self, _: &mut Context<Self>) -> InitializedModel<Self> {
+    ///         // ... blocking setup of a device on a shared bus ...
+    ///
+    ///         self.into()
+    ///     }
+    /// }
+    ///
+    /// // Devices on the same bus are initialized sequentially.
+    /// let mut bench = SimInit::new().parallel_init();
+    /// for i in 0..4 {
+    ///     bench = bench.add_model_sequential_init(
+    ///         Device {},
+    ///         Mailbox::new(),
+    ///         format!("device{}", i),
+    ///     );
+    /// }
+    /// let (_simu, _scheduler) = bench.init(MonotonicTime::EPOCH).unwrap();
+    /// ```
+    ///
+    /// [`Model::init`]: crate::model::Model::init
+    pub fn add_model_sequential_init<P: ProtoModel>(
         mut self,
         model: P,
         mailbox: Mailbox<P::Model>,
         name: impl Into<String>,
     ) -> Self {
-        let mut name = name.into();
+        let (next, last) = oneshot::channel();
+        let init_turn = InitTurn::new(self.last_init_turn.replace(last), next);
+
+        self.add_model_with_turn(model, mailbox, name.into(), Some(init_turn))
+    }
+
+    /// Adds a model and its mailbox to the simulation bench, optionally with
+    /// a turn in the sequential initialization of models.
+    fn add_model_with_turn<P: ProtoModel>(
+        mut self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        mut name: String,
+        init_turn: Option<InitTurn>,
+    ) -> Self {
         if name.is_empty() {
             name = String::from("<unknown>");
         };
-        self.observers
-            .push((name.clone(), Box::new(mailbox.0.observer())));
         let scheduler = GlobalScheduler::new(
             self.scheduler_queue.clone(),
             self.time.reader(),
-            self.is_halted.clone(),
+            self.halt_signal.clone(),
         );
 
+        let mut pending_models = PendingModels::default();
         add_model(
             model,
             mailbox,
             name,
             scheduler,
-            &self.executor,
+            &mut pending_models,
             &self.abort_signal,
             &mut self.model_names,
+            &self.rng_seed,
+            &self.panic_registry,
+            &self.description_registry,
+            self.deterministic_ordering,
+            false,
+            init_turn,
+            &mut self.validation_failures,
         );
+        pending_models.spawn_all(&self.executor, &mut self.observers);
 
         self
     }
 
+    /// Adds several models and their mailboxes to the simulation bench.
+    ///
+    /// This is equivalent to calling [`SimInit::add_model`] for each entry.
+    /// Since [`ModelEntry`] is type-erased, the models may be of different
+    /// types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::simulation::{Mailbox, ModelEntry, SimInit};
+    ///
+    /// #[derive(Default)]
+    /// pub struct Cell {}
+    /// impl Model for Cell {}
+    ///
+    /// // A grid of 4x4 cells.
+    /// let bench = SimInit::new().add_models((0..16).map(|i| {
+    ///     ModelEntry::new(
+    ///         Cell::default(),
+    ///         Mailbox::new(),
+    ///         format!("cell_{}_{}", i / 4, i % 4),
+    ///     )
+    /// }));
+    /// ```
+    pub fn add_models(self, entries: impl IntoIterator<Item = ModelEntry>) -> Self {
+        entries
+            .into_iter()
+            .fold(self, |sim_init, entry| (entry.add)(sim_init))
+    }
+
     /// Synchronizes the simulation with the provided [`Clock`].
     ///
     /// If the clock isn't explicitly set then the default [`NoClock`] is used,
@@ -133,6 +297,22 @@ impl SimInit {
         self
     }
 
+    /// Sets the seed from which the pseudo-random number generators of all
+    /// models are derived.
+    ///
+    /// The generator of each model, accessed with
+    /// [`Context::rng`](crate::model::Context::rng), is seeded from this seed
+    /// and from the index of the model, which makes simulation runs
+    /// reproducible even with multi-threaded execution. If no seed is set, the
+    /// generators are seeded from a source of entropy.
+    ///
+    /// The seed applies to all models, including those added before this call.
+    pub fn set_seed(self, seed: u64) -> Self {
+        *self.rng_seed.lock().unwrap() = Some(seed);
+
+        self
+    }
+
     /// Sets a timeout for the call to [`SimInit::init`] and for any subsequent
     /// simulation step.
     ///
@@ -150,6 +330,189 @@ impl SimInit {
         self
     }
 
+    /// Sets a limit on the total wall clock time of the simulation.
+    ///
+    /// The limit covers the call to [`SimInit::init`] and all subsequent
+    /// simulation steps. Unlike the timeout set with
+    /// [`set_timeout`](SimInit::set_timeout), which applies to each step
+    /// individually, the limit is a budget for the whole simulation run. It is
+    /// meant as a safeguard against runaway simulations, for instance in CI,
+    /// where a model stuck in an infinite loop or a deadlock would otherwise
+    /// hang the test runner.
+    ///
+    /// Once the limit is exceeded, the executor is stopped and an
+    /// [`ExecutionError::WallClockTimeout`] error is returned. The limit is
+    /// also checked after each synchronization with the simulation
+    /// [`Clock`], but note that a wait for the clock is not interrupted when
+    /// the limit is exceeded.
+    ///
+    /// A null duration disables the limit, which is the default behavior.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn set_wall_clock_limit(mut self, limit: Duration) -> Self {
+        self.wall_clock_limit = limit;
+
+        self
+    }
+
+    /// Returns a thread-safe handle to the specified event source which can be
+    /// used to inject events into the simulation at the current simulation
+    /// time.
+    ///
+    /// See [`ExternalEventSource`] for more details.
+    pub fn external_event_source<T: Clone + Send + 'static>(
+        &self,
+        source: EventSource<T>,
+    ) -> ExternalEventSource<T> {
+        ExternalEventSource::new(
+            source,
+            ActionInjector::new(&self.scheduler_queue, self.time.reader()),
+            self.external_signal.clone(),
+        )
+    }
+
+    /// Makes the processing order of events and queries reproducible.
+    ///
+    /// By default, the relative order in which a model receives messages sent
+    /// within the same time slice by different models is not specified (see
+    /// the [`ports`](crate::ports) module). With this option, the simulation
+    /// runs on a single thread, irrespective of the number of threads
+    /// requested, and the order of execution only depends on the simulation
+    /// bench and on its inputs, so that repeated runs produce identical
+    /// orderings.
+    ///
+    /// The exact ordering rule is the following:
+    ///
+    /// * actions scheduled for the same time are processed by increasing
+    ///   priority, then by origin, actions scheduled with a [`Scheduler`] or
+    ///   the [`Simulation`] being processed first and actions scheduled by
+    ///   models being processed in the order in which the models were added to
+    ///   the bench (submodels are added before their parent), and finally in
+    ///   the order in which they were scheduled,
+    /// * each action, and each message sent by a model, is processed to the
+    ///   point where it is blocked before the next one is processed, messages
+    ///   being delivered to models in the order in which they were sent.
+    ///
+    /// In other words, when two models send an event to the same input port
+    /// within the same time slice, the event that reaches the model first is
+    /// the one which causal chain originates from the action that is
+    /// processed first.
+    ///
+    /// This option only affects the simulation benches for which it is
+    /// requested: by default, same-time actions scheduled by different models
+    /// are processed in an unspecified order.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if a model was already added to the bench, since the
+    /// ordering key of a model is assigned when it is added. It should
+    /// therefore be called right after the builder is created, or be set in a
+    /// [`SimConfig`].
+    pub fn deterministic_ordering(mut self) -> Self {
+        assert!(
+            self.model_names.is_empty(),
+            "deterministic ordering must be requested before any model is added"
+        );
+        self.deterministic_ordering = true;
+
+        if let Executor::MtExecutor(_) = self.executor {
+            let simulation_context = SimulationContext {
+                time_reader: self.time.reader(),
+                scheduler: GlobalScheduler::new(
+                    self.scheduler_queue.clone(),
+                    self.time.reader(),
+                    self.halt_signal.clone(),
+                ),
+            };
+            self.executor =
+                Executor::new_single_threaded(simulation_context, self.abort_signal.clone());
+        }
+
+        self
+    }
+
+    /// Catches panics raised by model input and replier methods rather than
+    /// aborting the simulation.
+    ///
+    /// By default, a panic in a model is a fatal error reported as an
+    /// [`ExecutionError::Panic`]. With this option, a panic raised while a
+    /// model processes an event or a query is caught, the message is discarded
+    /// and the model keeps processing subsequent messages. The panic is then
+    /// reported as a non-fatal [`ExecutionError::ModelPanic`] by the
+    /// simulation method that was being executed, after all other actions of
+    /// the current step have been processed.
+    ///
+    /// This option applies to all models, including those added before this
+    /// call. Panics raised by [`Model::init`](crate::model::Model::init) are
+    /// not caught.
+    ///
+    /// # Unwind safety
+    ///
+    /// Models are not required to be unwind-safe: a model that panicked may
+    /// be left in an inconsistent state, for instance if the panic occurred
+    /// while some of its fields were being updated. Likewise, the panicking
+    /// method may have only sent some of its events. Messages awaiting a reply
+    /// from a panicking replier are also interrupted and discarded. It is the
+    /// responsibility of the caller to decide whether the simulation can be
+    /// meaningfully resumed after a model panic.
+    ///
+    /// Note that the panic hook is still called when a panic is caught, so the
+    /// panic message is printed to the standard error by default.
+    pub fn catch_model_panics(self) -> Self {
+        self.panic_registry.enable();
+
+        self
+    }
+
+    /// Checks the invariants of all models at the end of each time slice.
+    ///
+    /// With this option, the [`Model::check_invariants`] method of each model
+    /// and submodel is called once all actions scheduled for the current
+    /// simulation time have been processed by a stepping method of
+    /// [`Simulation`], as well as after each action processed immediately with
+    /// [`Simulation::process`], [`Simulation::process_event`] or
+    /// [`Simulation::process_query`]. A violation is reported as a fatal
+    /// [`ExecutionError::InvariantViolated`] error.
+    ///
+    /// Invariants are not checked by default since this requires an additional
+    /// round of messages to all models for each time slice.
+    ///
+    /// [`Model::check_invariants`]: crate::model::Model::check_invariants
+    pub fn check_invariants(mut self) -> Self {
+        self.check_invariants = true;
+
+        self
+    }
+
+    /// Runs the [`Model::init`] methods of all models in parallel across the
+    /// worker threads.
+    ///
+    /// Model initializers are always executed concurrently, but they are
+    /// otherwise only spread to other worker threads as tasks get woken, for
+    /// instance when messages are sent. Initializers performing blocking
+    /// operations, such as opening a device, thus tend to be executed
+    /// sequentially on a single thread. With this option, all worker threads
+    /// are put to work from the start, which can considerably shorten the call
+    /// to [`SimInit::init`] for large benches. This option has no effect on
+    /// single-threaded simulations.
+    ///
+    /// Events and queries sent by a model during its initialization are
+    /// processed by the recipient after its own initialization has completed,
+    /// just as without this option, and events sent by the same model are
+    /// processed in the order they were sent. The relative processing order of
+    /// events sent by different models during initialization is in turn
+    /// unspecified.
+    ///
+    /// Models which initialization must not run concurrently with that of
+    /// other models can opt out by being added with
+    /// [`SimInit::add_model_sequential_init`].
+    ///
+    /// [`Model::init`]: crate::model::Model::init
+    pub fn parallel_init(mut self) -> Self {
+        self.parallel_init = true;
+
+        self
+    }
+
     /// Builds a simulation initialized at the specified simulation time,
     /// executing the [`Model::init`](crate::model::Model::init) method on all
     /// model initializers.
@@ -160,6 +523,17 @@ impl SimInit {
         mut self,
         start_time: MonotonicTime,
     ) -> Result<(Simulation, Scheduler), ExecutionError> {
+        if !self.validation_failures.is_empty() {
+            return Err(ExecutionError::ValidationFailure(self.validation_failures));
+        }
+        // A limit too large to be represented as an instant can never be
+        // exceeded and is treated as no limit.
+        let wall_clock_deadline = if self.wall_clock_limit.is_zero() {
+            None
+        } else {
+            Instant::now().checked_add(self.wall_clock_limit)
+        };
+
         self.time.write(start_time);
         if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(start_time) {
             if let Some(tolerance) = &self.clock_tolerance {
@@ -169,11 +543,8 @@ impl SimInit {
             }
         }
 
-        let scheduler = Scheduler::new(
-            self.scheduler_queue.clone(),
-            self.time.reader(),
-            self.is_halted.clone(),
-        );
+        // The clock reference is only queried by the simulation after the first
+        // synchronization since some clocks are initialized at this point.
         let mut simulation = Simulation::new(
             self.executor,
             self.scheduler_queue,
@@ -181,14 +552,57 @@ impl SimInit {
             self.clock,
             self.clock_tolerance,
             self.timeout,
+            wall_clock_deadline,
             self.observers,
+            self.abort_signal,
             self.model_names,
-            self.is_halted,
+            self.rng_seed,
+            self.panic_registry,
+            self.description_registry,
+            self.halt_signal,
+            self.external_signal,
+            self.deterministic_ordering,
+            self.check_invariants,
         );
-        simulation.run()?;
+        let scheduler = simulation.scheduler();
+        simulation.run_with(self.parallel_init)?;
 
         Ok((simulation, scheduler))
     }
+
+    /// Builds a simulation initialized at the current system time.
+    ///
+    /// This is a convenience method equivalent to [`SimInit::init`] with a
+    /// start time obtained from [`SystemTime::now`], so that the simulation
+    /// time lines up with the wall clock from the first step, for instance
+    /// when used with an [`AutoSystemClock`](crate::time::AutoSystemClock).
+    ///
+    /// Since [`MonotonicTime`] is a TAI timestamp whereas the system time is a
+    /// UTC-based Unix timestamp, the TAI-UTC offset in seconds (a.k.a. *leap
+    /// seconds*) must be provided. This offset has been 37s since 2017-01-01.
+    /// No leap second table is used: the start time is the Unix time plus the
+    /// provided offset, following the same convention as the `chrono`
+    /// conversions of [`MonotonicTime`]. Note that the Unix
+    /// time is ambiguous during a leap second insertion, so the start time may
+    /// be off by up to one second if the simulation is initialized during a
+    /// leap second.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the system time is earlier than the Unix epoch.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn init_now(self, leap_secs: i64) -> Result<(Simulation, Scheduler), ExecutionError> {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the system time is earlier than the Unix epoch");
+        let start_time = MonotonicTime::new(
+            since_epoch.as_secs() as i64 + leap_secs,
+            since_epoch.subsec_nanos(),
+        )
+        .unwrap();
+
+        self.init(start_time)
+    }
 }
 
 impl Default for SimInit {
@@ -202,3 +616,230 @@ impl fmt::Debug for SimInit {
         f.debug_struct("SimInit").finish_non_exhaustive()
     }
 }
+
+/// A type-erased model prototype with its mailbox and name.
+///
+/// A `ModelEntry` can be added to a simulation bench with
+/// [`SimInit::add_models`].
+pub struct ModelEntry {
+    add: Box<dyn FnOnce(SimInit) -> SimInit>,
+}
+
+impl ModelEntry {
+    /// Creates a new entry for the specified model prototype, mailbox and
+    /// name.
+    ///
+    /// The arguments follow the same rules as for [`SimInit::add_model`].
+    pub fn new<P: ProtoModel + 'static>(
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+
+        Self {
+            add: Box::new(move |sim_init| sim_init.add_model(model, mailbox, name)),
+        }
+    }
+}
+
+impl fmt::Debug for ModelEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelEntry").finish_non_exhaustive()
+    }
+}
+
+/// A reusable configuration for a [`SimInit`] builder.
+///
+/// A `SimConfig` captures the settings of a simulation bench that do not
+/// depend on its models, namely the number of threads, the clock and its
+/// tolerance, the timeout and wall clock limit, the seed, the ordering policy,
+/// and the panic, invariant checking and initialization options. It makes it
+/// possible to create several benches with the same settings, for instance to
+/// run parameter sweeps, using [`SimInit::with_config`]. Models must still be
+/// instantiated anew for each bench.
+///
+/// Since clocks are stateful, the configuration holds a clock factory which is
+/// called each time a builder is created.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::simulation::{SimConfig, SimInit};
+/// use nexosim::time::{AutoSystemClock, MonotonicTime};
+///
+/// let config = SimConfig::with_num_threads(2)
+///     .set_clock(AutoSystemClock::new)
+///     .set_clock_tolerance(Duration::from_millis(10))
+///     .set_seed(42);
+///
+/// for _ in 0..3 {
+///     let bench = SimInit::with_config(&config);
+///     // Add the models of this trial...
+///     let (_simu, _scheduler) = bench.init(MonotonicTime::EPOCH).unwrap();
+///     // Run the trial...
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SimConfig {
+    num_threads: usize,
+    clock_factory: Option<Arc<ClockFactory>>,
+    clock_tolerance: Option<Duration>,
+    timeout: Duration,
+    wall_clock_limit: Duration,
+    seed: Option<u64>,
+    deterministic_ordering: bool,
+    catch_model_panics: bool,
+    check_invariants: bool,
+    parallel_init: bool,
+}
+
+impl SimConfig {
+    /// Creates a configuration for a multithreaded simulation running on all
+    /// available logical threads.
+    pub fn new() -> Self {
+        Self::with_num_threads(num_cpus::get())
+    }
+
+    /// Creates a configuration for a simulation running on the specified
+    /// number of threads.
+    ///
+    /// See [`SimInit::with_num_threads`].
+    pub fn with_num_threads(num_threads: usize) -> Self {
+        Self {
+            num_threads,
+            clock_factory: None,
+            clock_tolerance: None,
+            timeout: Duration::ZERO,
+            wall_clock_limit: Duration::ZERO,
+            seed: None,
+            deterministic_ordering: false,
+            catch_model_panics: false,
+            check_invariants: false,
+            parallel_init: false,
+        }
+    }
+
+    /// Sets a factory for the clock with which the simulation is
+    /// synchronized.
+    ///
+    /// The factory is called each time a builder is created with
+    /// [`SimInit::with_config`]. If no factory is set, the default
+    /// [`NoClock`] is used.
+    ///
+    /// See [`SimInit::set_clock`].
+    pub fn set_clock<C, F>(mut self, clock_factory: F) -> Self
+    where
+        C: Clock + 'static,
+        F: Fn() -> C + Send + Sync + 'static,
+    {
+        let clock_factory = move || -> Box<dyn Clock> { Box::new(clock_factory()) };
+        self.clock_factory = Some(Arc::new(clock_factory));
+
+        self
+    }
+
+    /// Specifies a tolerance for clock synchronization.
+    ///
+    /// See [`SimInit::set_clock_tolerance`].
+    pub fn set_clock_tolerance(mut self, tolerance: Duration) -> Self {
+        self.clock_tolerance = Some(tolerance);
+
+        self
+    }
+
+    /// Sets the seed from which the pseudo-random number generators of all
+    /// models are derived.
+    ///
+    /// See [`SimInit::set_seed`].
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+
+        self
+    }
+
+    /// Makes the processing order of events and queries reproducible.
+    ///
+    /// See [`SimInit::deterministic_ordering`].
+    pub fn deterministic_ordering(mut self) -> Self {
+        self.deterministic_ordering = true;
+
+        self
+    }
+
+    /// Catches panics raised by model input and replier methods rather than
+    /// aborting the simulation.
+    ///
+    /// See [`SimInit::catch_model_panics`].
+    pub fn catch_model_panics(mut self) -> Self {
+        self.catch_model_panics = true;
+
+        self
+    }
+
+    /// Checks the invariants of all models at the end of each time slice.
+    ///
+    /// See [`SimInit::check_invariants`].
+    pub fn check_invariants(mut self) -> Self {
+        self.check_invariants = true;
+
+        self
+    }
+
+    /// Runs the model initializers in parallel across the worker threads.
+    ///
+    /// See [`SimInit::parallel_init`].
+    pub fn parallel_init(mut self) -> Self {
+        self.parallel_init = true;
+
+        self
+    }
+
+    /// Sets a timeout for the call to [`SimInit::init`] and for any subsequent
+    /// simulation step.
+    ///
+    /// See [`SimInit::set_timeout`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Sets a limit on the total wall clock time of the simulation.
+    ///
+    /// See [`SimInit::set_wall_clock_limit`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn set_wall_clock_limit(mut self, limit: Duration) -> Self {
+        self.wall_clock_limit = limit;
+
+        self
+    }
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SimConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimConfig")
+            .field("num_threads", &self.num_threads)
+            .field("clock_tolerance", &self.clock_tolerance)
+            .field("timeout", &self.timeout)
+            .field("wall_clock_limit", &self.wall_clock_limit)
+            .field("seed", &self.seed)
+            .field("deterministic_ordering", &self.deterministic_ordering)
+            .field("catch_model_panics", &self.catch_model_panics)
+            .field("check_invariants", &self.check_invariants)
+            .field("parallel_init", &self.parallel_init)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A factory for boxed clocks.
+type ClockFactory = dyn Fn() -> Box<dyn Clock> + Send + Sync;
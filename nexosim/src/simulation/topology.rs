@@ -0,0 +1,127 @@
+use std::sync::{Arc, Mutex};
+
+/// The connection graph of a simulation bench.
+///
+/// A `Topology` lists the connections between the output or requestor ports
+/// of models and the input or replier ports of other models. It is obtained
+/// with [`Simulation::topology`](crate::simulation::Simulation::topology).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Topology {
+    connections: Vec<Connection>,
+}
+
+impl Topology {
+    /// Creates a topology from a list of connections, which may contain
+    /// duplicates.
+    pub(crate) fn new(mut connections: Vec<Connection>) -> Self {
+        connections.sort_by(|a, b| {
+            (&a.source_port, &a.target, a.port, a.event_type).cmp(&(
+                &b.source_port,
+                &b.target,
+                b.port,
+                b.event_type,
+            ))
+        });
+        connections.dedup();
+
+        Self { connections }
+    }
+
+    /// Returns all connections, sorted by source port label, target model and
+    /// target port.
+    pub fn connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
+    /// Renders the connection graph in the Graphviz DOT format.
+    ///
+    /// All models are rendered as nodes, including those without any
+    /// connection. Source ports are rendered as plain-text nodes.
+    pub(crate) fn to_dot(&self, model_names: &[String]) -> String {
+        let mut dot = String::from("digraph simulation {\n");
+        for name in model_names {
+            dot.push_str(&format!("    \"{}\";\n", escape(name)));
+        }
+        let mut source_ports: Vec<_> = self
+            .connections
+            .iter()
+            .map(|connection| &connection.source_port)
+            .collect();
+        source_ports.dedup();
+        for source_port in source_ports {
+            dot.push_str(&format!(
+                "    \"{}\" [shape=plaintext];\n",
+                escape(source_port)
+            ));
+        }
+        for connection in &self.connections {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                escape(&connection.source_port),
+                escape(&connection.target),
+                escape(connection.event_type)
+            ));
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+/// A connection from an output or requestor port to an input or replier port
+/// of a model.
+///
+/// Since output and requestor ports are plain struct fields, neither the port
+/// itself nor the model owning it can be identified automatically. The source
+/// of the connection is instead identified by the label of the port, which can
+/// be set with [`Output::set_label`](crate::ports::Output::set_label),
+/// [`Requestor::set_label`](crate::ports::Requestor::set_label) or
+/// [`UniRequestor::set_label`](crate::ports::UniRequestor::set_label).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Connection {
+    /// The label of the output or requestor port.
+    ///
+    /// This is, unless set to another value, the name of the port type as
+    /// returned by [`std::any::type_name`].
+    pub source_port: String,
+    /// The fully qualified name of the model owning the input or replier port.
+    pub target: String,
+    /// The name of the input or replier port.
+    ///
+    /// This is the name of the method, as returned by
+    /// [`std::any::type_name`], and is therefore only meant for display
+    /// purposes.
+    pub port: &'static str,
+    /// The name of the event or request type sent by the source port, as
+    /// returned by [`std::any::type_name`].
+    pub event_type: &'static str,
+}
+
+/// The label of an output or requestor port.
+///
+/// The label is shared by all clones of a port, and by all the connections
+/// made from the port, so that it can be changed at any time.
+#[derive(Clone, Debug)]
+pub(crate) struct PortLabel(Arc<Mutex<String>>);
+
+impl PortLabel {
+    /// Creates a label with the specified initial value.
+    pub(crate) fn new(label: &str) -> Self {
+        Self(Arc::new(Mutex::new(label.to_string())))
+    }
+
+    /// Returns the current value of the label.
+    pub(crate) fn get(&self) -> String {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Changes the value of the label.
+    pub(crate) fn set(&self, label: String) {
+        *self.0.lock().unwrap() = label;
+    }
+}
+
+/// Escapes a string so it can be used within a quoted DOT identifier.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -4,7 +4,17 @@
 //!
 //! * [`MonotonicTime`]: a monotonic timestamp based on the [TAI] time standard,
 //! * [`Clock`]: a trait for types that can synchronize a simulation,
-//!   implemented for instance by [`SystemClock`] and [`AutoSystemClock`].
+//!   implemented for instance by [`SystemClock`], [`AutoSystemClock`] and
+//!   [`ReplayClock`],
+//! * `ChronoConversion`: a trait providing conversions between
+//!   [`MonotonicTime`] and UTC date-times from the `chrono` crate, available
+//!   with the `chrono` feature flag,
+//! * [`Rfc3339`]: a trait providing formatting and parsing of [`MonotonicTime`]
+//!   as RFC 3339 date-time strings,
+//! * [`TimeHandle`]: a handle to read the simulation time from other threads,
+//! * [`Jitter`]: a random deviation for scheduling delays,
+//! * [`SimDuration`]: a duration with overflow-safe arithmetic against
+//!   [`MonotonicTime`].
 //!
 //! [TAI]: https://en.wikipedia.org/wiki/International_Atomic_Time
 //!
@@ -45,25 +55,91 @@
 //! impl Model for AlarmClock {}
 //! ```
 
+#[cfg(feature = "chrono")]
+mod chrono_conversion;
 mod clock;
+mod jitter;
 mod monotonic_time;
+mod rfc3339;
+mod sim_duration;
 
 pub use tai_time::MonotonicTime;
 
-pub use clock::{AutoSystemClock, Clock, NoClock, SyncStatus, SystemClock};
+#[cfg(feature = "chrono")]
+pub use chrono_conversion::ChronoConversion;
+pub use clock::{AutoSystemClock, Clock, NoClock, ReplayClock, SyncStatus, SystemClock};
+pub use jitter::Jitter;
 pub(crate) use monotonic_time::TearableAtomicTime;
+pub use rfc3339::{ParseRfc3339Error, Rfc3339};
+pub use sim_duration::SimDuration;
 
 pub(crate) type AtomicTime = crate::util::sync_cell::SyncCell<TearableAtomicTime>;
 pub(crate) type AtomicTimeReader = crate::util::sync_cell::SyncCellReader<TearableAtomicTime>;
 
+/// A lightweight handle to the current simulation time.
+///
+/// A `TimeHandle` is obtained with
+/// [`Scheduler::time_handle`](crate::simulation::Scheduler::time_handle). It
+/// can be cloned and sent to other threads, for instance to periodically
+/// monitor the progress of a simulation, and reading the time does not
+/// require any lock.
+///
+/// The simulation time is updated at the beginning of each time slice, before
+/// the actions scheduled for this time slice are processed.
+#[derive(Clone)]
+pub struct TimeHandle {
+    reader: AtomicTimeReader,
+}
+
+impl TimeHandle {
+    /// Creates a handle from a time reader.
+    pub(crate) fn new(reader: AtomicTimeReader) -> Self {
+        Self { reader }
+    }
+
+    /// Returns the current simulation time.
+    pub fn time(&self) -> MonotonicTime {
+        self.reader.read()
+    }
+}
+
+impl std::fmt::Debug for TimeHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeHandle")
+            .field("time", &self.time())
+            .finish()
+    }
+}
+
 /// Trait abstracting over time-absolute and time-relative deadlines.
 ///
-/// This trait is implemented by [`std::time::Duration`] and
+/// This trait is implemented by [`std::time::Duration`], [`SimDuration`] and
 /// [`MonotonicTime`].
+///
+/// Relative deadlines may lie beyond [`MonotonicTime::MAX`]. Such deadlines
+/// are rejected by all scheduling methods with
+/// [`SchedulingError::InvalidScheduledTime`](crate::simulation::SchedulingError::InvalidScheduledTime),
+/// irrespective of the deadline type.
 pub trait Deadline {
     /// Make this deadline into an absolute timestamp, using the provided
     /// current time as a reference.
+    ///
+    /// The behavior on overflow is implementation-defined: a `Duration`
+    /// panics, like the `+` operator, whereas a [`SimDuration`] saturates at
+    /// [`MonotonicTime::MAX`].
     fn into_time(self, now: MonotonicTime) -> MonotonicTime;
+
+    /// Make this deadline into an absolute timestamp, using the provided
+    /// current time as a reference, or returns `None` if the timestamp cannot
+    /// be represented as a `MonotonicTime`.
+    ///
+    /// The default implementation never returns `None`.
+    fn checked_into_time(self, now: MonotonicTime) -> Option<MonotonicTime>
+    where
+        Self: Sized,
+    {
+        Some(self.into_time(now))
+    }
 }
 
 impl Deadline for std::time::Duration {
@@ -71,6 +147,11 @@ impl Deadline for std::time::Duration {
     fn into_time(self, now: MonotonicTime) -> MonotonicTime {
         now + self
     }
+
+    #[inline(always)]
+    fn checked_into_time(self, now: MonotonicTime) -> Option<MonotonicTime> {
+        now.checked_add(self)
+    }
 }
 
 impl Deadline for MonotonicTime {
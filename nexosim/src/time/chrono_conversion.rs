@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+
+use super::MonotonicTime;
+
+/// Conversions between [`MonotonicTime`] and [`chrono::DateTime<Utc>`].
+///
+/// `MonotonicTime` is a TAI timestamp whereas `DateTime<Utc>` is a UTC
+/// timestamp. The two time scales differ by an integral number of leap
+/// seconds, which value changes each time a leap second is inserted: the
+/// conversion methods therefore take as argument the TAI-UTC offset in seconds
+/// (a.k.a. *leap seconds*) applicable to the converted date. This offset has
+/// been 37s since 2017-01-01.
+///
+/// No leap second table is embedded, so it is up to the caller to provide the
+/// proper offset. For a simulation spanning a leap second insertion, a single
+/// offset will yield a timestamp off by one second on one side of the leap
+/// second.
+///
+/// The conversions are performed by the `tai-time` crate, see
+/// [`MonotonicTime::from_chrono_date_time`] and
+/// [`MonotonicTime::to_chrono_date_time`].
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use nexosim::time::{ChronoConversion, MonotonicTime};
+///
+/// let date_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+/// let t = MonotonicTime::from_chrono(&date_time, 37).unwrap();
+///
+/// assert_eq!(t, MonotonicTime::new(1_735_689_600 + 37, 0).unwrap());
+/// assert_eq!(t.to_chrono(37), Some(date_time));
+/// ```
+pub trait ChronoConversion: Sized {
+    /// Creates a timestamp from a UTC date-time, given the TAI-UTC offset in
+    /// seconds.
+    ///
+    /// `None` is returned if the timestamp is out of the range supported by
+    /// `MonotonicTime`.
+    fn from_chrono(date_time: &DateTime<Utc>, leap_secs: i64) -> Option<Self>;
+
+    /// Converts a timestamp to a UTC date-time, given the TAI-UTC offset in
+    /// seconds.
+    ///
+    /// `None` is returned if the date-time is out of the range supported by
+    /// chrono.
+    fn to_chrono(&self, leap_secs: i64) -> Option<DateTime<Utc>>;
+}
+
+impl ChronoConversion for MonotonicTime {
+    fn from_chrono(date_time: &DateTime<Utc>, leap_secs: i64) -> Option<Self> {
+        // Reject offsets that would overflow the timestamp.
+        date_time.timestamp().checked_add(leap_secs)?;
+
+        MonotonicTime::from_chrono_date_time(date_time, leap_secs)
+    }
+
+    fn to_chrono(&self, leap_secs: i64) -> Option<DateTime<Utc>> {
+        // Reject offsets that would overflow the timestamp.
+        self.as_secs().checked_sub(leap_secs)?;
+
+        self.to_chrono_date_time(leap_secs)
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn chrono_round_trip() {
+        let date_time = Utc
+            .with_ymd_and_hms(2017, 6, 30, 12, 34, 56)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::nanoseconds(123_456_789))
+            .unwrap();
+        let t = MonotonicTime::from_chrono(&date_time, 37).unwrap();
+
+        assert_eq!(
+            t,
+            MonotonicTime::new(1_498_826_096 + 37, 123_456_789).unwrap()
+        );
+        assert_eq!(t.to_chrono(37), Some(date_time));
+    }
+
+    #[test]
+    fn chrono_out_of_range() {
+        let date_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(MonotonicTime::from_chrono(&date_time, i64::MAX), None);
+        assert_eq!(MonotonicTime::MAX.to_chrono(0), None);
+        assert_eq!(MonotonicTime::EPOCH.to_chrono(i64::MIN), None);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::time::{Duration, Instant, SystemTime};
 
 use tai_time::MonotonicClock;
@@ -14,18 +15,38 @@ use crate::time::MonotonicTime;
 pub trait Clock: Send {
     /// Blocks until the deadline.
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus;
+
+    /// Returns a simulation time and a wall clock [`Instant`] which refer to
+    /// the same point in time, or `None` if this is not a real-time clock.
+    ///
+    /// This reference makes it possible to translate wall clock instants to
+    /// simulation time, see
+    /// [`Scheduler::schedule_at_wall_clock`](crate::simulation::Scheduler::schedule_at_wall_clock).
+    ///
+    /// The default implementation returns `None`.
+    fn wall_clock_ref(&self) -> Option<(MonotonicTime, Instant)> {
+        None
+    }
 }
 
 impl<C: Clock + ?Sized> Clock for &mut C {
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
         (**self).synchronize(deadline)
     }
+
+    fn wall_clock_ref(&self) -> Option<(MonotonicTime, Instant)> {
+        (**self).wall_clock_ref()
+    }
 }
 
 impl<C: Clock + ?Sized> Clock for Box<C> {
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
         (**self).synchronize(deadline)
     }
+
+    fn wall_clock_ref(&self) -> Option<(MonotonicTime, Instant)> {
+        (**self).wall_clock_ref()
+    }
 }
 
 /// The current synchronization status of a clock.
@@ -65,7 +86,11 @@ impl Clock for NoClock {
 /// This clock accepts an arbitrary reference time and remains synchronized with
 /// the system's monotonic clock.
 #[derive(Copy, Clone, Debug)]
-pub struct SystemClock(MonotonicClock);
+pub struct SystemClock {
+    clock: MonotonicClock,
+    simulation_ref: MonotonicTime,
+    wall_clock_ref: Instant,
+}
 
 impl SystemClock {
     /// Constructs a `SystemClock` with an offset between simulation clock and
@@ -94,10 +119,11 @@ impl SystemClock {
     ///     .init(t0);
     /// ```
     pub fn from_instant(simulation_ref: MonotonicTime, wall_clock_ref: Instant) -> Self {
-        Self(MonotonicClock::init_from_instant(
+        Self {
+            clock: MonotonicClock::init_from_instant(simulation_ref, wall_clock_ref),
             simulation_ref,
             wall_clock_ref,
-        ))
+        }
     }
 
     /// Constructs a `SystemClock` with an offset between simulation clock and
@@ -138,10 +164,18 @@ impl SystemClock {
     ///     .init(t0);
     /// ```
     pub fn from_system_time(simulation_ref: MonotonicTime, wall_clock_ref: SystemTime) -> Self {
-        Self(MonotonicClock::init_from_system_time(
-            simulation_ref,
-            wall_clock_ref,
-        ))
+        let clock = MonotonicClock::init_from_system_time(simulation_ref, wall_clock_ref);
+
+        // Match the monotonic clock with the simulation time at construction
+        // time so that wall clock instants can be later translated.
+        let instant_ref = Instant::now();
+        let time_ref = clock.now();
+
+        Self {
+            clock,
+            simulation_ref: time_ref,
+            wall_clock_ref: instant_ref,
+        }
     }
 }
 
@@ -149,7 +183,7 @@ impl Clock for SystemClock {
     /// Blocks until the system time corresponds to the specified simulation
     /// time.
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
-        let now = self.0.now();
+        let now = self.clock.now();
         if now <= deadline {
             spin_sleep::sleep(deadline.duration_since(now));
 
@@ -158,6 +192,10 @@ impl Clock for SystemClock {
 
         SyncStatus::OutOfSync(now.duration_since(deadline))
     }
+
+    fn wall_clock_ref(&self) -> Option<(MonotonicTime, Instant)> {
+        Some((self.simulation_ref, self.wall_clock_ref))
+    }
 }
 
 /// An automatically initialized real-time [`Clock`] based on the system's
@@ -194,6 +232,131 @@ impl Clock for AutoSystemClock {
             Some(clock) => clock.synchronize(deadline),
         }
     }
+
+    /// Returns `None` until the first call to
+    /// [`synchronize`](Clock::synchronize) has initialized the time reference.
+    fn wall_clock_ref(&self) -> Option<(MonotonicTime, Instant)> {
+        self.inner.as_ref().and_then(|clock| clock.wall_clock_ref())
+    }
+}
+
+/// A [`Clock`] that replays the wall clock timing of a recorded session.
+///
+/// A `ReplayClock` is built from the sequence of wall clock timestamps at which
+/// the successive synchronizations of a simulation took place during a
+/// recorded session, expressed as offsets from an arbitrary origin. Note that
+/// the first synchronization is performed by
+/// [`SimInit::init`](crate::simulation::SimInit::init) and that subsequent
+/// synchronizations take place at the beginning of each time slice. Rather than
+/// synchronizing the simulation time with the system clock, each call to
+/// [`synchronize`](Clock::synchronize) pops the next recorded timestamp and
+/// blocks until the same wall clock duration has elapsed since the first
+/// synchronization as in the original session, possibly scaled by a speed
+/// factor. The first synchronization never blocks.
+///
+/// This makes it possible to re-run a captured session with its original
+/// timing, or a compressed or expanded version thereof, irrespective of the
+/// simulation time.
+///
+/// Once all recorded timestamps have been consumed, the clock no longer blocks
+/// and behaves like a [`NoClock`], i.e. the simulation runs as fast as
+/// possible.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::simulation::SimInit;
+/// use nexosim::time::{MonotonicTime, ReplayClock};
+///
+/// // Wall clock offsets recorded during a previous session.
+/// let recorded = [0, 120, 250, 1000].map(Duration::from_millis);
+///
+/// // Replay the session twice as fast.
+/// let clock = ReplayClock::new(recorded).with_speed(2.0);
+///
+/// let simu = SimInit::new()
+/// //  .add_model(...)
+/// //  .add_model(...)
+///     .set_clock(clock)
+///     .init(MonotonicTime::EPOCH);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplayClock {
+    timestamps: VecDeque<Duration>,
+    speed: f64,
+    reference: Option<(Duration, Instant)>,
+}
+
+impl ReplayClock {
+    /// Constructs a `ReplayClock` from recorded wall clock timestamps.
+    ///
+    /// The timestamps are offsets from an arbitrary origin and should be in
+    /// chronological order. A timestamp earlier than its predecessor is never
+    /// waited for and results in an out-of-sync status.
+    pub fn new(timestamps: impl IntoIterator<Item = Duration>) -> Self {
+        Self {
+            timestamps: timestamps.into_iter().collect(),
+            speed: 1.0,
+            reference: None,
+        }
+    }
+
+    /// Sets the replay speed factor.
+    ///
+    /// A factor greater than 1 compresses the recorded timing while a factor
+    /// lower than 1 expands it. The default factor is 1.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the speed factor is not strictly positive and
+    /// finite.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        assert!(
+            speed > 0.0 && speed.is_finite(),
+            "the speed factor must be strictly positive and finite"
+        );
+        self.speed = speed;
+
+        self
+    }
+
+    /// Returns the number of recorded timestamps that remain to be replayed.
+    pub fn remaining(&self) -> usize {
+        self.timestamps.len()
+    }
+}
+
+impl Clock for ReplayClock {
+    /// Blocks until the wall clock time elapsed since the first call matches
+    /// the next recorded timestamp, or returns immediately if all timestamps
+    /// have been consumed.
+    fn synchronize(&mut self, _: MonotonicTime) -> SyncStatus {
+        let timestamp = match self.timestamps.pop_front() {
+            Some(timestamp) => timestamp,
+            None => return SyncStatus::Synchronized,
+        };
+
+        let (timestamp_ref, instant_ref) = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some((timestamp, Instant::now()));
+
+                return SyncStatus::Synchronized;
+            }
+        };
+
+        let now = Instant::now();
+        let offset = timestamp.saturating_sub(timestamp_ref).div_f64(self.speed);
+        let target = instant_ref + offset;
+        if timestamp < timestamp_ref || now > target {
+            return SyncStatus::OutOfSync(now.saturating_duration_since(target));
+        }
+        spin_sleep::sleep(target.duration_since(now));
+
+        SyncStatus::Synchronized
+    }
 }
 
 #[cfg(test)]
@@ -220,4 +383,23 @@ mod test {
             elapsed,
         );
     }
+
+    #[test]
+    fn smoke_replay_clock() {
+        let t0 = MonotonicTime::EPOCH;
+        let timestamps = [1000, 1100, 1300].map(Duration::from_millis);
+        let mut clock = ReplayClock::new(timestamps).with_speed(2.0);
+
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        }
+        assert!(now.elapsed() >= Duration::from_millis(150));
+        assert_eq!(clock.remaining(), 0);
+
+        // The clock no longer blocks once the timestamps are exhausted.
+        let now = Instant::now();
+        assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        assert!(now.elapsed() < Duration::from_millis(100));
+    }
 }
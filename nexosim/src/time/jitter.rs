@@ -0,0 +1,56 @@
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// The smallest delay that can be sampled, which ensures that jittered events
+/// are always scheduled in the future.
+const MIN_DELAY: Duration = Duration::from_nanos(1);
+
+/// A random deviation applied to a scheduling delay.
+///
+/// Jitter is applied with
+/// [`Context::schedule_event_jittered`](crate::model::Context::schedule_event_jittered),
+/// which samples the deviation from the pseudo-random number generator of the
+/// model so that the simulation remains reproducible when a seed is set.
+///
+/// Jittered delays are clamped so they are always strictly positive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Jitter {
+    /// A deviation uniformly distributed between minus and plus the specified
+    /// duration.
+    Uniform(Duration),
+    /// A normally distributed deviation with a null mean and the specified
+    /// standard deviation.
+    Normal(Duration),
+}
+
+impl Jitter {
+    /// Returns the base delay with a random deviation, clamped to the
+    /// smallest strictly positive delay.
+    pub(crate) fn apply(&self, base: Duration, rng: &mut impl Rng) -> Duration {
+        let deviation = match *self {
+            Self::Uniform(max_deviation) => {
+                let max_deviation = max_deviation.as_secs_f64();
+
+                rng.gen_range(-max_deviation..=max_deviation)
+            }
+            Self::Normal(std_dev) => {
+                // Box-Muller transform; the first uniform sample lies in
+                // `(0, 1]` so that its logarithm is finite.
+                let u1 = 1.0 - rng.gen::<f64>();
+                let u2 = rng.gen::<f64>();
+
+                std_dev.as_secs_f64() * (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+            }
+        };
+
+        let delay = if deviation >= 0.0 {
+            base.saturating_add(Duration::from_secs_f64(deviation))
+        } else {
+            base.saturating_sub(Duration::from_secs_f64(-deviation))
+        };
+
+        delay.max(MIN_DELAY)
+    }
+}
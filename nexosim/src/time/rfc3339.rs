@@ -0,0 +1,307 @@
+use std::error::Error;
+use std::fmt;
+
+use super::MonotonicTime;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Formatting and parsing of [`MonotonicTime`] as RFC 3339 date-time strings.
+///
+/// `MonotonicTime` is a TAI timestamp which epoch, [`MonotonicTime::EPOCH`],
+/// is 1970-01-01 00:00:00 TAI. RFC 3339 date-times, on the other hand, are
+/// expressed in UTC or with an offset from UTC. The two time scales differ by
+/// an integral number of leap seconds, which value changes each time a leap
+/// second is inserted: the formatting and parsing methods therefore take as
+/// argument the TAI-UTC offset in seconds (a.k.a. *leap seconds*) applicable to
+/// the date. This offset has been 37s since 2017-01-01. No leap second table is
+/// embedded, so it is up to the caller to provide the proper offset.
+///
+/// Formatted date-times are always expressed in UTC with the `Z` suffix. The
+/// fractional part of the seconds is omitted if it is zero and is otherwise
+/// formatted with the full nanosecond precision.
+///
+/// When parsing, fractional seconds with more than 9 digits are truncated to
+/// the nanosecond. A leap second (*e.g.* 23:59:60) is accepted and converted
+/// to the TAI timestamp one second after the preceding UTC second, assuming
+/// that the offset provided is the offset in force before the leap second.
+///
+/// # Examples
+///
+/// ```
+/// use nexosim::time::{MonotonicTime, Rfc3339};
+///
+/// let t = MonotonicTime::from_rfc3339("2025-01-01T00:00:00.25Z", 37).unwrap();
+///
+/// assert_eq!(t, MonotonicTime::new(1_735_689_600 + 37, 250_000_000).unwrap());
+/// assert_eq!(
+///     t.to_rfc3339(37).unwrap(),
+///     "2025-01-01T00:00:00.250000000Z"
+/// );
+/// ```
+pub trait Rfc3339: Sized {
+    /// Formats a timestamp as an RFC 3339 UTC date-time, given the TAI-UTC
+    /// offset in seconds.
+    ///
+    /// `None` is returned if the year is out of the `0000-9999` range that
+    /// can be represented in RFC 3339.
+    fn to_rfc3339(&self, leap_secs: i64) -> Option<String>;
+
+    /// Parses a timestamp from an RFC 3339 date-time, given the TAI-UTC offset
+    /// in seconds.
+    ///
+    /// The date and time may be separated by `T`, `t` or a space.
+    fn from_rfc3339(s: &str, leap_secs: i64) -> Result<Self, ParseRfc3339Error>;
+}
+
+impl Rfc3339 for MonotonicTime {
+    fn to_rfc3339(&self, leap_secs: i64) -> Option<String> {
+        let secs = self.as_secs().checked_sub(leap_secs)?;
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+        let mut s = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day % 3600 / 60,
+            secs_of_day % 60
+        );
+        let nanos = self.subsec_nanos();
+        if nanos != 0 {
+            s.push_str(&format!(".{:09}", nanos));
+        }
+        s.push('Z');
+
+        Some(s)
+    }
+
+    fn from_rfc3339(s: &str, leap_secs: i64) -> Result<Self, ParseRfc3339Error> {
+        let b = s.as_bytes();
+        if b.len() < 20
+            || b[4] != b'-'
+            || b[7] != b'-'
+            || !matches!(b[10], b'T' | b't' | b' ')
+            || b[13] != b':'
+            || b[16] != b':'
+        {
+            return Err(ParseRfc3339Error);
+        }
+
+        let year = parse_digits(&b[0..4])?;
+        let month = parse_digits(&b[5..7])?;
+        let day = parse_digits(&b[8..10])?;
+        let hour = parse_digits(&b[11..13])?;
+        let minute = parse_digits(&b[14..16])?;
+        let second = parse_digits(&b[17..19])?;
+        if !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return Err(ParseRfc3339Error);
+        }
+
+        // Fractional seconds.
+        let mut rest = &b[19..];
+        let mut nanos = 0;
+        if let [b'.', frac @ ..] = rest {
+            let len = frac.iter().take_while(|c| c.is_ascii_digit()).count();
+            if len == 0 {
+                return Err(ParseRfc3339Error);
+            }
+            for (i, c) in frac[..len.min(9)].iter().enumerate() {
+                nanos += u32::from(c - b'0') * 10u32.pow(8 - i as u32);
+            }
+            rest = &frac[len..];
+        }
+
+        // UTC offset.
+        let offset = match rest {
+            [b'Z' | b'z'] => 0,
+            [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+                let offset_hours = parse_digits(&[*h1, *h2])?;
+                let offset_minutes = parse_digits(&[*m1, *m2])?;
+                if offset_hours > 23 || offset_minutes > 59 {
+                    return Err(ParseRfc3339Error);
+                }
+                let offset = i64::from(offset_hours * 3600 + offset_minutes * 60);
+
+                if *sign == b'+' {
+                    offset
+                } else {
+                    -offset
+                }
+            }
+            _ => return Err(ParseRfc3339Error),
+        };
+
+        let secs = days_from_civil(i64::from(year), month, day) * SECS_PER_DAY
+            + i64::from(hour * 3600 + minute * 60 + second)
+            - offset;
+        let secs = secs.checked_add(leap_secs).ok_or(ParseRfc3339Error)?;
+
+        MonotonicTime::new(secs, nanos).ok_or(ParseRfc3339Error)
+    }
+}
+
+/// An error returned when a string cannot be parsed as an RFC 3339 date-time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseRfc3339Error;
+
+impl fmt::Display for ParseRfc3339Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the string is not a valid RFC 3339 date-time")
+    }
+}
+
+impl Error for ParseRfc3339Error {}
+
+/// Parses a string of ASCII digits.
+fn parse_digits(b: &[u8]) -> Result<u32, ParseRfc3339Error> {
+    b.iter().try_fold(0, |acc, c| {
+        if c.is_ascii_digit() {
+            Ok(acc * 10 + u32::from(c - b'0'))
+        } else {
+            Err(ParseRfc3339Error)
+        }
+    })
+}
+
+/// Returns the number of days in the specified month of the proleptic
+/// Gregorian calendar.
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01 for the specified date of the
+/// proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the date of the proleptic Gregorian calendar for the specified
+/// number of days since 1970-01-01.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc3339_format() {
+        let t = MonotonicTime::new(1_735_689_600 + 37, 0).unwrap();
+        assert_eq!(t.to_rfc3339(37).unwrap(), "2025-01-01T00:00:00Z");
+
+        let t = MonotonicTime::new(-1, 500_000_000).unwrap();
+        assert_eq!(t.to_rfc3339(0).unwrap(), "1969-12-31T23:59:59.500000000Z");
+
+        let t = MonotonicTime::new(951_782_400, 1).unwrap();
+        assert_eq!(t.to_rfc3339(0).unwrap(), "2000-02-29T00:00:00.000000001Z");
+
+        let t = MonotonicTime::new(253_402_300_800, 0).unwrap();
+        assert_eq!(t.to_rfc3339(0), None);
+    }
+
+    #[test]
+    fn rfc3339_parse() {
+        assert_eq!(
+            MonotonicTime::from_rfc3339("2025-01-01T00:00:00Z", 37),
+            Ok(MonotonicTime::new(1_735_689_600 + 37, 0).unwrap())
+        );
+        assert_eq!(
+            MonotonicTime::from_rfc3339("2025-01-01t01:30:00+01:30", 37),
+            Ok(MonotonicTime::new(1_735_689_600 + 37, 0).unwrap())
+        );
+        assert_eq!(
+            MonotonicTime::from_rfc3339("1969-12-31 23:59:59.5z", 0),
+            Ok(MonotonicTime::new(-1, 500_000_000).unwrap())
+        );
+        assert_eq!(
+            MonotonicTime::from_rfc3339("1970-01-01T00:00:00.1234567891Z", 0),
+            Ok(MonotonicTime::new(0, 123_456_789).unwrap())
+        );
+        assert_eq!(
+            MonotonicTime::from_rfc3339("2024-02-29T00:00:00-00:00", 0),
+            Ok(MonotonicTime::new(1_709_164_800, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn rfc3339_parse_leap_second() {
+        let before = MonotonicTime::from_rfc3339("2016-12-31T23:59:59Z", 36).unwrap();
+        let leap = MonotonicTime::from_rfc3339("2016-12-31T23:59:60Z", 36).unwrap();
+        let after = MonotonicTime::from_rfc3339("2017-01-01T00:00:00Z", 37).unwrap();
+
+        assert_eq!(leap.as_secs(), before.as_secs() + 1);
+        assert_eq!(after.as_secs(), leap.as_secs() + 1);
+    }
+
+    #[test]
+    fn rfc3339_parse_invalid() {
+        for s in [
+            "",
+            "2025-01-01T00:00:00",
+            "2025-01-01T00:00:00.Z",
+            "2025-01-01T24:00:00Z",
+            "2025-02-29T00:00:00Z",
+            "2025-13-01T00:00:00Z",
+            "2025-01-01T00:00:00+0100",
+            "2025-01-01T00:00:00Z ",
+            "2025-01-01T00:00:61Z",
+            "2025/01/01T00:00:00Z",
+        ] {
+            assert_eq!(
+                MonotonicTime::from_rfc3339(s, 0),
+                Err(ParseRfc3339Error),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn rfc3339_round_trip() {
+        for secs in [-62_167_219_200 + 37, -1, 0, 1_483_228_837, 253_402_300_799] {
+            let t = MonotonicTime::new(secs, 987_654_321).unwrap();
+            let s = t.to_rfc3339(37).unwrap();
+
+            assert_eq!(MonotonicTime::from_rfc3339(&s, 37), Ok(t));
+        }
+    }
+}
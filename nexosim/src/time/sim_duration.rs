@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use tai_time::MonotonicTime;
+
+use super::Deadline;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// A non-negative span of simulation time with overflow-safe arithmetic
+/// against [`MonotonicTime`].
+///
+/// `SimDuration` is a thin wrapper around [`std::time::Duration`] which can be
+/// converted to and from a `Duration` at no cost. Contrarily to the `+` and
+/// `-` operators defined between `MonotonicTime` and `Duration`, which panic
+/// if the result lies outside the range of `MonotonicTime`, its methods make
+/// overflow explicit with a choice of checked or saturating variants.
+///
+/// When used as a [`Deadline`], a `SimDuration` saturates at
+/// [`MonotonicTime::MAX`] in [`Deadline::into_time`], but like a `Duration`,
+/// it is rejected by scheduling methods if the deadline cannot be represented
+/// as a `MonotonicTime`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::time::{MonotonicTime, SimDuration};
+///
+/// let delay = SimDuration::from_secs(10);
+///
+/// let t0 = MonotonicTime::EPOCH;
+/// let t1 = delay.saturating_add_to(t0);
+/// assert_eq!(t1, MonotonicTime::new(10, 0).unwrap());
+/// assert_eq!(SimDuration::between(t0, t1), Some(delay));
+///
+/// // Overflow is detected or clamped instead of causing a panic.
+/// assert_eq!(delay.checked_add_to(MonotonicTime::MAX), None);
+/// assert_eq!(delay.saturating_add_to(MonotonicTime::MAX), MonotonicTime::MAX);
+/// assert_eq!(delay.saturating_sub_from(MonotonicTime::MIN), MonotonicTime::MIN);
+///
+/// // Conversions from and to `std::time::Duration` are lossless.
+/// assert_eq!(Duration::from(delay), Duration::from_secs(10));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimDuration(Duration);
+
+impl SimDuration {
+    /// A null duration.
+    pub const ZERO: Self = Self(Duration::ZERO);
+
+    /// The largest representable duration.
+    pub const MAX: Self = Self(Duration::MAX);
+
+    /// Creates a duration from a number of whole seconds and additional
+    /// nanoseconds.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the carry of the nanoseconds into the seconds
+    /// overflows, like [`Duration::new`].
+    pub const fn new(secs: u64, nanos: u32) -> Self {
+        Self(Duration::new(secs, nanos))
+    }
+
+    /// Creates a duration from a number of whole seconds.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(Duration::from_secs(secs))
+    }
+
+    /// Creates a duration from a number of milliseconds.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(Duration::from_millis(millis))
+    }
+
+    /// Creates a duration from a number of microseconds.
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(Duration::from_micros(micros))
+    }
+
+    /// Creates a duration from a number of nanoseconds.
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(Duration::from_nanos(nanos))
+    }
+
+    /// Returns the duration elapsed between two timestamps, or `None` if
+    /// `later` is earlier than `earlier`.
+    ///
+    /// Contrarily to [`MonotonicTime::duration_since`], this never panics,
+    /// even for timestamps lying at the opposite bounds of `MonotonicTime`.
+    pub fn between(earlier: MonotonicTime, later: MonotonicTime) -> Option<Self> {
+        let delta = to_nanos(later) - to_nanos(earlier);
+        if delta < 0 {
+            return None;
+        }
+
+        // The difference between two timestamps is less than 2^64 seconds
+        // so the cast to `u64` is lossless.
+        Some(Self(Duration::new(
+            (delta / NANOS_PER_SEC) as u64,
+            (delta % NANOS_PER_SEC) as u32,
+        )))
+    }
+
+    /// Returns the duration elapsed between two timestamps, or a null duration
+    /// if `later` is earlier than `earlier`.
+    pub fn saturating_between(earlier: MonotonicTime, later: MonotonicTime) -> Self {
+        Self::between(earlier, later).unwrap_or(Self::ZERO)
+    }
+
+    /// Returns the timestamp that lies this duration after `time`, or `None`
+    /// if it cannot be represented as a `MonotonicTime`.
+    pub fn checked_add_to(self, time: MonotonicTime) -> Option<MonotonicTime> {
+        time.checked_add(self.0)
+    }
+
+    /// Returns the timestamp that lies this duration after `time`, clamped to
+    /// [`MonotonicTime::MAX`].
+    pub fn saturating_add_to(self, time: MonotonicTime) -> MonotonicTime {
+        self.checked_add_to(time).unwrap_or(MonotonicTime::MAX)
+    }
+
+    /// Returns the timestamp that lies this duration before `time`, or `None`
+    /// if it cannot be represented as a `MonotonicTime`.
+    pub fn checked_sub_from(self, time: MonotonicTime) -> Option<MonotonicTime> {
+        time.checked_sub(self.0)
+    }
+
+    /// Returns the timestamp that lies this duration before `time`, clamped to
+    /// [`MonotonicTime::MIN`].
+    pub fn saturating_sub_from(self, time: MonotonicTime) -> MonotonicTime {
+        self.checked_sub_from(time).unwrap_or(MonotonicTime::MIN)
+    }
+
+    /// Adds two durations, returning `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Adds two durations, saturating at [`SimDuration::MAX`].
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    /// Subtracts two durations, returning `None` if the result would be
+    /// negative.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Subtracts two durations, saturating at [`SimDuration::ZERO`].
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Returns the underlying `std::time::Duration`.
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for SimDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<SimDuration> for Duration {
+    fn from(duration: SimDuration) -> Self {
+        duration.0
+    }
+}
+
+impl Deadline for SimDuration {
+    #[inline(always)]
+    fn into_time(self, now: MonotonicTime) -> MonotonicTime {
+        self.saturating_add_to(now)
+    }
+
+    #[inline(always)]
+    fn checked_into_time(self, now: MonotonicTime) -> Option<MonotonicTime> {
+        self.checked_add_to(now)
+    }
+}
+
+/// Returns the signed number of nanoseconds elapsed since the epoch.
+fn to_nanos(time: MonotonicTime) -> i128 {
+    time.as_secs() as i128 * NANOS_PER_SEC + time.subsec_nanos() as i128
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sim_duration_add_sub_bounds() {
+        let t = MonotonicTime::new(-5, 500_000_000).unwrap();
+        let d = SimDuration::new(2, 700_000_000);
+
+        assert_eq!(
+            d.checked_add_to(t),
+            Some(MonotonicTime::new(-2, 200_000_000).unwrap())
+        );
+        assert_eq!(
+            d.checked_sub_from(t),
+            Some(MonotonicTime::new(-8, 800_000_000).unwrap())
+        );
+
+        let near_max = MonotonicTime::MAX
+            .checked_sub(Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(
+            SimDuration::from_secs(1).checked_add_to(near_max),
+            Some(MonotonicTime::MAX)
+        );
+        assert_eq!(SimDuration::from_secs(2).checked_add_to(near_max), None);
+        assert_eq!(
+            SimDuration::from_secs(2).saturating_add_to(near_max),
+            MonotonicTime::MAX
+        );
+
+        assert_eq!(
+            SimDuration::from_nanos(1).checked_sub_from(MonotonicTime::MIN),
+            None
+        );
+        assert_eq!(
+            SimDuration::from_nanos(1).saturating_sub_from(MonotonicTime::MIN),
+            MonotonicTime::MIN
+        );
+        assert_eq!(
+            SimDuration::from_secs(1).into_time(MonotonicTime::MAX),
+            MonotonicTime::MAX
+        );
+        assert_eq!(
+            SimDuration::from_secs(1).checked_into_time(MonotonicTime::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn sim_duration_between() {
+        let t0 = MonotonicTime::new(-1, 900_000_000).unwrap();
+        let t1 = MonotonicTime::new(1, 100_000_000).unwrap();
+
+        assert_eq!(
+            SimDuration::between(t0, t1),
+            Some(SimDuration::new(1, 200_000_000))
+        );
+        assert_eq!(SimDuration::between(t1, t0), None);
+        assert_eq!(SimDuration::saturating_between(t1, t0), SimDuration::ZERO);
+
+        // The full range of `MonotonicTime` does not overflow.
+        assert!(SimDuration::between(MonotonicTime::MIN, MonotonicTime::MAX).is_some());
+        assert_eq!(
+            SimDuration::between(MonotonicTime::MAX, MonotonicTime::MIN),
+            None
+        );
+    }
+}
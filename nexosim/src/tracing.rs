@@ -11,6 +11,19 @@
 //! - a unique field called `name`, associated to the model name provided in
 //!   [`SimInit::add_model`](crate::simulation::SimInit::add_model).
 //!
+//! Additionally, each invocation of an input or replier port is wrapped in a
+//! nested [`tracing::Span`] with the following metadata:
+//!
+//! - name: `port`,
+//! - target: `nexosim`,
+//! - verbosity level: [`Level::TRACE`](tracing::Level::TRACE),
+//! - a unique field called `method`, associated to the type name of the port
+//!   method, *e.g.* `my_simulation::MyModel::some_input_port`.
+//!
+//! Because `port` spans are entered and exited each time the port method is
+//! polled, they can be used to profile the execution of models with span-based
+//! subscribers such as `tracing-chrome`.
+//!
 //! The emission of `model` spans can be readily used for [event
 //! filtering](#event-filtering-examples), using for instance the
 //! [`tracing_subscriber::fmt`][mod@tracing_subscriber::fmt] subscriber. By
@@ -77,6 +90,19 @@
 //! 2024-09-10T14:39:24.670921Z  INFO my_simulation: something happened outside the simulation
 //! ```
 //!
+//! This is particularly useful for simulations synchronized with a real-time
+//! clock, where the wall clock timestamps make it possible to correlate
+//! simulation events with external logs or to diagnose synchronization drift.
+//! Since the system time is only queried when an event is actually recorded,
+//! simulations that do not log events bear no overhead. For a custom
+//! wall clock format, a different timer can be provided with
+//! `SimulationTime::with_custom_timer_always`.
+//!
+//! Models can also use
+//! [`Context::log_event`](crate::model::Context::log_event), which emits
+//! events that carry the simulation time and the model name as fields, and
+//! are thus correctly stamped whatever the timer configuration.
+//!
 //!
 //! # Event filtering examples
 //!
@@ -1,5 +1,7 @@
+pub(crate) mod background_task;
 pub(crate) mod bit;
 pub(crate) mod cached_rw_lock;
+pub(crate) mod catch_unwind;
 pub(crate) mod indexed_priority_queue;
 pub(crate) mod priority_queue;
 pub(crate) mod rng;
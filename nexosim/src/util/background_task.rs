@@ -0,0 +1,231 @@
+//! Detached tasks running on a shared background thread.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::Context;
+use std::thread;
+
+use futures_task::ArcWake;
+use parking::{Parker, Unparker};
+
+/// A boxed background future.
+type BoxedFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The reactor that polls all background tasks on a single thread.
+///
+/// The thread is started lazily when the first task is spawned and lives for
+/// the remaining lifetime of the process. It is parked whenever no task is
+/// ready to make progress.
+struct Reactor {
+    /// Tasks that were woken and are waiting to be polled.
+    ready_queue: Mutex<VecDeque<Arc<Task>>>,
+    /// Handle to unpark the reactor thread.
+    unparker: Unparker,
+}
+
+impl Reactor {
+    /// Returns the global reactor, starting its thread if necessary.
+    fn get() -> &'static Reactor {
+        static REACTOR: OnceLock<Reactor> = OnceLock::new();
+
+        REACTOR.get_or_init(|| {
+            let parker = Parker::new();
+            let reactor = Reactor {
+                ready_queue: Mutex::new(VecDeque::new()),
+                unparker: parker.unparker(),
+            };
+            thread::Builder::new()
+                .name("nexosim-background".into())
+                .spawn(move || run(parker))
+                .expect("the background task thread could not be spawned");
+
+            reactor
+        })
+    }
+
+    /// Pushes a task to the ready queue and unparks the reactor thread.
+    fn schedule(&self, task: Arc<Task>) {
+        self.ready_queue.lock().unwrap().push_back(task);
+        self.unparker.unpark();
+    }
+}
+
+/// A task managed by the reactor.
+struct Task {
+    /// The future, or `None` once it has completed or was cancelled.
+    future: Mutex<Option<BoxedFuture>>,
+    /// Set when the task is in the ready queue.
+    is_scheduled: AtomicBool,
+    /// Set when the task is cancelled.
+    is_cancelled: AtomicBool,
+    /// Set once the future has been dropped.
+    is_finished: AtomicBool,
+}
+
+impl ArcWake for Task {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        // Ordering: Acquire ordering synchronizes with the Release store in
+        // the reactor thread, which guarantees that the task is re-scheduled
+        // if woken after it was removed from the ready queue.
+        if !arc_self.is_scheduled.swap(true, Ordering::AcqRel) {
+            Reactor::get().schedule(arc_self.clone());
+        }
+    }
+}
+
+/// A handle to a future polled to completion on the shared background thread.
+///
+/// All background futures are polled on the same thread, which is parked until
+/// one of the futures is woken, so the future can be woken from any thread,
+/// including from an I/O reactor running outside the simulation. Since the
+/// thread is shared, futures should not block.
+///
+/// The task is cancelled when the handle is dropped: the future is then
+/// dropped by the background thread the next time it processes the task,
+/// which happens immediately unless the thread is busy polling a future.
+pub(crate) struct BackgroundTask {
+    task: Arc<Task>,
+}
+
+impl BackgroundTask {
+    /// Submits the future to the background thread.
+    pub(crate) fn spawn<F>(future: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(Box::pin(future))),
+            is_scheduled: AtomicBool::new(false),
+            is_cancelled: AtomicBool::new(false),
+            is_finished: AtomicBool::new(false),
+        });
+        ArcWake::wake_by_ref(&task);
+
+        Self { task }
+    }
+
+    /// Returns `true` if the future has completed or was cancelled.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.task.is_finished.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for BackgroundTask {
+    fn drop(&mut self) {
+        // Ordering: Release ordering synchronizes with the Acquire load in the
+        // reactor thread.
+        self.task.is_cancelled.store(true, Ordering::Release);
+        ArcWake::wake_by_ref(&self.task);
+    }
+}
+
+/// Polls the tasks as they are woken.
+fn run(parker: Parker) {
+    let reactor = Reactor::get();
+
+    loop {
+        let task = match reactor.ready_queue.lock().unwrap().pop_front() {
+            Some(task) => task,
+            None => {
+                parker.park();
+                continue;
+            }
+        };
+
+        // Ordering: Release ordering synchronizes with the Acquire swap in
+        // `wake_by_ref`.
+        task.is_scheduled.store(false, Ordering::Release);
+
+        let mut future = task.future.lock().unwrap();
+        let is_done = match future.as_mut() {
+            None => continue,
+            Some(_) if task.is_cancelled.load(Ordering::Acquire) => true,
+            Some(fut) => {
+                let waker = futures_task::waker(task.clone());
+                let mut cx = Context::from_waker(&waker);
+
+                // A panicking future is treated as completed so it does not
+                // bring down the other tasks.
+                panic::catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(&mut cx)))
+                    .map_or(true, |poll| poll.is_ready())
+            }
+        };
+
+        if is_done {
+            *future = None;
+            task.is_finished.store(true, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A future that never completes and signals when dropped.
+    struct PendingForever(mpsc::Sender<()>);
+
+    impl Future for PendingForever {
+        type Output = ();
+
+        fn poll(self: std::pin::Pin<&mut Self>, _cx: &mut Context<'_>) -> std::task::Poll<()> {
+            std::task::Poll::Pending
+        }
+    }
+
+    impl Drop for PendingForever {
+        fn drop(&mut self) {
+            let _ = self.0.send(());
+        }
+    }
+
+    #[test]
+    fn background_task_completion() {
+        let (sender, receiver) = mpsc::channel();
+
+        let task = BackgroundTask::spawn(async move {
+            sender.send(42).unwrap();
+        });
+
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(42));
+        drop(task);
+    }
+
+    #[test]
+    fn background_task_cancellation() {
+        let (sender, receiver) = mpsc::channel();
+
+        let task = BackgroundTask::spawn(PendingForever(sender));
+        assert!(!task.is_finished());
+
+        drop(task);
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(()));
+    }
+
+    #[test]
+    fn background_task_shared_thread() {
+        let (sender, receiver) = mpsc::channel();
+
+        let tasks: Vec<_> = (0..2)
+            .map(|_| {
+                let sender = sender.clone();
+                BackgroundTask::spawn(async move {
+                    sender.send(thread::current().id()).unwrap();
+                })
+            })
+            .collect();
+
+        let first = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, thread::current().id());
+        drop(tasks);
+    }
+}
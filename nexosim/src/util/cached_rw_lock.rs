@@ -40,6 +40,21 @@ impl<T: Clone> CachedRwLock<T> {
         &self.value
     }
 
+    /// Applies a closure to the shared data, or to the local cache if it is up
+    /// to date.
+    ///
+    /// Unlike [`CachedRwLock::read`], this does not synchronize the local
+    /// cache and can therefore be called on a shared reference.
+    pub(crate) fn read_shared<U>(&self, f: impl FnOnce(&T) -> U) -> LockResult<U> {
+        if self.shared.epoch.load(Ordering::Relaxed) == self.epoch {
+            return LockResult::Ok(f(&self.value));
+        }
+        match self.shared.value.lock() {
+            LockResult::Ok(shared) => LockResult::Ok(f(&shared)),
+            LockResult::Err(poison) => LockResult::Err(PoisonError::new(f(&poison.into_inner()))),
+        }
+    }
+
     /// Synchronizes the local cache if it is behind the shared data and gives
     /// access to it.
     #[allow(dead_code)]
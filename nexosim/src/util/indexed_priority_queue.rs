@@ -61,6 +61,25 @@ impl<K: Copy + Ord, V> IndexedPriorityQueue<K, V> {
         }
     }
 
+    /// Creates an empty `PriorityQueue` which insertion keys are built from
+    /// epochs starting at the specified value.
+    ///
+    /// This makes it possible to guarantee that the insertion keys of the new
+    /// queue never match the insertion keys of another queue.
+    pub(crate) fn with_first_epoch(first_epoch: u64) -> Self {
+        Self {
+            heap: Vec::new(),
+            slab: Vec::new(),
+            first_free_node: None,
+            next_epoch: first_epoch,
+        }
+    }
+
+    /// Returns the epoch that will be used for the next insertion.
+    pub(crate) fn next_epoch(&self) -> u64 {
+        self.next_epoch
+    }
+
     /// Creates an empty `PriorityQueue` with at least the specified capacity.
     pub(crate) fn with_capacity(capacity: usize) -> Self {
         Self {
@@ -584,6 +603,19 @@ mod tests {
         check(operations.into_iter());
     }
 
+    #[test]
+    fn indexed_priority_queue_extract_from_successor() {
+        let mut queue = IndexedPriorityQueue::new();
+        let insert_key = queue.insert(0, 'a');
+
+        // A queue starting at the next epoch must not accept insertion keys
+        // of the first queue, even though the slab index is the same.
+        let mut successor = IndexedPriorityQueue::with_first_epoch(queue.next_epoch());
+        let successor_insert_key = successor.insert(0, 'b');
+        assert_eq!(successor.extract(insert_key), None);
+        assert_eq!(successor.extract(successor_insert_key), Some((0, 'b')));
+    }
+
     #[test]
     fn indexed_priority_queue_fuzz() {
         use std::cell::Cell;
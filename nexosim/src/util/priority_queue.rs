@@ -81,6 +81,29 @@ impl<K: Copy + Ord, V> PriorityQueue<K, V> {
         self.heap.push(item);
     }
 
+    /// Inserts a sequence of key-value pairs.
+    ///
+    /// The relative insertion order of equal keys is the order of the
+    /// sequence.
+    ///
+    /// The heap is rebuilt in a single pass, with *O*(N+M) theoretical
+    /// complexity for M inserted pairs, rather than *O*(M·log(N+M)) for
+    /// individual insertions.
+    pub(crate) fn insert_all<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let next_epoch = &mut self.next_epoch;
+        self.heap.extend(items.into_iter().map(|(key, value)| {
+            // Build an element from the user-provided key-value and a unique
+            // epoch.
+            let epoch = *next_epoch;
+            assert_ne!(epoch, u64::MAX);
+            *next_epoch += 1;
+            Item { key, value, epoch }
+        }));
+    }
+
     /// Pulls the value with the lowest key.
     ///
     /// If there are several equal lowest keys, the value which was inserted
@@ -107,6 +130,33 @@ impl<K: Copy + Ord, V> PriorityQueue<K, V> {
 
         Some((key, value))
     }
+
+    /// Returns an iterator over all values, in arbitrary order.
+    pub(crate) fn values(&self) -> impl Iterator<Item = &V> {
+        self.heap.iter().map(|item| &item.value)
+    }
+
+    /// Removes and returns the first key-value pair found which value
+    /// satisfies the predicate.
+    ///
+    /// Note that the pair that is returned is not necessarily the one with the
+    /// lowest key if several values satisfy the predicate.
+    ///
+    /// This operation has *O*(N) non-amortized theoretical complexity.
+    pub(crate) fn extract<P>(&mut self, mut predicate: P) -> Option<(K, V)>
+    where
+        P: FnMut(&V) -> bool,
+    {
+        let pos = self.heap.iter().position(|item| predicate(&item.value))?;
+
+        // The heap must be rebuilt since arbitrary items cannot be removed from
+        // a `BinaryHeap`.
+        let mut items = std::mem::take(&mut self.heap).into_vec();
+        let Item { key, value, .. } = items.swap_remove(pos);
+        self.heap = BinaryHeap::from(items);
+
+        Some((key, value))
+    }
 }
 
 #[cfg(all(test, not(nexosim_loom)))]
@@ -140,4 +190,45 @@ mod tests {
         assert_eq!(q.peek(), Some((&5, &'e')));
         assert_eq!(q.pull(), Some((5, 'e')));
     }
+
+    #[test]
+    fn priority_extract() {
+        let mut q = PriorityQueue::new();
+
+        q.insert(3, 'c');
+        q.insert(1, 'a');
+        q.insert(2, 'y');
+        q.insert(4, 'd');
+        q.insert(2, 'z');
+
+        assert_eq!(q.extract(|v| *v == 'y'), Some((2, 'y')));
+        assert_eq!(q.extract(|v| *v == 'y'), None);
+
+        // Re-insert an extracted value with a new key.
+        let (_, v) = q.extract(|v| *v == 'a').unwrap();
+        q.insert(5, v);
+
+        assert_eq!(q.pull(), Some((2, 'z')));
+        assert_eq!(q.pull(), Some((3, 'c')));
+        assert_eq!(q.pull(), Some((4, 'd')));
+        assert_eq!(q.pull(), Some((5, 'a')));
+        assert_eq!(q.pull(), None);
+    }
+
+    #[test]
+    fn priority_insert_all() {
+        let mut q = PriorityQueue::new();
+
+        q.insert(2, 'x');
+        q.insert(4, 'd');
+        q.insert_all([(3, 'c'), (2, 'y'), (1, 'a'), (2, 'z')]);
+
+        assert_eq!(q.pull(), Some((1, 'a')));
+        assert_eq!(q.pull(), Some((2, 'x')));
+        assert_eq!(q.pull(), Some((2, 'y')));
+        assert_eq!(q.pull(), Some((2, 'z')));
+        assert_eq!(q.pull(), Some((3, 'c')));
+        assert_eq!(q.pull(), Some((4, 'd')));
+        assert_eq!(q.pull(), None);
+    }
 }
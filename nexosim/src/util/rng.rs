@@ -1,6 +1,12 @@
 //! Pseudo-random number generation.
 
 use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 /// A pseudo-random generator for 64-bit integers based on Wang Yi's Wyrand.
 ///
@@ -36,6 +42,84 @@ impl Rng {
     }
 }
 
+/// The pseudo-random number generator of a model.
+///
+/// The generator is based on the ChaCha algorithm with 8 rounds. Unlike the
+/// generators of the `rand` crate, such as `StdRng`, whose algorithm may
+/// change between versions, the sequence generated by a `SimRng` for a given
+/// simulation seed is part of the stable behavior of the simulator.
+///
+/// `SimRng` implements the [`RngCore`] trait, so the methods of the
+/// [`rand::Rng`] extension trait are available.
+#[derive(Clone, Debug)]
+pub struct SimRng(ChaCha8Rng);
+
+impl SimRng {
+    /// Creates a generator from a 64-bit seed.
+    fn from_seed(seed: u64) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed))
+    }
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// The simulation seed shared by all model RNGs, if any.
+pub(crate) type SharedSeed = Arc<Mutex<Option<u64>>>;
+
+/// A lazily initialized pseudo-random number generator owned by a model.
+///
+/// The generator is seeded on first use. If a simulation seed was set, the
+/// generator seed is deterministically derived from the simulation seed and
+/// from the model index so that each model has its own reproducible sequence.
+/// Otherwise, the generator is seeded from a source of entropy.
+#[derive(Debug)]
+pub(crate) struct ModelRng {
+    seed: SharedSeed,
+    model_index: usize,
+    rng: Option<SimRng>,
+}
+
+impl ModelRng {
+    /// Creates a new model RNG.
+    pub(crate) fn new(seed: SharedSeed, model_index: usize) -> Self {
+        Self {
+            seed,
+            model_index,
+            rng: None,
+        }
+    }
+
+    /// Returns the generator, seeding it if necessary.
+    pub(crate) fn get(&mut self) -> &mut SimRng {
+        self.rng.get_or_insert_with(|| {
+            let seed = match *self.seed.lock().unwrap() {
+                // Mix the model index with a golden-ratio based increment so
+                // that models with neighboring indices get unrelated seeds.
+                Some(seed) => seed ^ (self.model_index as u64).wrapping_mul(0x9E3779B97F4A7C15),
+                None => RandomState::new().build_hasher().finish(),
+            };
+
+            SimRng::from_seed(seed)
+        })
+    }
+}
+
 #[cfg(all(test, not(nexosim_loom), not(miri)))]
 mod tests {
     use super::*;
@@ -1,13 +1,42 @@
 // Integration tests follow the organization suggested by Matklad:
 // https://matklad.github.io/2021/02/27/delete-cargo-integration-tests.html
 
+mod model_addition;
+#[cfg(not(miri))]
+mod model_background_task;
+mod model_batch_send;
+mod model_best_effort_query;
+mod model_delayed_connection;
+mod model_inspection;
+mod model_interception;
+mod model_invariants;
+mod model_log_event;
+mod model_lossy_connection;
+#[cfg(feature = "manifest")]
+mod model_manifest;
+mod model_output_flush;
+#[cfg(not(miri))]
+mod model_parallel_init;
+mod model_prioritized_mailbox;
+mod model_query_aggregation;
+mod model_query_reduction;
+mod model_removal;
+mod model_rng;
 mod model_scheduling;
+mod model_sink_disconnection;
+mod model_topology;
+mod model_validation;
+#[cfg(feature = "dev-hooks")]
+mod simulation_causal_trace;
 #[cfg(not(miri))]
 mod simulation_clock_sync;
 mod simulation_deadlock;
+#[cfg(feature = "dev-hooks")]
+mod simulation_dispatch_order;
 mod simulation_message_loss;
 mod simulation_no_recipient;
 mod simulation_panic;
+mod simulation_query;
 mod simulation_scheduling;
 #[cfg(not(miri))]
 mod simulation_timeout;
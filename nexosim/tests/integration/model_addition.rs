@@ -0,0 +1,155 @@
+//! Addition of models to a started simulation.
+
+use std::time::Duration;
+
+use nexosim::model::{BuildContext, Context, InitializedModel, Model, ProtoModel};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Address, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<MonotonicTime>,
+}
+impl TestModel {
+    async fn action(&mut self, _: (), cx: &mut Context<Self>) {
+        self.output.send(cx.time()).await;
+    }
+}
+impl Model for TestModel {
+    async fn init(self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+        cx.schedule_event(Duration::from_secs(1), Self::action, ())
+            .unwrap();
+
+        self.into()
+    }
+}
+
+fn model_addition(num_threads: usize) {
+    let mut model1 = TestModel::default();
+    let mut model2 = TestModel::default();
+    let mbox1 = Mailbox::new();
+    let mbox2 = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model1.output.connect_sink(&output);
+    model2.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.step_until(Duration::from_secs(3)).unwrap();
+    assert_eq!(output.next(), Some(t0 + Duration::from_secs(1)));
+
+    // The late model is initialized at the current simulation time.
+    let addr2 = simu.add_model(model2, mbox2, "model2").unwrap();
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(t0 + Duration::from_secs(4)));
+
+    simu.process_event(TestModel::action, (), addr2).unwrap();
+    assert_eq!(output.next(), Some(t0 + Duration::from_secs(4)));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn model_addition_st() {
+    model_addition(1);
+}
+
+#[test]
+fn model_addition_mt() {
+    model_addition(MT_NUM_THREADS);
+}
+
+struct ChildModel {
+    output: Output<u32>,
+}
+impl ChildModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for ChildModel {}
+
+struct ParentModel {
+    child_address: Address<ChildModel>,
+    to_child: Output<u32>,
+}
+impl ParentModel {
+    async fn input(&mut self, value: u32) {
+        self.to_child.send(value * 10).await;
+    }
+    async fn child_address(&mut self) -> Address<ChildModel> {
+        self.child_address.clone()
+    }
+}
+impl Model for ParentModel {}
+
+struct ProtoParentModel {
+    output: Output<u32>,
+}
+impl ProtoModel for ProtoParentModel {
+    type Model = ParentModel;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> ParentModel {
+        let child = ChildModel {
+            output: self.output,
+        };
+        let child_mbox = Mailbox::new();
+        let mut to_child = Output::default();
+        to_child.connect(ChildModel::input, &child_mbox);
+        let child_address = cx.add_submodel_with_address(child, child_mbox, "child");
+
+        ParentModel {
+            child_address,
+            to_child,
+        }
+    }
+}
+
+fn exposed_submodel(num_threads: usize) {
+    let mut parent = ProtoParentModel {
+        output: Output::default(),
+    };
+    let parent_mbox = Mailbox::new();
+    let parent_addr = parent_mbox.address();
+
+    let mut output = EventBuffer::new();
+    parent.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(parent, parent_mbox, "parent")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    let child_addr = simu
+        .process_query(ParentModel::child_address, (), &parent_addr)
+        .unwrap();
+
+    // The sub-model is reachable both through its parent and directly.
+    simu.process_event(ParentModel::input, 1, &parent_addr)
+        .unwrap();
+    simu.process_event(ChildModel::input, 2, child_addr)
+        .unwrap();
+    assert_eq!(output.next(), Some(10));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn exposed_submodel_st() {
+    exposed_submodel(1);
+}
+
+#[test]
+fn exposed_submodel_mt() {
+    exposed_submodel(MT_NUM_THREADS);
+}
@@ -0,0 +1,124 @@
+//! Background tasks spawned by models.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use nexosim::model::{Context, InitializedModel, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sends a notification when dropped.
+struct DropNotifier(mpsc::Sender<&'static str>);
+
+impl Drop for DropNotifier {
+    fn drop(&mut self) {
+        let _ = self.0.send("cancelled");
+    }
+}
+
+/// A model that injects events into its own input from a background task.
+struct Listener {
+    output: Output<u32>,
+    values: Vec<u32>,
+    notifier: mpsc::Sender<&'static str>,
+}
+impl Listener {
+    fn new(values: Vec<u32>, notifier: mpsc::Sender<&'static str>) -> Self {
+        Self {
+            output: Output::default(),
+            values,
+            notifier,
+        }
+    }
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Listener {
+    async fn init(mut self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+        let injector = cx.injector();
+        let values = std::mem::take(&mut self.values);
+        let notifier = self.notifier.clone();
+
+        cx.spawn(async move {
+            for value in values {
+                injector.send(Listener::input, value).unwrap();
+            }
+            notifier.send("sent").unwrap();
+
+            // The task can now only end by being cancelled.
+            let _notifier = DropNotifier(notifier);
+            std::future::pending::<()>().await;
+        });
+
+        self.into()
+    }
+}
+
+fn background_task(num_threads: usize) {
+    let (notifier, notifications) = mpsc::channel();
+
+    let mut model = Listener::new(vec![1, 2, 3], notifier);
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, _scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, Mailbox::new(), "listener")
+        .init(t0)
+        .unwrap();
+
+    assert_eq!(notifications.recv_timeout(TIMEOUT), Ok("sent"));
+
+    // Injected events are processed at the current time.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0);
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(output.next(), Some(3));
+    assert!(output.next().is_none());
+
+    // The background task is cancelled when the model is removed.
+    simu.remove_model("listener").unwrap();
+    assert_eq!(notifications.recv_timeout(TIMEOUT), Ok("cancelled"));
+}
+
+fn background_task_simulation_drop(num_threads: usize) {
+    let (notifier, notifications) = mpsc::channel();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (simu, _scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(Listener::new(Vec::new(), notifier), Mailbox::new(), "")
+        .init(t0)
+        .unwrap();
+
+    assert_eq!(notifications.recv_timeout(TIMEOUT), Ok("sent"));
+
+    // The background task is cancelled when the simulation is dropped.
+    drop(simu);
+    assert_eq!(notifications.recv_timeout(TIMEOUT), Ok("cancelled"));
+}
+
+#[test]
+fn background_task_st() {
+    background_task(1);
+}
+
+#[test]
+fn background_task_mt() {
+    background_task(MT_NUM_THREADS);
+}
+
+#[test]
+fn background_task_simulation_drop_st() {
+    background_task_simulation_drop(1);
+}
+
+#[test]
+fn background_task_simulation_drop_mt() {
+    background_task_simulation_drop(MT_NUM_THREADS);
+}
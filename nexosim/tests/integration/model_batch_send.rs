@@ -0,0 +1,136 @@
+//! Sending batches of events.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Emitter {
+    output: Output<usize>,
+    values: Vec<usize>,
+}
+impl Emitter {
+    fn new(values: Vec<usize>) -> Self {
+        Self {
+            output: Output::default(),
+            values,
+        }
+    }
+    async fn burst(&mut self) {
+        self.output.send_all(self.values.clone()).await;
+    }
+}
+impl Model for Emitter {}
+
+#[derive(Default)]
+struct Receiver {
+    output: Output<usize>,
+}
+impl Receiver {
+    async fn input(&mut self, value: usize) {
+        self.output.send(value).await;
+    }
+    async fn double(&mut self, value: usize) {
+        self.output.send(2 * value).await;
+    }
+}
+impl Model for Receiver {}
+
+fn batch_send_contiguous(num_threads: usize) {
+    let mut emitter1 = Emitter::new(vec![1, 2, 3]);
+    let mut emitter2 = Emitter::new(vec![10, 20, 30]);
+    let mut receiver = Receiver::default();
+    let emitter1_mbox = Mailbox::new();
+    let emitter2_mbox = Mailbox::new();
+    let emitter1_addr = emitter1_mbox.address();
+    let emitter2_addr = emitter2_mbox.address();
+
+    // The receiver mailbox can only hold one message at a time.
+    let receiver_mbox = Mailbox::with_capacity(1);
+    let output = EventBuffer::new();
+    receiver.output.connect_sink(&output);
+    emitter1.output.connect(Receiver::input, &receiver_mbox);
+    emitter2.output.connect(Receiver::input, &receiver_mbox);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(emitter1, emitter1_mbox, "emitter1")
+        .add_model(emitter2, emitter2_mbox, "emitter2")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap();
+
+    // Both batches are sent at the same time.
+    scheduler
+        .schedule_event(Duration::from_secs(1), Emitter::burst, (), &emitter1_addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(1), Emitter::burst, (), &emitter2_addr)
+        .unwrap();
+    simu.step().unwrap();
+
+    // Each batch is processed contiguously.
+    let output: Vec<_> = output.collect();
+    assert!(
+        output == vec![1, 2, 3, 10, 20, 30] || output == vec![10, 20, 30, 1, 2, 3],
+        "unexpected output: {:?}",
+        output
+    );
+}
+
+#[test]
+fn batch_send_contiguous_st() {
+    batch_send_contiguous(1);
+}
+
+#[test]
+fn batch_send_contiguous_mt() {
+    batch_send_contiguous(MT_NUM_THREADS);
+}
+
+fn batch_send_map(num_threads: usize) {
+    let mut emitter = Emitter::new(vec![1, 2, 3, 4]);
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let emitter_addr = emitter_mbox.address();
+
+    let receiver_mbox = Mailbox::with_capacity(1);
+    let output = EventBuffer::new();
+    receiver.output.connect_sink(&output);
+    emitter
+        .output
+        .map_connect(|v: &usize| v + 1, Receiver::input, &receiver_mbox);
+    emitter.output.filter_map_connect(
+        |v: &usize| (v % 2 == 0).then_some(*v),
+        Receiver::double,
+        &receiver_mbox,
+    );
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Connections are served in turn, and the events of a batch are mapped
+    // and filtered individually.
+    simu.process_event(Emitter::burst, (), &emitter_addr)
+        .unwrap();
+    assert_eq!(output.collect::<Vec<_>>(), vec![2, 3, 4, 5, 4, 8]);
+}
+
+#[test]
+fn batch_send_map_st() {
+    batch_send_map(1);
+}
+
+#[test]
+fn batch_send_map_mt() {
+    batch_send_map(MT_NUM_THREADS);
+}
@@ -0,0 +1,172 @@
+//! Best-effort queries skipping unreachable replier ports.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output, Requestor};
+use nexosim::simulation::{Address, ExecutionError, Mailbox, SimInit, Simulation};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Replier {
+    id: usize,
+}
+impl Replier {
+    async fn reply(&mut self) -> usize {
+        self.id
+    }
+}
+impl Model for Replier {}
+
+#[derive(Default)]
+struct Requester {
+    requestor: Requestor<(), usize>,
+    output: Output<(Vec<usize>, usize)>,
+}
+impl Requester {
+    async fn query(&mut self) {
+        let replies = self.requestor.send(()).await.collect();
+        self.output.send((replies, 0)).await;
+    }
+    async fn query_best_effort(&mut self) {
+        let (replies, failure_count) = self.requestor.send_best_effort(()).await;
+        let replies = replies.collect();
+        self.output.send((replies, failure_count)).await;
+    }
+}
+impl Model for Requester {}
+
+/// Builds a bench with `replier_count` repliers, identified by their
+/// connection index, among which the repliers at the indices listed in
+/// `dead_repliers` have their mailbox dropped.
+fn bench(
+    num_threads: usize,
+    replier_count: usize,
+    dead_repliers: &[usize],
+) -> (
+    Simulation,
+    Address<Requester>,
+    EventBuffer<(Vec<usize>, usize)>,
+) {
+    let mut requester = Requester::default();
+    let requester_mbox = Mailbox::new();
+    let requester_addr = requester_mbox.address();
+
+    let output = EventBuffer::new();
+    requester.output.connect_sink(&output);
+
+    let replier_mboxes: Vec<Mailbox<Replier>> = (0..replier_count)
+        .map(|_| {
+            let replier_mbox = Mailbox::new();
+            requester.requestor.connect(Replier::reply, &replier_mbox);
+
+            replier_mbox
+        })
+        .collect();
+
+    let mut sim_init =
+        SimInit::with_num_threads(num_threads).add_model(requester, requester_mbox, "requester");
+    for (id, replier_mbox) in replier_mboxes.into_iter().enumerate() {
+        // The mailboxes of dead repliers are dropped.
+        if !dead_repliers.contains(&id) {
+            sim_init = sim_init.add_model(Replier { id }, replier_mbox, format!("replier{}", id));
+        }
+    }
+
+    let simu = sim_init.init(MonotonicTime::EPOCH).unwrap().0;
+
+    (simu, requester_addr, output)
+}
+
+fn best_effort_query_all_reachable(num_threads: usize) {
+    let (mut simu, addr, mut output) = bench(num_threads, 3, &[]);
+
+    simu.process_event(Requester::query_best_effort, (), &addr)
+        .unwrap();
+    assert_eq!(output.next(), Some((vec![0, 1, 2], 0)));
+}
+
+fn best_effort_query_single_replier(num_threads: usize) {
+    let (mut simu, addr, mut output) = bench(num_threads, 1, &[0]);
+
+    simu.process_event(Requester::query_best_effort, (), &addr)
+        .unwrap();
+    assert_eq!(output.next(), Some((Vec::new(), 1)));
+
+    // A regular query fails on the same setup.
+    match simu.process_event(Requester::query, (), &addr) {
+        Err(ExecutionError::NoRecipient { model }) => {
+            assert_eq!(model, Some(String::from("requester")));
+        }
+        _ => panic!("missing recipient not detected"),
+    }
+}
+
+fn best_effort_query_multiple_repliers(num_threads: usize) {
+    let (mut simu, addr, mut output) = bench(num_threads, 4, &[1]);
+
+    // Replies arrive in connection order.
+    simu.process_event(Requester::query_best_effort, (), &addr)
+        .unwrap();
+    assert_eq!(output.next(), Some((vec![0, 2, 3], 1)));
+
+    // Unreachable repliers are skipped on each query.
+    simu.process_event(Requester::query_best_effort, (), &addr)
+        .unwrap();
+    assert_eq!(output.next(), Some((vec![0, 2, 3], 1)));
+
+    // A regular query fails on the same setup.
+    match simu.process_event(Requester::query, (), &addr) {
+        Err(ExecutionError::NoRecipient { model }) => {
+            assert_eq!(model, Some(String::from("requester")));
+        }
+        _ => panic!("missing recipient not detected"),
+    }
+}
+
+fn best_effort_query_multiple_failures(num_threads: usize) {
+    let (mut simu, addr, mut output) = bench(num_threads, 5, &[0, 3, 4]);
+
+    simu.process_event(Requester::query_best_effort, (), &addr)
+        .unwrap();
+    assert_eq!(output.next(), Some((vec![1, 2], 3)));
+}
+
+#[test]
+fn best_effort_query_all_reachable_st() {
+    best_effort_query_all_reachable(1);
+}
+
+#[test]
+fn best_effort_query_all_reachable_mt() {
+    best_effort_query_all_reachable(MT_NUM_THREADS);
+}
+
+#[test]
+fn best_effort_query_single_replier_st() {
+    best_effort_query_single_replier(1);
+}
+
+#[test]
+fn best_effort_query_single_replier_mt() {
+    best_effort_query_single_replier(MT_NUM_THREADS);
+}
+
+#[test]
+fn best_effort_query_multiple_repliers_st() {
+    best_effort_query_multiple_repliers(1);
+}
+
+#[test]
+fn best_effort_query_multiple_repliers_mt() {
+    best_effort_query_multiple_repliers(MT_NUM_THREADS);
+}
+
+#[test]
+fn best_effort_query_multiple_failures_st() {
+    best_effort_query_multiple_failures(1);
+}
+
+#[test]
+fn best_effort_query_multiple_failures_mt() {
+    best_effort_query_multiple_failures(MT_NUM_THREADS);
+}
@@ -0,0 +1,139 @@
+//! Connections with an inline delivery delay.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Emitter {
+    output: Output<usize>,
+}
+impl Emitter {
+    async fn burst(&mut self, count: usize) {
+        for i in 0..count {
+            self.output.send(i).await;
+        }
+    }
+}
+impl Model for Emitter {}
+
+#[derive(Default)]
+struct Receiver {
+    output: Output<(usize, MonotonicTime)>,
+}
+impl Receiver {
+    async fn input(&mut self, value: usize, cx: &mut Context<Self>) {
+        self.output.send((value, cx.time())).await;
+    }
+}
+impl Model for Receiver {}
+
+fn delayed_connection(num_threads: usize) {
+    const BURST_SIZE: usize = 4;
+    const DELAY: Duration = Duration::from_secs(2);
+
+    let mut emitter = Emitter::default();
+    let mut delayed_receiver = Receiver::default();
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let delayed_receiver_mbox = Mailbox::new();
+    let receiver_mbox = Mailbox::new();
+
+    let mut delayed_output = EventBuffer::new();
+    let mut output = EventBuffer::new();
+    delayed_receiver.output.connect_sink(&delayed_output);
+    receiver.output.connect_sink(&output);
+    emitter
+        .output
+        .connect_delayed(Receiver::input, &delayed_receiver_mbox, DELAY);
+    emitter.output.connect(Receiver::input, &receiver_mbox);
+
+    let emitter_addr = emitter_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(delayed_receiver, delayed_receiver_mbox, "delayed_receiver")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Emitter::burst, BURST_SIZE, &emitter_addr)
+        .unwrap();
+
+    // The regular connection delivers the events immediately.
+    assert_eq!(
+        output.by_ref().collect::<Vec<_>>(),
+        (0..BURST_SIZE).map(|i| (i, t0)).collect::<Vec<_>>()
+    );
+    assert!(delayed_output.next().is_none());
+
+    // The delayed connection delivers the events later, in the order they were
+    // sent.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + DELAY);
+    assert_eq!(
+        delayed_output.by_ref().collect::<Vec<_>>(),
+        (0..BURST_SIZE).map(|i| (i, t0 + DELAY)).collect::<Vec<_>>()
+    );
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn delayed_connection_st() {
+    delayed_connection(1);
+}
+
+#[test]
+fn delayed_connection_mt() {
+    delayed_connection(MT_NUM_THREADS);
+}
+
+fn delayed_connection_overflow(num_threads: usize) {
+    const DELAY: Duration = Duration::from_secs(2);
+
+    let mut emitter = Emitter::default();
+    let mut delayed_receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let delayed_receiver_mbox = Mailbox::new();
+
+    let mut delayed_output = EventBuffer::new();
+    delayed_receiver.output.connect_sink(&delayed_output);
+    emitter
+        .output
+        .connect_delayed(Receiver::input, &delayed_receiver_mbox, DELAY);
+
+    let emitter_addr = emitter_mbox.address();
+
+    // The delivery time of the events exceeds the largest simulation time.
+    let t0 = MonotonicTime::MAX - Duration::from_secs(1);
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(delayed_receiver, delayed_receiver_mbox, "delayed_receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // The events are dropped without aborting the simulation.
+    simu.process_event(Emitter::burst, 2, &emitter_addr)
+        .unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0);
+    assert!(delayed_output.next().is_none());
+}
+
+#[test]
+fn delayed_connection_overflow_st() {
+    delayed_connection_overflow(1);
+}
+
+#[test]
+fn delayed_connection_overflow_mt() {
+    delayed_connection_overflow(MT_NUM_THREADS);
+}
@@ -0,0 +1,79 @@
+//! Read-only inspection of models between simulation steps.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Counter {
+    count: u64,
+}
+impl Counter {
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+impl Model for Counter {}
+
+struct Other {}
+impl Model for Other {}
+
+fn model_inspection(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(Counter::default(), mbox, "counter")
+        .add_model(Other {}, Mailbox::new(), "other")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+            Counter::increment,
+            (),
+            &addr,
+        )
+        .unwrap();
+
+    simu.step_until(Duration::from_secs(2)).unwrap();
+    assert_eq!(simu.inspect_model::<Counter>("counter").unwrap().count, 2);
+
+    // The model is released once the simulation is resumed.
+    simu.process_event(Counter::increment, (), &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.inspect_model::<Counter>("counter").unwrap().count, 4);
+
+    // Repeated inspections see the same state.
+    assert_eq!(simu.inspect_model::<Counter>("counter").unwrap().count, 4);
+
+    // Unknown name or mismatched type.
+    assert!(matches!(
+        simu.inspect_model::<Counter>("unknown"),
+        Err(ExecutionError::ModelNotFound(_))
+    ));
+    assert!(matches!(
+        simu.inspect_model::<Counter>("other"),
+        Err(ExecutionError::ModelNotFound(_))
+    ));
+
+    simu.step().unwrap();
+    assert_eq!(simu.inspect_model::<Counter>("counter").unwrap().count, 5);
+}
+
+#[test]
+fn model_inspection_st() {
+    model_inspection(1);
+}
+
+#[test]
+fn model_inspection_mt() {
+    model_inspection(MT_NUM_THREADS);
+}
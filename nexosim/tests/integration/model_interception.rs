@@ -0,0 +1,98 @@
+//! Interception of the events delivered to a model.
+
+use std::sync::{Arc, Mutex};
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Emitter {
+    output: Output<u32>,
+}
+impl Emitter {
+    async fn emit(&mut self, values: Vec<u32>) {
+        for value in values {
+            self.output.send(value).await;
+        }
+    }
+}
+impl Model for Emitter {}
+
+#[derive(Default)]
+struct Receiver {
+    number: Output<u32>,
+    text: Output<String>,
+}
+impl Receiver {
+    async fn on_number(&mut self, value: u32) {
+        self.number.send(value).await;
+    }
+    async fn on_text(&mut self, value: String) {
+        self.text.send(value).await;
+    }
+}
+impl Model for Receiver {}
+
+fn model_interception(num_threads: usize) {
+    let mut emitter = Emitter::default();
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let emitter_addr = emitter_mbox.address();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let receiver_mbox = Mailbox::new()
+        .with_interceptor({
+            let log = log.clone();
+            move |value: &mut u32| log.lock().unwrap().push(*value)
+        })
+        .with_interceptor(|value: &mut u32| *value *= 10)
+        .with_interceptor(|value: &mut u32| *value += 1);
+    let receiver_addr = receiver_mbox.address();
+
+    emitter.output.connect(Receiver::on_number, &receiver_addr);
+
+    let mut numbers = EventBuffer::new();
+    let mut texts = EventBuffer::new();
+    receiver.number.connect_sink(&numbers);
+    receiver.text.connect_sink(&texts);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Events sent from another model are intercepted in order.
+    simu.process_event(Emitter::emit, vec![1, 2, 3], &emitter_addr)
+        .unwrap();
+    assert_eq!(numbers.by_ref().collect::<Vec<_>>(), vec![11, 21, 31]);
+
+    // Events injected from the bench are intercepted too.
+    simu.process_event(Receiver::on_number, 4, &receiver_addr)
+        .unwrap();
+    assert_eq!(numbers.next(), Some(41));
+
+    // Events of other types are left untouched.
+    simu.process_event(Receiver::on_text, "hello".to_string(), &receiver_addr)
+        .unwrap();
+    assert_eq!(texts.next(), Some("hello".to_string()));
+    assert!(numbers.next().is_none());
+
+    assert_eq!(*log.lock().unwrap(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn model_interception_st() {
+    model_interception(1);
+}
+
+#[test]
+fn model_interception_mt() {
+    model_interception(MT_NUM_THREADS);
+}
@@ -0,0 +1,214 @@
+//! Invariant checking at the end of each time slice and of each immediate
+//! action.
+
+use std::time::Duration;
+
+use nexosim::model::{BuildContext, Model, ProtoModel};
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Counter {
+    count: u32,
+    max_count: u32,
+}
+impl Counter {
+    fn new(max_count: u32) -> Self {
+        Self {
+            count: 0,
+            max_count,
+        }
+    }
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+impl Model for Counter {
+    fn check_invariants(&self) -> Result<(), String> {
+        if self.count > self.max_count {
+            return Err(format!("the count exceeds {}", self.max_count));
+        }
+
+        Ok(())
+    }
+}
+
+fn model_invariants(num_threads: usize) {
+    let good_mbox = Mailbox::new();
+    let good_addr = good_mbox.address();
+    let bad_mbox = Mailbox::new();
+    let bad_addr = bad_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(Counter::new(10), good_mbox, "good")
+        .add_model(Counter::new(1), bad_mbox, "bad")
+        .check_invariants()
+        .init(t0)
+        .unwrap();
+
+    for secs in 1..=2 {
+        scheduler
+            .schedule_event(
+                Duration::from_secs(secs),
+                Counter::increment,
+                (),
+                &good_addr,
+            )
+            .unwrap();
+        scheduler
+            .schedule_event(Duration::from_secs(secs), Counter::increment, (), &bad_addr)
+            .unwrap();
+    }
+
+    simu.step().unwrap();
+
+    match simu.step() {
+        Err(ExecutionError::InvariantViolated { model, message }) => {
+            assert_eq!(model, "bad");
+            assert_eq!(message, "the count exceeds 1");
+        }
+        _ => panic!("invariant violation not detected"),
+    }
+
+    // The violation is a fatal error.
+    assert!(matches!(simu.step(), Err(ExecutionError::Terminated)));
+}
+
+struct Parent {}
+impl Model for Parent {}
+
+struct ProtoParent {
+    child: Counter,
+    child_mbox: Mailbox<Counter>,
+}
+impl ProtoModel for ProtoParent {
+    type Model = Parent;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> Parent {
+        cx.add_submodel(self.child, self.child_mbox, "child");
+
+        Parent {}
+    }
+}
+
+fn model_invariants_submodel(num_threads: usize) {
+    let child_mbox = Mailbox::new();
+    let child_addr = child_mbox.address();
+    let parent = ProtoParent {
+        child: Counter::new(1),
+        child_mbox,
+    };
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(parent, Mailbox::new(), "parent")
+        .check_invariants()
+        .init(t0)
+        .unwrap();
+
+    for secs in 1..=2 {
+        scheduler
+            .schedule_event(
+                Duration::from_secs(secs),
+                Counter::increment,
+                (),
+                &child_addr,
+            )
+            .unwrap();
+    }
+
+    simu.step().unwrap();
+
+    match simu.step() {
+        Err(ExecutionError::InvariantViolated { model, message }) => {
+            assert_eq!(model, "parent.child");
+            assert_eq!(message, "the count exceeds 1");
+        }
+        _ => panic!("invariant violation not detected"),
+    }
+}
+
+fn model_invariants_process_event(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, _scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(Counter::new(1), mbox, "counter")
+        .check_invariants()
+        .init(t0)
+        .unwrap();
+
+    simu.process_event(Counter::increment, (), &addr).unwrap();
+
+    match simu.process_event(Counter::increment, (), &addr) {
+        Err(ExecutionError::InvariantViolated { model, message }) => {
+            assert_eq!(model, "counter");
+            assert_eq!(message, "the count exceeds 1");
+        }
+        _ => panic!("invariant violation not detected"),
+    }
+
+    // The violation is a fatal error.
+    assert!(matches!(simu.step(), Err(ExecutionError::Terminated)));
+}
+
+fn model_invariants_disabled(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(Counter::new(0), mbox, "counter")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), Counter::increment, (), &addr)
+        .unwrap();
+
+    // Invariants are not checked by default.
+    simu.step().unwrap();
+}
+
+#[test]
+fn model_invariants_st() {
+    model_invariants(1);
+}
+
+#[test]
+fn model_invariants_mt() {
+    model_invariants(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_invariants_disabled_st() {
+    model_invariants_disabled(1);
+}
+
+#[test]
+fn model_invariants_disabled_mt() {
+    model_invariants_disabled(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_invariants_submodel_st() {
+    model_invariants_submodel(1);
+}
+
+#[test]
+fn model_invariants_submodel_mt() {
+    model_invariants_submodel(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_invariants_process_event_st() {
+    model_invariants_process_event(1);
+}
+
+#[test]
+fn model_invariants_process_event_mt() {
+    model_invariants_process_event(MT_NUM_THREADS);
+}
@@ -0,0 +1,140 @@
+//! Log events emitted from models with `Context::log_event`.
+
+use nexosim::model::{Context, LogLevel, Model};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel;
+impl TestModel {
+    async fn log(&mut self, level: LogLevel, cx: &mut Context<Self>) {
+        cx.log_event(level, format_args!("{:?} event", level));
+    }
+}
+impl Model for TestModel {}
+
+const LEVELS: [LogLevel; 5] = [
+    LogLevel::Error,
+    LogLevel::Warn,
+    LogLevel::Info,
+    LogLevel::Debug,
+    LogLevel::Trace,
+];
+
+/// Processes a log request at each verbosity level.
+fn log_all_levels(num_threads: usize, t0: MonotonicTime) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel, mbox, "logger")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    for level in LEVELS {
+        simu.process_event(TestModel::log, level, &addr).unwrap();
+    }
+}
+
+fn model_log_event(num_threads: usize) {
+    // Logging never interferes with the simulation, whether or not the
+    // `tracing` feature is enabled and a subscriber is installed.
+    log_all_levels(num_threads, MonotonicTime::EPOCH);
+}
+
+#[test]
+fn model_log_event_st() {
+    model_log_event(1);
+}
+
+#[test]
+fn model_log_event_mt() {
+    model_log_event(MT_NUM_THREADS);
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_events {
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::layer::{Context as LayerContext, Layer, SubscriberExt};
+
+    use super::*;
+
+    /// A tracing event captured by `RecordingLayer`.
+    #[derive(Debug, Default, PartialEq)]
+    struct Record {
+        level: Option<Level>,
+        target: String,
+        message: String,
+        sim_time: String,
+        model: String,
+    }
+    impl Visit for Record {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "model" {
+                self.model = value.to_string();
+            }
+        }
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            match field.name() {
+                "message" => self.message = format!("{:?}", value),
+                "sim_time" => self.sim_time = format!("{:?}", value),
+                "model" => self.model = format!("{:?}", value),
+                _ => {}
+            }
+        }
+    }
+
+    /// A layer that records all events.
+    #[derive(Clone, Default)]
+    struct RecordingLayer(Arc<Mutex<Vec<Record>>>);
+    impl<S: Subscriber> Layer<S> for RecordingLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+            let mut record = Record {
+                level: Some(*event.metadata().level()),
+                target: event.metadata().target().to_string(),
+                ..Default::default()
+            };
+            event.record(&mut record);
+
+            self.0.lock().unwrap().push(record);
+        }
+    }
+
+    #[test]
+    fn model_log_event_tracing_st() {
+        let layer = RecordingLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        let t0 = MonotonicTime::new(100, 0).unwrap();
+
+        // The subscriber is only installed on the current thread, which is
+        // also the thread running the models of a single-threaded simulation.
+        tracing::subscriber::with_default(subscriber, || log_all_levels(1, t0));
+
+        let records = layer.0.lock().unwrap();
+        let expected: Vec<_> = LEVELS
+            .iter()
+            .zip([
+                Level::ERROR,
+                Level::WARN,
+                Level::INFO,
+                Level::DEBUG,
+                Level::TRACE,
+            ])
+            .map(|(level, tracing_level)| Record {
+                level: Some(tracing_level),
+                target: "nexosim".to_string(),
+                message: format!("{:?} event", level),
+                sim_time: t0.to_string(),
+                model: "logger".to_string(),
+            })
+            .collect();
+
+        assert_eq!(*records, expected);
+    }
+}
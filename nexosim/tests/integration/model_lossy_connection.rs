@@ -0,0 +1,233 @@
+//! Lossy connections and non-blocking sends to saturated mailboxes.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output, TrySendError};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Emitter {
+    output: Output<usize>,
+    rejected: Output<usize>,
+    partially_sent: Output<(usize, usize)>,
+}
+impl Emitter {
+    async fn burst(&mut self, count: usize) {
+        for i in 0..count {
+            self.output.send(i).await;
+        }
+    }
+    async fn try_burst(&mut self, count: usize) {
+        for i in 0..count {
+            match self.output.try_send(i) {
+                Ok(()) => {}
+                Err(TrySendError::Full(i)) => self.rejected.send(i).await,
+                Err(TrySendError::PartiallySent(i, undelivered)) => {
+                    self.partially_sent.send((i, undelivered)).await
+                }
+            }
+        }
+    }
+}
+impl Model for Emitter {}
+
+#[derive(Default)]
+struct Receiver {
+    output: Output<usize>,
+}
+impl Receiver {
+    async fn input(&mut self, value: usize) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Receiver {}
+
+fn lossy_connection(num_threads: usize) {
+    const BURST_SIZE: usize = 4;
+
+    let mut emitter = Emitter::default();
+    let mut lossy_receiver = Receiver::default();
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let lossy_receiver_mbox = Mailbox::with_capacity(1);
+    let receiver_mbox = Mailbox::new();
+
+    let lossy_output = EventBuffer::with_capacity(BURST_SIZE);
+    let output = EventBuffer::with_capacity(BURST_SIZE);
+    lossy_receiver.output.connect_sink(&lossy_output);
+    receiver.output.connect_sink(&output);
+    let loss_counter = emitter
+        .output
+        .connect_lossy(Receiver::input, &lossy_receiver_mbox);
+    emitter.output.connect(Receiver::input, &receiver_mbox);
+
+    let emitter_addr = emitter_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(lossy_receiver, lossy_receiver_mbox, "lossy_receiver")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Emitter::burst, BURST_SIZE, &emitter_addr)
+        .unwrap();
+
+    // The regular connection applies backpressure so no event is lost.
+    assert_eq!(
+        output.collect::<Vec<_>>(),
+        (0..BURST_SIZE).collect::<Vec<_>>()
+    );
+
+    // Events are dropped on the lossy connection when the mailbox is full.
+    let lossy_output: Vec<_> = lossy_output.collect();
+    assert!(!lossy_output.is_empty());
+    assert_eq!(
+        lossy_output.len() + loss_counter.count() as usize,
+        BURST_SIZE
+    );
+    if num_threads == 1 {
+        // With a single thread, the receiver cannot run before the burst is
+        // complete.
+        assert_eq!(lossy_output, vec![0]);
+    }
+}
+
+#[test]
+fn lossy_connection_st() {
+    lossy_connection(1);
+}
+
+#[test]
+fn lossy_connection_mt() {
+    lossy_connection(MT_NUM_THREADS);
+}
+
+fn try_send(num_threads: usize) {
+    const BURST_SIZE: usize = 4;
+
+    let mut emitter = Emitter::default();
+    let mut small_receiver = Receiver::default();
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let small_receiver_mbox = Mailbox::with_capacity(1);
+    let receiver_mbox = Mailbox::new();
+
+    let small_output = EventBuffer::with_capacity(BURST_SIZE);
+    let output = EventBuffer::with_capacity(BURST_SIZE);
+    let rejected = EventBuffer::with_capacity(BURST_SIZE);
+    small_receiver.output.connect_sink(&small_output);
+    receiver.output.connect_sink(&output);
+    emitter.rejected.connect_sink(&rejected);
+    emitter
+        .output
+        .connect(Receiver::input, &small_receiver_mbox);
+    emitter.output.connect(Receiver::input, &receiver_mbox);
+
+    let emitter_addr = emitter_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(small_receiver, small_receiver_mbox, "small_receiver")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Emitter::try_burst, BURST_SIZE, &emitter_addr)
+        .unwrap();
+
+    // Events are either sent to all receivers or returned.
+    let small_output: Vec<_> = small_output.collect();
+    let output: Vec<_> = output.collect();
+    let rejected: Vec<_> = rejected.collect();
+    assert!(!small_output.is_empty());
+    assert_eq!(small_output, output);
+    assert_eq!(small_output.len() + rejected.len(), BURST_SIZE);
+    if num_threads == 1 {
+        // With a single thread, the receivers cannot run before the burst is
+        // complete.
+        assert_eq!(output, vec![0]);
+        assert_eq!(rejected, vec![1, 2, 3]);
+    }
+}
+
+#[test]
+fn try_send_st() {
+    try_send(1);
+}
+
+#[test]
+fn try_send_mt() {
+    try_send(MT_NUM_THREADS);
+}
+
+fn try_send_partial(num_threads: usize) {
+    const BURST_SIZE: usize = 4;
+
+    let mut emitter = Emitter::default();
+    let mut receiver = Receiver::default();
+    let emitter_mbox = Mailbox::new();
+    let receiver_mbox = Mailbox::with_capacity(1);
+
+    let output = EventBuffer::with_capacity(2 * BURST_SIZE);
+    let rejected = EventBuffer::with_capacity(BURST_SIZE);
+    let partially_sent = EventBuffer::with_capacity(BURST_SIZE);
+    receiver.output.connect_sink(&output);
+    emitter.rejected.connect_sink(&rejected);
+    emitter.partially_sent.connect_sink(&partially_sent);
+
+    // Both connections target the same mailbox, which can only hold one
+    // event.
+    emitter.output.connect(Receiver::input, &receiver_mbox);
+    emitter.output.connect(Receiver::input, &receiver_mbox);
+
+    let emitter_addr = emitter_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Emitter::try_burst, BURST_SIZE, &emitter_addr)
+        .unwrap();
+
+    // Each event is either sent to both connections, sent to only one of them
+    // and reported as partially sent, or returned.
+    let output: Vec<_> = output.collect();
+    let rejected: Vec<_> = rejected.collect();
+    let partially_sent: Vec<_> = partially_sent.collect();
+    for i in 0..BURST_SIZE {
+        let count = output.iter().filter(|&&v| v == i).count();
+        match count {
+            2 => {}
+            1 => assert!(partially_sent.contains(&(i, 1))),
+            _ => assert!(rejected.contains(&i)),
+        }
+    }
+    if num_threads == 1 {
+        // With a single thread, the receiver cannot run before the burst is
+        // complete.
+        assert_eq!(output, vec![0]);
+        assert_eq!(partially_sent, vec![(0, 1)]);
+        assert_eq!(rejected, vec![1, 2, 3]);
+    }
+}
+
+#[test]
+fn try_send_partial_st() {
+    try_send_partial(1);
+}
+
+#[test]
+fn try_send_partial_mt() {
+    try_send_partial(MT_NUM_THREADS);
+}
@@ -0,0 +1,67 @@
+//! Aggregation of model configuration snapshots into a manifest.
+
+use nexosim::model::{BuildContext, Model, ProtoModel};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+use serde_json::json;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Pump {
+    flow_rate: f64,
+}
+impl Model for Pump {
+    fn describe(&self) -> Option<serde_json::Value> {
+        Some(json!({ "flow_rate": self.flow_rate }))
+    }
+}
+
+struct Valve {}
+impl Model for Valve {}
+
+struct ProtoStation {
+    flow_rate: f64,
+}
+impl ProtoModel for ProtoStation {
+    type Model = Valve;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> Valve {
+        let pump = Pump {
+            flow_rate: self.flow_rate,
+        };
+        cx.add_submodel(pump, Mailbox::new(), "pump");
+
+        Valve {}
+    }
+}
+
+fn model_manifest(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(Pump { flow_rate: 1.5 }, Mailbox::new(), "pump")
+        .add_model(ProtoStation { flow_rate: 2.5 }, Mailbox::new(), "station")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    let expected = json!({
+        "pump": { "flow_rate": 1.5 },
+        "station.pump": { "flow_rate": 2.5 },
+    });
+    assert_eq!(simu.manifest(), expected);
+
+    // Models added to a started simulation are included too.
+    simu.add_model(Pump { flow_rate: 3.5 }, Mailbox::new(), "extra_pump")
+        .unwrap();
+    assert_eq!(simu.manifest()["extra_pump"], json!({ "flow_rate": 3.5 }));
+}
+
+#[test]
+fn model_manifest_st() {
+    model_manifest(1);
+}
+
+#[test]
+fn model_manifest_mt() {
+    model_manifest(MT_NUM_THREADS);
+}
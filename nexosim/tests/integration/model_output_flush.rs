@@ -0,0 +1,120 @@
+//! Flushing of output ports shared between models.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+/// A model that sends bursts of events on a clone of a flushable port.
+struct Producer {
+    output: Output<usize>,
+}
+impl Producer {
+    async fn burst(&mut self, count: usize) {
+        for i in 0..count {
+            self.output.send(i).await;
+        }
+    }
+}
+impl Model for Producer {}
+
+/// A model that flushes a flushable port.
+struct Flusher {
+    output: Output<usize>,
+    done: Output<()>,
+}
+impl Flusher {
+    async fn flush(&mut self) {
+        self.output.flush().await;
+        self.done.send(()).await;
+    }
+}
+impl Model for Flusher {}
+
+#[derive(Default)]
+struct Receiver {
+    output: Output<usize>,
+}
+impl Receiver {
+    async fn input(&mut self, value: usize) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Receiver {}
+
+#[derive(Default)]
+struct Trigger {
+    output: Output<usize>,
+}
+impl Trigger {
+    async fn trigger(&mut self, count: usize) {
+        self.output.send(count).await;
+    }
+}
+impl Model for Trigger {}
+
+fn output_flush(num_threads: usize) {
+    const BURST_SIZE: usize = 8;
+
+    let mut trigger = Trigger::default();
+    let mut receiver = Receiver::default();
+    let trigger_mbox = Mailbox::new();
+    let producer_mbox = Mailbox::new();
+    let flusher_mbox = Mailbox::new();
+    // The small capacity makes the producer wait for the receiver.
+    let receiver_mbox = Mailbox::with_capacity(1);
+
+    let mut output = Output::new_flushable();
+    output.connect(Receiver::input, &receiver_mbox);
+    let producer = Producer {
+        output: output.clone(),
+    };
+    let mut flusher = Flusher {
+        output,
+        done: Output::default(),
+    };
+
+    let received = EventBuffer::with_capacity(BURST_SIZE);
+    let done = EventBuffer::new();
+    receiver.output.connect_sink(&received);
+    flusher.done.connect_sink(&done);
+    trigger.output.connect(Producer::burst, &producer_mbox);
+    trigger
+        .output
+        .map_connect(|_| (), Flusher::flush, &flusher_mbox);
+
+    let trigger_addr = trigger_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(trigger, trigger_mbox, "trigger")
+        .add_model(producer, producer_mbox, "producer")
+        .add_model(flusher, flusher_mbox, "flusher")
+        .add_model(receiver, receiver_mbox, "receiver")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // The flush completes even though the producer is concurrently blocked
+    // on the mailbox of the receiver.
+    simu.process_event(Trigger::trigger, BURST_SIZE, &trigger_addr)
+        .unwrap();
+
+    assert_eq!(done.count(), 1);
+    assert_eq!(
+        received.collect::<Vec<_>>(),
+        (0..BURST_SIZE).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn output_flush_st() {
+    output_flush(1);
+}
+
+#[test]
+fn output_flush_mt() {
+    output_flush(MT_NUM_THREADS);
+}
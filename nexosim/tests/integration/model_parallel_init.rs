@@ -0,0 +1,145 @@
+//! Parallel initialization of models.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use nexosim::model::{Context, InitializedModel, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+/// A barrier that gives up after a timeout.
+struct Barrier {
+    count: Mutex<usize>,
+    condvar: Condvar,
+    size: usize,
+}
+impl Barrier {
+    fn new(size: usize) -> Self {
+        Self {
+            count: Mutex::new(0),
+            condvar: Condvar::new(),
+            size,
+        }
+    }
+
+    /// Blocks until all threads have reached the barrier and returns `true`,
+    /// or returns `false` on timeout.
+    fn wait(&self) -> bool {
+        let mut count = self.count.lock().unwrap();
+        *count += 1;
+        self.condvar.notify_all();
+        let (count, _) = self
+            .condvar
+            .wait_timeout_while(count, Duration::from_secs(5), |count| *count < self.size)
+            .unwrap();
+
+        *count >= self.size
+    }
+}
+
+// A model which initializer blocks until the initializers of as many models as
+// the barrier size are running.
+struct BlockingModel {
+    output: Output<bool>,
+    barrier: Arc<Barrier>,
+}
+impl Model for BlockingModel {
+    async fn init(mut self, _: &mut Context<Self>) -> InitializedModel<Self> {
+        let is_parallel = self.barrier.wait();
+        self.output.send(is_parallel).await;
+
+        self.into()
+    }
+}
+
+// A model which initializer blocks for a while and reports whether another
+// sequentially initialized model was being initialized at the same time.
+struct SequentialModel {
+    output: Output<(usize, bool)>,
+    id: usize,
+    active_count: Arc<AtomicUsize>,
+}
+impl Model for SequentialModel {
+    async fn init(mut self, _: &mut Context<Self>) -> InitializedModel<Self> {
+        let is_alone = self.active_count.fetch_add(1, Ordering::SeqCst) == 0;
+        thread::sleep(Duration::from_millis(20));
+        self.active_count.fetch_sub(1, Ordering::SeqCst);
+        self.output.send((self.id, is_alone)).await;
+
+        self.into()
+    }
+}
+
+fn model_parallel_init(num_threads: usize, num_models: usize) {
+    // All worker threads must be busy at the same time at some point.
+    let barrier = Arc::new(Barrier::new(num_threads));
+    let mut output = EventBuffer::new();
+
+    let mut bench = SimInit::with_num_threads(num_threads).parallel_init();
+    for i in 0..num_models {
+        let mut model = BlockingModel {
+            output: Output::default(),
+            barrier: barrier.clone(),
+        };
+        model.output.connect_sink(&output);
+        bench = bench.add_model(model, Mailbox::new(), format!("model{}", i));
+    }
+
+    let t0 = MonotonicTime::EPOCH;
+    let (_simu, _scheduler) = bench.init(t0).unwrap();
+
+    for _ in 0..num_models {
+        assert_eq!(output.next(), Some(true));
+    }
+    assert!(output.next().is_none());
+}
+
+fn model_parallel_init_opt_out(num_threads: usize, num_models: usize) {
+    let active_count = Arc::new(AtomicUsize::new(0));
+    let mut output = EventBuffer::new();
+
+    let mut bench = SimInit::with_num_threads(num_threads).parallel_init();
+    for id in 0..num_models {
+        let mut model = SequentialModel {
+            output: Output::default(),
+            id,
+            active_count: active_count.clone(),
+        };
+        model.output.connect_sink(&output);
+        bench = bench.add_model_sequential_init(model, Mailbox::new(), format!("model{}", id));
+    }
+
+    let t0 = MonotonicTime::EPOCH;
+    let (_simu, _scheduler) = bench.init(t0).unwrap();
+
+    // Models are initialized one at a time and in order.
+    for id in 0..num_models {
+        assert_eq!(output.next(), Some((id, true)));
+    }
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn model_parallel_init_st() {
+    model_parallel_init(1, 2);
+}
+
+#[test]
+fn model_parallel_init_mt() {
+    model_parallel_init(MT_NUM_THREADS, 2 * MT_NUM_THREADS);
+}
+
+#[test]
+fn model_parallel_init_opt_out_st() {
+    model_parallel_init_opt_out(1, 3);
+}
+
+#[test]
+fn model_parallel_init_opt_out_mt() {
+    model_parallel_init_opt_out(MT_NUM_THREADS, 2 * MT_NUM_THREADS);
+}
@@ -0,0 +1,83 @@
+//! Prioritized processing of messages.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Message {
+    Command(u32),
+    Data(u32),
+}
+
+#[derive(Default)]
+struct TestModel {
+    commands: Output<u32>,
+    data: Output<u32>,
+    output: Output<Message>,
+}
+impl TestModel {
+    // Sends messages to itself, which can only be processed once this method
+    // has completed.
+    async fn burst(&mut self) {
+        self.data.send(1).await;
+        self.commands.send(1).await;
+        self.data.send(2).await;
+        self.data.send(3).await;
+        self.commands.send(2).await;
+    }
+    async fn command(&mut self, id: u32) {
+        self.output.send(Message::Command(id)).await;
+    }
+    async fn data(&mut self, id: u32) {
+        self.output.send(Message::Data(id)).await;
+    }
+}
+impl Model for TestModel {}
+
+fn prioritized_mailbox(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new_prioritized();
+    let addr = mbox.address();
+
+    model
+        .commands
+        .connect(TestModel::command, addr.high_priority());
+    model.data.connect(TestModel::data, &addr);
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::burst, (), &addr).unwrap();
+
+    // FIFO order within each class, high-priority class first.
+    assert_eq!(
+        output.by_ref().collect::<Vec<_>>(),
+        vec![
+            Message::Command(1),
+            Message::Command(2),
+            Message::Data(1),
+            Message::Data(2),
+            Message::Data(3),
+        ]
+    );
+}
+
+#[test]
+fn prioritized_mailbox_st() {
+    prioritized_mailbox(1);
+}
+
+#[test]
+fn prioritized_mailbox_mt() {
+    prioritized_mailbox(MT_NUM_THREADS);
+}
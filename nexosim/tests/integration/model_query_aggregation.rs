@@ -0,0 +1,113 @@
+//! Aggregation of heterogeneous replies from a single requestor.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventSlot, Output, Requestor};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Status {
+    Pump(f64),
+    Valve(bool),
+    Tank(u32),
+}
+
+struct Pump {
+    flow_rate: f64,
+}
+impl Pump {
+    async fn flow_rate(&mut self) -> f64 {
+        self.flow_rate
+    }
+}
+impl Model for Pump {}
+
+struct Valve {
+    is_open: bool,
+}
+impl Valve {
+    async fn is_open(&mut self) -> bool {
+        self.is_open
+    }
+}
+impl Model for Valve {}
+
+struct Tank {
+    level: u32,
+}
+impl Tank {
+    async fn level(&mut self) -> u32 {
+        self.level
+    }
+}
+impl Model for Tank {}
+
+#[derive(Default)]
+struct Supervisor {
+    status: Requestor<(), Status>,
+    report: Output<Vec<Status>>,
+}
+impl Supervisor {
+    async fn check(&mut self) {
+        let statuses = self.status.send(()).await.collect();
+        self.report.send(statuses).await;
+    }
+}
+impl Model for Supervisor {}
+
+fn heterogeneous_replies(num_threads: usize) {
+    let mut supervisor = Supervisor::default();
+    let pump_mbox = Mailbox::new();
+    let valve_mbox = Mailbox::new();
+    let tank_mbox = Mailbox::new();
+    let supervisor_mbox = Mailbox::new();
+
+    supervisor
+        .status
+        .connect_variant(Status::Pump, Pump::flow_rate, &pump_mbox);
+    supervisor
+        .status
+        .connect_variant(Status::Valve, Valve::is_open, &valve_mbox);
+    supervisor
+        .status
+        .connect_variant(Status::Tank, Tank::level, &tank_mbox);
+
+    let mut report = EventSlot::new();
+    supervisor.report.connect_sink(&report);
+    let supervisor_addr = supervisor_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(Pump { flow_rate: 1.5 }, pump_mbox, "pump")
+        .add_model(Valve { is_open: true }, valve_mbox, "valve")
+        .add_model(Tank { level: 42 }, tank_mbox, "tank")
+        .add_model(supervisor, supervisor_mbox, "supervisor")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Supervisor::check, (), &supervisor_addr)
+        .unwrap();
+
+    // Replies are yielded in the order the replier ports were connected.
+    assert_eq!(
+        report.next(),
+        Some(vec![
+            Status::Pump(1.5),
+            Status::Valve(true),
+            Status::Tank(42)
+        ])
+    );
+}
+
+#[test]
+fn heterogeneous_replies_st() {
+    heterogeneous_replies(1);
+}
+
+#[test]
+fn heterogeneous_replies_mt() {
+    heterogeneous_replies(MT_NUM_THREADS);
+}
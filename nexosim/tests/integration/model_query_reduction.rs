@@ -0,0 +1,194 @@
+//! Collection and reduction of the replies of a requestor.
+
+use std::collections::BTreeSet;
+
+use nexosim::model::Model;
+use nexosim::ports::{EventSlot, Output, Requestor};
+use nexosim::simulation::{Address, Mailbox, SimInit, Simulation};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Sensor {
+    value: u32,
+}
+impl Sensor {
+    async fn read(&mut self, offset: u32) -> u32 {
+        self.value + offset
+    }
+}
+impl Model for Sensor {}
+
+#[derive(Default)]
+struct Supervisor {
+    readings: Requestor<u32, u32>,
+    collected: Output<BTreeSet<u32>>,
+    total: Output<u32>,
+    enumerated: Output<Vec<(usize, u32)>>,
+}
+impl Supervisor {
+    async fn collect(&mut self, offset: u32) {
+        let readings = self.readings.send_collect(offset).await;
+        self.collected.send(readings).await;
+    }
+    async fn sum(&mut self, offset: u32) {
+        let total = self.readings.send_fold(offset, 0, |sum, r| sum + r).await;
+        self.total.send(total).await;
+    }
+    async fn enumerate(&mut self, offset: u32) {
+        let readings = self.readings.send_enumerated(offset).await.collect();
+        self.enumerated.send(readings).await;
+    }
+}
+impl Model for Supervisor {}
+
+/// Creates a bench with a supervisor connected to sensors with the specified
+/// values.
+fn supervisor_bench(
+    num_threads: usize,
+    values: &[u32],
+) -> (
+    Simulation,
+    Address<Supervisor>,
+    EventSlot<BTreeSet<u32>>,
+    EventSlot<u32>,
+) {
+    let mut supervisor = Supervisor::default();
+    let supervisor_mbox = Mailbox::new();
+    let supervisor_addr = supervisor_mbox.address();
+
+    let collected = EventSlot::new();
+    let total = EventSlot::new();
+    supervisor.collected.connect_sink(&collected);
+    supervisor.total.connect_sink(&total);
+
+    let mut bench = SimInit::with_num_threads(num_threads);
+    for (i, &value) in values.iter().enumerate() {
+        let sensor_mbox = Mailbox::new();
+        supervisor.readings.connect(Sensor::read, &sensor_mbox);
+        bench = bench.add_model(Sensor { value }, sensor_mbox, format!("sensor_{i}"));
+    }
+
+    let simu = bench
+        .add_model(supervisor, supervisor_mbox, "supervisor")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    (simu, supervisor_addr, collected, total)
+}
+
+fn query_collect(num_threads: usize) {
+    let (mut simu, addr, mut collected, _) = supervisor_bench(num_threads, &[3, 1, 2, 1]);
+
+    // Replies can be collected into any container.
+    simu.process_event(Supervisor::collect, 10, &addr).unwrap();
+    assert_eq!(collected.next(), Some(BTreeSet::from([11, 12, 13])));
+}
+
+#[test]
+fn query_collect_st() {
+    query_collect(1);
+}
+
+#[test]
+fn query_collect_mt() {
+    query_collect(MT_NUM_THREADS);
+}
+
+fn query_fold(num_threads: usize) {
+    let (mut simu, addr, _, mut total) = supervisor_bench(num_threads, &[3, 1, 2, 1]);
+
+    simu.process_event(Supervisor::sum, 10, &addr).unwrap();
+    assert_eq!(total.next(), Some(47));
+}
+
+#[test]
+fn query_fold_st() {
+    query_fold(1);
+}
+
+#[test]
+fn query_fold_mt() {
+    query_fold(MT_NUM_THREADS);
+}
+
+fn query_fold_unconnected(num_threads: usize) {
+    let (mut simu, addr, mut collected, mut total) = supervisor_bench(num_threads, &[]);
+
+    // Without replier, the initial value is returned.
+    simu.process_event(Supervisor::sum, 10, &addr).unwrap();
+    assert_eq!(total.next(), Some(0));
+    simu.process_event(Supervisor::collect, 10, &addr).unwrap();
+    assert_eq!(collected.next(), Some(BTreeSet::new()));
+}
+
+#[test]
+fn query_fold_unconnected_st() {
+    query_fold_unconnected(1);
+}
+
+#[test]
+fn query_fold_unconnected_mt() {
+    query_fold_unconnected(MT_NUM_THREADS);
+}
+
+fn query_enumerated(num_threads: usize) {
+    let mut supervisor = Supervisor::default();
+    let supervisor_mbox = Mailbox::new();
+    let supervisor_addr = supervisor_mbox.address();
+
+    let mut enumerated = EventSlot::new();
+    supervisor.enumerated.connect_sink(&enumerated);
+
+    // The second sensor only replies to even offsets.
+    let sensor_mboxes: Vec<Mailbox<Sensor>> = (0..3).map(|_| Mailbox::new()).collect();
+    supervisor.readings.connect(Sensor::read, &sensor_mboxes[0]);
+    supervisor.readings.filter_map_connect(
+        |offset: &u32| (offset % 2 == 0).then_some(*offset),
+        |r: u32| r,
+        Sensor::read,
+        &sensor_mboxes[1],
+    );
+    supervisor.readings.connect(Sensor::read, &sensor_mboxes[2]);
+
+    let mut bench = SimInit::with_num_threads(num_threads);
+    for (i, sensor_mbox) in sensor_mboxes.into_iter().enumerate() {
+        let sensor = Sensor {
+            value: 100 * i as u32,
+        };
+        bench = bench.add_model(sensor, sensor_mbox, format!("sensor_{i}"));
+    }
+    let mut simu = bench
+        .add_model(supervisor, supervisor_mbox, "supervisor")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    // Replies are indexed by connection order.
+    simu.process_event(Supervisor::enumerate, 2, &supervisor_addr)
+        .unwrap();
+    assert_eq!(enumerated.next(), Some(vec![(0, 2), (1, 102), (2, 202)]));
+
+    // Filtered connections are accounted for in the indexing.
+    simu.process_event(Supervisor::enumerate, 1, &supervisor_addr)
+        .unwrap();
+    assert_eq!(enumerated.next(), Some(vec![(0, 1), (2, 201)]));
+
+    // Indices are not affected by non-enumerated queries.
+    simu.process_event(Supervisor::sum, 1, &supervisor_addr)
+        .unwrap();
+    simu.process_event(Supervisor::enumerate, 2, &supervisor_addr)
+        .unwrap();
+    assert_eq!(enumerated.next(), Some(vec![(0, 2), (1, 102), (2, 202)]));
+}
+
+#[test]
+fn query_enumerated_st() {
+    query_enumerated(1);
+}
+
+#[test]
+fn query_enumerated_mt() {
+    query_enumerated(MT_NUM_THREADS);
+}
@@ -0,0 +1,137 @@
+//! Removal of models from a started simulation.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel {
+    is_dropped: Arc<AtomicBool>,
+    terminated: Output<()>,
+}
+impl TestModel {
+    async fn input(&mut self) {}
+}
+impl Model for TestModel {
+    async fn terminate(&mut self, _: &mut Context<Self>) {
+        self.terminated.send(()).await;
+    }
+}
+impl Drop for TestModel {
+    fn drop(&mut self) {
+        self.is_dropped.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct SenderModel {
+    output: Output<()>,
+}
+impl SenderModel {
+    async fn trigger(&mut self) {
+        self.output.send(()).await;
+    }
+}
+impl Model for SenderModel {}
+
+fn model_removal(num_threads: usize) {
+    const MODEL_NAME: &str = "testmodel";
+
+    let is_dropped = Arc::new(AtomicBool::new(false));
+    let mut terminated = EventBuffer::new();
+    let mut model = TestModel {
+        is_dropped: is_dropped.clone(),
+        terminated: Output::default(),
+    };
+    model.terminated.connect_sink(&terminated);
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, MODEL_NAME)
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::input, (), &addr).unwrap();
+
+    // The model cannot be removed while its address is alive.
+    assert!(matches!(
+        simu.remove_model(MODEL_NAME),
+        Err(ExecutionError::ModelConnected(_))
+    ));
+    assert!(!is_dropped.load(Ordering::Relaxed));
+    assert_eq!(terminated.next(), None);
+
+    // The model is terminated before being dropped.
+    drop(addr);
+    simu.remove_model(MODEL_NAME).unwrap();
+    assert_eq!(terminated.next(), Some(()));
+    assert!(is_dropped.load(Ordering::Relaxed));
+
+    assert!(matches!(
+        simu.remove_model(MODEL_NAME),
+        Err(ExecutionError::ModelNotFound(_))
+    ));
+}
+
+fn model_removal_connected(num_threads: usize) {
+    const MODEL_NAME: &str = "testmodel";
+
+    let is_dropped = Arc::new(AtomicBool::new(false));
+    let model = TestModel {
+        is_dropped: is_dropped.clone(),
+        terminated: Output::default(),
+    };
+    let mbox = Mailbox::new();
+
+    let mut sender = SenderModel::default();
+    sender.output.connect(TestModel::input, &mbox);
+    let sender_mbox = Mailbox::new();
+    let sender_addr = sender_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, MODEL_NAME)
+        .add_model(sender, sender_mbox, "sender")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // A model connected to an output port cannot be removed.
+    assert!(matches!(
+        simu.remove_model(MODEL_NAME),
+        Err(ExecutionError::ModelConnected(_))
+    ));
+    assert!(!is_dropped.load(Ordering::Relaxed));
+
+    // The rejected removal leaves the simulation untouched.
+    simu.process_event(SenderModel::trigger, (), &sender_addr)
+        .unwrap();
+}
+
+#[test]
+fn model_removal_st() {
+    model_removal(1);
+}
+
+#[test]
+fn model_removal_mt() {
+    model_removal(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_removal_connected_st() {
+    model_removal_connected(1);
+}
+
+#[test]
+fn model_removal_connected_mt() {
+    model_removal_connected(MT_NUM_THREADS);
+}
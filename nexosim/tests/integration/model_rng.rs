@@ -0,0 +1,160 @@
+//! Seeded pseudo-random number generation within `Model` input methods.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::{Jitter, MonotonicTime};
+use rand::Rng;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u64>,
+}
+impl TestModel {
+    async fn draw(&mut self, _: (), cx: &mut Context<Self>) {
+        let value = cx.rng().gen();
+        self.output.send(value).await;
+    }
+}
+impl Model for TestModel {}
+
+/// Draws a random number from each of two models and returns the results.
+fn draw_pair(num_threads: usize, seed: u64) -> (u64, u64) {
+    let mut model1 = TestModel::default();
+    let mut model2 = TestModel::default();
+    let mbox1 = Mailbox::new();
+    let mbox2 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let addr2 = mbox2.address();
+
+    let mut output1 = EventBuffer::new();
+    let mut output2 = EventBuffer::new();
+    model1.output.connect_sink(&output1);
+    model2.output.connect_sink(&output2);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .set_seed(seed)
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::draw, (), addr2).unwrap();
+    simu.process_event(TestModel::draw, (), addr1).unwrap();
+
+    (output1.next().unwrap(), output2.next().unwrap())
+}
+
+fn model_rng_seeded(num_threads: usize) {
+    let (a1, a2) = draw_pair(num_threads, 42);
+    let (b1, b2) = draw_pair(num_threads, 42);
+    let (c1, c2) = draw_pair(num_threads, 43);
+
+    // Same seed, same sequences.
+    assert_eq!((a1, a2), (b1, b2));
+    // Each model has its own sequence.
+    assert_ne!(a1, a2);
+    // Different seed, different sequences.
+    assert_ne!((a1, a2), (c1, c2));
+    // The sequences do not depend on the number of threads.
+    assert_eq!((a1, a2), draw_pair(1, 42));
+}
+
+#[test]
+fn model_rng_seeded_st() {
+    model_rng_seeded(1);
+}
+
+#[test]
+fn model_rng_seeded_mt() {
+    model_rng_seeded(MT_NUM_THREADS);
+}
+
+const JITTER_BASE: Duration = Duration::from_millis(100);
+
+#[derive(Default)]
+struct JitteredModel {
+    output: Output<MonotonicTime>,
+}
+impl JitteredModel {
+    fn start(&mut self, jitter: Jitter, cx: &mut Context<Self>) {
+        cx.schedule_event_jittered(JITTER_BASE, jitter, Self::tick, jitter)
+            .unwrap();
+    }
+    async fn tick(&mut self, jitter: Jitter, cx: &mut Context<Self>) {
+        self.output.send(cx.time()).await;
+        cx.schedule_event_jittered(JITTER_BASE, jitter, Self::tick, jitter)
+            .unwrap();
+    }
+}
+impl Model for JitteredModel {}
+
+/// Returns the time of the first jittered events of a model.
+fn jittered_ticks(num_threads: usize, seed: u64, jitter: Jitter) -> Vec<MonotonicTime> {
+    let mut model = JitteredModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .set_seed(seed)
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(JitteredModel::start, jitter, addr)
+        .unwrap();
+    for _ in 0..20 {
+        simu.step().unwrap();
+    }
+
+    output.collect()
+}
+
+fn schedule_event_jittered(num_threads: usize) {
+    let uniform = Jitter::Uniform(Duration::from_millis(10));
+    let normal = Jitter::Normal(Duration::from_millis(10));
+
+    let ticks = jittered_ticks(num_threads, 42, uniform);
+    assert_eq!(ticks.len(), 20);
+
+    // The delays are jittered within bounds.
+    let mut last_tick = MonotonicTime::EPOCH;
+    let mut delays = Vec::new();
+    for tick in &ticks {
+        let delay = tick.duration_since(last_tick);
+        assert!(delay >= Duration::from_millis(90) && delay <= Duration::from_millis(110));
+        delays.push(delay);
+        last_tick = *tick;
+    }
+    assert!(delays.iter().any(|delay| *delay != delays[0]));
+
+    // Same seed, same jitter.
+    assert_eq!(ticks, jittered_ticks(num_threads, 42, uniform));
+    assert_eq!(
+        jittered_ticks(num_threads, 42, normal),
+        jittered_ticks(1, 42, normal)
+    );
+    // Different seed, different jitter.
+    assert_ne!(ticks, jittered_ticks(num_threads, 43, uniform));
+}
+
+#[test]
+fn schedule_event_jittered_st() {
+    schedule_event_jittered(1);
+}
+
+#[test]
+fn schedule_event_jittered_mt() {
+    schedule_event_jittered(MT_NUM_THREADS);
+}
@@ -243,6 +243,69 @@ fn model_cancel_periodic_event(num_threads: usize) {
     assert!(output.next().is_none());
 }
 
+fn model_schedule_conditional_periodic_event(num_threads: usize) {
+    #[derive(Default)]
+    struct TestModel {
+        output: Output<()>,
+        is_enabled: bool,
+    }
+    impl TestModel {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            self.is_enabled = true;
+            cx.schedule_conditional_periodic_event(
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+                |model: &Self| model.is_enabled,
+                Self::action,
+                (),
+            )
+            .unwrap();
+        }
+        fn set_enabled(&mut self, is_enabled: bool) {
+            self.is_enabled = is_enabled;
+        }
+        async fn action(&mut self) {
+            self.output.send(()).await;
+        }
+    }
+    impl Model for TestModel {}
+
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::trigger, (), &addr).unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert!(output.next().is_some());
+
+    // The guard no longer holds: the event should be skipped.
+    simu.process_event(TestModel::set_enabled, false, &addr)
+        .unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(5));
+    assert!(output.next().is_none());
+
+    // The guard holds again: the event should be delivered.
+    simu.process_event(TestModel::set_enabled, true, &addr)
+        .unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(8));
+    assert!(output.next().is_some());
+    assert!(output.next().is_none());
+}
+
 #[test]
 fn model_schedule_event_st() {
     model_schedule_event(1);
@@ -292,3 +355,295 @@ fn model_cancel_periodic_event_st() {
 fn model_cancel_periodic_event_mt() {
     model_cancel_periodic_event(MT_NUM_THREADS);
 }
+
+#[test]
+fn model_schedule_conditional_periodic_event_st() {
+    model_schedule_conditional_periodic_event(1);
+}
+
+#[test]
+fn model_schedule_conditional_periodic_event_mt() {
+    model_schedule_conditional_periodic_event(MT_NUM_THREADS);
+}
+
+fn model_schedule_after_last(num_threads: usize) {
+    #[derive(Default)]
+    struct TestModel {
+        output: Output<usize>,
+    }
+    impl TestModel {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            cx.schedule_event(Duration::from_secs(2), Self::action, 1)
+                .unwrap();
+            cx.schedule_after_last(Duration::from_secs(1), Self::action, 2)
+                .unwrap();
+            cx.schedule_after_last(Duration::from_secs(1), Self::action, 3)
+                .unwrap();
+        }
+        fn trigger_once(&mut self, _: (), cx: &mut Context<Self>) {
+            cx.schedule_after_last(Duration::from_secs(1), Self::action, 4)
+                .unwrap();
+        }
+        fn trigger_out_of_order(&mut self, _: (), cx: &mut Context<Self>) {
+            cx.schedule_event(Duration::from_secs(3), Self::action, 5)
+                .unwrap();
+            cx.schedule_event(Duration::from_secs(1), Self::action, 6)
+                .unwrap();
+            cx.schedule_after_last(Duration::from_secs(1), Self::action, 7)
+                .unwrap();
+        }
+        async fn action(&mut self, value: usize) {
+            self.output.send(value).await;
+        }
+    }
+    impl Model for TestModel {}
+
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::trigger, (), &addr).unwrap();
+    for (value, secs) in [(1, 2), (2, 3), (3, 4)] {
+        simu.step().unwrap();
+        assert_eq!(simu.time(), t0 + Duration::from_secs(secs));
+        assert_eq!(output.next(), Some(value));
+    }
+
+    // The last deadline has elapsed, so the delay is counted from now.
+    simu.step_until(Duration::from_secs(6)).unwrap();
+    simu.process_event(TestModel::trigger_once, (), &addr)
+        .unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(11));
+    assert_eq!(output.next(), Some(4));
+
+    // The delay is counted from the latest deadline rather than from the
+    // deadline of the last scheduled event.
+    simu.process_event(TestModel::trigger_out_of_order, (), &addr)
+        .unwrap();
+    for (value, secs) in [(6, 12), (5, 14), (7, 15)] {
+        simu.step().unwrap();
+        assert_eq!(simu.time(), t0 + Duration::from_secs(secs));
+        assert_eq!(output.next(), Some(value));
+    }
+}
+
+#[test]
+fn model_schedule_after_last_st() {
+    model_schedule_after_last(1);
+}
+
+#[test]
+fn model_schedule_after_last_mt() {
+    model_schedule_after_last(MT_NUM_THREADS);
+}
+
+fn model_cancel_periodic_event_with_callback(num_threads: usize) {
+    #[derive(Default)]
+    struct TestModel {
+        output: Output<&'static str>,
+        key: Option<ActionKey>,
+    }
+    impl TestModel {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            self.key = cx
+                .schedule_keyed_periodic_event_with_cancel(
+                    Duration::from_secs(2),
+                    Duration::from_secs(3),
+                    Self::action,
+                    (),
+                    Self::stopped,
+                )
+                .ok();
+        }
+        fn stop(&mut self) {
+            self.key.take().unwrap().cancel();
+        }
+        async fn action(&mut self) {
+            self.output.send("tick").await;
+        }
+        async fn stopped(&mut self) {
+            self.output.send("stopped").await;
+        }
+    }
+    impl Model for TestModel {}
+
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::trigger, (), &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some("tick"));
+
+    // The cancellation event replaces the next periodic event.
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(TestModel::stop, (), &addr).unwrap();
+    assert!(output.next().is_none());
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(5));
+    assert_eq!(output.next(), Some("stopped"));
+
+    simu.step_until(Duration::from_secs(10)).unwrap();
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn model_cancel_periodic_event_with_callback_st() {
+    model_cancel_periodic_event_with_callback(1);
+}
+
+#[test]
+fn model_cancel_periodic_event_with_callback_mt() {
+    model_cancel_periodic_event_with_callback(MT_NUM_THREADS);
+}
+
+fn model_schedule_event_coalesced(num_threads: usize) {
+    #[derive(Default)]
+    struct TestModel {
+        output: Output<u32>,
+    }
+    impl TestModel {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            // Only the first of two identical events is scheduled.
+            for _ in 0..2 {
+                cx.schedule_event_coalesced(Duration::from_secs(2), 1, Self::action, 1)
+                    .unwrap();
+            }
+            // Events with a different key are not coalesced.
+            cx.schedule_event_coalesced(Duration::from_secs(2), 2, Self::action, 2)
+                .unwrap();
+            // Events with a different deadline are not coalesced.
+            cx.schedule_event_coalesced(Duration::from_secs(3), 1, Self::action, 3)
+                .unwrap();
+        }
+        async fn action(&mut self, value: u32) {
+            self.output.send(value).await;
+        }
+    }
+    impl Model for TestModel {}
+
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::trigger, (), &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.next(), Some(3));
+    assert_eq!(output.next(), None);
+
+    // Events are no longer coalesced once processed.
+    simu.step_until(Duration::from_secs(1)).unwrap();
+    simu.process_event(TestModel::trigger, (), &addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(6));
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn model_schedule_event_coalesced_st() {
+    model_schedule_event_coalesced(1);
+}
+
+#[test]
+fn model_schedule_event_coalesced_mt() {
+    model_schedule_event_coalesced(MT_NUM_THREADS);
+}
+
+fn model_schedule_deterministic_ordering(num_threads: usize) {
+    struct TestModel {
+        id: usize,
+        output: Output<usize>,
+    }
+    impl TestModel {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            cx.schedule_event(Duration::from_secs(1), Self::action, ())
+                .unwrap();
+        }
+        async fn action(&mut self) {
+            self.output.send(self.id).await;
+        }
+    }
+    impl Model for TestModel {}
+
+    const MODEL_COUNT: usize = 8;
+
+    for _ in 0..10 {
+        let output = EventBuffer::new();
+        let mut sim_init = SimInit::with_num_threads(num_threads).deterministic_ordering();
+        let mut addresses = Vec::new();
+
+        // Create the mailboxes in reverse order to make sure that the ordering
+        // does not depend on the mailboxes.
+        let mut mailboxes: Vec<_> = (0..MODEL_COUNT).map(|_| Mailbox::new()).collect();
+        mailboxes.reverse();
+        for (id, mbox) in mailboxes.into_iter().enumerate() {
+            let mut model = TestModel {
+                id,
+                output: Output::default(),
+            };
+            model.output.connect_sink(&output);
+            addresses.push(mbox.address());
+            sim_init = sim_init.add_model(model, mbox, id.to_string());
+        }
+
+        let t0 = MonotonicTime::EPOCH;
+        let mut simu = sim_init.init(t0).unwrap().0;
+
+        // Schedule the actions in reverse order of addition.
+        for addr in addresses.iter().rev() {
+            simu.process_event(TestModel::trigger, (), addr).unwrap();
+        }
+        simu.step().unwrap();
+
+        assert_eq!(
+            output.collect::<Vec<_>>(),
+            (0..MODEL_COUNT).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn model_schedule_deterministic_ordering_st() {
+    model_schedule_deterministic_ordering(1);
+}
+
+#[test]
+fn model_schedule_deterministic_ordering_mt() {
+    model_schedule_deterministic_ordering(MT_NUM_THREADS);
+}
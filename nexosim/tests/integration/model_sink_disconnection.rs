@@ -0,0 +1,73 @@
+//! Disconnection of event sinks from output ports.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct PassThroughModel {
+    output: Output<usize>,
+}
+impl PassThroughModel {
+    async fn input(&mut self, value: usize) {
+        self.output.send(value).await;
+    }
+}
+impl Model for PassThroughModel {}
+
+fn sink_disconnection(num_threads: usize) {
+    let mut model = PassThroughModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut permanent_sink = EventBuffer::new();
+    let mut transient_sink = EventBuffer::new();
+    model.output.connect_sink(&permanent_sink);
+    let line_id = model.output.connect_sink(&transient_sink);
+
+    // Keep a clone of the port to manage connections once the model has been
+    // moved into the simulation.
+    let mut output = model.output.clone();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, _scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    simu.process_event(PassThroughModel::input, 1, &addr)
+        .unwrap();
+    assert_eq!(permanent_sink.next(), Some(1));
+    assert_eq!(transient_sink.next(), Some(1));
+
+    // The disconnection made on the clone applies to the model's port.
+    output.disconnect_sink(line_id).unwrap();
+    simu.process_event(PassThroughModel::input, 2, &addr)
+        .unwrap();
+    assert_eq!(permanent_sink.next(), Some(2));
+    assert!(transient_sink.next().is_none());
+
+    // A line cannot be disconnected twice.
+    assert!(output.disconnect_sink(line_id).is_err());
+
+    // The sink can be reconnected with a new line identifier.
+    let new_line_id = output.connect_sink(&transient_sink);
+    assert_ne!(new_line_id, line_id);
+    simu.process_event(PassThroughModel::input, 3, &addr)
+        .unwrap();
+    assert_eq!(permanent_sink.next(), Some(3));
+    assert_eq!(transient_sink.next(), Some(3));
+}
+
+#[test]
+fn sink_disconnection_st() {
+    sink_disconnection(1);
+}
+
+#[test]
+fn sink_disconnection_mt() {
+    sink_disconnection(MT_NUM_THREADS);
+}
@@ -0,0 +1,134 @@
+//! Introspection of the connections between models.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Emitter {
+    output: Output<usize>,
+}
+impl Emitter {
+    async fn emit(&mut self, value: usize) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Emitter {}
+
+#[derive(Default)]
+struct Doubler {
+    output: Output<u64>,
+}
+impl Doubler {
+    async fn input(&mut self, value: usize) {
+        self.output.send(2 * value as u64).await;
+    }
+    async fn unused_input(&mut self, _: usize) {}
+}
+impl Model for Doubler {}
+
+fn model_topology(num_threads: usize) {
+    let mut emitter = Emitter::default();
+    let mut doubler = Doubler::default();
+    let emitter_mbox = Mailbox::new();
+    let doubler_mbox = Mailbox::new();
+    let emitter_addr = emitter_mbox.address();
+
+    emitter.output.set_label("emitter.output");
+    emitter.output.connect(Doubler::input, &doubler_mbox);
+    let output = EventBuffer::new();
+    doubler.output.connect_sink(&output);
+
+    // This connection is never used but should be reported nonetheless. The
+    // label applies to connections made before it is set.
+    let mut idle_emitter = Emitter::default();
+    idle_emitter
+        .output
+        .connect(Doubler::unused_input, &doubler_mbox);
+    idle_emitter.output.set_label("idle_emitter.output");
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, emitter_mbox, "emitter")
+        .add_model(doubler, doubler_mbox, "doubler")
+        .add_model(idle_emitter, Mailbox::new(), "idle_emitter")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Connections are reported as soon as the bench is assembled.
+    let topology = simu.topology();
+    let connections = topology.connections();
+    assert_eq!(connections.len(), 2);
+    assert_eq!(connections[0].source_port, "emitter.output");
+    assert_eq!(connections[0].target, "doubler");
+    assert!(connections[0].port.ends_with("Doubler::input"));
+    assert_eq!(connections[0].event_type, "usize");
+    assert_eq!(connections[1].source_port, "idle_emitter.output");
+    assert_eq!(connections[1].target, "doubler");
+    assert!(connections[1].port.ends_with("Doubler::unused_input"));
+
+    assert_eq!(
+        simu.to_dot(),
+        "digraph simulation {\n    \"emitter\";\n    \"doubler\";\n    \"idle_emitter\";\n    \"emitter.output\" [shape=plaintext];\n    \"idle_emitter.output\" [shape=plaintext];\n    \"emitter.output\" -> \"doubler\" [label=\"usize\"];\n    \"idle_emitter.output\" -> \"doubler\" [label=\"usize\"];\n}\n"
+    );
+
+    // Using a connection does not change the topology.
+    simu.process_event(Emitter::emit, 3, &emitter_addr).unwrap();
+    simu.process_event(Emitter::emit, 4, &emitter_addr).unwrap();
+    assert_eq!(output.collect::<Vec<_>>(), vec![6, 8]);
+    assert_eq!(simu.topology(), topology);
+
+    // The connections of a port are no longer reported once the model owning
+    // it is removed.
+    simu.remove_model("idle_emitter").unwrap();
+    let connections = simu.topology().connections().to_vec();
+    assert_eq!(connections.len(), 1);
+    assert_eq!(connections[0].source_port, "emitter.output");
+}
+
+fn model_topology_default_label(num_threads: usize) {
+    let mut emitter = Emitter::default();
+    let doubler_mbox = Mailbox::new();
+    emitter.output.connect(Doubler::input, &doubler_mbox);
+
+    let t0 = MonotonicTime::EPOCH;
+    let simu = SimInit::with_num_threads(num_threads)
+        .add_model(emitter, Mailbox::new(), "emitter")
+        .add_model(Doubler::default(), doubler_mbox, "doubler")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Unlabeled ports are identified by their type.
+    let topology = simu.topology();
+    let connections = topology.connections();
+    assert_eq!(connections.len(), 1);
+    assert_eq!(
+        connections[0].source_port,
+        "nexosim::ports::output::Output<usize>"
+    );
+}
+
+#[test]
+fn model_topology_st() {
+    model_topology(1);
+}
+
+#[test]
+fn model_topology_mt() {
+    model_topology(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_topology_default_label_st() {
+    model_topology_default_label(1);
+}
+
+#[test]
+fn model_topology_default_label_mt() {
+    model_topology_default_label(MT_NUM_THREADS);
+}
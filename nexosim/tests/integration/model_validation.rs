@@ -0,0 +1,175 @@
+//! Validation of model prototypes.
+
+use nexosim::model::{BuildContext, Model, ProtoModel};
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit, ValidationInfo};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct Counter {
+    count: u32,
+}
+impl Counter {
+    async fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+impl Model for Counter {}
+
+struct ProtoCounter {
+    initial_count: u32,
+    max_count: u32,
+}
+impl ProtoModel for ProtoCounter {
+    type Model = Counter;
+
+    fn validate(&self, cx: &BuildContext<Self>) -> Result<(), String> {
+        if self.initial_count > self.max_count {
+            return Err(format!(
+                "the initial count of '{}' exceeds its maximum count",
+                cx.name()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn build(self, _: &mut BuildContext<Self>) -> Counter {
+        Counter {
+            count: self.initial_count,
+        }
+    }
+}
+
+struct ProtoParent {
+    child: ProtoCounter,
+}
+impl ProtoModel for ProtoParent {
+    type Model = Counter;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> Counter {
+        cx.add_submodel(self.child, Mailbox::new(), "child");
+
+        Counter { count: 0 }
+    }
+}
+
+fn model_validation(num_threads: usize) {
+    let valid = ProtoCounter {
+        initial_count: 1,
+        max_count: 2,
+    };
+    let invalid = ProtoCounter {
+        initial_count: 3,
+        max_count: 2,
+    };
+    let parent = ProtoParent {
+        child: ProtoCounter {
+            initial_count: 5,
+            max_count: 0,
+        },
+    };
+
+    let t0 = MonotonicTime::EPOCH;
+    let result = SimInit::with_num_threads(num_threads)
+        .add_model(valid, Mailbox::new(), "valid")
+        .add_model(invalid, Mailbox::new(), "invalid")
+        .add_model(parent, Mailbox::new(), "parent")
+        .init(t0);
+
+    match result {
+        Err(ExecutionError::ValidationFailure(failures)) => assert_eq!(
+            failures,
+            vec![
+                ValidationInfo {
+                    model: "invalid".into(),
+                    message: "the initial count of 'invalid' exceeds its maximum count".into(),
+                },
+                ValidationInfo {
+                    model: "parent.child".into(),
+                    message: "the initial count of 'parent.child' exceeds its maximum count".into(),
+                },
+            ]
+        ),
+        _ => panic!("validation failure expected"),
+    }
+}
+
+fn model_validation_success(num_threads: usize) {
+    let counter = ProtoCounter {
+        initial_count: 1,
+        max_count: 2,
+    };
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(counter, mbox, "counter")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Counter::increment, (), &addr).unwrap();
+}
+
+fn model_validation_runtime(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads).init(t0).unwrap().0;
+
+    // The failure of a submodel is reported and the model is not added.
+    let parent = ProtoParent {
+        child: ProtoCounter {
+            initial_count: 5,
+            max_count: 0,
+        },
+    };
+    match simu.add_model(parent, Mailbox::new(), "parent") {
+        Err(ExecutionError::ValidationFailure(failures)) => assert_eq!(
+            failures,
+            vec![ValidationInfo {
+                model: "parent.child".into(),
+                message: "the initial count of 'parent.child' exceeds its maximum count".into(),
+            }]
+        ),
+        _ => panic!("validation failure expected"),
+    }
+
+    // The error is not fatal.
+    let counter = ProtoCounter {
+        initial_count: 1,
+        max_count: 2,
+    };
+    let addr = simu.add_model(counter, Mailbox::new(), "counter").unwrap();
+    simu.process_event(Counter::increment, (), &addr).unwrap();
+}
+
+#[test]
+fn model_validation_st() {
+    model_validation(1);
+}
+
+#[test]
+fn model_validation_mt() {
+    model_validation(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_validation_success_st() {
+    model_validation_success(1);
+}
+
+#[test]
+fn model_validation_success_mt() {
+    model_validation_success(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_validation_runtime_st() {
+    model_validation_runtime(1);
+}
+
+#[test]
+fn model_validation_runtime_mt() {
+    model_validation_runtime(MT_NUM_THREADS);
+}
@@ -0,0 +1,92 @@
+//! Recording of the causal relations between processed events.
+
+use nexosim::dev_hooks::CausalTracer;
+use nexosim::model::Model;
+use nexosim::ports::Output;
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value + 1).await;
+    }
+}
+impl Model for TestModel {}
+
+fn causal_trace(num_threads: usize) {
+    let tracer = CausalTracer::new();
+
+    let mut source = TestModel::default();
+    let mut relay = TestModel::default();
+    let branch = TestModel::default();
+    let leaf = TestModel::default();
+    let source_mbox = tracer.tap(Mailbox::new(), "source");
+    let relay_mbox = Mailbox::new(); // not tapped
+    let branch_mbox = tracer.tap(Mailbox::new(), "branch");
+    let leaf_mbox = tracer.tap(Mailbox::new(), "leaf");
+    let source_addr = source_mbox.address();
+
+    // Make a tree: source -> {branch, relay -> leaf}.
+    source.output.connect(TestModel::input, &branch_mbox);
+    source.output.connect(TestModel::input, &relay_mbox);
+    relay.output.connect(TestModel::input, &leaf_mbox);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(source, source_mbox, "source")
+        .add_model(relay, relay_mbox, "relay")
+        .add_model(branch, branch_mbox, "branch")
+        .add_model(leaf, leaf_mbox, "leaf")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::input, 0, &source_addr)
+        .unwrap();
+    simu.process_event(TestModel::input, 0, &source_addr)
+        .unwrap();
+
+    let trace = tracer.trace();
+    assert_eq!(trace.deliveries().len(), 6);
+
+    let roots: Vec<_> = trace
+        .deliveries()
+        .iter()
+        .filter(|delivery| delivery.label == "source")
+        .collect();
+    assert_eq!(roots.len(), 2);
+
+    for root in roots {
+        // Events injected from the simulation start new causal chains.
+        assert_eq!(root.parent, None);
+
+        // Causality is propagated through the untapped relay model.
+        let mut children: Vec<_> = trace
+            .children(root.id)
+            .map(|delivery| delivery.label.as_str())
+            .collect();
+        children.sort();
+        assert_eq!(children, ["branch", "leaf"]);
+        assert_eq!(trace.descendants(root.id).len(), 2);
+
+        for child in trace.children(root.id) {
+            assert_eq!(trace.root(child.id), Some(root));
+        }
+    }
+}
+
+#[test]
+fn causal_trace_st() {
+    causal_trace(1);
+}
+
+#[test]
+fn causal_trace_mt() {
+    causal_trace(MT_NUM_THREADS);
+}
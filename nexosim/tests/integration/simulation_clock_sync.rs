@@ -1,11 +1,12 @@
-//! Loss of synchronization during simulation step execution.
+//! Clock synchronization and loss of synchronization during simulation step
+//! execution.
 
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use nexosim::model::Model;
-use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
-use nexosim::time::{AutoSystemClock, MonotonicTime};
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit, StepOutcome};
+use nexosim::time::{AutoSystemClock, MonotonicTime, SystemClock};
 
 const MT_NUM_THREADS: usize = 4;
 
@@ -115,3 +116,259 @@ fn clock_sync_with_tolerance_st() {
 fn clock_sync_with_tolerance_mt() {
     clock_sync_with_tolerance(MT_NUM_THREADS);
 }
+
+fn try_step(num_threads: usize) {
+    const TICK_MS: u64 = 200;
+
+    let model = TestModel::default();
+    let clock = AutoSystemClock::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .set_clock(clock)
+        .init(t0)
+        .unwrap();
+
+    let tick = Duration::from_millis(TICK_MS);
+    scheduler
+        .schedule_event(tick, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+
+    // The wall clock has not reached the event yet.
+    assert!(matches!(simu.try_step(), Err(ExecutionError::WouldBlock)));
+    assert_eq!(simu.time(), t0);
+
+    thread::sleep(tick + Duration::from_millis(50));
+
+    let report = simu.try_step().unwrap();
+    assert_eq!(report.time, t0 + tick);
+    assert_eq!(report.event_count, 1);
+    assert_eq!(simu.time(), t0 + tick);
+}
+
+#[test]
+fn try_step_st() {
+    try_step(1);
+}
+
+#[test]
+fn try_step_mt() {
+    try_step(MT_NUM_THREADS);
+}
+
+fn step_unbounded_until(num_threads: usize) {
+    const TICK_MS: u64 = 100;
+
+    let model = TestModel::default();
+    let clock = AutoSystemClock::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .set_clock(clock)
+        .init(t0)
+        .unwrap();
+
+    let tick = Duration::from_millis(TICK_MS);
+    scheduler
+        .schedule_periodic_event(tick, tick, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+
+    // The periodic event keeps the simulation alive until the deadline.
+    let start = Instant::now();
+    let outcome = simu
+        .step_unbounded_until(start + 5 * tick + tick / 2)
+        .unwrap();
+    assert_eq!(outcome, StepOutcome::DeadlineReached);
+    assert_eq!(simu.time(), t0 + 5 * tick);
+    assert!(start.elapsed() < 6 * tick);
+}
+
+fn step_unbounded_until_idle(num_threads: usize) {
+    const TICK_MS: u64 = 100;
+
+    let model = TestModel::default();
+    let clock = AutoSystemClock::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .set_clock(clock)
+        .init(t0)
+        .unwrap();
+
+    let tick = Duration::from_millis(TICK_MS);
+    scheduler
+        .schedule_event(tick, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+
+    let outcome = simu
+        .step_unbounded_until(Instant::now() + 10 * tick)
+        .unwrap();
+    assert_eq!(outcome, StepOutcome::Idle);
+    assert_eq!(simu.time(), t0 + tick);
+}
+
+#[test]
+fn step_unbounded_until_st() {
+    step_unbounded_until(1);
+}
+
+#[test]
+fn step_unbounded_until_mt() {
+    step_unbounded_until(MT_NUM_THREADS);
+}
+
+#[test]
+fn step_unbounded_until_idle_st() {
+    step_unbounded_until_idle(1);
+}
+
+#[test]
+fn step_unbounded_until_idle_mt() {
+    step_unbounded_until_idle(MT_NUM_THREADS);
+}
+
+fn clock_switch(num_threads: usize) {
+    const WARM_UP_S: u64 = 3600;
+    const TICK_MS: u64 = 100;
+
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .init(t0)
+        .unwrap();
+
+    // The warm-up runs as fast as possible.
+    let warm_up = Duration::from_secs(WARM_UP_S);
+    let start = Instant::now();
+    simu.step_until(warm_up).unwrap();
+    assert!(start.elapsed() < warm_up);
+    assert!(scheduler
+        .schedule_at_wall_clock(Instant::now(), TestModel::block_for, Duration::ZERO, &addr)
+        .is_err());
+
+    // The real-time clock takes over without time discontinuity.
+    simu.set_clock(AutoSystemClock::new()).unwrap();
+    let tick = Duration::from_millis(TICK_MS);
+    scheduler
+        .schedule_event(tick, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+    let start = Instant::now();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + warm_up + tick);
+    assert!(start.elapsed() >= tick);
+    assert!(start.elapsed() < 5 * tick);
+
+    // Schedulers obtained before the switch use the new clock reference.
+    assert!(scheduler
+        .schedule_at_wall_clock(
+            Instant::now() + tick,
+            TestModel::block_for,
+            Duration::ZERO,
+            &addr
+        )
+        .is_ok());
+}
+
+#[test]
+fn clock_switch_st() {
+    clock_switch(1);
+}
+
+#[test]
+fn clock_switch_mt() {
+    clock_switch(MT_NUM_THREADS);
+}
+
+fn clock_switch_ahead(num_threads: usize) {
+    const WARM_UP_S: u64 = 3600;
+    const TICK_MS: u64 = 100;
+
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .init(t0)
+        .unwrap();
+
+    let warm_up = Duration::from_secs(WARM_UP_S);
+    simu.step_until(warm_up).unwrap();
+
+    // A clock referenced to the start of the simulation would only reach the
+    // current simulation time an hour from now.
+    let res = simu.set_clock(SystemClock::from_instant(t0, Instant::now()));
+    if let Err(ExecutionError::ClockAhead(lead)) = res {
+        assert!(lead > warm_up - Duration::from_secs(1));
+        assert!(lead <= warm_up);
+    } else {
+        panic!("set_clock did not return an `ExecutionError::ClockAhead` error");
+    }
+
+    // The previous clock remains in use.
+    let tick = Duration::from_millis(TICK_MS);
+    scheduler
+        .schedule_event(tick, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+    let start = Instant::now();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + warm_up + tick);
+    assert!(start.elapsed() < tick);
+
+    // A clock consistent with the simulation time is accepted.
+    simu.set_clock(SystemClock::from_instant(simu.time(), Instant::now()))
+        .unwrap();
+}
+
+#[test]
+fn clock_switch_ahead_st() {
+    clock_switch_ahead(1);
+}
+
+#[test]
+fn clock_switch_ahead_mt() {
+    clock_switch_ahead(MT_NUM_THREADS);
+}
+
+fn init_now(num_threads: usize) {
+    const LEAP_SECS: i64 = 37;
+
+    let unix_time = |t: MonotonicTime| {
+        UNIX_EPOCH + Duration::new((t.as_secs() - LEAP_SECS) as u64, t.subsec_nanos())
+    };
+
+    let before = SystemTime::now();
+    let simu = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), Mailbox::new(), "test")
+        .init_now(LEAP_SECS)
+        .unwrap()
+        .0;
+    let after = SystemTime::now();
+
+    let t0 = unix_time(simu.time());
+    assert!(before <= t0 && t0 <= after);
+}
+
+#[test]
+fn init_now_st() {
+    init_now(1);
+}
+
+#[test]
+fn init_now_mt() {
+    init_now(MT_NUM_THREADS);
+}
@@ -212,6 +212,65 @@ fn deadlock_on_multiple_query_loopback(num_threads: usize) {
     }
 }
 
+/// Generates a deadlock involving a model blocked on a saturated mailbox while
+/// its own mailbox is empty.
+fn deadlock_on_blocked_sender(num_threads: usize) {
+    const MODEL0_NAME: &str = "testmodel0";
+    const MODEL1_NAME: &str = "testmodel1";
+    const MODEL2_NAME: &str = "testmodel2";
+
+    let mut model0 = TestModel::default();
+    let mut model1 = TestModel::default();
+    let mut model2 = TestModel::default();
+    let mbox0 = Mailbox::new();
+    let mbox1 = Mailbox::with_capacity(1);
+    let mbox2 = Mailbox::new();
+    let addr0 = mbox0.address();
+    let addr1 = mbox1.address();
+    let addr2 = mbox2.address();
+
+    // Model 0 sends 3 events to model 1, which can only store 1 event.
+    for _ in 0..3 {
+        model0
+            .output
+            .connect(TestModel::activate_requestor, addr1.clone());
+    }
+
+    // Model 1 sends a query to model 2, which queries model 1 in return.
+    model1
+        .requestor
+        .connect(TestModel::activate_requestor, addr2);
+    model2
+        .requestor
+        .connect(TestModel::activate_requestor, addr1);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model0, mbox0, MODEL0_NAME)
+        .add_model(model1, mbox1, MODEL1_NAME)
+        .add_model(model2, mbox2, MODEL2_NAME)
+        .init(t0)
+        .unwrap()
+        .0;
+
+    match simu.process_event(TestModel::activate_output, (), addr0) {
+        Err(ExecutionError::Deadlock(deadlock_info)) => {
+            // We expect the mailbox of model 1 to be full.
+            assert!(deadlock_info.contains(&DeadlockInfo {
+                model: MODEL1_NAME.into(),
+                mailbox_size: 1,
+            }));
+            // We expect model 0 to be reported even though its mailbox is
+            // empty.
+            assert!(deadlock_info.contains(&DeadlockInfo {
+                model: MODEL0_NAME.into(),
+                mailbox_size: 0,
+            }));
+        }
+        _ => panic!("deadlock not detected"),
+    }
+}
+
 #[test]
 fn deadlock_on_mailbox_overflow_st() {
     deadlock_on_mailbox_overflow(1);
@@ -251,3 +310,13 @@ fn deadlock_on_multiple_query_loopback_st() {
 fn deadlock_on_multiple_query_loopback_mt() {
     deadlock_on_multiple_query_loopback(MT_NUM_THREADS);
 }
+
+#[test]
+fn deadlock_on_blocked_sender_st() {
+    deadlock_on_blocked_sender(1);
+}
+
+#[test]
+fn deadlock_on_blocked_sender_mt() {
+    deadlock_on_blocked_sender(MT_NUM_THREADS);
+}
@@ -0,0 +1,68 @@
+//! Recording of the order in which events are processed.
+
+use nexosim::dev_hooks::OrderRecorder;
+use nexosim::model::Model;
+use nexosim::ports::Output;
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value + 1).await;
+    }
+}
+impl Model for TestModel {}
+
+fn dispatch_order(num_threads: usize) {
+    let recorder = OrderRecorder::new();
+
+    let mut model1 = TestModel::default();
+    let mut model2 = TestModel::default();
+    let model3 = TestModel::default();
+    let mbox1 = recorder.tap(Mailbox::new(), "model1");
+    let mbox2 = recorder.tap(Mailbox::new(), "model2");
+    let mbox3 = recorder.tap(Mailbox::new(), "model3");
+    let addr1 = mbox1.address();
+
+    // Make a chain: model1 -> model2 -> model3.
+    model1.output.connect(TestModel::input, &mbox2);
+    model2.output.connect(TestModel::input, &mbox3);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .add_model(model3, mbox3, "model3")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::input, 0, &addr1).unwrap();
+    simu.process_event(TestModel::input, 0, &addr1).unwrap();
+
+    let log = recorder.log();
+    assert_eq!(log.dispatches().len(), 6);
+    log.assert_before("model1", "model2");
+    log.assert_before("model2", "model3");
+    log.assert_before(("model3", 0), ("model1", 1));
+    log.assert_after(("model3", 1), ("model2", 1));
+    assert!(!log.is_before("model3", "model1"));
+    assert!(!log.is_before("model1", ("model3", 2)));
+    assert_eq!(log.dispatches()[0].event_type, "u32");
+}
+
+#[test]
+fn dispatch_order_st() {
+    dispatch_order(1);
+}
+
+#[test]
+fn dispatch_order_mt() {
+    dispatch_order(MT_NUM_THREADS);
+}
@@ -1,7 +1,7 @@
 //! Model panic reporting.
 
 use nexosim::model::Model;
-use nexosim::ports::Output;
+use nexosim::ports::{EventSlot, Output, Requestor};
 use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
 use nexosim::time::MonotonicTime;
 
@@ -71,3 +71,84 @@ fn model_panic_st() {
 fn model_panic_mt() {
     model_panic(MT_NUM_THREADS);
 }
+
+#[derive(Default)]
+struct Divider;
+impl Divider {
+    async fn divide(&mut self, divisor: u32) -> u32 {
+        if divisor == 0 {
+            panic!("division by zero");
+        }
+        100 / divisor
+    }
+}
+impl Model for Divider {}
+
+#[derive(Default)]
+struct Client {
+    requestor: Requestor<u32, u32>,
+    output: Output<u32>,
+}
+impl Client {
+    async fn ask(&mut self, divisor: u32) {
+        let quotient = self.requestor.send(divisor).await.next().unwrap();
+        self.output.send(quotient).await;
+    }
+}
+impl Model for Client {}
+
+/// Catch a panic in a replier and resume the simulation.
+fn model_panic_caught(num_threads: usize) {
+    let mut client = Client::default();
+    let client_mbox = Mailbox::new();
+    let divider_mbox = Mailbox::new();
+    let client_addr = client_mbox.address();
+    let divider_addr = divider_mbox.address();
+
+    client.requestor.connect(Divider::divide, &divider_mbox);
+    let mut output = EventSlot::new();
+    client.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .catch_model_panics()
+        .add_model(client, client_mbox, "client")
+        .add_model(Divider, divider_mbox, "divider")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // The panic of the replier is reported rather than that of the requestor.
+    match simu.process_event(Client::ask, 0, &client_addr) {
+        Err(ExecutionError::ModelPanic { model, info }) => {
+            assert_eq!(model, "divider");
+            assert_eq!(info, "division by zero");
+        }
+        _ => panic!("panic not detected"),
+    }
+    assert!(output.next().is_none());
+
+    // The simulation can be resumed.
+    simu.process_event(Client::ask, 4, &client_addr).unwrap();
+    assert_eq!(output.next(), Some(25));
+
+    match simu.process_query(Divider::divide, 0, &divider_addr) {
+        Err(ExecutionError::ModelPanic { model, .. }) => assert_eq!(model, "divider"),
+        _ => panic!("panic not detected"),
+    }
+    assert_eq!(
+        simu.process_query(Divider::divide, 5, &divider_addr)
+            .unwrap(),
+        20
+    );
+}
+
+#[test]
+fn model_panic_caught_st() {
+    model_panic_caught(1);
+}
+
+#[test]
+fn model_panic_caught_mt() {
+    model_panic_caught(MT_NUM_THREADS);
+}
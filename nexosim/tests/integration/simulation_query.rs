@@ -0,0 +1,68 @@
+//! Queries processed from a `Simulation` instance.
+
+use nexosim::model::Model;
+use nexosim::ports::QuerySource;
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel {
+    value: u32,
+}
+impl TestModel {
+    async fn scaled_value(&mut self, factor: u32) -> u32 {
+        self.value * factor
+    }
+}
+impl Model for TestModel {}
+
+fn process_query(num_threads: usize) {
+    let mbox1 = Mailbox::new();
+    let mbox2 = Mailbox::new();
+    let addr1 = mbox1.address();
+
+    let mut source = QuerySource::new();
+    source.connect(TestModel::scaled_value, &mbox1);
+    source.connect(TestModel::scaled_value, &mbox2);
+    let disconnected_source = QuerySource::<u32, u32>::new();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel { value: 3 }, mbox1, "model1")
+        .add_model(TestModel { value: 5 }, mbox2, "model2")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // Single reply from a replier port.
+    assert_eq!(
+        simu.process_query(TestModel::scaled_value, 2, &addr1)
+            .unwrap(),
+        6
+    );
+
+    // All replies from a query source, in connection order.
+    assert_eq!(simu.process_query_source(&source, 2).unwrap(), vec![6, 10]);
+
+    // No replier port.
+    assert!(matches!(
+        simu.process_query_source(&disconnected_source, 2),
+        Err(ExecutionError::BadQuery)
+    ));
+    let dropped_addr = Mailbox::<TestModel>::new().address();
+    assert!(matches!(
+        simu.process_query(TestModel::scaled_value, 2, dropped_addr),
+        Err(ExecutionError::BadQuery)
+    ));
+}
+
+#[test]
+fn process_query_st() {
+    process_query(1);
+}
+
+#[test]
+fn process_query_mt() {
+    process_query(MT_NUM_THREADS);
+}
@@ -1,13 +1,19 @@
 //! Event scheduling from a `Simulation` instance.
 
+use std::thread;
 use std::time::Duration;
 
 #[cfg(not(miri))]
 use nexosim::model::Context;
 use nexosim::model::Model;
-use nexosim::ports::{EventBuffer, Output};
-use nexosim::simulation::{Address, Mailbox, Scheduler, SimInit, Simulation};
-use nexosim::time::MonotonicTime;
+use nexosim::ports::{
+    ClosureSink, EventBuffer, EventSource, ExternalEventSource, Output, QuerySource, Timed,
+};
+use nexosim::simulation::{
+    Address, CancellationToken, ExecutionError, Mailbox, Scheduler, SchedulingError, SimInit,
+    Simulation, StepOutcome, StepReport,
+};
+use nexosim::time::{MonotonicTime, SimDuration};
 
 const MT_NUM_THREADS: usize = 4;
 
@@ -24,6 +30,9 @@ impl<T: Clone + Send + 'static> PassThroughModel<T> {
     pub async fn input(&mut self, arg: T) {
         self.output.send(arg).await;
     }
+    pub async fn echo(&mut self, arg: T) -> T {
+        arg
+    }
 }
 impl<T: Clone + Send + 'static> Model for PassThroughModel<T> {}
 
@@ -132,6 +141,84 @@ fn schedule_keyed_events(num_threads: usize) {
     assert!(output.next().is_none());
 }
 
+fn reschedule_keyed_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let event_key = scheduler
+        .schedule_keyed_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 2, &addr)
+        .unwrap();
+
+    // Move the 1st event past the 2nd one, twice in a row.
+    scheduler
+        .reschedule(&event_key, Duration::from_secs(4))
+        .unwrap();
+    scheduler
+        .reschedule(&event_key, Duration::from_secs(3))
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(2));
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.next(), Some(1));
+    assert!(output.next().is_none());
+
+    // The event has already been processed.
+    assert_eq!(
+        scheduler.reschedule(&event_key, Duration::from_secs(1)),
+        Err(SchedulingError::ExpiredActionKey)
+    );
+}
+
+fn reschedule_keyed_periodic_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    // Queue a periodic event at t0 + 3s + k*2s.
+    let event_key = scheduler
+        .schedule_keyed_periodic_event(
+            Duration::from_secs(3),
+            Duration::from_secs(2),
+            PassThroughModel::input,
+            1,
+            &addr,
+        )
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.next(), Some(1));
+    assert!(output.next().is_none());
+
+    // Delay the next occurrence from t0+5s to t0+7s.
+    scheduler
+        .reschedule(&event_key, Duration::from_secs(4))
+        .unwrap();
+
+    // Later occurrences keep the original period.
+    for k in 0..10 {
+        simu.step().unwrap();
+        assert_eq!(
+            simu.time(),
+            t0 + Duration::from_secs(7) + k * Duration::from_secs(2)
+        );
+        assert_eq!(output.next(), Some(1));
+        assert!(output.next().is_none());
+    }
+
+    // The key remains valid for the later occurrences.
+    assert!(event_key.is_pending());
+    event_key.cancel();
+    simu.step().unwrap();
+    assert!(output.next().is_none());
+}
+
 fn schedule_periodic_events(num_threads: usize) {
     let t0 = MonotonicTime::EPOCH;
     let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
@@ -215,6 +302,42 @@ fn schedule_periodic_keyed_events(num_threads: usize) {
     }
 }
 
+fn keyed_event_pending_state(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, _output) = passthrough_bench(num_threads, t0);
+
+    let event_t1 = scheduler
+        .schedule_keyed_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    let event_t2 = scheduler
+        .schedule_keyed_event(Duration::from_secs(2), PassThroughModel::input, 2, &addr)
+        .unwrap();
+    let periodic_event = scheduler
+        .schedule_keyed_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+            PassThroughModel::input,
+            3,
+            &addr,
+        )
+        .unwrap();
+    assert!(event_t1.is_pending());
+    assert!(event_t2.is_pending());
+    assert!(periodic_event.is_pending());
+
+    // A processed event is no longer pending, unlike a periodic event.
+    simu.step().unwrap();
+    assert!(!event_t1.is_pending());
+    assert!(event_t2.is_pending());
+    assert!(periodic_event.is_pending());
+
+    // A cancelled event is no longer pending.
+    event_t2.clone().cancel();
+    assert!(!event_t2.is_pending());
+    periodic_event.clone().cancel();
+    assert!(!periodic_event.is_pending());
+}
+
 #[test]
 fn schedule_events_st() {
     schedule_events(1);
@@ -235,6 +358,26 @@ fn schedule_keyed_events_mt() {
     schedule_keyed_events(MT_NUM_THREADS);
 }
 
+#[test]
+fn reschedule_keyed_events_st() {
+    reschedule_keyed_events(1);
+}
+
+#[test]
+fn reschedule_keyed_events_mt() {
+    reschedule_keyed_events(MT_NUM_THREADS);
+}
+
+#[test]
+fn reschedule_keyed_periodic_events_st() {
+    reschedule_keyed_periodic_events(1);
+}
+
+#[test]
+fn reschedule_keyed_periodic_events_mt() {
+    reschedule_keyed_periodic_events(MT_NUM_THREADS);
+}
+
 #[test]
 fn schedule_periodic_events_st() {
     schedule_periodic_events(1);
@@ -255,6 +398,16 @@ fn schedule_periodic_keyed_events_mt() {
     schedule_periodic_keyed_events(MT_NUM_THREADS);
 }
 
+#[test]
+fn keyed_event_pending_state_st() {
+    keyed_event_pending_state(1);
+}
+
+#[test]
+fn keyed_event_pending_state_mt() {
+    keyed_event_pending_state(MT_NUM_THREADS);
+}
+
 #[cfg(not(miri))]
 use std::time::{Instant, SystemTime};
 
@@ -469,6 +622,43 @@ fn auto_system_clock(num_threads: usize) {
     }
 }
 
+#[cfg(not(miri))]
+fn schedule_at_wall_clock(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    const TOLERANCE: f64 = 0.005; // [s]
+
+    let (mut simu, scheduler, addr, mut stamp) =
+        timestamp_bench(num_threads, t0, AutoSystemClock::new());
+    let instant_t0 = stamp.next().unwrap().0;
+
+    // Queue a single event 0.2s after initialization in wall clock time.
+    scheduler
+        .schedule_at_wall_clock(
+            instant_t0 + Duration::from_secs_f64(0.2),
+            TimestampModel::trigger,
+            (),
+            &addr,
+        )
+        .unwrap();
+
+    // An instant in the past of the simulation cannot be scheduled.
+    assert!(scheduler
+        .schedule_at_wall_clock(instant_t0, TimestampModel::trigger, (), &addr)
+        .is_err());
+
+    simu.step().unwrap();
+
+    let expected_time = 0.2;
+    let measured_time = (stamp.next().unwrap().0 - instant_t0).as_secs_f64();
+    assert!(
+        (expected_time - measured_time).abs() <= TOLERANCE,
+        "Expected t = {:.6}s +/- {:.6}s, measured t = {:.6}s",
+        expected_time,
+        TOLERANCE,
+        measured_time,
+    );
+}
+
 #[cfg(not(miri))]
 #[test]
 fn system_clock_from_instant_st() {
@@ -504,3 +694,888 @@ fn auto_system_clock_st() {
 fn auto_system_clock_mt() {
     auto_system_clock(MT_NUM_THREADS);
 }
+
+#[cfg(not(miri))]
+#[test]
+fn schedule_at_wall_clock_st() {
+    schedule_at_wall_clock(1);
+}
+
+#[cfg(not(miri))]
+#[test]
+fn schedule_at_wall_clock_mt() {
+    schedule_at_wall_clock(MT_NUM_THREADS);
+}
+
+#[test]
+fn schedule_at_wall_clock_without_real_time_clock() {
+    let t0 = MonotonicTime::EPOCH;
+    let (_simu, scheduler, addr, _output) = passthrough_bench::<()>(1, t0);
+
+    assert_eq!(
+        scheduler.schedule_at_wall_clock(
+            std::time::Instant::now() + Duration::from_secs(1),
+            PassThroughModel::input,
+            (),
+            &addr
+        ),
+        Err(SchedulingError::NoRealTimeClock)
+    );
+}
+
+fn halt_with_reason(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, mut scheduler, _addr, _output) = passthrough_bench::<()>(num_threads, t0);
+
+    scheduler.halt_with_reason("stop requested");
+    // The original reason should be preserved.
+    scheduler.halt_with_reason("ignored");
+
+    match simu.step() {
+        Err(ExecutionError::Halted(reason)) => assert_eq!(reason, "stop requested"),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn halt_with_reason_st() {
+    halt_with_reason(1);
+}
+
+#[test]
+fn halt_with_reason_mt() {
+    halt_with_reason(MT_NUM_THREADS);
+}
+
+fn step_until_idle_or(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+
+    // All events complete before the deadline.
+    assert_eq!(
+        simu.step_until_idle_or(Duration::from_secs(2)).unwrap(),
+        StepOutcome::Idle
+    );
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+
+    scheduler
+        .schedule_event(Duration::from_secs(3), PassThroughModel::input, 2, &addr)
+        .unwrap();
+
+    // The deadline is reached before the event is processed.
+    assert_eq!(
+        simu.step_until_idle_or(Duration::from_secs(1)).unwrap(),
+        StepOutcome::DeadlineReached
+    );
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert!(output.next().is_none());
+
+    // The deadline coincides with the last event.
+    assert_eq!(
+        simu.step_until_idle_or(Duration::from_secs(2)).unwrap(),
+        StepOutcome::DeadlineReached
+    );
+    assert_eq!(simu.time(), t0 + Duration::from_secs(4));
+    assert_eq!(output.next(), Some(2));
+}
+
+#[test]
+fn step_until_idle_or_st() {
+    step_until_idle_or(1);
+}
+
+#[test]
+fn step_until_idle_or_mt() {
+    step_until_idle_or(MT_NUM_THREADS);
+}
+
+fn step_while(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+            PassThroughModel::input,
+            (),
+            &addr,
+        )
+        .unwrap();
+
+    // The condition is checked between time slices so the slice that makes
+    // it false is completed, but no further slice is processed.
+    let mut slice_count = 0;
+    assert_eq!(
+        simu.step_while(|simu| {
+            slice_count += 1;
+            simu.time() < t0 + Duration::from_millis(2500)
+        })
+        .unwrap(),
+        StepOutcome::Interrupted
+    );
+    assert_eq!(slice_count, 4);
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.by_ref().count(), 3);
+
+    // The condition is checked before the first slice.
+    assert_eq!(
+        simu.step_while(|_| false).unwrap(),
+        StepOutcome::Interrupted
+    );
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn step_while_st() {
+    step_while(1);
+}
+
+#[test]
+fn step_while_mt() {
+    step_while(MT_NUM_THREADS);
+}
+
+fn step_unbounded_cancellable(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let token = CancellationToken::new();
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    // Cancel the token upon the second event.
+    let mut event_count = 0;
+    let cancel_handle = token.clone();
+    model.output.connect_sink(&ClosureSink::new(move |_: ()| {
+        event_count += 1;
+        if event_count == 2 {
+            cancel_handle.cancel();
+        }
+    }));
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, (), &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, (), &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(3), PassThroughModel::input, (), &addr)
+        .unwrap();
+
+    // The time slice during which the token is cancelled is completed.
+    assert!(matches!(
+        simu.step_unbounded_cancellable(&token),
+        Err(ExecutionError::Cancelled)
+    ));
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+
+    // The token remains cancelled.
+    assert!(matches!(
+        simu.step_unbounded_cancellable(&token),
+        Err(ExecutionError::Cancelled)
+    ));
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+
+    // The simulation can be resumed with a new token.
+    simu.step_unbounded_cancellable(&CancellationToken::new())
+        .unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+}
+
+#[test]
+fn step_unbounded_cancellable_st() {
+    step_unbounded_cancellable(1);
+}
+
+#[test]
+fn step_unbounded_cancellable_mt() {
+    step_unbounded_cancellable(MT_NUM_THREADS);
+}
+
+fn time_hook(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    simu.set_time_hook(move |time| tx.send(time).unwrap());
+
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            PassThroughModel::input,
+            (),
+            &addr,
+        )
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, (), &addr)
+        .unwrap();
+
+    // The hook is called once per time slice, but not when the simulation
+    // time is moved to a deadline without any scheduled action.
+    simu.step().unwrap();
+    simu.step_until(Duration::from_secs(3)).unwrap();
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        vec![
+            t0 + Duration::from_secs(1),
+            t0 + Duration::from_secs(2),
+            t0 + Duration::from_secs(3)
+        ]
+    );
+    assert_eq!(output.by_ref().count(), 3);
+
+    // The hook is not called when the simulation time does not change.
+    simu.process_event(PassThroughModel::input, (), &addr)
+        .unwrap();
+    simu.step_until(Duration::from_millis(500)).unwrap();
+    assert!(rx.try_iter().next().is_none());
+}
+
+#[test]
+fn time_hook_st() {
+    time_hook(1);
+}
+
+#[test]
+fn time_hook_mt() {
+    time_hook(MT_NUM_THREADS);
+}
+
+fn time_hook_cancel(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let key = scheduler
+        .schedule_keyed_event(Duration::from_secs(2), PassThroughModel::input, (), &addr)
+        .unwrap();
+
+    // The hook cancels the only scheduled event.
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut key = Some(key);
+    simu.set_time_hook(move |time| {
+        if let Some(key) = key.take() {
+            key.cancel();
+        }
+        tx.send(time).unwrap();
+    });
+
+    // The simulation time is left unchanged since no action remains.
+    simu.step().unwrap();
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        vec![t0 + Duration::from_secs(2)]
+    );
+    assert_eq!(simu.time(), t0);
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn time_hook_cancel_st() {
+    time_hook_cancel(1);
+}
+
+#[test]
+fn time_hook_cancel_mt() {
+    time_hook_cancel(MT_NUM_THREADS);
+}
+
+fn timed_sink(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = Timed::new(EventBuffer::new());
+    model.output.connect_sink(&output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    simu.step().unwrap();
+    simu.process_event(PassThroughModel::input, 2, &addr)
+        .unwrap();
+
+    assert_eq!(output.next(), Some((t0 + Duration::from_secs(2), 1)));
+    assert_eq!(output.next(), Some((t0 + Duration::from_secs(2), 2)));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn timed_sink_st() {
+    timed_sink(1);
+}
+
+#[test]
+fn timed_sink_mt() {
+    timed_sink(MT_NUM_THREADS);
+}
+
+fn finish(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(3), PassThroughModel::input, 2, &addr)
+        .unwrap();
+    simu.process_event(PassThroughModel::input, 0, &addr)
+        .unwrap();
+    simu.step().unwrap();
+
+    let report = simu.report();
+    assert_eq!(report.time, t0 + Duration::from_secs(1));
+    assert_eq!(report.action_count, 1);
+    assert_eq!(report.model_count, 1);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    for hook_id in 0..2 {
+        let tx = tx.clone();
+        simu.add_terminate_hook(move |report| tx.send((hook_id, report.time)).unwrap());
+    }
+    assert!(rx.try_recv().is_err());
+
+    let report = simu.finish().unwrap();
+    assert_eq!(report.time, t0 + Duration::from_secs(3));
+    assert_eq!(report.action_count, 2);
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        vec![(0, report.time), (1, report.time)]
+    );
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn finish_st() {
+    finish(1);
+}
+
+#[test]
+fn finish_mt() {
+    finish(MT_NUM_THREADS);
+}
+
+fn is_quiescent(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, _output) = passthrough_bench(num_threads, t0);
+
+    assert!(simu.is_quiescent());
+
+    let key = scheduler
+        .schedule_keyed_event(Duration::from_secs(1), PassThroughModel::input, (), &addr)
+        .unwrap();
+    assert!(!simu.is_quiescent());
+
+    // Cancelled events are ignored.
+    key.cancel();
+    assert!(simu.is_quiescent());
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, (), &addr)
+        .unwrap();
+    simu.step().unwrap();
+    assert!(simu.is_quiescent());
+}
+
+#[test]
+fn is_quiescent_st() {
+    is_quiescent(1);
+}
+
+#[test]
+fn is_quiescent_mt() {
+    is_quiescent(MT_NUM_THREADS);
+}
+
+fn step_observed(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 2, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 3, &addr)
+        .unwrap();
+
+    let mut query_source = QuerySource::new();
+    query_source.connect(PassThroughModel::echo, &addr);
+    scheduler
+        .schedule(Duration::from_secs(2), query_source.query(4).0)
+        .unwrap();
+
+    assert_eq!(
+        simu.step_observed().unwrap(),
+        StepReport {
+            time: t0 + Duration::from_secs(1),
+            event_count: 2,
+            query_count: 0,
+        }
+    );
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+
+    assert_eq!(
+        simu.step_observed().unwrap(),
+        StepReport {
+            time: t0 + Duration::from_secs(2),
+            event_count: 1,
+            query_count: 1,
+        }
+    );
+    assert_eq!(output.next(), Some(3));
+
+    // No more events are scheduled.
+    assert_eq!(
+        simu.step_observed().unwrap(),
+        StepReport {
+            time: t0 + Duration::from_secs(2),
+            event_count: 0,
+            query_count: 0,
+        }
+    );
+}
+
+#[test]
+fn step_observed_st() {
+    step_observed(1);
+}
+
+#[test]
+fn step_observed_mt() {
+    step_observed(MT_NUM_THREADS);
+}
+
+fn schedule_with_priority(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_with_priority(Duration::from_secs(1), -1, source.event(2))
+        .unwrap();
+    scheduler
+        .schedule_with_priority(Duration::from_secs(1), 0, source.event(3))
+        .unwrap();
+    scheduler
+        .schedule_with_priority(Duration::from_secs(1), 1, source.event(4))
+        .unwrap();
+
+    simu.step().unwrap();
+
+    // Lower priority values are processed first, ties are processed in
+    // scheduling order.
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(3));
+    assert_eq!(output.next(), Some(4));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn schedule_with_priority_st() {
+    schedule_with_priority(1);
+}
+
+#[test]
+fn schedule_with_priority_mt() {
+    schedule_with_priority(MT_NUM_THREADS);
+}
+
+fn schedule_from_iter(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    // Non-monotonic offsets are rejected.
+    let events = [(Duration::from_secs(2), 1), (Duration::from_secs(1), 2)];
+    assert_eq!(
+        source.schedule_from_iter(&scheduler, events),
+        Err(SchedulingError::NonMonotonicSchedule)
+    );
+
+    // Offsets beyond the range of `MonotonicTime` are rejected, and so is the
+    // whole sequence.
+    let events = [(Duration::from_secs(1), 1), (Duration::MAX, 2)];
+    assert_eq!(
+        source.schedule_from_iter(&scheduler, events),
+        Err(SchedulingError::InvalidScheduledTime)
+    );
+
+    let events = [
+        (Duration::from_secs(1), 1),
+        (Duration::from_secs(1), 2),
+        (Duration::from_secs(3), 3),
+    ];
+    assert_eq!(source.schedule_from_iter(&scheduler, events), Ok(3));
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.next(), Some(3));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn schedule_from_iter_st() {
+    schedule_from_iter(1);
+}
+
+#[test]
+fn schedule_from_iter_mt() {
+    schedule_from_iter(MT_NUM_THREADS);
+}
+
+fn schedule_batch(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    // A batch containing a past time is rejected as a whole.
+    let actions = [
+        (t0 + Duration::from_secs(1), source.event(0)),
+        (t0, source.event(0)),
+    ];
+    assert_eq!(
+        scheduler.schedule_batch(actions),
+        Err(SchedulingError::InvalidScheduledTime)
+    );
+    assert_eq!(scheduler.pending_event_count(), 0);
+
+    // Times need not be ordered and actions scheduled for the same time are
+    // processed in batch order.
+    let actions = [
+        (t0 + Duration::from_secs(2), source.event(3)),
+        (t0 + Duration::from_secs(1), source.event(1)),
+        (t0 + Duration::from_secs(2), source.event(4)),
+        (t0 + Duration::from_secs(1), source.event(2)),
+    ];
+    scheduler.schedule_batch(actions).unwrap();
+    assert_eq!(scheduler.pending_event_count(), 4);
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(3));
+    assert_eq!(output.next(), Some(4));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn schedule_batch_st() {
+    schedule_batch(1);
+}
+
+#[test]
+fn schedule_batch_mt() {
+    schedule_batch(MT_NUM_THREADS);
+}
+
+fn external_event_source(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    let sim_init = SimInit::with_num_threads(num_threads).add_model(model, mbox, "");
+    let external: ExternalEventSource<usize> = sim_init.external_event_source(source);
+    let (mut simu, scheduler) = sim_init.init(t0).unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 0, &addr)
+        .unwrap();
+
+    // Events sent from another thread are processed at the current time.
+    let external_clone = external.clone();
+    thread::spawn(move || {
+        external_clone.send(1).unwrap();
+        external_clone.send(2).unwrap();
+    })
+    .join()
+    .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0);
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(0));
+
+    // Events can no longer be sent once the simulation has been dropped.
+    drop(simu);
+    drop(scheduler);
+    assert!(matches!(external.send(3), Err(ExecutionError::Terminated)));
+}
+
+#[test]
+fn external_event_source_st() {
+    external_event_source(1);
+}
+
+#[test]
+fn external_event_source_mt() {
+    external_event_source(MT_NUM_THREADS);
+}
+
+fn external_event_source_wake_up(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+    let addr = mbox.address();
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    let sim_init = SimInit::with_num_threads(num_threads).add_model(model, mbox, "");
+    let external: ExternalEventSource<usize> = sim_init.external_event_source(source);
+    let (mut simu, _scheduler) = sim_init.init(t0).unwrap();
+
+    // The idle simulation waits for events sent from another thread until the
+    // external source is dropped.
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        external.send(1).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        external.send(2).unwrap();
+    });
+
+    simu.step_unbounded().unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(simu.time(), t0);
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn external_event_source_wake_up_st() {
+    external_event_source_wake_up(1);
+}
+
+#[test]
+fn external_event_source_wake_up_mt() {
+    external_event_source_wake_up(MT_NUM_THREADS);
+}
+
+fn event_source_min_interval(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let mut source = EventSource::new().with_min_interval(Duration::from_secs(1));
+    source.connect(PassThroughModel::input, &addr);
+
+    // A burst of events is spread out one interval apart.
+    for i in 1..=3 {
+        scheduler
+            .schedule(Duration::from_secs(1), source.event(i))
+            .unwrap();
+    }
+    scheduler
+        .schedule(Duration::from_secs(5), source.event(4))
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert_eq!(output.next(), Some(2));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(3));
+    assert_eq!(output.next(), Some(3));
+    assert!(output.next().is_none());
+
+    // Events far enough apart are not delayed.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(5));
+    assert_eq!(output.next(), Some(4));
+    assert!(output.next().is_none());
+
+    // Events processed directly are rate-limited as well.
+    simu.process(source.event(5)).unwrap();
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(6));
+    assert_eq!(output.next(), Some(5));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn event_source_min_interval_st() {
+    event_source_min_interval(1);
+}
+
+#[test]
+fn event_source_min_interval_mt() {
+    event_source_min_interval(MT_NUM_THREADS);
+}
+
+fn time_handle(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, _output) = passthrough_bench::<()>(num_threads, t0);
+
+    let time_handle = scheduler.time_handle();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, (), &addr)
+        .unwrap();
+    assert_eq!(time_handle.time(), t0);
+
+    simu.step().unwrap();
+    let monitor = thread::spawn(move || time_handle.time());
+    assert_eq!(monitor.join().unwrap(), t0 + Duration::from_secs(2));
+}
+
+#[test]
+fn time_handle_st() {
+    time_handle(1);
+}
+
+#[test]
+fn time_handle_mt() {
+    time_handle(MT_NUM_THREADS);
+}
+
+fn pending_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, _output) = passthrough_bench::<()>(num_threads, t0);
+
+    assert_eq!(simu.pending_event_count(), 0);
+    assert_eq!(scheduler.next_event_time(), None);
+
+    let key = scheduler
+        .schedule_keyed_event(Duration::from_secs(1), PassThroughModel::input, (), &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, (), &addr)
+        .unwrap();
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(3),
+            Duration::from_secs(1),
+            PassThroughModel::input,
+            (),
+            &addr,
+        )
+        .unwrap();
+    assert_eq!(scheduler.pending_event_count(), 3);
+    assert_eq!(
+        scheduler.next_event_time(),
+        Some(t0 + Duration::from_secs(1))
+    );
+
+    // Cancelled events are ignored.
+    key.cancel();
+    assert_eq!(simu.pending_event_count(), 2);
+    assert_eq!(
+        scheduler.next_event_time(),
+        Some(t0 + Duration::from_secs(2))
+    );
+
+    // Periodic events remain pending.
+    simu.step_until(Duration::from_secs(3)).unwrap();
+    assert_eq!(simu.pending_event_count(), 1);
+    assert_eq!(
+        scheduler.next_event_time(),
+        Some(t0 + Duration::from_secs(4))
+    );
+}
+
+#[test]
+fn pending_events_st() {
+    pending_events(1);
+}
+
+#[test]
+fn pending_events_mt() {
+    pending_events(MT_NUM_THREADS);
+}
+
+fn schedule_overflowing_deadline(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (_simu, scheduler, addr, _output) = passthrough_bench(num_threads, t0);
+
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &addr);
+
+    // Relative deadlines beyond the range of `MonotonicTime` are rejected
+    // rather than causing a panic, irrespective of their type.
+    assert_eq!(
+        scheduler.schedule(Duration::MAX, source.event(1)),
+        Err(SchedulingError::InvalidScheduledTime)
+    );
+    assert_eq!(
+        scheduler.schedule(SimDuration::MAX, source.event(2)),
+        Err(SchedulingError::InvalidScheduledTime)
+    );
+    assert_eq!(scheduler.pending_event_count(), 0);
+}
+
+#[test]
+fn schedule_overflowing_deadline_st() {
+    schedule_overflowing_deadline(1);
+}
+
+#[test]
+fn schedule_overflowing_deadline_mt() {
+    schedule_overflowing_deadline(MT_NUM_THREADS);
+}
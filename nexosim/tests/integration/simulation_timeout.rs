@@ -1,4 +1,4 @@
-//! Timeout during simulation step execution.
+//! Timeout during simulation step execution and wall clock limit.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -8,7 +8,7 @@ use std::time::Duration;
 use nexosim::model::Model;
 use nexosim::ports::Output;
 use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
-use nexosim::time::MonotonicTime;
+use nexosim::time::{AutoSystemClock, MonotonicTime};
 
 const MT_NUM_THREADS: usize = 4;
 
@@ -83,6 +83,76 @@ fn timeout_triggered(num_threads: usize) {
     assert!(!model_is_alive.load(Ordering::Relaxed));
 }
 
+fn wall_clock_limit_triggered(num_threads: usize) {
+    let (mut model, model_is_alive) = TestModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    // Make a loopback connection.
+    model.output.connect(TestModel::input, addr.clone());
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .set_timeout(Duration::from_secs(10))
+        .set_wall_clock_limit(Duration::from_secs(1))
+        .init(t0)
+        .unwrap()
+        .0;
+
+    assert!(matches!(
+        simu.process_event(TestModel::input, (), addr.clone()),
+        Err(ExecutionError::WallClockTimeout)
+    ));
+
+    // Make sure the request to stop the simulation has succeeded.
+    thread::sleep(Duration::from_millis(10));
+    assert!(!model_is_alive.load(Ordering::Relaxed));
+
+    assert!(matches!(
+        simu.process_event(TestModel::input, (), addr),
+        Err(ExecutionError::Terminated)
+    ));
+}
+
+fn wall_clock_limit_clock_wait(num_threads: usize) {
+    let (model, _) = TestModel::new();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, Mailbox::new(), "test")
+        .set_clock(AutoSystemClock::new())
+        .set_wall_clock_limit(Duration::from_millis(100))
+        .init(t0)
+        .unwrap()
+        .0;
+
+    // The limit is exceeded while waiting for the clock.
+    assert!(matches!(
+        simu.step_until(Duration::from_millis(300)),
+        Err(ExecutionError::WallClockTimeout)
+    ));
+    assert!(matches!(
+        simu.step_until(Duration::from_millis(300)),
+        Err(ExecutionError::Terminated)
+    ));
+}
+
+fn wall_clock_limit_unbounded(num_threads: usize) {
+    let (model, _) = TestModel::new();
+
+    // A limit that cannot be represented as an instant is never exceeded.
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, Mailbox::new(), "test")
+        .set_wall_clock_limit(Duration::MAX)
+        .init(t0)
+        .unwrap()
+        .0;
+
+    assert!(simu.step_until(Duration::from_secs(1)).is_ok());
+}
+
 #[test]
 fn timeout_untriggered_st() {
     timeout_untriggered(1);
@@ -102,3 +172,33 @@ fn timeout_triggered_st() {
 fn timeout_triggered_mt() {
     timeout_triggered(MT_NUM_THREADS);
 }
+
+#[test]
+fn wall_clock_limit_triggered_st() {
+    wall_clock_limit_triggered(1);
+}
+
+#[test]
+fn wall_clock_limit_triggered_mt() {
+    wall_clock_limit_triggered(MT_NUM_THREADS);
+}
+
+#[test]
+fn wall_clock_limit_clock_wait_st() {
+    wall_clock_limit_clock_wait(1);
+}
+
+#[test]
+fn wall_clock_limit_clock_wait_mt() {
+    wall_clock_limit_clock_wait(MT_NUM_THREADS);
+}
+
+#[test]
+fn wall_clock_limit_unbounded_st() {
+    wall_clock_limit_unbounded(1);
+}
+
+#[test]
+fn wall_clock_limit_unbounded_mt() {
+    wall_clock_limit_unbounded(MT_NUM_THREADS);
+}